@@ -0,0 +1,224 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+
+use super::{Agent, AgentStatus};
+use crate::app::config::{BudgetAction, BudgetConfig};
+
+/// Which limit of a [`BudgetConfig`] an agent went over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BudgetKind {
+    Runtime,
+    AwaitingInput,
+    Idle,
+    Tokens,
+}
+
+impl BudgetKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BudgetKind::Runtime => "runtime",
+            BudgetKind::AwaitingInput => "awaiting input",
+            BudgetKind::Idle => "idle",
+            BudgetKind::Tokens => "token",
+        }
+    }
+}
+
+/// Limits `agent` is currently over.
+pub fn exceeded(budget: &BudgetConfig, agent: &Agent, now: DateTime<Utc>) -> Vec<BudgetKind> {
+    remaining(budget, agent, now)
+        .into_iter()
+        .filter(|(_, left)| *left <= 0)
+        .map(|(kind, _)| kind)
+        .collect()
+}
+
+/// Short description of what is left, e.g. `1h12m left · idle 8m left`.
+pub fn summary(budget: &BudgetConfig, agent: &Agent, now: DateTime<Utc>) -> Option<String> {
+    let parts: Vec<String> = remaining(budget, agent, now)
+        .into_iter()
+        .map(|(kind, left)| {
+            if left <= 0 {
+                return format!("{} over budget", kind.label());
+            }
+            match kind {
+                BudgetKind::Runtime => format!("{} left", format_minutes(left)),
+                BudgetKind::Tokens => format!("{} tokens left", format_tokens(left as u64)),
+                _ => format!("{} {} left", kind.label(), format_minutes(left)),
+            }
+        })
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" · "))
+    }
+}
+
+/// Remaining minutes (or tokens) for each limit that applies right now.
+/// Idle and awaiting-input limits only apply while in that status.
+fn remaining(budget: &BudgetConfig, agent: &Agent, now: DateTime<Utc>) -> Vec<(BudgetKind, i64)> {
+    let minutes_since = |t: DateTime<Utc>| now.signed_duration_since(t).num_minutes();
+    let mut left = Vec::new();
+    if let Some(max) = budget.max_runtime_mins {
+        left.push((
+            BudgetKind::Runtime,
+            max as i64 - minutes_since(agent.session_started_at),
+        ));
+    }
+    if let Some(max) = budget.max_awaiting_input_mins {
+        if agent.status == AgentStatus::AwaitingInput {
+            left.push((
+                BudgetKind::AwaitingInput,
+                max as i64 - minutes_since(agent.status_since),
+            ));
+        }
+    }
+    if let Some(max) = budget.max_idle_mins {
+        if agent.status == AgentStatus::Idle {
+            left.push((
+                BudgetKind::Idle,
+                max as i64 - minutes_since(agent.status_since),
+            ));
+        }
+    }
+    if let (Some(max), Some(used)) = (budget.max_tokens, agent.token_usage) {
+        left.push((BudgetKind::Tokens, max as i64 - used as i64));
+    }
+    left
+}
+
+/// Parse a budget typed into the budget prompt, e.g.
+/// `runtime=2h idle=30m awaiting=10m tokens=500k action=stop`.
+pub fn parse_budget(input: &str) -> Result<BudgetConfig> {
+    let mut budget = BudgetConfig::default();
+    for part in input.split_whitespace() {
+        let (key, value) = part
+            .split_once('=')
+            .with_context(|| format!("Expected key=value, got '{}'", part))?;
+        match key {
+            "runtime" => budget.max_runtime_mins = Some(parse_minutes(value)?),
+            "awaiting" => budget.max_awaiting_input_mins = Some(parse_minutes(value)?),
+            "idle" => budget.max_idle_mins = Some(parse_minutes(value)?),
+            "tokens" => budget.max_tokens = Some(parse_tokens(value)?),
+            "action" => {
+                budget.action = match value {
+                    "notify" => BudgetAction::Notify,
+                    "interrupt" => BudgetAction::Interrupt,
+                    "stop" => BudgetAction::Stop,
+                    _ => bail!("Unknown budget action '{}'", value),
+                }
+            }
+            _ => bail!("Unknown budget '{}'", key),
+        }
+    }
+    Ok(budget)
+}
+
+/// `90`, `90m`, `2h`, `1h30m`; bare numbers are minutes.
+fn parse_minutes(value: &str) -> Result<u64> {
+    if let Ok(minutes) = value.parse() {
+        return Ok(minutes);
+    }
+    let mut total = 0;
+    let mut digits = String::new();
+    for c in value.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            'h' | 'm' if !digits.is_empty() => {
+                let n: u64 = digits.parse()?;
+                total += if c == 'h' { n * 60 } else { n };
+                digits.clear();
+            }
+            _ => bail!("Invalid duration '{}'", value),
+        }
+    }
+    if !digits.is_empty() {
+        bail!("Invalid duration '{}'", value);
+    }
+    Ok(total)
+}
+
+/// `250000`, `250k`, `1.5m`.
+fn parse_tokens(value: &str) -> Result<u64> {
+    let lower = value.to_lowercase();
+    let (number, scale) = match lower.chars().last() {
+        Some('k') => (&lower[..lower.len() - 1], 1_000.0),
+        Some('m') => (&lower[..lower.len() - 1], 1_000_000.0),
+        _ => (lower.as_str(), 1.0),
+    };
+    let n: f64 = number
+        .parse()
+        .with_context(|| format!("Invalid token count '{}'", value))?;
+    Ok((n * scale) as u64)
+}
+
+fn format_minutes(minutes: i64) -> String {
+    if minutes >= 60 {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn format_tokens(tokens: u64) -> String {
+    if tokens >= 1000 {
+        format!("{}k", tokens / 1000)
+    } else {
+        tokens.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_parse_budget() {
+        let budget =
+            parse_budget("runtime=1h30m idle=20 awaiting=10m tokens=1.5m action=stop").unwrap();
+        assert_eq!(budget.max_runtime_mins, Some(90));
+        assert_eq!(budget.max_idle_mins, Some(20));
+        assert_eq!(budget.max_awaiting_input_mins, Some(10));
+        assert_eq!(budget.max_tokens, Some(1_500_000));
+        assert_eq!(budget.action, BudgetAction::Stop);
+        assert!(parse_budget("").unwrap().is_empty());
+        assert!(parse_budget("runtime=2x").is_err());
+        assert!(parse_budget("speed=1").is_err());
+        assert!(parse_budget("action=pause").is_err());
+    }
+
+    #[test]
+    fn test_exceeded_only_counts_current_status() {
+        let now = Utc::now();
+        let mut agent = Agent::new("a".into(), "b".into(), "/tmp".into());
+        agent.session_started_at = now - Duration::minutes(50);
+        agent.status = AgentStatus::Idle;
+        agent.status_since = now - Duration::minutes(31);
+        agent.token_usage = Some(90_000);
+        let budget = BudgetConfig {
+            max_runtime_mins: Some(60),
+            max_awaiting_input_mins: Some(5),
+            max_idle_mins: Some(30),
+            max_tokens: Some(100_000),
+            action: BudgetAction::Notify,
+        };
+        assert_eq!(exceeded(&budget, &agent, now), vec![BudgetKind::Idle]);
+        assert_eq!(
+            summary(&budget, &agent, now).unwrap(),
+            "10m left · idle over budget · 10k tokens left"
+        );
+
+        agent.status = AgentStatus::AwaitingInput;
+        assert_eq!(
+            exceeded(&budget, &agent, now),
+            vec![BudgetKind::AwaitingInput]
+        );
+        agent.token_usage = Some(120_000);
+        assert_eq!(
+            exceeded(&budget, &agent, now),
+            vec![BudgetKind::AwaitingInput, BudgetKind::Tokens]
+        );
+    }
+}
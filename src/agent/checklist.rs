@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecklistState {
+    Pending,
+    InProgress,
+    Done,
+}
+
+impl ChecklistState {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ChecklistState::Pending => "○",
+            ChecklistState::InProgress => "▶",
+            ChecklistState::Done => "✓",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub state: ChecklistState,
+}
+
+/// An agent's todo list as shown in its pane. The counts can cover more
+/// than `items` when the agent collapses finished items ("+3 completed") or
+/// prints a summary line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Checklist {
+    pub items: Vec<ChecklistItem>,
+    pub completed: u32,
+    pub total: u32,
+}
+
+impl Checklist {
+    pub fn push(&mut self, text: &str, state: ChecklistState) {
+        self.total += 1;
+        if state == ChecklistState::Done {
+            self.completed += 1;
+        }
+        self.items.push(ChecklistItem {
+            text: text.trim().to_string(),
+            state,
+        });
+    }
+
+    pub fn progress(&self) -> (u32, u32) {
+        (self.completed, self.total)
+    }
+
+    /// The item being worked on.
+    pub fn current(&self) -> Option<&ChecklistItem> {
+        self.items
+            .iter()
+            .find(|item| item.state == ChecklistState::InProgress)
+    }
+}
+
+/// A change between two versions of an agent's plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanChange {
+    pub at: DateTime<Utc>,
+    pub description: String,
+}
+
+/// Describe how the plan's items changed, one line per item.
+pub fn plan_changes(old: &[ChecklistItem], new: &[ChecklistItem]) -> Vec<String> {
+    let mut changes = Vec::new();
+    for item in old {
+        if !new.iter().any(|n| n.text == item.text) {
+            changes.push(format!("Removed \"{}\"", item.text));
+        }
+    }
+    for item in new {
+        match old.iter().find(|o| o.text == item.text) {
+            None if old.is_empty() => {}
+            None => changes.push(format!("Added \"{}\"", item.text)),
+            Some(before) if before.state != item.state => changes.push(match item.state {
+                ChecklistState::Done => format!("Finished \"{}\"", item.text),
+                ChecklistState::InProgress => format!("Started \"{}\"", item.text),
+                ChecklistState::Pending => format!("Reopened \"{}\"", item.text),
+            }),
+            Some(_) => {}
+        }
+    }
+    if old.is_empty() && !new.is_empty() {
+        changes.insert(
+            0,
+            format!(
+                "Plan with {} item{}",
+                new.len(),
+                if new.len() == 1 { "" } else { "s" }
+            ),
+        );
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, state: ChecklistState) -> ChecklistItem {
+        ChecklistItem {
+            text: text.to_string(),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_plan_changes() {
+        use ChecklistState::*;
+        let first = vec![item("Write parser", InProgress), item("Add tests", Pending)];
+        assert_eq!(plan_changes(&[], &first), vec!["Plan with 2 items"]);
+
+        let second = vec![
+            item("Write parser", Done),
+            item("Add tests", InProgress),
+            item("Update README", Pending),
+        ];
+        assert_eq!(
+            plan_changes(&first, &second),
+            vec![
+                "Finished \"Write parser\"",
+                "Started \"Add tests\"",
+                "Added \"Update README\"",
+            ]
+        );
+
+        let third = vec![item("Write parser", Done), item("Add tests", InProgress)];
+        assert_eq!(
+            plan_changes(&second, &third),
+            vec!["Removed \"Update README\""]
+        );
+        assert!(plan_changes(&third, &third).is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::{AgentStatus, ForegroundProcess, StatusDetection};
+use crate::app::config::Config;
+
+/// Claude Code lifecycle events Grove listens to.
+const EVENTS: [&str; 6] = [
+    "UserPromptSubmit",
+    "PreToolUse",
+    "PostToolUse",
+    "Notification",
+    "Stop",
+    "SessionEnd",
+];
+
+/// Marks hook commands written by Grove so they can be replaced.
+const MARKER: &str = "# grove-status-hook";
+
+/// Worktree-local Claude Code settings, kept out of git.
+const SETTINGS_FILE: &str = ".claude/settings.local.json";
+
+/// Latest lifecycle event reported by Claude Code's hooks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookEvent {
+    pub name: String,
+    pub tool: Option<String>,
+    pub message: Option<String>,
+}
+
+/// File the hooks write the latest event to.
+pub fn event_path(agent_id: Uuid) -> Result<PathBuf> {
+    Ok(Config::config_dir()?
+        .join("hooks")
+        .join(format!("{}.json", agent_id.as_simple())))
+}
+
+/// Point the worktree's Claude Code hooks at the agent's event file and
+/// clear any event left over from a previous session.
+pub fn install(worktree_path: &str, agent_id: Uuid) -> Result<()> {
+    let events = event_path(agent_id)?;
+    if let Some(dir) = events.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create hooks directory")?;
+    }
+    let _ = std::fs::remove_file(&events);
+
+    let settings_path = Path::new(worktree_path).join(SETTINGS_FILE);
+    let settings = match std::fs::read_to_string(&settings_path) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", settings_path.display()))?,
+        Err(_) => json!({}),
+    };
+    let settings = merge_settings(settings, &hook_command(&events));
+    if let Some(dir) = settings_path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create .claude directory")?;
+    }
+    std::fs::write(&settings_path, serde_json::to_string_pretty(&settings)?)
+        .with_context(|| format!("Failed to write {}", settings_path.display()))?;
+
+    crate::git::exclude_from_git(worktree_path, SETTINGS_FILE)
+}
+
+/// Remove an agent's event file.
+pub fn remove(agent_id: Uuid) {
+    if let Ok(path) = event_path(agent_id) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Latest event for an agent, if its hooks have reported anything.
+pub fn read_event(agent_id: Uuid) -> Option<HookEvent> {
+    let content = std::fs::read_to_string(event_path(agent_id).ok()?).ok()?;
+    parse_event(&content)
+}
+
+fn parse_event(content: &str) -> Option<HookEvent> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    let field = |key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+    Some(HookEvent {
+        name: field("hook_event_name")?,
+        tool: field("tool_name"),
+        message: field("message"),
+    })
+}
+
+/// Shell command run by every hook: store the event JSON (read from stdin)
+/// as a single line.
+fn hook_command(events: &Path) -> String {
+    let path = events.display().to_string().replace('\'', r"'\''");
+    format!("tr -d '\\n' > '{}' {}", path, MARKER)
+}
+
+/// Add Grove's hook to each event, replacing earlier Grove hooks and
+/// keeping everything else in the settings.
+fn merge_settings(mut settings: Value, command: &str) -> Value {
+    if !settings.is_object() {
+        settings = json!({});
+    }
+    let hooks = settings
+        .as_object_mut()
+        .and_then(|s| {
+            s.entry("hooks")
+                .and_modify(|h| {
+                    if !h.is_object() {
+                        *h = json!({});
+                    }
+                })
+                .or_insert_with(|| json!({}))
+                .as_object_mut()
+        })
+        .expect("hooks is an object");
+
+    for event in EVENTS {
+        let entries = hooks.entry(event).or_insert_with(|| json!([]));
+        if !entries.is_array() {
+            *entries = json!([]);
+        }
+        let list = entries.as_array_mut().expect("entries is an array");
+        list.retain(|entry| !entry.to_string().contains(MARKER));
+        let mut entry = json!({ "hooks": [{ "type": "command", "command": command }] });
+        if event.ends_with("ToolUse") {
+            entry["matcher"] = json!("*");
+        }
+        list.push(entry);
+    }
+    settings
+}
+
+/// Status from the latest hook event, falling back to `scraped` (the regex
+/// detection) when there is no event or the event can't tell.
+pub fn resolve(
+    event: Option<&HookEvent>,
+    output: &str,
+    foreground: &ForegroundProcess,
+    scraped: StatusDetection,
+) -> StatusDetection {
+    let Some(event) = event else {
+        return scraped;
+    };
+    // Claude has exited; its last event no longer applies
+    if *foreground == ForegroundProcess::Shell {
+        return scraped;
+    }
+    // An interrupted turn fires no Stop event
+    let interrupted = output
+        .lines()
+        .rev()
+        .filter(|l| !l.trim().is_empty())
+        .take(8)
+        .any(|l| l.contains("Interrupted"));
+    if interrupted {
+        return scraped;
+    }
+
+    let hook = |status: AgentStatus, reason: String| {
+        StatusDetection::new(status)
+            .with_reason(reason)
+            .with_pattern(format!("hook:{}", event.name))
+    };
+    match event.name.as_str() {
+        "UserPromptSubmit" | "PostToolUse" | "PreToolUse" => {
+            // A permission prompt can show before its Notification arrives
+            if scraped.status == AgentStatus::AwaitingInput {
+                return scraped;
+            }
+            let reason = match (&event.tool, event.name.as_str()) {
+                (Some(tool), "PreToolUse") => format!("Hook: running {}", tool),
+                (Some(tool), _) => format!("Hook: finished {}", tool),
+                _ => "Hook: prompt submitted".to_string(),
+            };
+            hook(AgentStatus::Running, reason)
+        }
+        "Notification" => {
+            let needs_permission = event
+                .message
+                .as_deref()
+                .is_some_and(|m| m.contains("permission"));
+            // Once approved the tool runs without a new event until it finishes
+            if needs_permission && scraped.status != AgentStatus::Running {
+                hook(
+                    AgentStatus::AwaitingInput,
+                    event.message.clone().unwrap_or_default(),
+                )
+            } else {
+                scraped
+            }
+        }
+        "Stop" => match scraped.status {
+            AgentStatus::AwaitingInput | AgentStatus::Completed | AgentStatus::Error(_) => scraped,
+            _ => hook(AgentStatus::Idle, "Hook: turn finished".to_string()),
+        },
+        _ => scraped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, tool: Option<&str>, message: Option<&str>) -> HookEvent {
+        HookEvent {
+            name: name.to_string(),
+            tool: tool.map(String::from),
+            message: message.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_merge_settings_keeps_user_hooks_and_replaces_grove_hooks() {
+        let settings = json!({
+            "permissions": { "allow": ["Bash(cargo test)"] },
+            "hooks": {
+                "Stop": [{ "hooks": [{ "type": "command", "command": "say done" }] }]
+            }
+        });
+        let once = merge_settings(settings, &format!("old {}", MARKER));
+        let twice = merge_settings(once, &format!("new {}", MARKER));
+
+        assert_eq!(twice["permissions"]["allow"][0], "Bash(cargo test)");
+        let stop = twice["hooks"]["Stop"].as_array().unwrap();
+        assert_eq!(stop.len(), 2);
+        assert_eq!(stop[0]["hooks"][0]["command"], "say done");
+        assert_eq!(stop[1]["hooks"][0]["command"], format!("new {}", MARKER));
+        assert_eq!(twice["hooks"]["PreToolUse"][0]["matcher"], "*");
+        assert!(twice["hooks"]["Notification"][0].get("matcher").is_none());
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let json = r#"{"session_id":"abc","hook_event_name":"PreToolUse","tool_name":"Bash","tool_input":{"command":"cargo test"}}"#;
+        let event = parse_event(json).unwrap();
+        assert_eq!(event.name, "PreToolUse");
+        assert_eq!(event.tool.as_deref(), Some("Bash"));
+        assert!(parse_event("not json").is_none());
+    }
+
+    #[test]
+    fn test_resolve_prefers_hooks_and_falls_back() {
+        let claude = ForegroundProcess::ClaudeRunning;
+        let idle = || StatusDetection::new(AgentStatus::Idle);
+        let running = || StatusDetection::new(AgentStatus::Running);
+
+        let tool = event("PreToolUse", Some("Bash"), None);
+        let status = resolve(Some(&tool), "", &claude, idle());
+        assert_eq!(status.status, AgentStatus::Running);
+        assert_eq!(status.pattern.as_deref(), Some("hook:PreToolUse"));
+
+        let permission = event(
+            "Notification",
+            None,
+            Some("Claude needs your permission to use Bash"),
+        );
+        let status = resolve(Some(&permission), "", &claude, idle());
+        assert_eq!(status.status, AgentStatus::AwaitingInput);
+        // Approved and running again
+        let status = resolve(Some(&permission), "", &claude, running());
+        assert_eq!(status.status, AgentStatus::Running);
+
+        let stop = event("Stop", None, None);
+        assert_eq!(
+            resolve(Some(&stop), "", &claude, running()).status,
+            AgentStatus::Idle
+        );
+
+        // Interrupted turns, an exited Claude and missing events use the regex
+        let output = "⎿  Interrupted · What should Claude do instead?\n> ";
+        assert_eq!(
+            resolve(Some(&tool), output, &claude, idle()).status,
+            AgentStatus::Idle
+        );
+        assert_eq!(
+            resolve(Some(&tool), "", &ForegroundProcess::Shell, idle()).status,
+            AgentStatus::Idle
+        );
+        assert_eq!(resolve(None, "", &claude, idle()).status, AgentStatus::Idle);
+    }
+}
@@ -1,8 +1,10 @@
 use regex::Regex;
 use std::sync::LazyLock;
 
+use super::checklist::{Checklist, ChecklistState};
 use super::{AgentStatus, StatusReason};
 use crate::app::config::AiAgent;
+use crate::core::common::strip_ansi;
 use crate::core::git_providers::gitlab::{MergeRequestStatus, PipelineStatus};
 use chrono::Utc;
 
@@ -102,10 +104,6 @@ impl ForegroundProcess {
     }
 }
 
-/// Pattern to strip ANSI escape codes
-static ANSI_ESCAPE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*[a-zA-Z]|\x1b\].*?\x07").unwrap());
-
 /// Pattern to detect GitLab MR URLs in output
 static MR_URL_PATTERN: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"https://[^/]+/[^/]+/[^/]+/-/merge_requests/(\d+)").unwrap());
@@ -262,9 +260,29 @@ pub fn detect_mr_url(output: &str) -> Option<MergeRequestStatus> {
     })
 }
 
-/// Strip ANSI escape codes from text
-fn strip_ansi(text: &str) -> String {
-    ANSI_ESCAPE.replace_all(text, "").to_string()
+/// Running token total in OpenCode's side panel, e.g. "78,330 tokens"
+static OPENCODE_TOKENS_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{1,3}(?:,\d{3})+|\d+) tokens$").unwrap());
+
+/// Detect the tokens an agent has used so far. Only OpenCode shows a running
+/// total; Claude Code's counters are per turn and are not used.
+pub fn detect_token_usage(output: &str, ai_agent: AiAgent) -> Option<u64> {
+    if ai_agent != AiAgent::Opencode {
+        return None;
+    }
+    let clean_output = strip_ansi(output);
+    clean_output.lines().rev().find_map(|line| {
+        let chars: Vec<char> = line.trim().chars().collect();
+        let side_panel: String = chars[chars.len().saturating_sub(SIDE_PANEL_WIDTH)..]
+            .iter()
+            .collect();
+        let side_panel = side_panel.trim();
+        // Take the trailing "N tokens" cell of the panel
+        let cell = side_panel.rsplit("  ").next()?.trim();
+        OPENCODE_TOKENS_PATTERN
+            .captures(cell)
+            .and_then(|caps| caps[1].replace(',', "").parse().ok())
+    })
 }
 
 /// Pattern to detect collapsed task count like "... +3 completed"
@@ -281,6 +299,11 @@ const SIDE_PANEL_WIDTH: usize = 60;
 /// Detect checklist progress based on agent type.
 /// Routes to the appropriate detection function for each AI agent.
 pub fn detect_checklist_progress(output: &str, ai_agent: AiAgent) -> Option<(u32, u32)> {
+    detect_checklist(output, ai_agent).map(|checklist| checklist.progress())
+}
+
+/// Detect the agent's checklist items based on agent type.
+pub fn detect_checklist(output: &str, ai_agent: AiAgent) -> Option<Checklist> {
     match ai_agent {
         AiAgent::ClaudeCode => detect_checklist_claude_code(output),
         AiAgent::Opencode => detect_checklist_opencode(output),
@@ -288,28 +311,41 @@ pub fn detect_checklist_progress(output: &str, ai_agent: AiAgent) -> Option<(u32
     }
 }
 
-/// Detect checklist progress from Claude Code output.
+/// Line-start markers and the item state they stand for. Bracketed markers
+/// come first so `[✓]` isn't read as `[` followed by text.
+const CHECKLIST_MARKERS: [(&str, ChecklistState); 18] = [
+    ("[✓]", ChecklistState::Done),
+    ("[✔]", ChecklistState::Done),
+    ("[✅]", ChecklistState::Done),
+    ("[•]", ChecklistState::InProgress),
+    ("[○]", ChecklistState::Pending),
+    ("[ ]", ChecklistState::Pending),
+    ("✓", ChecklistState::Done),
+    ("✔", ChecklistState::Done),
+    ("☑", ChecklistState::Done),
+    ("✅", ChecklistState::Done),
+    ("◼", ChecklistState::InProgress),
+    ("■", ChecklistState::InProgress),
+    ("▪", ChecklistState::InProgress),
+    ("●", ChecklistState::InProgress),
+    ("◻", ChecklistState::Pending),
+    ("□", ChecklistState::Pending),
+    ("☐", ChecklistState::Pending),
+    ("○", ChecklistState::Pending),
+];
+
+/// Lines that introduce Claude Code's todo list.
+const CHECKLIST_HEADERS: [&str; 2] = ["Update Todos", "Todos"];
+
+/// Detect checklist items from Claude Code output.
 /// Checks: task summary lines, collapsed counts, and line-start checkboxes.
-fn detect_checklist_claude_code(output: &str) -> Option<(u32, u32)> {
+/// Claude Code also starts every tool call with an unindented `●`, so that
+/// marker only counts indented inside a todo block, right after its header
+/// or another checklist line.
+fn detect_checklist_claude_code(output: &str) -> Option<Checklist> {
     let clean_output = strip_ansi(output);
-
-    // First, try to find the authoritative task summary line
-    // e.g., "11 tasks (9 done, 1 in progress, 1 open)"
-    for line in clean_output.lines() {
-        if let Some(caps) = TASK_SUMMARY_PATTERN.captures(line) {
-            if let (Some(total_match), Some(done_match)) = (caps.get(1), caps.get(2)) {
-                if let (Ok(total), Ok(done)) = (
-                    total_match.as_str().parse::<u32>(),
-                    done_match.as_str().parse::<u32>(),
-                ) {
-                    return Some((done, total));
-                }
-            }
-        }
-    }
-
-    let mut completed = 0u32;
-    let mut total = 0u32;
+    let mut checklist = Checklist::default();
+    let mut in_block = false;
 
     for line in clean_output.lines() {
         let trimmed = line.trim();
@@ -318,66 +354,58 @@ fn detect_checklist_claude_code(output: &str) -> Option<(u32, u32)> {
         if let Some(caps) = COLLAPSED_TASKS_PATTERN.captures(trimmed) {
             if let Some(count_match) = caps.get(1) {
                 if let Ok(count) = count_match.as_str().parse::<u32>() {
-                    completed += count;
-                    total += count;
+                    checklist.completed += count;
+                    checklist.total += count;
+                    in_block = true;
                     continue;
                 }
             }
         }
 
-        // Check line start for Claude Code style checkboxes
-        // Tree chars: │ ├ └ ─ followed by space, then the checkbox
-        let check_part = trimmed.trim_start_matches(['│', '├', '└', '─', ' ']);
-
-        if check_part.starts_with("[✓]")
-            || check_part.starts_with("[✔]")
-            || check_part.starts_with("[✅]")
-        {
-            completed += 1;
-            total += 1;
-        } else if check_part.starts_with("[•]")
-            || check_part.starts_with("[○]")
-            || check_part.starts_with("[ ]")
-        {
-            total += 1;
+        let title = trimmed.trim_start_matches(['●', ' ']);
+        if CHECKLIST_HEADERS.contains(&title) {
+            in_block = true;
+            continue;
         }
-        // Check for completed items (checkmarks) - various Unicode checkmarks
-        else if check_part.starts_with('✓')
-            || check_part.starts_with('✔')
-            || check_part.starts_with('☑')
-            || check_part.starts_with('✅')
-        {
-            completed += 1;
-            total += 1;
-        }
-        // Check for in-progress items (filled shapes) or not-started items (empty shapes)
-        else if check_part.starts_with('◼')
-            || check_part.starts_with('■')
-            || check_part.starts_with('▪')
-            || check_part.starts_with('●')
-            || check_part.starts_with('◻')
-            || check_part.starts_with('□')
-            || check_part.starts_with('☐')
-            || check_part.starts_with('○')
-        {
-            total += 1;
+
+        // Check line start for Claude Code style checkboxes
+        // Tree chars: │ ├ └ ─ ⎿ followed by space, then the checkbox
+        let check_part = trimmed.trim_start_matches(['│', '├', '└', '─', '⎿', ' ']);
+        let marker = CHECKLIST_MARKERS
+            .iter()
+            .find(|(marker, _)| check_part.starts_with(marker))
+            .filter(|(marker, _)| *marker != "●" || (in_block && !line.starts_with('●')));
+        in_block = marker.is_some();
+        if let Some((marker, state)) = marker {
+            checklist.push(&check_part[marker.len()..], *state);
         }
     }
 
-    if total > 0 {
-        Some((completed, total))
-    } else {
-        None
+    // The task summary line is authoritative for the counts
+    // e.g., "11 tasks (9 done, 1 in progress, 1 open)"
+    for line in clean_output.lines() {
+        if let Some(caps) = TASK_SUMMARY_PATTERN.captures(line) {
+            if let (Some(total_match), Some(done_match)) = (caps.get(1), caps.get(2)) {
+                if let (Ok(total), Ok(done)) = (
+                    total_match.as_str().parse::<u32>(),
+                    done_match.as_str().parse::<u32>(),
+                ) {
+                    checklist.completed = done;
+                    checklist.total = total;
+                    return Some(checklist);
+                }
+            }
+        }
     }
+
+    (checklist.total > 0).then_some(checklist)
 }
 
-/// Detect checklist progress from OpenCode side panel.
+/// Detect checklist items from OpenCode side panel.
 /// Only checks the rightmost portion of lines where the side panel is rendered.
-fn detect_checklist_opencode(output: &str) -> Option<(u32, u32)> {
+fn detect_checklist_opencode(output: &str) -> Option<Checklist> {
     let clean_output = strip_ansi(output);
-
-    let mut completed = 0u32;
-    let mut total = 0u32;
+    let mut checklist = Checklist::default();
 
     for line in clean_output.lines() {
         let trimmed = line.trim();
@@ -393,28 +421,21 @@ fn detect_checklist_opencode(output: &str) -> Option<(u32, u32)> {
         };
 
         // Simple string matching - avoid regex for performance
-        if side_panel.contains("[✓]") || side_panel.contains("[✔]") || side_panel.contains("[✅]")
-        {
-            completed += 1;
-            total += 1;
-        } else if side_panel.contains("[•]")
-            || side_panel.contains("[○]")
-            || side_panel.contains("[ ]")
-        {
-            total += 1;
+        let found = CHECKLIST_MARKERS
+            .iter()
+            .filter(|(marker, _)| marker.starts_with('['))
+            .find_map(|(marker, state)| side_panel.find(marker).map(|i| (i, marker, state)));
+        if let Some((index, marker, state)) = found {
+            checklist.push(&side_panel[index + marker.len()..], *state);
         }
     }
 
-    if total > 0 {
-        Some((completed, total))
-    } else {
-        None
-    }
+    (checklist.total > 0).then_some(checklist)
 }
 
 /// Generic checklist detection for Codex/Gemini agents.
 /// Uses Claude Code style detection as a fallback.
-fn detect_checklist_generic(output: &str) -> Option<(u32, u32)> {
+fn detect_checklist_generic(output: &str) -> Option<Checklist> {
     detect_checklist_claude_code(output)
 }
 
@@ -1267,6 +1288,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_detect_token_usage() {
+        let output = "  ┃  - ❯ (prompt)                                      78,330 tokens\n\
+                      \x20 ┃  - Separator line                                   38% used\n";
+        assert_eq!(detect_token_usage(output, AiAgent::Opencode), Some(78_330));
+        assert_eq!(
+            detect_token_usage("✢ Sketching… (1m 14s · ↓ 2.9k tokens)", AiAgent::ClaudeCode),
+            None
+        );
+        assert_eq!(detect_token_usage("no usage here", AiAgent::Opencode), None);
+    }
+
     #[test]
     fn test_opencode_idle_with_plan_mode() {
         let output = r#"  ┃  hints.                                                                                                                                           Claude Code status check refinement:
@@ -1433,6 +1466,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_claude_code_checklist_items() {
+        let output = "  ⎿  ✓ Write parser\n     ◼ Add tests\n     ◻ Update README";
+        let checklist = detect_checklist(output, AiAgent::ClaudeCode).unwrap();
+        let items: Vec<_> = checklist
+            .items
+            .iter()
+            .map(|i| (i.text.as_str(), i.state))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("Write parser", ChecklistState::Done),
+                ("Add tests", ChecklistState::InProgress),
+                ("Update README", ChecklistState::Pending),
+            ]
+        );
+        assert_eq!(checklist.current().unwrap().text, "Add tests");
+    }
+
+    #[test]
+    fn test_claude_code_tool_bullets_are_not_checklist_items() {
+        let output = r#"● Read(src/parser.rs)
+  ⎿  Read 120 lines
+● Update Todos
+  ⎿  ✓ Write parser
+     ● Add tests
+     ◻ Update README
+● Bash(cargo test)
+  ⎿  running 3 tests
+● The parser is done, moving on to the tests."#;
+        let checklist = detect_checklist(output, AiAgent::ClaudeCode).unwrap();
+        let items: Vec<_> = checklist
+            .items
+            .iter()
+            .map(|i| (i.text.as_str(), i.state))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("Write parser", ChecklistState::Done),
+                ("Add tests", ChecklistState::InProgress),
+                ("Update README", ChecklistState::Pending),
+            ]
+        );
+        assert_eq!(checklist.progress(), (1, 3));
+    }
+
+    #[test]
+    fn test_opencode_side_panel_items() {
+        let output = "  ┃  Let me continue.                                                                                                                     [•] Create src/hooks/useTodos.ts";
+        let checklist = detect_checklist(output, AiAgent::Opencode).unwrap();
+        assert_eq!(checklist.items[0].text, "Create src/hooks/useTodos.ts");
+        assert_eq!(checklist.items[0].state, ChecklistState::InProgress);
+    }
+
     #[test]
     fn test_claude_code_task_summary() {
         // Claude Code shows authoritative task summary line
@@ -0,0 +1,178 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::{detect_status_for_agent, AgentStatus, ForegroundProcess, StatusDetection};
+use crate::app::config::{AiAgent, Config};
+
+/// A pane capture saved with the status Grove detected and the status it
+/// should have detected, replayed by `grove detect`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectionFixture {
+    pub ai_agent: AiAgent,
+    /// `#{pane_current_command}` when the pane was captured
+    pub foreground: String,
+    pub detected: AgentStatus,
+    pub expected: AgentStatus,
+    pub captured_at: DateTime<Utc>,
+    /// Plain pane capture, as used for status detection
+    pub content: String,
+}
+
+impl DetectionFixture {
+    /// Capture a tmux session the way the status poller does and record what
+    /// the detector makes of it.
+    pub fn capture(session_name: &str, ai_agent: AiAgent, expected: AgentStatus) -> Result<Self> {
+        let content = tmux_output(&["capture-pane", "-t", session_name, "-p", "-J", "-S", "-100"])?;
+        let foreground = tmux_output(&[
+            "display-message",
+            "-t",
+            session_name,
+            "-p",
+            "#{pane_current_command}",
+        ])?
+        .trim()
+        .to_string();
+        let mut fixture = Self {
+            ai_agent,
+            foreground,
+            detected: AgentStatus::Idle,
+            expected,
+            captured_at: Utc::now(),
+            content,
+        };
+        fixture.detected = fixture.replay().status;
+        Ok(fixture)
+    }
+
+    /// Run the capture through the detector again.
+    pub fn replay(&self) -> StatusDetection {
+        let foreground =
+            ForegroundProcess::from_command_for_agent(&self.foreground, self.ai_agent.clone());
+        detect_status_for_agent(&self.content, foreground, self.ai_agent.clone())
+    }
+
+    /// Whether `detection` matches the expected status (error messages aside).
+    pub fn matches(&self, detection: &StatusDetection) -> bool {
+        std::mem::discriminant(&detection.status) == std::mem::discriminant(&self.expected)
+    }
+
+    /// Write the fixture to `dir` as `<name>-<timestamp>.json`.
+    pub fn save(&self, dir: &Path, name: &str) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir).context("Failed to create fixture directory")?;
+        let stem: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let path = dir.join(format!(
+            "{}-{}.json",
+            stem,
+            self.captured_at.format("%Y%m%d-%H%M%S")
+        ));
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Fixtures in a `.json` file or in every `.json` file of a directory.
+    pub fn load(path: &Path) -> Result<Vec<(PathBuf, Self)>> {
+        let files = if path.is_dir() {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                .collect();
+            files.sort();
+            files
+        } else {
+            vec![path.to_path_buf()]
+        };
+        files
+            .into_iter()
+            .map(|file| {
+                let content = std::fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?;
+                let fixture = serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse {}", file.display()))?;
+                Ok((file, fixture))
+            })
+            .collect()
+    }
+}
+
+/// Where captured fixtures are written.
+pub fn fixtures_dir() -> Result<PathBuf> {
+    Ok(Config::config_dir()?.join("fixtures"))
+}
+
+/// Status from a name typed by the user.
+pub fn parse_status(input: &str) -> Option<AgentStatus> {
+    match input
+        .trim()
+        .to_lowercase()
+        .replace([' ', '_'], "-")
+        .as_str()
+    {
+        "running" => Some(AgentStatus::Running),
+        "awaiting" | "awaiting-input" | "waiting" => Some(AgentStatus::AwaitingInput),
+        "completed" | "done" => Some(AgentStatus::Completed),
+        "idle" => Some(AgentStatus::Idle),
+        "error" => Some(AgentStatus::Error(String::new())),
+        "stopped" => Some(AgentStatus::Stopped),
+        _ => None,
+    }
+}
+
+fn tmux_output(args: &[&str]) -> Result<String> {
+    let output = Command::new("tmux")
+        .args(args)
+        .output()
+        .context("Failed to execute tmux")?;
+    if !output.status.success() {
+        bail!(
+            "tmux {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every fixture checked into `fixtures/detector` must still detect as expected.
+    #[test]
+    fn test_checked_in_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/detector");
+        let fixtures = DetectionFixture::load(&dir).unwrap();
+        assert!(!fixtures.is_empty());
+        for (path, fixture) in fixtures {
+            let detection = fixture.replay();
+            assert!(
+                fixture.matches(&detection),
+                "{}: expected {:?}, detected {:?} ({})",
+                path.display(),
+                fixture.expected,
+                detection.status,
+                detection.reason.unwrap_or_default()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(parse_status(" Awaiting "), Some(AgentStatus::AwaitingInput));
+        assert_eq!(
+            parse_status(AgentStatus::AwaitingInput.label()),
+            Some(AgentStatus::AwaitingInput)
+        );
+        assert_eq!(parse_status("done"), Some(AgentStatus::Completed));
+        assert!(matches!(parse_status("error"), Some(AgentStatus::Error(_))));
+        assert_eq!(parse_status("busy"), None);
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::Path;
+
+use crate::app::config::GitProvider;
+
+/// Existing work that can be adopted as an agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportSource {
+    /// A local (or `origin/`) branch that gets a fresh worktree.
+    Branch(String),
+    /// A worktree that already exists on disk.
+    Worktree(String),
+    /// A pull/merge request fetched from origin by number.
+    PullRequest(u64),
+}
+
+impl ImportSource {
+    /// Parse user input: `#123` / `!123` / `123` is a PR/MR number, an existing
+    /// directory is a worktree, anything else is a branch name.
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return None;
+        }
+
+        let number = input.trim_start_matches(['#', '!']);
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            return number.parse().ok().map(ImportSource::PullRequest);
+        }
+
+        let expanded = if let Some(rest) = input.strip_prefix("~/") {
+            dirs::home_dir()
+                .map(|home| home.join(rest).to_string_lossy().to_string())
+                .unwrap_or_else(|| input.to_string())
+        } else {
+            input.to_string()
+        };
+
+        let looks_like_path =
+            expanded.starts_with('/') || expanded.starts_with("./") || expanded.starts_with("../");
+        if looks_like_path && Path::new(&expanded).is_dir() {
+            return Some(ImportSource::Worktree(expanded));
+        }
+
+        Some(ImportSource::Branch(input.to_string()))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            ImportSource::Branch(branch) => format!("branch '{}'", branch),
+            ImportSource::Worktree(path) => format!("worktree '{}'", path),
+            ImportSource::PullRequest(number) => format!("PR #{}", number),
+        }
+    }
+}
+
+/// Whether two worktree paths point at the same directory.
+pub fn same_worktree(a: &str, b: &str) -> bool {
+    let canonical = |p: &str| std::fs::canonicalize(p).unwrap_or_else(|_| p.into());
+    canonical(a) == canonical(b)
+}
+
+/// Remote ref and local branch name used to fetch a PR/MR for a provider.
+pub fn pull_request_refspec(provider: GitProvider, number: u64) -> (String, String) {
+    match provider {
+        GitProvider::GitLab => (
+            format!("refs/merge-requests/{}/head", number),
+            format!("mr-{}", number),
+        ),
+        GitProvider::GitHub | GitProvider::Codeberg => (
+            format!("refs/pull/{}/head", number),
+            format!("pr-{}", number),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pull_request_numbers() {
+        assert_eq!(
+            ImportSource::parse("#42"),
+            Some(ImportSource::PullRequest(42))
+        );
+        assert_eq!(
+            ImportSource::parse("!7"),
+            Some(ImportSource::PullRequest(7))
+        );
+        assert_eq!(
+            ImportSource::parse(" 100 "),
+            Some(ImportSource::PullRequest(100))
+        );
+    }
+
+    #[test]
+    fn test_parse_branch_and_worktree() {
+        assert_eq!(
+            ImportSource::parse("feature/login"),
+            Some(ImportSource::Branch("feature/login".to_string()))
+        );
+        assert_eq!(ImportSource::parse("   "), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        assert_eq!(
+            ImportSource::parse(&path),
+            Some(ImportSource::Worktree(path.clone()))
+        );
+    }
+
+    #[test]
+    fn test_same_worktree() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        assert!(same_worktree(&path, &format!("{}/.", path)));
+        assert!(!same_worktree(&path, "/nonexistent/worktree"));
+    }
+
+    #[test]
+    fn test_pull_request_refspec() {
+        assert_eq!(
+            pull_request_refspec(GitProvider::GitHub, 5),
+            ("refs/pull/5/head".to_string(), "pr-5".to_string())
+        );
+        assert_eq!(
+            pull_request_refspec(GitProvider::GitLab, 5),
+            ("refs/merge-requests/5/head".to_string(), "mr-5".to_string())
+        );
+    }
+}
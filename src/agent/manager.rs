@@ -2,8 +2,8 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-use super::{Agent, AgentStatus};
-use crate::app::config::AiAgent;
+use super::{pull_request_refspec, Agent, AgentStatus, ImportSource, InitialPrompt};
+use crate::app::config::{AiAgent, GitProvider, LaunchProfile};
 use crate::git::Worktree;
 use crate::tmux::TmuxSession;
 
@@ -11,6 +11,10 @@ use crate::tmux::TmuxSession;
 pub struct AgentManager {
     pub repo_path: String,
     pub worktree_base: PathBuf,
+    /// Install Claude Code status hooks before starting sessions
+    pub claude_hooks: bool,
+    /// Socket of Grove's MCP server, given to every session when set
+    pub mcp_socket: Option<PathBuf>,
 }
 
 impl AgentManager {
@@ -18,40 +22,170 @@ impl AgentManager {
         Self {
             repo_path: repo_path.to_string(),
             worktree_base,
+            claude_hooks: false,
+            mcp_socket: None,
         }
     }
 
-    /// Create a new agent with worktree and tmux session.
+    pub fn with_claude_hooks(mut self, enabled: bool) -> Self {
+        self.claude_hooks = enabled;
+        self
+    }
+
+    pub fn with_mcp_socket(mut self, socket: Option<PathBuf>) -> Self {
+        self.mcp_socket = socket;
+        self
+    }
+
+    /// Point Claude Code's hooks at the agent's event file and finish the
+    /// session's `command` with the agent's profile and Grove's MCP server.
+    fn prepare_session(&self, agent: &Agent, ai_agent: &AiAgent, command: &str) -> String {
+        if self.claude_hooks && *ai_agent == AiAgent::ClaudeCode {
+            if let Err(e) = super::claude_hooks::install(&agent.worktree_path, agent.id) {
+                tracing::warn!("Failed to install Claude Code hooks: {}", e);
+            }
+        }
+        let command = super::profile::launch_command(agent.profile.as_ref(), command);
+        let Some(socket) = &self.mcp_socket else {
+            return command;
+        };
+        crate::mcp::launch::ServerCommand::new(socket, agent.id)
+            .and_then(|server| {
+                crate::mcp::launch::apply(ai_agent, &command, &agent.worktree_path, &server)
+            })
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to set up Grove's MCP server: {}", e);
+                command
+            })
+    }
+
+    /// Create a new agent with its worktree. The tmux session is started
+    /// separately by `start_session`, once the worktree is provisioned.
     pub fn create_agent(
         &self,
         name: &str,
         branch: &str,
-        ai_agent: &AiAgent,
+        base_branch: Option<&str>,
+        profile: Option<&LaunchProfile>,
         worktree_symlinks: &[String],
     ) -> Result<Agent> {
         tracing::debug!(
-            "AgentManager::create_agent - name: {:?}, branch: {:?}, repo_path: {:?}, worktree_base: {:?}",
+            "AgentManager::create_agent - name: {:?}, branch: {:?}, base: {:?}, repo_path: {:?}, worktree_base: {:?}",
             name,
             branch,
+            base_branch,
             self.repo_path,
             self.worktree_base
         );
         let worktree = Worktree::new(&self.repo_path, self.worktree_base.clone());
         let worktree_path = worktree
-            .create(branch)
+            .create_from(branch, base_branch)
             .context("Failed to create worktree")?;
 
         worktree
             .create_symlinks(&worktree_path, worktree_symlinks)
             .context("Failed to create worktree symlinks")?;
 
-        let agent = Agent::new(name.to_string(), branch.to_string(), worktree_path.clone());
+        let mut agent = Agent::new(name.to_string(), branch.to_string(), worktree_path);
+        if let Some(base) = base_branch {
+            agent.base_branch = Some(base.to_string());
+            agent.base_commit = worktree.branch_tip(base).ok();
+        }
+
+        agent.profile = profile.cloned();
+        Ok(agent)
+    }
+
+    /// Command that starts a new agent's first session with `prompt`, or its
+    /// profile's initial prompt filled in from `task`.
+    pub fn initial_command(
+        &self,
+        agent: &Agent,
+        ai_agent: &AiAgent,
+        task: Option<&str>,
+        prompt: Option<&InitialPrompt>,
+    ) -> String {
+        super::profile::initial_command(
+            agent.profile.as_ref(),
+            ai_agent,
+            agent,
+            &self.repo_path,
+            task,
+            prompt,
+        )
+    }
 
+    /// Start the agent's tmux session running `command`. Does nothing if the
+    /// session is already running (e.g. the user attached during setup).
+    pub fn start_session(&self, agent: &Agent, ai_agent: &AiAgent, command: &str) -> Result<()> {
         let session = TmuxSession::new(&agent.tmux_session);
+        if session.exists() {
+            return Ok(());
+        }
+        let command = self.prepare_session(agent, ai_agent, command);
         session
-            .create(&worktree_path, ai_agent.command())
-            .context("Failed to create tmux session")?;
+            .create(&agent.worktree_path, &command)
+            .context("Failed to create tmux session")
+    }
+
+    /// Worktree an import of `source` would use, without touching git.
+    pub fn import_worktree_path(&self, source: &ImportSource, provider: GitProvider) -> String {
+        let worktree = Worktree::new(&self.repo_path, self.worktree_base.clone());
+        match source {
+            ImportSource::Branch(branch) => worktree.worktree_path_for_branch(branch),
+            ImportSource::Worktree(path) => PathBuf::from(path),
+            ImportSource::PullRequest(number) => {
+                worktree.worktree_path_for_branch(&pull_request_refspec(provider, *number).1)
+            }
+        }
+        .to_string_lossy()
+        .to_string()
+    }
+
+    /// Adopt an existing branch, worktree or PR/MR as an agent. Its session,
+    /// started with `start_session`, should resume any earlier AI
+    /// conversation in the worktree (see `resume_command`).
+    pub fn import_agent(
+        &self,
+        source: &ImportSource,
+        provider: GitProvider,
+        profile: Option<&LaunchProfile>,
+        worktree_symlinks: &[String],
+    ) -> Result<Agent> {
+        tracing::debug!("AgentManager::import_agent - source: {:?}", source);
+        let worktree = Worktree::new(&self.repo_path, self.worktree_base.clone());
 
+        let (branch, worktree_path, adopted) = match source {
+            ImportSource::Branch(branch) => {
+                worktree.ensure_local_branch(branch)?;
+                let path = worktree
+                    .create(branch)
+                    .context("Failed to create worktree")?;
+                (branch.clone(), path, false)
+            }
+            ImportSource::Worktree(path) => {
+                let branch = worktree.branch_for_existing(path)?;
+                (branch, path.clone(), true)
+            }
+            ImportSource::PullRequest(number) => {
+                let (remote_ref, branch) = pull_request_refspec(provider, *number);
+                worktree.fetch_pull_request(&remote_ref, &branch)?;
+                let path = worktree
+                    .create(&branch)
+                    .context("Failed to create worktree")?;
+                (branch, path, false)
+            }
+        };
+
+        if !adopted {
+            worktree
+                .create_symlinks(&worktree_path, worktree_symlinks)
+                .context("Failed to create worktree symlinks")?;
+        }
+
+        let mut agent = Agent::new(branch.clone(), branch, worktree_path);
+        agent.adopted_worktree = adopted;
+        agent.profile = profile.cloned();
         Ok(agent)
     }
 
@@ -62,9 +196,10 @@ impl AgentManager {
         if session.exists() {
             session.kill().context("Failed to kill tmux session")?;
         }
+        super::claude_hooks::remove(agent.id);
 
-        // Remove worktree
-        if Path::new(&agent.worktree_path).exists() {
+        // Remove worktree (adopted worktrees are left alone)
+        if !agent.adopted_worktree && Path::new(&agent.worktree_path).exists() {
             let worktree = Worktree::new(&self.repo_path, self.worktree_base.clone());
             worktree
                 .remove(&agent.worktree_path)
@@ -80,14 +215,36 @@ impl AgentManager {
         let session = TmuxSession::new(&agent.tmux_session);
 
         if !session.exists() {
+            let command = self.prepare_session(agent, ai_agent, ai_agent.command());
             session
-                .create(&agent.worktree_path, ai_agent.command())
+                .create(&agent.worktree_path, &command)
                 .context("Failed to create tmux session")?;
         }
 
         session.attach()
     }
 
+    /// Start a session for an agent restored from the last run, resuming its
+    /// AI conversation. Does nothing if the session is still running.
+    pub fn continue_agent(&self, agent: &Agent, ai_agent: &AiAgent) -> Result<()> {
+        let session = TmuxSession::new(&agent.tmux_session);
+        if session.exists() {
+            return Ok(());
+        }
+        let command = self.prepare_session(
+            agent,
+            ai_agent,
+            &resume_command(
+                ai_agent,
+                &agent.worktree_path,
+                agent.ai_session_id.as_deref(),
+            ),
+        );
+        session
+            .create(&agent.worktree_path, &command)
+            .context("Failed to create tmux session")
+    }
+
     /// Get the current output from an agent's tmux session.
     pub fn capture_output(&self, agent: &Agent, lines: usize) -> Result<String> {
         let session = TmuxSession::new(&agent.tmux_session);
@@ -126,20 +283,41 @@ impl AgentManager {
     pub fn restart_agent(&self, agent: &Agent, ai_agent: &AiAgent) -> Result<()> {
         let session = TmuxSession::new(&agent.tmux_session);
 
+        let command = self.prepare_session(agent, ai_agent, ai_agent.command());
         if !session.exists() {
-            session.create(&agent.worktree_path, ai_agent.command())?;
+            session.create(&agent.worktree_path, &command)?;
         } else {
-            let _ = std::process::Command::new("tmux")
-                .args(["send-keys", "-t", &agent.tmux_session, "C-c"])
-                .output();
+            let _ = session.interrupt();
 
             std::thread::sleep(std::time::Duration::from_millis(100));
-            session.send_keys(ai_agent.command())?;
+            session.send_keys(&command)?;
         }
 
         Ok(())
     }
 
+    /// Replace an agent's tmux session with one that resumes `session_id`, or
+    /// starts a new conversation for `None`.
+    pub fn relaunch_agent(
+        &self,
+        agent: &Agent,
+        ai_agent: &AiAgent,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        let session = TmuxSession::new(&agent.tmux_session);
+        if session.exists() {
+            session.kill().context("Failed to stop tmux session")?;
+        }
+        let command = self.prepare_session(
+            agent,
+            ai_agent,
+            &super::sessions::launch_command(ai_agent, session_id),
+        );
+        session
+            .create(&agent.worktree_path, &command)
+            .context("Failed to create tmux session")
+    }
+
     /// Get info about all currently running grove sessions.
     pub fn list_running_sessions() -> Result<Vec<String>> {
         crate::tmux::list_grove_sessions()
@@ -159,3 +337,50 @@ impl AgentManager {
             .collect())
     }
 }
+
+/// Build the command that launches `ai_agent` in `worktree_path`, resuming the
+/// cached session or the most recent one found for that directory.
+pub fn resume_command(
+    ai_agent: &AiAgent,
+    worktree_path: &str,
+    cached_session_id: Option<&str>,
+) -> String {
+    let cached = cached_session_id
+        .filter(|id| !id.is_empty())
+        .map(String::from);
+
+    match ai_agent {
+        AiAgent::Opencode => {
+            let session_id = cached.or_else(|| {
+                crate::opencode::find_session_by_directory(worktree_path)
+                    .ok()
+                    .flatten()
+            });
+            crate::opencode::build_command_with_session(ai_agent.command(), session_id.as_deref())
+        }
+        AiAgent::ClaudeCode => {
+            let session_id = cached.or_else(|| {
+                crate::claude_code::find_session_by_directory(worktree_path)
+                    .ok()
+                    .flatten()
+            });
+            crate::claude_code::build_resume_command(ai_agent.command(), session_id.as_deref())
+        }
+        AiAgent::Codex => {
+            let session_id = cached.or_else(|| {
+                crate::codex::find_session_by_directory(worktree_path)
+                    .ok()
+                    .flatten()
+            });
+            crate::codex::build_resume_command(ai_agent.command(), session_id.as_deref())
+        }
+        AiAgent::Gemini => {
+            let session_id = cached.or_else(|| {
+                crate::gemini::find_session_by_directory(worktree_path)
+                    .ok()
+                    .flatten()
+            });
+            crate::gemini::build_resume_command(ai_agent.command(), session_id.as_deref())
+        }
+    }
+}
@@ -1,10 +1,37 @@
+pub mod budget;
+pub mod checklist;
+pub mod claude_hooks;
 pub mod detector;
+pub mod fixture;
+pub mod import;
 pub mod manager;
 pub mod model;
+pub mod overlap;
+pub mod profile;
+pub mod provision;
+pub mod resources;
+pub mod sessions;
+pub mod stack;
+pub mod stall;
+pub mod transcript;
 
+pub use checklist::{Checklist, ChecklistItem, ChecklistState, PlanChange};
 pub use detector::{
-    detect_checklist_progress, detect_mr_url, detect_status, detect_status_for_agent,
-    detect_status_with_process, ForegroundProcess, StatusDetection,
+    detect_checklist, detect_checklist_progress, detect_mr_url, detect_status,
+    detect_status_for_agent, detect_status_with_process, detect_token_usage, ForegroundProcess,
+    StatusDetection,
 };
-pub use manager::AgentManager;
+pub use fixture::DetectionFixture;
+pub use import::{pull_request_refspec, same_worktree, ImportSource};
+pub use manager::{resume_command, AgentManager};
 pub use model::{Agent, AgentStatus, ProjectMgmtTaskStatus, StatusReason};
+pub use overlap::{
+    detect_overlaps, find_overlaps, overlap_levels, AgentOverlap, OverlapCandidate, OverlapLevel,
+};
+pub use profile::InitialPrompt;
+pub use provision::{prepare_files, run_hooks, TemplateVars};
+pub use resources::{ProcessUsage, ResourceUsage};
+pub use sessions::SessionSummary;
+pub use stack::restack_order;
+pub use stall::{Stall, StallDetector};
+pub use transcript::{TranscriptEvent, TranscriptEventKind, TranscriptTail};
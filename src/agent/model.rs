@@ -4,6 +4,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::checklist::plan_changes;
+use super::{Checklist, PlanChange, Stall, TranscriptEvent};
+use crate::app::config::{AiAgent, BudgetConfig, LaunchProfile};
 use crate::core::git_providers::codeberg::PullRequestStatus as CodebergPullRequestStatus;
 use crate::core::git_providers::github::PullRequestStatus;
 use crate::core::git_providers::gitlab::MergeRequestStatus;
@@ -15,6 +18,8 @@ use crate::core::projects::notion::NotionTaskStatus;
 use crate::git::GitSyncStatus;
 
 const ACTIVITY_HISTORY_SIZE: usize = 20;
+const TRANSCRIPT_HISTORY_SIZE: usize = 200;
+const PLAN_HISTORY_SIZE: usize = 100;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StatusReason {
@@ -240,6 +245,12 @@ pub struct Agent {
     /// Checklist progress (completed, total) if a checklist is detected
     #[serde(skip)]
     pub checklist_progress: Option<(u32, u32)>,
+    /// Last checklist items seen in the pane
+    #[serde(skip)]
+    pub plan: Option<Checklist>,
+    /// How the plan's items changed over time
+    #[serde(skip)]
+    pub plan_history: VecDeque<PlanChange>,
     /// Legacy Asana task status (for backward compatibility during migration)
     #[serde(default, skip_serializing)]
     pub asana_task_status: AsanaTaskStatus,
@@ -249,6 +260,9 @@ pub struct Agent {
     /// Whether a work summary has been requested for this agent
     #[serde(default)]
     pub summary_requested: bool,
+    /// Latest work summary the agent posted through Grove's MCP server
+    #[serde(default)]
+    pub summary: Option<String>,
     /// Whether to auto-continue this session on app restart
     #[serde(default)]
     pub continue_session: bool,
@@ -258,6 +272,40 @@ pub struct Agent {
     /// Legacy field for backward compatibility (migrated to ai_session_id)
     #[serde(default, skip_serializing)]
     pub opencode_session_id: Option<String>,
+    /// Worktree was adopted from outside Grove and is left on disk when deleted
+    #[serde(default)]
+    pub adopted_worktree: bool,
+    /// Branch this agent was started from; `None` means the repo's main branch
+    #[serde(default)]
+    pub base_branch: Option<String>,
+    /// Tip of `base_branch` the agent's commits currently sit on (used to restack)
+    #[serde(default)]
+    pub base_commit: Option<String>,
+    /// Port allocated for this agent's dev server
+    #[serde(default)]
+    pub dev_port: Option<u16>,
+    /// Overrides the repo's budget for this agent
+    #[serde(default)]
+    pub budget: Option<BudgetConfig>,
+    /// Launch profile the agent was created with, reused on every restart
+    #[serde(default)]
+    pub profile: Option<LaunchProfile>,
+    /// When the agent session was started or last resumed by Grove. Kept
+    /// across restarts of Grove, since the session keeps running
+    #[serde(default = "Utc::now")]
+    pub session_started_at: DateTime<Utc>,
+    /// When `status` last changed
+    #[serde(skip, default = "Utc::now")]
+    pub status_since: DateTime<Utc>,
+    /// Tokens reported by the AI agent, when it shows a running total
+    #[serde(skip)]
+    pub token_usage: Option<u64>,
+    /// Set while a running agent looks stalled or stuck in a loop
+    #[serde(skip)]
+    pub stall: Option<Stall>,
+    /// Recent events from the AI agent's session log (Codex, Gemini)
+    #[serde(skip)]
+    pub transcript: VecDeque<TranscriptEvent>,
     #[serde(skip)]
     pub status_reason: Option<StatusReason>,
     #[serde(skip)]
@@ -289,18 +337,53 @@ impl Agent {
             last_activity: Utc::now(),
             activity_history: VecDeque::with_capacity(ACTIVITY_HISTORY_SIZE),
             checklist_progress: None,
+            plan: None,
+            plan_history: VecDeque::new(),
             asana_task_status: AsanaTaskStatus::None,
             pm_task_status: ProjectMgmtTaskStatus::None,
             summary_requested: false,
+            summary: None,
             continue_session: true,
             ai_session_id: None,
             opencode_session_id: None,
+            adopted_worktree: false,
+            base_branch: None,
+            base_commit: None,
+            dev_port: None,
+            budget: None,
+            profile: None,
+            session_started_at: Utc::now(),
+            status_since: Utc::now(),
+            token_usage: None,
+            stall: None,
+            transcript: VecDeque::new(),
             status_reason: None,
             pending_status: None,
             pending_status_count: 0,
         }
     }
 
+    /// Branch used for diffs, merges and divergence: the agent's base or main.
+    pub fn base_branch_or<'a>(&'a self, main_branch: &'a str) -> &'a str {
+        self.base_branch.as_deref().unwrap_or(main_branch)
+    }
+
+    /// AI agent from the launch profile, or `default`.
+    pub fn ai_agent_or(&self, default: &AiAgent) -> AiAgent {
+        self.profile
+            .as_ref()
+            .and_then(|p| p.ai_agent.clone())
+            .unwrap_or_else(|| default.clone())
+    }
+
+    /// Ref to diff against: local base for stacked agents, `origin/<main>` otherwise.
+    pub fn diff_base_ref(&self, main_branch: &str) -> String {
+        match &self.base_branch {
+            Some(base) => base.clone(),
+            None => format!("origin/{}", main_branch),
+        }
+    }
+
     pub fn migrate_legacy(&mut self) {
         if !matches!(self.asana_task_status, AsanaTaskStatus::None)
             && matches!(self.pm_task_status, ProjectMgmtTaskStatus::None)
@@ -323,6 +406,39 @@ impl Agent {
         }
     }
 
+    pub fn push_transcript(&mut self, events: Vec<TranscriptEvent>) {
+        self.transcript.extend(events);
+        let excess = self
+            .transcript
+            .len()
+            .saturating_sub(TRANSCRIPT_HISTORY_SIZE);
+        self.transcript.drain(..excess);
+    }
+
+    /// Record the checklist currently in the pane. The plan is kept when the
+    /// checklist scrolls out of view.
+    pub fn update_checklist(&mut self, checklist: Option<Checklist>) {
+        self.checklist_progress = checklist.as_ref().map(Checklist::progress);
+        let Some(checklist) = checklist.filter(|c| !c.items.is_empty()) else {
+            return;
+        };
+        let old_items = self
+            .plan
+            .as_ref()
+            .map(|p| p.items.as_slice())
+            .unwrap_or(&[]);
+        let now = Utc::now();
+        for description in plan_changes(old_items, &checklist.items) {
+            self.plan_history.push_back(PlanChange {
+                at: now,
+                description,
+            });
+        }
+        let excess = self.plan_history.len().saturating_sub(PLAN_HISTORY_SIZE);
+        self.plan_history.drain(..excess);
+        self.plan = Some(checklist);
+    }
+
     /// Get sparkline data as 0/1 values for rendering
     pub fn sparkline_data(&self) -> Vec<u64> {
         self.activity_history
@@ -363,6 +479,9 @@ impl Agent {
     }
 
     pub fn set_status(&mut self, status: AgentStatus) {
+        if std::mem::discriminant(&status) != std::mem::discriminant(&self.status) {
+            self.status_since = Utc::now();
+        }
         self.status = status;
     }
 }
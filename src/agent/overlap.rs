@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::git::{GitSync, Worktree};
+
+/// Two agents whose branches touch the same files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentOverlap {
+    pub a: Uuid,
+    pub b: Uuid,
+    /// Files changed on both branches
+    pub files: Vec<String>,
+    /// Files a trial merge of the two branches could not resolve
+    pub conflicts: Vec<String>,
+}
+
+impl AgentOverlap {
+    pub fn involves(&self, id: Uuid) -> bool {
+        self.a == id || self.b == id
+    }
+
+    pub fn other(&self, id: Uuid) -> Uuid {
+        if self.a == id {
+            self.b
+        } else {
+            self.a
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OverlapLevel {
+    /// Shares changed files with another agent
+    Overlap,
+    /// Would conflict when merged with another agent
+    Conflict,
+}
+
+impl OverlapLevel {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            OverlapLevel::Overlap => "◇",
+            OverlapLevel::Conflict => "⚡",
+        }
+    }
+}
+
+/// Agent data needed to compute overlaps off the UI thread.
+#[derive(Debug, Clone)]
+pub struct OverlapCandidate {
+    pub id: Uuid,
+    pub worktree_path: String,
+    pub branch: String,
+    pub base_ref: String,
+}
+
+/// Compute changed files per agent, pair up overlapping agents and trial-merge
+/// each overlapping pair to find real conflicts.
+pub fn detect_overlaps(worktree: &Worktree, candidates: &[OverlapCandidate]) -> Vec<AgentOverlap> {
+    let changed: Vec<(Uuid, Vec<String>)> = candidates
+        .iter()
+        .filter_map(|c| {
+            GitSync::new(&c.worktree_path)
+                .changed_files(&c.base_ref)
+                .ok()
+                .map(|files| (c.id, files))
+        })
+        .collect();
+
+    let mut overlaps = find_overlaps(&changed);
+    for overlap in &mut overlaps {
+        let branch_of = |id: Uuid| {
+            candidates
+                .iter()
+                .find(|c| c.id == id)
+                .map(|c| c.branch.as_str())
+        };
+        if let (Some(a), Some(b)) = (branch_of(overlap.a), branch_of(overlap.b)) {
+            overlap.conflicts = worktree.trial_merge_conflicts(a, b).unwrap_or_default();
+        }
+    }
+
+    overlaps
+}
+
+/// Pair up agents that changed at least one common file. Conflicts are left
+/// empty for the caller to fill in with a trial merge.
+pub fn find_overlaps(changed: &[(Uuid, Vec<String>)]) -> Vec<AgentOverlap> {
+    let mut overlaps = Vec::new();
+
+    for (i, (a, files_a)) in changed.iter().enumerate() {
+        for (b, files_b) in &changed[i + 1..] {
+            let mut files: Vec<String> = files_a
+                .iter()
+                .filter(|f| files_b.contains(f))
+                .cloned()
+                .collect();
+            if files.is_empty() {
+                continue;
+            }
+            files.sort();
+            files.dedup();
+            overlaps.push(AgentOverlap {
+                a: *a,
+                b: *b,
+                files,
+                conflicts: Vec::new(),
+            });
+        }
+    }
+
+    overlaps
+}
+
+/// Worst overlap level per agent.
+pub fn overlap_levels(overlaps: &[AgentOverlap]) -> HashMap<Uuid, OverlapLevel> {
+    let mut levels: HashMap<Uuid, OverlapLevel> = HashMap::new();
+    for overlap in overlaps {
+        let level = if overlap.conflicts.is_empty() {
+            OverlapLevel::Overlap
+        } else {
+            OverlapLevel::Conflict
+        };
+        for id in [overlap.a, overlap.b] {
+            let entry = levels.entry(id).or_insert(level);
+            *entry = (*entry).max(level);
+        }
+    }
+    levels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_overlaps_pairs_shared_files() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let changed = vec![
+            (a, files(&["src/main.rs", "README.md"])),
+            (b, files(&["src/lib.rs"])),
+            (c, files(&["README.md", "src/lib.rs"])),
+        ];
+
+        let overlaps = find_overlaps(&changed);
+        assert_eq!(overlaps.len(), 2);
+        assert_eq!(overlaps[0].a, a);
+        assert_eq!(overlaps[0].b, c);
+        assert_eq!(overlaps[0].files, files(&["README.md"]));
+        assert_eq!(overlaps[1].a, b);
+        assert_eq!(overlaps[1].b, c);
+        assert_eq!(overlaps[1].files, files(&["src/lib.rs"]));
+    }
+
+    #[test]
+    fn test_overlap_levels_keep_worst() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let overlaps = vec![
+            AgentOverlap {
+                a,
+                b,
+                files: files(&["x"]),
+                conflicts: Vec::new(),
+            },
+            AgentOverlap {
+                a: b,
+                b: c,
+                files: files(&["y"]),
+                conflicts: files(&["y"]),
+            },
+        ];
+
+        let levels = overlap_levels(&overlaps);
+        assert_eq!(levels[&a], OverlapLevel::Overlap);
+        assert_eq!(levels[&b], OverlapLevel::Conflict);
+        assert_eq!(levels[&c], OverlapLevel::Conflict);
+    }
+}
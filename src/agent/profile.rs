@@ -0,0 +1,189 @@
+use super::{Agent, TemplateVars};
+use crate::app::config::{AiAgent, LaunchProfile};
+use crate::core::common::{sanitize_branch_name, shell_quote};
+
+/// Profiles from the repo config, then global ones the repo doesn't replace.
+pub fn available(global: &[LaunchProfile], repo: &[LaunchProfile]) -> Vec<LaunchProfile> {
+    repo.iter()
+        .chain(
+            global
+                .iter()
+                .filter(|g| !repo.iter().any(|r| r.name == g.name)),
+        )
+        .cloned()
+        .collect()
+}
+
+/// `command` with the profile's environment in front and its arguments after.
+pub fn launch_command(profile: Option<&LaunchProfile>, command: &str) -> String {
+    let Some(profile) = profile else {
+        return command.to_string();
+    };
+    let mut parts: Vec<String> = profile
+        .env
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, shell_quote(value)))
+        .collect();
+    parts.push(command.to_string());
+    parts.extend(profile.args.iter().map(|arg| shell_quote(arg)));
+    parts.join(" ")
+}
+
+/// First prompt of a new agent, given instead of its profile's.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitialPrompt {
+    /// Filled in with the same variables as a profile's initial prompt
+    Template(String),
+    /// Sent as written
+    Text(String),
+}
+
+/// Command for a new agent's session, starting with `prompt` or else the
+/// profile's initial prompt. The rest of the profile is applied by
+/// [`launch_command`].
+pub fn initial_command(
+    profile: Option<&LaunchProfile>,
+    ai_agent: &AiAgent,
+    agent: &Agent,
+    repo_path: &str,
+    task: Option<&str>,
+    prompt: Option<&InitialPrompt>,
+) -> String {
+    let command = ai_agent.command();
+    let render = |template: &str| {
+        let mut vars = TemplateVars::for_agent(agent, repo_path);
+        vars.set("task", task.unwrap_or_default());
+        vars.render(template)
+    };
+    let prompt = match prompt {
+        Some(InitialPrompt::Text(text)) => text.clone(),
+        Some(InitialPrompt::Template(template)) => render(template),
+        None => match profile.and_then(|p| p.initial_prompt.as_deref()) {
+            Some(template) => render(template),
+            None => return command.to_string(),
+        },
+    };
+    let flag = match ai_agent {
+        AiAgent::ClaudeCode | AiAgent::Codex => "",
+        AiAgent::Opencode => "--prompt ",
+        AiAgent::Gemini => "--prompt-interactive ",
+    };
+    format!("{} {}{}", command, flag, shell_quote(prompt.trim()))
+}
+
+/// Branch for an agent named `name`, from the profile's template if it has one.
+pub fn branch_name(profile: Option<&LaunchProfile>, name: &str) -> String {
+    match profile.and_then(|p| p.branch_template.as_deref()) {
+        Some(template) => {
+            let mut vars = TemplateVars::default();
+            vars.set("name", &sanitize_branch_name(name));
+            sanitize_branch_name(&vars.render(template))
+        }
+        None => sanitize_branch_name(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> LaunchProfile {
+        LaunchProfile {
+            name: "opus".to_string(),
+            args: vec!["--model".to_string(), "opus".to_string()],
+            env: [("MAX_THINKING_TOKENS".to_string(), "8000".to_string())].into(),
+            initial_prompt: Some("Work on {{task}} in {{branch}}, don't push".to_string()),
+            branch_template: Some("feature/{{name}}".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_launch_command() {
+        assert_eq!(launch_command(None, "claude"), "claude");
+        assert_eq!(
+            launch_command(Some(&profile()), "claude --resume abc"),
+            "MAX_THINKING_TOKENS=8000 claude --resume abc --model opus"
+        );
+    }
+
+    #[test]
+    fn test_initial_command() {
+        let agent = Agent::new(
+            "login".to_string(),
+            "feature/login".to_string(),
+            "/tmp/login".to_string(),
+        );
+        let profile = profile();
+        let command = initial_command(
+            Some(&profile),
+            &AiAgent::Gemini,
+            &agent,
+            "/repo",
+            Some("Fix login"),
+            None,
+        );
+        assert_eq!(
+            launch_command(Some(&profile), &command),
+            "MAX_THINKING_TOKENS=8000 gemini --prompt-interactive \
+             'Work on Fix login in feature/login, don'\\''t push' --model opus"
+        );
+        assert_eq!(
+            initial_command(None, &AiAgent::Codex, &agent, "/repo", None, None),
+            "codex"
+        );
+        let text = InitialPrompt::Text("Keep {{branch}} as is".to_string());
+        assert_eq!(
+            initial_command(
+                Some(&profile),
+                &AiAgent::ClaudeCode,
+                &agent,
+                "/repo",
+                None,
+                Some(&text)
+            ),
+            "claude 'Keep {{branch}} as is'"
+        );
+        let template = InitialPrompt::Template("Continue {{branch}}".to_string());
+        assert_eq!(
+            initial_command(
+                None,
+                &AiAgent::Codex,
+                &agent,
+                "/repo",
+                None,
+                Some(&template)
+            ),
+            "codex 'Continue feature/login'"
+        );
+    }
+
+    #[test]
+    fn test_branch_name() {
+        assert_eq!(branch_name(None, "Fix Login"), "fix-login");
+        assert_eq!(
+            branch_name(Some(&profile()), "Fix Login"),
+            "feature/fix-login"
+        );
+    }
+
+    #[test]
+    fn test_available_prefers_repo_profiles() {
+        let global = vec![
+            LaunchProfile {
+                name: "fast".to_string(),
+                ..Default::default()
+            },
+            profile(),
+        ];
+        let repo = vec![LaunchProfile {
+            name: "opus".to_string(),
+            ai_agent: Some(AiAgent::Codex),
+            ..Default::default()
+        }];
+        let profiles = available(&global, &repo);
+        let names: Vec<_> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["opus", "fast"]);
+        assert_eq!(profiles[0].ai_agent, Some(AiAgent::Codex));
+    }
+}
@@ -0,0 +1,259 @@
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use super::Agent;
+use crate::app::config::ProvisioningConfig;
+
+/// Lines of hook output kept for the error when a hook fails.
+const FAILURE_TAIL_LINES: usize = 15;
+
+/// Per-agent values available to templates as `{{name}}` and to hooks as
+/// `GROVE_<NAME>` environment variables.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateVars(Vec<(String, String)>);
+
+impl TemplateVars {
+    pub fn for_agent(agent: &Agent, repo_path: &str) -> Self {
+        let mut vars = Self::default();
+        vars.set("agent_name", &agent.name);
+        vars.set("agent_id", &agent.id.to_string());
+        vars.set("branch", &agent.branch);
+        vars.set("worktree", &agent.worktree_path);
+        vars.set("repo", repo_path);
+        vars.set("session", &agent.tmux_session);
+        if let Some(port) = agent.dev_port {
+            vars.set("port", &port.to_string());
+        }
+        vars
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        match self.0.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.0.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    /// Replace `{{key}}` placeholders. Unknown placeholders are left as-is.
+    pub fn render(&self, content: &str) -> String {
+        let mut rendered = content.to_string();
+        for (key, value) in &self.0 {
+            rendered = rendered.replace(&format!("{{{{{}}}}}", key), value);
+        }
+        rendered
+    }
+
+    fn env(&self) -> impl Iterator<Item = (String, &str)> {
+        self.0
+            .iter()
+            .map(|(k, v)| (format!("GROVE_{}", k.to_uppercase()), v.as_str()))
+    }
+}
+
+impl ProvisioningConfig {
+    /// The setup Grove runs for `agent`. Worktrees adopted from an existing
+    /// checkout belong to the user, so nothing is copied, rendered or run in
+    /// them.
+    pub fn for_agent(&self, agent: &Agent) -> ProvisioningConfig {
+        if agent.adopted_worktree {
+            ProvisioningConfig::default()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+/// Copy and render the configured files into a fresh worktree. Files that
+/// already exist in the worktree are left alone.
+pub fn prepare_files(
+    config: &ProvisioningConfig,
+    repo_path: &Path,
+    worktree_path: &Path,
+    vars: &TemplateVars,
+) -> Result<()> {
+    for file in &config.copy_files {
+        let source = repo_path.join(file);
+        let target = worktree_path.join(file);
+        if !source.exists() || target.exists() {
+            continue;
+        }
+        copy_recursive(&source, &target).with_context(|| format!("Failed to copy {}", file))?;
+    }
+
+    for template in &config.templates {
+        let source = repo_path.join(&template.source);
+        let target = worktree_path.join(&template.target);
+        if target.exists() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&source)
+            .with_context(|| format!("Failed to read template {}", template.source))?;
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, vars.render(&content))
+            .with_context(|| format!("Failed to write {}", template.target))?;
+    }
+
+    Ok(())
+}
+
+/// Run hook commands in order, streaming each output line to `progress`.
+/// Stops at the first failing command.
+pub fn run_hooks(
+    commands: &[String],
+    worktree_path: &Path,
+    vars: &TemplateVars,
+    mut progress: impl FnMut(&str),
+) -> Result<()> {
+    for command in commands.iter().filter(|c| !c.trim().is_empty()) {
+        progress(&format!("$ {}", command));
+
+        let mut child = Command::new("sh")
+            .args(["-c", &format!("exec 2>&1; {}", command)])
+            .current_dir(worktree_path)
+            .envs(vars.env())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run hook: {}", command))?;
+
+        let mut tail: Vec<String> = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            for line in BufReader::new(stdout).lines().map_while(|l| l.ok()) {
+                progress(&line);
+                tail.push(line);
+                if tail.len() > FAILURE_TAIL_LINES {
+                    tail.remove(0);
+                }
+            }
+        }
+
+        let status = child.wait().context("Failed to wait for hook")?;
+        if !status.success() {
+            anyhow::bail!("'{}' failed ({})\n{}", command, status, tail.join("\n"));
+        }
+    }
+    Ok(())
+}
+
+fn copy_recursive(source: &Path, target: &Path) -> Result<()> {
+    if source.is_dir() {
+        std::fs::create_dir_all(target)?;
+        for entry in std::fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &target.join(entry.file_name()))?;
+        }
+    } else {
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(source, target)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::TemplateFile;
+
+    fn vars() -> TemplateVars {
+        let mut vars = TemplateVars::default();
+        vars.set("agent_name", "fix-login");
+        vars.set("port", "3001");
+        vars
+    }
+
+    #[test]
+    fn test_render_template() {
+        let rendered = vars().render("NAME={{agent_name}}\nPORT={{port}}\nX={{unknown}}\n");
+        assert_eq!(rendered, "NAME=fix-login\nPORT=3001\nX={{unknown}}\n");
+    }
+
+    #[test]
+    fn test_prepare_files_copies_and_renders() {
+        let repo = tempfile::tempdir().unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        std::fs::write(repo.path().join(".env"), "SECRET=1\n").unwrap();
+        std::fs::create_dir(repo.path().join("certs")).unwrap();
+        std::fs::write(repo.path().join("certs/dev.pem"), "pem").unwrap();
+        std::fs::write(repo.path().join(".env.tmpl"), "PORT={{port}}\n").unwrap();
+
+        let config = ProvisioningConfig {
+            copy_files: vec![".env".to_string(), "certs".to_string()],
+            templates: vec![TemplateFile {
+                source: ".env.tmpl".to_string(),
+                target: "config/.env.local".to_string(),
+            }],
+            ..Default::default()
+        };
+        prepare_files(&config, repo.path(), worktree.path(), &vars()).unwrap();
+
+        let read = |name: &str| std::fs::read_to_string(worktree.path().join(name)).unwrap();
+        assert_eq!(read(".env"), "SECRET=1\n");
+        assert_eq!(read("certs/dev.pem"), "pem");
+        assert_eq!(read("config/.env.local"), "PORT=3001\n");
+    }
+
+    #[test]
+    fn test_adopted_worktree_is_left_alone() {
+        let repo = tempfile::tempdir().unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        std::fs::write(repo.path().join(".env"), "SECRET=1\n").unwrap();
+        std::fs::write(repo.path().join(".env.tmpl"), "PORT={{port}}\n").unwrap();
+        std::fs::write(worktree.path().join("notes.txt"), "mine").unwrap();
+
+        let config = ProvisioningConfig {
+            copy_files: vec![".env".to_string()],
+            templates: vec![TemplateFile {
+                source: ".env.tmpl".to_string(),
+                target: ".env.local".to_string(),
+            }],
+            post_create: vec!["touch hooked".to_string()],
+            ..Default::default()
+        };
+        let mut agent = Agent::new(
+            "mine".to_string(),
+            "mine".to_string(),
+            worktree.path().to_string_lossy().to_string(),
+        );
+        agent.adopted_worktree = true;
+
+        let setup = config.for_agent(&agent);
+        prepare_files(&setup, repo.path(), worktree.path(), &vars()).unwrap();
+        run_hooks(&setup.post_create, worktree.path(), &vars(), |_| {}).unwrap();
+
+        let files: Vec<_> = std::fs::read_dir(worktree.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(files, vec!["notes.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(worktree.path().join("notes.txt")).unwrap(),
+            "mine"
+        );
+    }
+
+    #[test]
+    fn test_run_hooks_streams_output_and_stops_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let commands = vec![
+            "echo \"hello $GROVE_AGENT_NAME\"".to_string(),
+            "echo oops >&2; exit 3".to_string(),
+            "touch never".to_string(),
+        ];
+        let mut lines = Vec::new();
+        let err = run_hooks(&commands, dir.path(), &vars(), |l| {
+            lines.push(l.to_string())
+        })
+        .unwrap_err();
+
+        assert!(lines.contains(&"hello fix-login".to_string()));
+        assert!(lines.contains(&"oops".to_string()));
+        assert!(err.to_string().contains("oops"));
+        assert!(!dir.path().join("never").exists());
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use sysinfo::{Pid, System};
+use uuid::Uuid;
+
+use crate::core::common::{children_by_parent, descendants};
+use crate::devserver::tmux_session_name;
+
+/// Processes listed in the detail view.
+const TOP_PROCESSES: usize = 3;
+
+/// CPU and memory of everything running under an agent: its tmux pane,
+/// whatever the agent spawned (`cargo`, `node`, ...) and its dev servers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceUsage {
+    /// Summed over the tree, so 100 is one full core
+    pub cpu_percent: f32,
+    /// Resident memory summed over the tree
+    pub memory_bytes: u64,
+    /// Processes below the pane shells
+    pub child_count: usize,
+    /// Busiest processes, for the detail view
+    pub top: Vec<ProcessUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessUsage {
+    pub name: String,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+impl ResourceUsage {
+    /// Whether usage is over either limit. A limit of 0 is disabled.
+    pub fn exceeds(&self, cpu_percent: f32, memory_mb: u64) -> bool {
+        (cpu_percent > 0.0 && self.cpu_percent > cpu_percent)
+            || (memory_mb > 0 && self.memory_bytes > memory_mb * 1024 * 1024)
+    }
+
+    /// Compact form for the agent list, e.g. `143% 1.2G 12`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{:.0}% {} {}",
+            self.cpu_percent,
+            format_bytes(self.memory_bytes),
+            self.child_count
+        )
+    }
+}
+
+/// `512M`, `1.2G`.
+pub fn format_bytes(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    let mb = bytes as f64 / MB;
+    if mb >= 1024.0 {
+        format!("{:.1}G", mb / 1024.0)
+    } else {
+        format!("{:.0}M", mb)
+    }
+}
+
+/// Tmux sessions belonging to an agent: its own `tmux_session` and those of
+/// its dev services among `sessions`.
+pub fn agent_sessions<'a>(
+    agent_id: Uuid,
+    tmux_session: &'a str,
+    sessions: &'a [String],
+) -> Vec<&'a str> {
+    let dev_prefix = tmux_session_name(agent_id, "");
+    std::iter::once(tmux_session)
+        .chain(
+            sessions
+                .iter()
+                .filter(|session| session.starts_with(&dev_prefix))
+                .map(String::as_str),
+        )
+        .collect()
+}
+
+/// Child PIDs of every process.
+pub fn children_map(sys: &System) -> HashMap<u32, Vec<u32>> {
+    children_by_parent(
+        sys.processes()
+            .iter()
+            .filter_map(|(pid, process)| Some((pid.as_u32(), process.parent()?.as_u32()))),
+    )
+}
+
+/// Usage of the process trees under `roots`.
+pub fn tree_usage(sys: &System, children: &HashMap<u32, Vec<u32>>, roots: &[u32]) -> ResourceUsage {
+    let pids = descendants(children, roots);
+    let mut usage = ResourceUsage {
+        child_count: pids.len().saturating_sub(roots.len()),
+        ..Default::default()
+    };
+    let mut processes: Vec<ProcessUsage> = Vec::new();
+    for pid in pids {
+        let Some(process) = sys.process(Pid::from_u32(pid)) else {
+            continue;
+        };
+        usage.cpu_percent += process.cpu_usage();
+        usage.memory_bytes += process.memory();
+        processes.push(ProcessUsage {
+            name: process.name().to_string_lossy().to_string(),
+            cpu_percent: process.cpu_usage(),
+            memory_bytes: process.memory(),
+        });
+    }
+    processes.sort_by(|a, b| {
+        b.cpu_percent
+            .total_cmp(&a.cpu_percent)
+            .then(b.memory_bytes.cmp(&a.memory_bytes))
+    });
+    processes.truncate(TOP_PROCESSES);
+    usage.top = processes;
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thresholds_and_formatting() {
+        let usage = ResourceUsage {
+            cpu_percent: 250.0,
+            memory_bytes: 1536 * 1024 * 1024,
+            child_count: 7,
+            top: Vec::new(),
+        };
+        assert!(usage.exceeds(200.0, 0));
+        assert!(usage.exceeds(0.0, 1024));
+        assert!(!usage.exceeds(300.0, 2048));
+        assert!(!usage.exceeds(0.0, 0));
+        assert_eq!(usage.summary(), "250% 1.5G 7");
+        assert_eq!(format_bytes(300 * 1024 * 1024), "300M");
+    }
+
+    #[test]
+    fn test_agent_sessions() {
+        let id = Uuid::new_v4();
+        let sessions = vec![
+            tmux_session_name(id, "web"),
+            tmux_session_name(Uuid::new_v4(), "web"),
+            format!("grove-{}", id.as_simple()),
+        ];
+        assert_eq!(
+            agent_sessions(id, "imported-session", &sessions),
+            vec!["imported-session", sessions[0].as_str()]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::app::config::AiAgent;
+
+/// A past conversation of an AI agent in a worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionSummary {
+    pub id: String,
+    pub updated_at: DateTime<Utc>,
+    pub first_prompt: Option<String>,
+    pub message_count: usize,
+}
+
+/// Every session `ai_agent` has stored for `worktree_path`, newest first.
+pub fn list_sessions(ai_agent: &AiAgent, worktree_path: &str) -> Result<Vec<SessionSummary>> {
+    let mut sessions = match ai_agent {
+        AiAgent::ClaudeCode => crate::claude_code::list_sessions(worktree_path)?,
+        AiAgent::Opencode => crate::opencode::list_sessions(worktree_path)?,
+        AiAgent::Codex => crate::codex::list_sessions(worktree_path)?,
+        AiAgent::Gemini => crate::gemini::list_sessions(worktree_path)?,
+    };
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.updated_at));
+    Ok(sessions)
+}
+
+/// Command that resumes `session_id`, or starts a new conversation for `None`.
+pub fn launch_command(ai_agent: &AiAgent, session_id: Option<&str>) -> String {
+    let base = ai_agent.command();
+    match ai_agent {
+        AiAgent::ClaudeCode => crate::claude_code::build_resume_command(base, session_id),
+        AiAgent::Opencode => crate::opencode::build_command_with_session(base, session_id),
+        // Codex resumes its latest session when given no ID
+        AiAgent::Codex => match session_id {
+            Some(_) => crate::codex::build_resume_command(base, session_id),
+            None => base.to_string(),
+        },
+        AiAgent::Gemini => crate::gemini::build_resume_command(base, session_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_command() {
+        assert_eq!(
+            launch_command(&AiAgent::ClaudeCode, Some("abc")),
+            "claude --resume abc"
+        );
+        assert_eq!(
+            launch_command(&AiAgent::Codex, Some("abc")),
+            "codex resume abc"
+        );
+        assert_eq!(launch_command(&AiAgent::Codex, None), "codex");
+        assert_eq!(launch_command(&AiAgent::Opencode, None), "opencode");
+    }
+}
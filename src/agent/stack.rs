@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+
+use uuid::Uuid;
+
+use super::Agent;
+
+/// Agents to rebase when `root` changes, parents before children: `root`
+/// itself (if it has a base) followed by every agent stacked on it.
+pub fn restack_order(agents: &[&Agent], root: Uuid) -> Vec<Uuid> {
+    let Some(root_agent) = agents.iter().find(|a| a.id == root) else {
+        return Vec::new();
+    };
+
+    let mut order = Vec::new();
+    if root_agent.base_branch.is_some() {
+        order.push(root);
+    }
+
+    let mut queue = VecDeque::from([root_agent.branch.as_str()]);
+    let mut visited = vec![root];
+    while let Some(branch) = queue.pop_front() {
+        for child in agents
+            .iter()
+            .filter(|a| a.base_branch.as_deref() == Some(branch))
+        {
+            if visited.contains(&child.id) {
+                continue;
+            }
+            visited.push(child.id);
+            order.push(child.id);
+            queue.push_back(child.branch.as_str());
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(branch: &str, base: Option<&str>) -> Agent {
+        let mut agent = Agent::new(branch.to_string(), branch.to_string(), String::new());
+        agent.base_branch = base.map(String::from);
+        agent
+    }
+
+    #[test]
+    fn test_restack_order_follows_chain() {
+        let a = agent("a", None);
+        let b = agent("b", Some("a"));
+        let c = agent("c", Some("b"));
+        let other = agent("other", None);
+        let agents = vec![&c, &other, &b, &a];
+
+        assert_eq!(restack_order(&agents, a.id), vec![b.id, c.id]);
+        assert_eq!(restack_order(&agents, b.id), vec![b.id, c.id]);
+        assert!(restack_order(&agents, other.id).is_empty());
+    }
+
+    #[test]
+    fn test_restack_order_handles_cycles() {
+        let a = agent("a", Some("b"));
+        let b = agent("b", Some("a"));
+        let agents = vec![&a, &b];
+
+        assert_eq!(restack_order(&agents, a.id), vec![a.id, b.id]);
+    }
+}
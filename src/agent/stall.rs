@@ -0,0 +1,224 @@
+use regex::Regex;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use crate::core::common::strip_ansi;
+
+/// How recent a repeated output block must be to count as a live loop,
+/// in meaningful lines from the bottom (leaves room for the input box).
+const CYCLE_TAIL_LINES: usize = 8;
+
+/// Longest block of lines checked for repetition.
+const MAX_CYCLE_LINES: usize = 20;
+
+/// Lines that change while an agent works without producing anything:
+/// spinners, "✻ Thinking…" indicators and interrupt hints with timers.
+static SPINNER_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[⠁⠃⠇⡇⡏⡟⡿⣿⠋⠙⠹⠸⠼⠴⠦⠧⠏◐◓◑◒⣾⣽⣻⢿⣟⣯⣷]|[✢✳✶✻✽✦✧]\s*\w+…|(?i)esc to (interrupt|cancel)")
+        .unwrap()
+});
+
+/// Tool calls: Claude Code's `⏺ Bash(cargo test)` and Codex's `• Ran cargo test`.
+static TOOL_CALL: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        Regex::new(r"^\s*[⏺●]\s*([A-Z]\w*\(.*\))\s*$").unwrap(),
+        Regex::new(r"^\s*[•●]\s*Ran\s+(.+?)\s*$").unwrap(),
+    ]
+});
+
+/// Why a running agent looks stuck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stall {
+    /// Only spinners and timers changed for the stall window
+    Stalled,
+    /// The same tool call or block of output keeps recurring
+    Looping(String),
+}
+
+impl Stall {
+    pub fn label(&self) -> String {
+        match self {
+            Stall::Stalled => "stalled".to_string(),
+            Stall::Looping(reason) => format!("looping ({})", reason),
+        }
+    }
+}
+
+/// Watches an agent's pane across polls.
+pub struct StallDetector {
+    stall_after: Duration,
+    repeat_threshold: usize,
+    signature: u64,
+    changed_at: Instant,
+}
+
+impl StallDetector {
+    pub fn new(stall_after: Duration, repeat_threshold: usize, now: Instant) -> Self {
+        Self {
+            stall_after,
+            repeat_threshold: repeat_threshold.max(2),
+            signature: 0,
+            changed_at: now,
+        }
+    }
+
+    /// Feed the latest pane capture. Only a `running` agent can stall or loop.
+    pub fn observe(&mut self, content: &str, running: bool, now: Instant) -> Option<Stall> {
+        let lines = meaningful_lines(content);
+        let signature = signature(&lines);
+        if signature != self.signature || !running {
+            self.signature = signature;
+            self.changed_at = now;
+        }
+        if !running {
+            return None;
+        }
+
+        if let Some(call) = repeated_tool_call(content, self.repeat_threshold) {
+            return Some(Stall::Looping(format!(
+                "{} ×{}",
+                call, self.repeat_threshold
+            )));
+        }
+        if let Some(len) = output_cycle(&lines, self.repeat_threshold) {
+            return Some(Stall::Looping(format!(
+                "{} line{} of output ×{}",
+                len,
+                if len == 1 { "" } else { "s" },
+                self.repeat_threshold
+            )));
+        }
+        if now.duration_since(self.changed_at) >= self.stall_after {
+            return Some(Stall::Stalled);
+        }
+        None
+    }
+}
+
+/// Pane lines with text, minus spinner lines and with digits removed so
+/// elapsed-time and token counters don't count as progress.
+fn meaningful_lines(content: &str) -> Vec<String> {
+    let clean = strip_ansi(content);
+    clean
+        .lines()
+        .filter(|line| !SPINNER_LINE.is_match(line))
+        .filter(|line| line.chars().any(char::is_alphabetic))
+        .map(|line| {
+            line.chars()
+                .filter(|c| !c.is_ascii_digit())
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
+
+fn signature(lines: &[String]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    lines.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The call, when the last `threshold` tool calls on screen are identical.
+fn repeated_tool_call(content: &str, threshold: usize) -> Option<String> {
+    let clean = strip_ansi(content);
+    let calls: Vec<&str> = clean
+        .lines()
+        .filter_map(|line| {
+            TOOL_CALL
+                .iter()
+                .find_map(|re| re.captures(line))
+                .and_then(|caps| caps.get(1))
+                .map(|m| m.as_str())
+        })
+        .collect();
+    let last = calls.last()?;
+    let tail = calls.len().checked_sub(threshold)?;
+    calls[tail..]
+        .iter()
+        .all(|call| call == last)
+        .then(|| last.to_string())
+}
+
+/// Length of a block of lines repeated `threshold` times in a row, ending
+/// near the bottom of the pane.
+fn output_cycle(lines: &[String], threshold: usize) -> Option<usize> {
+    for end in (lines.len().saturating_sub(CYCLE_TAIL_LINES)..=lines.len()).rev() {
+        for len in 1..=MAX_CYCLE_LINES {
+            let Some(start) = end.checked_sub(len * threshold) else {
+                break;
+            };
+            let block = &lines[end - len..end];
+            if lines[start..end].chunks(len).all(|chunk| chunk == block) {
+                return Some(len);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector(now: Instant) -> StallDetector {
+        StallDetector::new(Duration::from_secs(60), 3, now)
+    }
+
+    #[test]
+    fn test_spinner_only_activity_stalls() {
+        let start = Instant::now();
+        let mut d = detector(start);
+        let frame = |spinner: &str, secs: u32| {
+            format!(
+                "⏺ Reading the config\n\n{} Pondering… ({}s · esc to interrupt)\n> ",
+                spinner, secs
+            )
+        };
+        assert_eq!(d.observe(&frame("✻", 1), true, start), None);
+        let later = start + Duration::from_secs(30);
+        assert_eq!(d.observe(&frame("✶", 31), true, later), None);
+        let later = start + Duration::from_secs(61);
+        assert_eq!(
+            d.observe(&frame("✻", 62), true, later),
+            Some(Stall::Stalled)
+        );
+
+        // New output resets the window
+        let output = format!("{}\nFound the bug", frame("✻", 63));
+        assert_eq!(d.observe(&output, true, later), None);
+        // An idle agent never stalls
+        let much_later = later + Duration::from_secs(600);
+        assert_eq!(d.observe(&output, false, much_later), None);
+    }
+
+    #[test]
+    fn test_repeated_tool_calls_loop() {
+        let now = Instant::now();
+        let mut d = detector(now);
+        let call = "⏺ Bash(cargo test)\n  ⎿  error[E0425]: cannot find value `x`\n";
+        let twice = format!("⏺ Read(src/lib.rs)\n{}⏺ Update(src/lib.rs)\n{}", call, call);
+        assert_eq!(d.observe(&twice, true, now), None);
+        let thrice = format!("{}{}", twice.replace("⏺ Update(src/lib.rs)\n", ""), call);
+        assert_eq!(
+            d.observe(&thrice, true, now),
+            Some(Stall::Looping("Bash(cargo test) ×3".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_repeated_output_block_loops() {
+        let now = Instant::now();
+        let mut d = detector(now);
+        let retry = "Connecting to db at 5432\nConnection refused, retrying in 2s\n";
+        let output = format!("Starting\n{}{}{}\n> ", retry, retry, retry);
+        assert_eq!(
+            d.observe(&output, true, now),
+            Some(Stall::Looping("2 lines of output ×3".to_string()))
+        );
+        let varied = "test a ... ok\ntest b ... ok\ntest c ... ok\n> ";
+        assert_eq!(d.observe(varied, true, now), None);
+    }
+}
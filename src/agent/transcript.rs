@@ -0,0 +1,331 @@
+use chrono::{DateTime, Utc};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{AgentStatus, ForegroundProcess, StatusDetection};
+use crate::app::config::AiAgent;
+
+/// How often to look for a newer session log for a worktree.
+const SEARCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Longest text kept for one event.
+const MAX_TEXT_LEN: usize = 300;
+
+/// A "still working" event older than this no longer outweighs an idle
+/// pane (cancelled turns aren't always logged).
+const WORKING_EVENT_TTL: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptEventKind {
+    /// The user sent a prompt
+    Prompt,
+    /// The agent replied
+    Message,
+    /// The agent ran a tool or command
+    ToolCall,
+    /// The agent is waiting for the user to approve a command or edit
+    ApprovalRequest,
+    /// The agent finished its turn
+    TurnComplete,
+    Error,
+}
+
+impl TranscriptEventKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TranscriptEventKind::Prompt => "prompt",
+            TranscriptEventKind::Message => "reply",
+            TranscriptEventKind::ToolCall => "tool",
+            TranscriptEventKind::ApprovalRequest => "approval",
+            TranscriptEventKind::TurnComplete => "done",
+            TranscriptEventKind::Error => "error",
+        }
+    }
+}
+
+/// One entry from an AI agent's session log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEvent {
+    pub kind: TranscriptEventKind,
+    pub text: String,
+    pub at: Option<DateTime<Utc>>,
+}
+
+impl TranscriptEvent {
+    pub fn new(kind: TranscriptEventKind, text: &str, at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            kind,
+            text: one_line(text),
+            at,
+        }
+    }
+}
+
+/// Collapse whitespace and cap the length so an event fits on a line or two.
+fn one_line(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() > MAX_TEXT_LEN {
+        let cut: String = collapsed.chars().take(MAX_TEXT_LEN - 1).collect();
+        format!("{}…", cut)
+    } else {
+        collapsed
+    }
+}
+
+/// Follows the session log Codex or Gemini writes for a worktree.
+pub struct TranscriptTail {
+    ai_agent: AiAgent,
+    worktree_path: String,
+    since: SystemTime,
+    path: Option<PathBuf>,
+    offset: u64,
+    seen_messages: usize,
+    modified: Option<SystemTime>,
+    searched_at: Option<Instant>,
+}
+
+impl TranscriptTail {
+    /// `None` for AI agents that don't write a session log Grove can read.
+    /// Logs last written before `since` belong to earlier sessions and are
+    /// ignored.
+    pub fn new(ai_agent: AiAgent, worktree_path: &str, since: SystemTime) -> Option<Self> {
+        if !matches!(ai_agent, AiAgent::Codex | AiAgent::Gemini) {
+            return None;
+        }
+        Some(Self {
+            ai_agent,
+            worktree_path: worktree_path.trim_end_matches('/').to_string(),
+            since,
+            path: None,
+            offset: 0,
+            seen_messages: 0,
+            modified: None,
+            searched_at: None,
+        })
+    }
+
+    /// Events written since the last poll.
+    pub fn poll(&mut self) -> Vec<TranscriptEvent> {
+        let due = self
+            .searched_at
+            .is_none_or(|at| at.elapsed() >= SEARCH_INTERVAL);
+        if due {
+            self.searched_at = Some(Instant::now());
+            let latest = match self.ai_agent {
+                AiAgent::Codex => crate::codex::find_rollout(&self.worktree_path, self.since),
+                AiAgent::Gemini => crate::gemini::find_chat(&self.worktree_path, self.since),
+                _ => None,
+            };
+            if latest.is_some() && latest != self.path {
+                tracing::debug!("Following session log {:?}", latest);
+                self.path = latest;
+                self.offset = 0;
+                self.seen_messages = 0;
+                self.modified = None;
+            }
+        }
+        let Some(path) = self.path.clone() else {
+            return Vec::new();
+        };
+        match self.ai_agent {
+            AiAgent::Codex => self.read_lines(&path),
+            AiAgent::Gemini => self.read_chat(&path),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Codex appends one JSON object per line.
+    fn read_lines(&mut self, path: &Path) -> Vec<TranscriptEvent> {
+        let Ok(mut file) = std::fs::File::open(path) else {
+            return Vec::new();
+        };
+        let mut buf = Vec::new();
+        if file.seek(SeekFrom::Start(self.offset)).is_err() || file.read_to_end(&mut buf).is_err() {
+            return Vec::new();
+        }
+        // Leave a partly written last line for the next poll
+        let Some(end) = buf.iter().rposition(|&b| b == b'\n') else {
+            return Vec::new();
+        };
+        self.offset += end as u64 + 1;
+        String::from_utf8_lossy(&buf[..end])
+            .lines()
+            .flat_map(crate::codex::parse_rollout_line)
+            .collect()
+    }
+
+    /// Gemini rewrites the whole chat file after each message.
+    fn read_chat(&mut self, path: &Path) -> Vec<TranscriptEvent> {
+        let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified.is_none() || modified == self.modified {
+            return Vec::new();
+        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Vec::new();
+        };
+        match crate::gemini::parse_chat(&content, self.seen_messages) {
+            Some((events, total)) => {
+                self.modified = modified;
+                self.seen_messages = total;
+                events
+            }
+            // Caught mid-write; try again next poll
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Newest file in `dirs` with a name starting with `prefix` and ending with
+/// `suffix`, written at or after `since` and accepted by `matches`.
+pub(crate) fn newest_file(
+    dirs: impl IntoIterator<Item = PathBuf>,
+    prefix: &str,
+    suffix: &str,
+    since: SystemTime,
+    matches: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    dirs.into_iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with(prefix) && name.ends_with(suffix)
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            (modified >= since).then(|| (modified, entry.path()))
+        })
+        .filter(|(_, path)| matches(path))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Status from the latest session log event, falling back to `scraped` (the
+/// regex detection) when there is none or it can't tell. `age` is how long
+/// ago the event was read.
+pub fn resolve(
+    event: Option<&TranscriptEvent>,
+    age: Duration,
+    foreground: &ForegroundProcess,
+    scraped: StatusDetection,
+) -> StatusDetection {
+    let Some(event) = event else {
+        return scraped;
+    };
+    // The agent has exited; its last event no longer applies
+    if *foreground == ForegroundProcess::Shell {
+        return scraped;
+    }
+    // Approval prompts aren't always logged, and the pane shows them first
+    if scraped.status == AgentStatus::AwaitingInput {
+        return scraped;
+    }
+
+    let logged = |status: AgentStatus, reason: String| {
+        StatusDetection::new(status)
+            .with_reason(reason)
+            .with_pattern(format!("transcript:{}", event.kind.label()))
+    };
+    match event.kind {
+        TranscriptEventKind::Prompt
+        | TranscriptEventKind::Message
+        | TranscriptEventKind::ToolCall => {
+            if age >= WORKING_EVENT_TTL && scraped.status != AgentStatus::Running {
+                return scraped;
+            }
+            logged(
+                AgentStatus::Running,
+                format!("Session log: {} {}", event.kind.label(), event.text),
+            )
+        }
+        TranscriptEventKind::ApprovalRequest => {
+            // Once approved the command runs without a new event
+            if scraped.status == AgentStatus::Running {
+                scraped
+            } else {
+                logged(AgentStatus::AwaitingInput, event.text.clone())
+            }
+        }
+        TranscriptEventKind::TurnComplete => match scraped.status {
+            AgentStatus::Completed | AgentStatus::Error(_) => scraped,
+            _ => logged(AgentStatus::Idle, "Session log: turn finished".to_string()),
+        },
+        TranscriptEventKind::Error => {
+            if scraped.status == AgentStatus::Running {
+                scraped
+            } else {
+                logged(AgentStatus::Error(event.text.clone()), event.text.clone())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: TranscriptEventKind, text: &str) -> TranscriptEvent {
+        TranscriptEvent::new(kind, text, None)
+    }
+
+    #[test]
+    fn test_event_text_is_one_line() {
+        let e = event(TranscriptEventKind::Message, "Done.\n\n  All   tests pass");
+        assert_eq!(e.text, "Done. All tests pass");
+        let long = event(TranscriptEventKind::Message, &"x".repeat(400));
+        assert_eq!(long.text.chars().count(), MAX_TEXT_LEN);
+        assert!(long.text.ends_with('…'));
+    }
+
+    #[test]
+    fn test_resolve_prefers_session_log_and_falls_back() {
+        let codex = ForegroundProcess::CodexRunning;
+        let fresh = Duration::from_secs(1);
+        let idle = || StatusDetection::new(AgentStatus::Idle);
+        let running = || StatusDetection::new(AgentStatus::Running);
+        let awaiting = || StatusDetection::new(AgentStatus::AwaitingInput);
+
+        let tool = event(TranscriptEventKind::ToolCall, "cargo test");
+        let status = resolve(Some(&tool), fresh, &codex, idle());
+        assert_eq!(status.status, AgentStatus::Running);
+        assert_eq!(status.pattern.as_deref(), Some("transcript:tool"));
+        // A long-silent log loses to an idle pane
+        let stale = Duration::from_secs(600);
+        assert_eq!(
+            resolve(Some(&tool), stale, &codex, idle()).status,
+            AgentStatus::Idle
+        );
+
+        let approval = event(TranscriptEventKind::ApprovalRequest, "rm -rf target");
+        assert_eq!(
+            resolve(Some(&approval), fresh, &codex, idle()).status,
+            AgentStatus::AwaitingInput
+        );
+        assert_eq!(
+            resolve(Some(&approval), fresh, &codex, running()).status,
+            AgentStatus::Running
+        );
+
+        let done = event(TranscriptEventKind::TurnComplete, "");
+        assert_eq!(
+            resolve(Some(&done), fresh, &codex, running()).status,
+            AgentStatus::Idle
+        );
+        assert_eq!(
+            resolve(Some(&done), fresh, &codex, awaiting()).status,
+            AgentStatus::AwaitingInput
+        );
+
+        assert_eq!(
+            resolve(Some(&tool), fresh, &ForegroundProcess::Shell, idle()).status,
+            AgentStatus::Idle
+        );
+        assert_eq!(
+            resolve(None, fresh, &codex, running()).status,
+            AgentStatus::Running
+        );
+    }
+}
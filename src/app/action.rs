@@ -1,7 +1,12 @@
 use uuid::Uuid;
 
-use crate::agent::{ProjectMgmtTaskStatus, StatusReason};
-use crate::app::config::AutomationActionType;
+use std::collections::HashMap;
+
+use crate::agent::{
+    AgentOverlap, AgentStatus, Checklist, ImportSource, InitialPrompt, ProjectMgmtTaskStatus,
+    ResourceUsage, Stall, StatusReason, TranscriptEvent,
+};
+use crate::app::config::{AutomationActionType, BudgetConfig};
 use crate::app::task_list::TaskListItem;
 use crate::app::ToastLevel;
 
@@ -16,6 +21,16 @@ pub enum Action {
         name: String,
         branch: String,
         task: Option<TaskListItem>,
+        base_branch: Option<String>,
+        /// Name of the launch profile to start the agent with
+        profile: Option<String>,
+        /// Initial prompt, used instead of the profile's
+        prompt: Option<InitialPrompt>,
+    },
+    ImportAgent {
+        source: ImportSource,
+        /// Name of the launch profile to start the agent with
+        profile: Option<String>,
     },
     DeleteAgent {
         id: Uuid,
@@ -43,6 +58,26 @@ pub enum Action {
         id: Uuid,
         output: String,
     },
+    SetAgentBudget {
+        id: Uuid,
+        budget: Option<BudgetConfig>,
+    },
+    UpdateStall {
+        id: Uuid,
+        stall: Option<Stall>,
+    },
+    CaptureDetectionFixture {
+        id: Uuid,
+        expected: AgentStatus,
+    },
+    UpdateTokenUsage {
+        id: Uuid,
+        tokens: u64,
+    },
+    AppendTranscript {
+        id: Uuid,
+        events: Vec<TranscriptEvent>,
+    },
     SetAgentNote {
         id: Uuid,
         note: Option<String>,
@@ -55,6 +90,105 @@ pub enum Action {
     MergeMain {
         id: Uuid,
     },
+    MainSyncComplete {
+        id: Uuid,
+        outcome: crate::git::SyncOutcome,
+    },
+    MainSyncFailed {
+        id: Uuid,
+        message: String,
+    },
+    SendConflictsToAgent,
+    AbortMainSync,
+    DismissMergeConflict,
+    RestackAgent {
+        id: Uuid,
+    },
+    AgentRestacked {
+        id: Uuid,
+        base_commit: String,
+    },
+    ToggleMergeQueue {
+        id: Uuid,
+    },
+    LandMergeQueue,
+    MergeQueueProgress {
+        message: String,
+    },
+    MergeQueueFinished {
+        ids: Vec<Uuid>,
+        report: crate::git::LandReport,
+    },
+    MergeQueueFailed {
+        message: String,
+    },
+    RunVerify {
+        id: Uuid,
+    },
+    VerifyFinished {
+        id: Uuid,
+        run: crate::verify::VerifyRun,
+    },
+    SendVerifyFailures {
+        id: Uuid,
+    },
+    CreateCheckpoint {
+        id: Uuid,
+        label: String,
+    },
+    CheckpointAll,
+    OpenCheckpoints {
+        id: Uuid,
+    },
+    CheckpointsLoaded {
+        id: Uuid,
+        checkpoints: Vec<crate::git::Checkpoint>,
+    },
+    CheckpointSelectNext,
+    CheckpointSelectPrev,
+    CheckpointShowDiff,
+    CheckpointDiffLoaded {
+        index: u32,
+        diff: String,
+    },
+    RestoreCheckpoint {
+        confirmed: bool,
+    },
+    CancelRestoreCheckpoint,
+    CheckpointRestored {
+        id: Uuid,
+        index: u32,
+        result: Result<(), String>,
+    },
+    CloseCheckpoints,
+    OpenSessionPicker {
+        id: Uuid,
+    },
+    SessionsLoaded {
+        id: Uuid,
+        sessions: Vec<crate::agent::SessionSummary>,
+    },
+    SessionPickerSelectNext,
+    SessionPickerSelectPrev,
+    ResumeSession {
+        id: Uuid,
+        session_id: Option<String>,
+    },
+    CloseSessionPicker,
+    /// Provision a new agent's worktree, then start its session with `command`
+    ProvisionAgent {
+        id: Uuid,
+        command: String,
+    },
+    ProvisionProgress {
+        id: Uuid,
+        line: String,
+    },
+    ProvisionFinished {
+        id: Uuid,
+        command: String,
+        result: Result<(), String>,
+    },
     PushBranch {
         id: Uuid,
     },
@@ -154,6 +288,30 @@ pub enum Action {
     ToggleTaskExpand,
     ToggleSubtaskStatus,
 
+    // Orchestrator
+    /// Dispatch one agent per subtask of the selected parent task
+    OrchestrateSelectedTask,
+    /// Adopt, dispatch and finish the subtasks of running orchestrations
+    AdvanceOrchestrations,
+    /// Subtask branches were merged into the parent's branch
+    OrchestrationMerged {
+        parent_branch: String,
+        result: Result<(), String>,
+    },
+    ToggleOrchestrator,
+    OrchestratorSelectNext,
+    OrchestratorSelectPrev,
+    RetryOrchestration,
+    CancelOrchestration,
+
+    // Autopilot
+    ToggleAutopilot,
+    /// Fetch the task queue if autopilot is on
+    AutopilotPoll,
+    AutopilotTasksFetched {
+        tasks: Result<Vec<TaskListItem>, String>,
+    },
+
     ToggleTaskListFilter,
     TaskListFilterNext,
     TaskListFilterPrev,
@@ -174,6 +332,11 @@ pub enum Action {
     ToggleHelp,
     ToggleLogs,
     ToggleStatusDebug,
+    ToggleOverlapMatrix,
+    RefreshOverlaps,
+    UpdateOverlaps {
+        overlaps: Vec<AgentOverlap>,
+    },
     ShowError(String),
     ShowToast {
         message: String,
@@ -189,6 +352,7 @@ pub enum Action {
     EnterInputMode(InputMode),
     ExitInputMode,
     UpdateInput(String),
+    CycleLaunchProfile,
     SubmitInput,
 
     RecordActivity {
@@ -196,9 +360,9 @@ pub enum Action {
         had_activity: bool,
     },
 
-    UpdateChecklistProgress {
+    UpdateChecklist {
         id: Uuid,
-        progress: Option<(u32, u32)>,
+        checklist: Option<Checklist>,
     },
 
     UpdateGlobalSystemMetrics {
@@ -206,6 +370,10 @@ pub enum Action {
         memory_used: u64,
         memory_total: u64,
     },
+    /// Resource usage of each agent's process tree
+    UpdateAgentResources {
+        usage: HashMap<Uuid, ResourceUsage>,
+    },
 
     SetLoading(Option<String>),
 
@@ -300,12 +468,48 @@ pub enum Action {
     DismissDevServerWarning,
     AppendDevServerLog {
         agent_id: Uuid,
+        service: String,
         line: String,
     },
     UpdateDevServerStatus {
         agent_id: Uuid,
+        service: String,
         status: crate::devserver::DevServerStatus,
     },
+    DevServerPortDetected {
+        agent_id: Uuid,
+        service: String,
+        port: u16,
+    },
+    DevServiceReady {
+        agent_id: Uuid,
+        service: String,
+        ready: bool,
+    },
+    DevServiceUnhealthy {
+        agent_id: Uuid,
+        service: String,
+        reason: String,
+    },
+    /// A service's command exited on its own
+    DevServiceExited {
+        agent_id: Uuid,
+        service: String,
+        code: i32,
+    },
+    /// Send the latest error block of the selected service to its agent
+    SendDevServerError,
+    StartLogSelection,
+    MoveLogSelection {
+        delta: i32,
+    },
+    SendLogSelection,
+    CancelLogSelection,
+    SelectDevService {
+        delta: i32,
+    },
+    ToggleDevService,
+    RestartDevService,
 
     // Project Setup Wizard
     ProjectSetupNavigateNext,
@@ -392,6 +596,18 @@ pub enum Action {
         agent_id: Uuid,
         action_type: AutomationActionType,
     },
+    /// Move the agent's linked task to the status named `status`
+    MoveTaskToStatus {
+        agent_id: Uuid,
+        status: String,
+    },
+
+    // MCP Server
+    /// A tool call from an agent, answered through `reply`
+    McpToolCall {
+        call: crate::mcp::ToolCall,
+        reply: crate::mcp::Reply,
+    },
 
     // Appearance Settings
     LoadAppearanceStatusOptions,
@@ -435,10 +651,15 @@ pub enum Action {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InputMode {
     NewAgent,
+    SelectBaseBranch,
+    ImportAgent,
     SetNote,
+    SetBudget,
+    CaptureFixture,
     ConfirmDelete,
     ConfirmMerge,
     ConfirmPush,
+    ConfirmLandQueue,
     ConfirmDeleteTask,
     AssignProjectTask,
     AssignAsana,
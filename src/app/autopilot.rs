@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::agent::Agent;
+use crate::app::config::AutopilotConfig;
+use crate::app::task_list::{resolve_starting, TaskListItem};
+use crate::core::common::sanitize_branch_name;
+
+/// What autopilot did with a task it picked up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Claim {
+    /// Agent creation was requested on `branch`
+    Starting {
+        branch: String,
+    },
+    /// The agent holds a slot until its task is done or it is deleted
+    Active {
+        agent_id: Uuid,
+    },
+    /// The slot was freed; the task is not picked up again
+    Released,
+    Failed {
+        reason: String,
+    },
+}
+
+impl Claim {
+    fn holds_slot(&self) -> bool {
+        matches!(self, Claim::Starting { .. } | Claim::Active { .. })
+    }
+
+    fn starting_branch(&self) -> Option<&str> {
+        match self {
+            Claim::Starting { branch } => Some(branch),
+            _ => None,
+        }
+    }
+}
+
+/// Spawns agents for queued tasks, keeping at most `max_active` running.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Autopilot {
+    #[serde(skip)]
+    pub enabled: bool,
+    /// Claims by task id
+    #[serde(default)]
+    pub claims: HashMap<String, Claim>,
+}
+
+impl Autopilot {
+    pub fn active(&self) -> usize {
+        self.claims.values().filter(|c| c.holds_slot()).count()
+    }
+
+    /// Forget failed picks and ones whose agent never got created, so their
+    /// tasks can be picked up again.
+    pub fn forget_unstarted(&mut self) {
+        self.claims
+            .retain(|_, c| !matches!(c, Claim::Starting { .. } | Claim::Failed { .. }));
+    }
+
+    /// Record the agent created on `branch`. Returns whether a claim took it.
+    pub fn agent_created(&mut self, branch: &str, agent_id: Uuid) -> bool {
+        resolve_starting(
+            self.claims.values_mut(),
+            branch,
+            Claim::starting_branch,
+            |claim| *claim = Claim::Active { agent_id },
+        )
+    }
+
+    pub fn agent_failed(&mut self, branch: &str, reason: &str) -> bool {
+        resolve_starting(
+            self.claims.values_mut(),
+            branch,
+            Claim::starting_branch,
+            |claim| {
+                *claim = Claim::Failed {
+                    reason: reason.to_string(),
+                }
+            },
+        )
+    }
+
+    /// Free the slots of agents that were deleted or whose task reached
+    /// `done_status`, returning a name for each freed slot to log.
+    pub fn release_finished(
+        &mut self,
+        agents: &HashMap<Uuid, Agent>,
+        done_status: &str,
+    ) -> Vec<String> {
+        let mut released = Vec::new();
+        for claim in self.claims.values_mut() {
+            let Claim::Active { agent_id } = claim else {
+                continue;
+            };
+            let finished = match agents.get(agent_id) {
+                Some(agent) => agent
+                    .pm_task_status
+                    .status_name_full()
+                    .is_some_and(|status| status.eq_ignore_ascii_case(done_status))
+                    .then(|| agent.name.clone()),
+                None => Some("deleted agent".to_string()),
+            };
+            if let Some(name) = finished {
+                released.push(name);
+                *claim = Claim::Released;
+            }
+        }
+        released
+    }
+
+    /// Claim queued tasks for the free slots and return them with the branch
+    /// their agent should use. Tasks already linked to an agent, or whose
+    /// branch is taken by an agent or another pick, are skipped.
+    pub fn pick(
+        &mut self,
+        tasks: &[TaskListItem],
+        agents: &HashMap<Uuid, Agent>,
+        config: &AutopilotConfig,
+    ) -> Vec<(TaskListItem, String)> {
+        let free = config.max_active.saturating_sub(self.active());
+        let mut branches: HashSet<String> = agents
+            .values()
+            .map(|a| a.branch.clone())
+            .chain(
+                self.claims
+                    .values()
+                    .filter_map(|c| c.starting_branch().map(String::from)),
+            )
+            .collect();
+        let picked: Vec<(TaskListItem, String)> = tasks
+            .iter()
+            .filter(|t| config.matches(t))
+            .filter(|t| !self.claims.contains_key(&t.id))
+            .filter(|t| !agents.values().any(|a| t.is_linked_to(a)))
+            .map(|t| (t.clone(), sanitize_branch_name(&t.name)))
+            .filter(|(_, branch)| branches.insert(branch.clone()))
+            .take(free)
+            .collect();
+        for (task, branch) in &picked {
+            self.claims.insert(
+                task.id.clone(),
+                Claim::Starting {
+                    branch: branch.clone(),
+                },
+            );
+        }
+        picked
+    }
+}
+
+impl AutopilotConfig {
+    /// Whether `task` is in the queue: one of `statuses` (any if empty) and
+    /// carrying one of `labels` (any if empty).
+    pub fn matches(&self, task: &TaskListItem) -> bool {
+        let status_ok = self.statuses.is_empty()
+            || self
+                .statuses
+                .iter()
+                .any(|s| s.eq_ignore_ascii_case(&task.status_name));
+        let label_ok = self.labels.is_empty()
+            || self
+                .labels
+                .iter()
+                .any(|l| task.labels.iter().any(|t| t.eq_ignore_ascii_case(l)));
+        status_ok && label_ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::task_list::fixtures::{linked_agent, task};
+
+    fn queued(id: &str, name: &str, status: &str, labels: &[&str]) -> TaskListItem {
+        TaskListItem {
+            status_name: status.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            ..task(id, name)
+        }
+    }
+
+    fn config() -> AutopilotConfig {
+        AutopilotConfig {
+            max_active: 2,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_matches_status_and_label() {
+        let config = config();
+        assert!(config.matches(&queued("1", "Fix login", "todo", &["Agent-Ready"])));
+        assert!(!config.matches(&queued("2", "Fix login", "Todo", &["bug"])));
+        assert!(!config.matches(&queued("3", "Fix login", "In Progress", &["agent-ready"])));
+
+        let any = AutopilotConfig {
+            statuses: Vec::new(),
+            labels: Vec::new(),
+            ..config
+        };
+        assert!(any.matches(&queued("4", "Fix login", "Backlog", &[])));
+    }
+
+    #[test]
+    fn test_pick_fills_free_slots_once() {
+        let mut autopilot = Autopilot::default();
+        let tasks = vec![
+            queued("1", "Fix login", "Todo", &["agent-ready"]),
+            queued("2", "Add search", "Todo", &["agent-ready"]),
+            queued("3", "Dark mode", "Todo", &["agent-ready"]),
+            queued("4", "Not ready", "Todo", &[]),
+        ];
+        let linked = linked_agent(&tasks[1], "Todo");
+        let agents: HashMap<Uuid, Agent> = [(linked.id, linked.clone())].into();
+
+        let picked = autopilot.pick(&tasks, &agents, &config());
+        let branches: Vec<_> = picked.iter().map(|(_, b)| b.as_str()).collect();
+        assert_eq!(branches, vec!["fix-login", "dark-mode"]);
+        assert_eq!(autopilot.active(), 2);
+        assert!(autopilot.pick(&tasks, &agents, &config()).is_empty());
+
+        assert!(autopilot.agent_failed("dark-mode", "branch exists"));
+        assert_eq!(autopilot.active(), 1);
+        assert!(autopilot.pick(&tasks, &agents, &config()).is_empty());
+
+        autopilot.forget_unstarted();
+        assert!(autopilot.claims.is_empty());
+        assert_eq!(autopilot.pick(&tasks, &agents, &config()).len(), 2);
+    }
+
+    #[test]
+    fn test_pick_skips_tasks_sharing_a_branch() {
+        let mut autopilot = Autopilot::default();
+        let tasks = vec![
+            queued("1", "Fix login", "Todo", &["agent-ready"]),
+            queued("2", "fix  LOGIN", "Todo", &["agent-ready"]),
+            queued("3", "Dark mode", "Todo", &["agent-ready"]),
+        ];
+        let picked = autopilot.pick(&tasks, &HashMap::new(), &config());
+        let ids: Vec<_> = picked.iter().map(|(t, _)| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+
+        autopilot.claims.remove("3");
+        let config = AutopilotConfig {
+            max_active: 3,
+            ..config()
+        };
+        let picked = autopilot.pick(&tasks, &HashMap::new(), &config);
+        let ids: Vec<_> = picked.iter().map(|(t, _)| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["3"]);
+    }
+
+    #[test]
+    fn test_release_finished() {
+        let mut autopilot = Autopilot::default();
+        let tasks = vec![
+            queued("1", "Fix login", "Todo", &["agent-ready"]),
+            queued("2", "Add search", "Todo", &["agent-ready"]),
+        ];
+        autopilot.pick(&tasks, &HashMap::new(), &config());
+        let login = linked_agent(&tasks[0], "Done");
+        let search = linked_agent(&tasks[1], "In Progress");
+        assert!(autopilot.agent_created("fix-login", login.id));
+        assert!(autopilot.agent_created("add-search", search.id));
+        let mut agents: HashMap<Uuid, Agent> =
+            [(login.id, login.clone()), (search.id, search.clone())].into();
+
+        assert_eq!(
+            autopilot.release_finished(&agents, "done"),
+            vec!["Fix login"]
+        );
+        assert_eq!(autopilot.active(), 1);
+
+        agents.remove(&search.id);
+        assert_eq!(
+            autopilot.release_finished(&agents, "done"),
+            vec!["deleted agent"]
+        );
+        assert_eq!(autopilot.active(), 0);
+        assert_eq!(autopilot.claims["1"], Claim::Released);
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
@@ -81,6 +82,18 @@ pub enum GitProvider {
     Codeberg,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncStrategy {
+    /// Grove merges the base branch itself
+    #[default]
+    Merge,
+    /// Grove rebases onto the base branch itself
+    Rebase,
+    /// Send the merge prompt to the agent
+    Prompt,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum CodebergCiProvider {
@@ -89,6 +102,24 @@ pub enum CodebergCiProvider {
     Woodpecker,
 }
 
+impl SyncStrategy {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SyncStrategy::Merge => "Merge",
+            SyncStrategy::Rebase => "Rebase",
+            SyncStrategy::Prompt => "Ask Agent",
+        }
+    }
+
+    pub fn all() -> &'static [SyncStrategy] {
+        &[
+            SyncStrategy::Merge,
+            SyncStrategy::Rebase,
+            SyncStrategy::Prompt,
+        ]
+    }
+}
+
 impl CodebergCiProvider {
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -218,7 +249,7 @@ fn default_editor() -> String {
     "code {path}".to_string()
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalConfig {
     #[serde(default)]
     pub ai_agent: AiAgent,
@@ -230,6 +261,31 @@ pub struct GlobalConfig {
     pub editor: String,
     #[serde(default)]
     pub debug_mode: bool,
+    /// Install Claude Code hooks in worktrees and use their events for status
+    #[serde(default)]
+    pub claude_hooks: bool,
+    /// Give launched agents Grove's MCP server
+    #[serde(default = "default_true")]
+    pub mcp_server: bool,
+    /// Launch profile for agents started without picking one: from a task,
+    /// by import, or as the preselected profile in the new-agent dialog
+    #[serde(default)]
+    pub default_profile: Option<String>,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            ai_agent: AiAgent::default(),
+            log_level: LogLevel::default(),
+            worktree_location: WorktreeLocation::default(),
+            editor: default_editor(),
+            debug_mode: false,
+            claude_hooks: false,
+            mcp_server: true,
+            default_profile: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -405,6 +461,8 @@ pub struct Config {
     pub task_list: TaskListConfig,
     #[serde(default)]
     pub tutorial_completed: bool,
+    #[serde(default)]
+    pub profiles: Vec<LaunchProfile>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -609,6 +667,10 @@ pub struct ColumnVisibility {
     #[serde(default = "default_true")]
     pub server: bool,
     #[serde(default = "default_true")]
+    pub tests: bool,
+    #[serde(default = "default_true")]
+    pub usage: bool,
+    #[serde(default = "default_true")]
     pub task: bool,
     #[serde(default = "default_true")]
     pub task_status: bool,
@@ -629,6 +691,8 @@ impl Default for ColumnVisibility {
             mr: true,
             pipeline: true,
             server: true,
+            tests: true,
+            usage: true,
             task: true,
             task_status: true,
             note: true,
@@ -669,6 +733,12 @@ impl ColumnVisibility {
         if self.server {
             count += 1;
         }
+        if self.tests {
+            count += 1;
+        }
+        if self.usage {
+            count += 1;
+        }
         if self.task {
             count += 1;
         }
@@ -769,18 +839,42 @@ pub struct Keybinds {
     pub nav_last: Keybind,
     #[serde(default = "default_new_agent")]
     pub new_agent: Keybind,
+    #[serde(default = "default_new_stacked_agent")]
+    pub new_stacked_agent: Keybind,
+    #[serde(default = "default_import_agent")]
+    pub import_agent: Keybind,
     #[serde(default = "default_delete_agent")]
     pub delete_agent: Keybind,
     #[serde(default = "default_attach")]
     pub attach: Keybind,
     #[serde(default = "default_set_note")]
     pub set_note: Keybind,
+    #[serde(default = "default_set_budget")]
+    pub set_budget: Keybind,
     #[serde(default = "default_yank")]
     pub yank: Keybind,
     #[serde(default = "default_copy_path")]
     pub copy_path: Keybind,
     #[serde(default = "default_merge")]
     pub merge: Keybind,
+    #[serde(default = "default_restack")]
+    pub restack: Keybind,
+    #[serde(default = "default_queue_agent")]
+    pub queue_agent: Keybind,
+    #[serde(default = "default_land_queue")]
+    pub land_queue: Keybind,
+    #[serde(default = "default_run_verify")]
+    pub run_verify: Keybind,
+    #[serde(default = "default_send_failures")]
+    pub send_failures: Keybind,
+    #[serde(default = "default_checkpoints")]
+    pub checkpoints: Keybind,
+    #[serde(default = "default_pick_session")]
+    pub pick_session: Keybind,
+    #[serde(default = "default_orchestrator")]
+    pub orchestrator: Keybind,
+    #[serde(default = "default_toggle_autopilot")]
+    pub toggle_autopilot: Keybind,
     #[serde(default = "default_push")]
     pub push: Keybind,
     #[serde(default = "default_fetch")]
@@ -791,6 +885,8 @@ pub struct Keybinds {
     pub toggle_diff: Keybind,
     #[serde(default = "default_toggle_logs")]
     pub toggle_logs: Keybind,
+    #[serde(default = "default_toggle_overlaps")]
+    pub toggle_overlaps: Keybind,
     #[serde(default = "default_open_mr")]
     pub open_mr: Keybind,
     #[serde(default = "default_asana_assign")]
@@ -834,6 +930,12 @@ fn default_nav_last() -> Keybind {
 fn default_new_agent() -> Keybind {
     Keybind::new("n")
 }
+fn default_new_stacked_agent() -> Keybind {
+    Keybind::new("b")
+}
+fn default_import_agent() -> Keybind {
+    Keybind::with_modifiers("i", vec!["Shift".to_string()])
+}
 fn default_delete_agent() -> Keybind {
     Keybind::new("d")
 }
@@ -843,6 +945,9 @@ fn default_attach() -> Keybind {
 fn default_set_note() -> Keybind {
     Keybind::with_modifiers("n", vec!["Shift".to_string()])
 }
+fn default_set_budget() -> Keybind {
+    Keybind::with_modifiers("d", vec!["Shift".to_string()])
+}
 fn default_yank() -> Keybind {
     Keybind::new("y")
 }
@@ -852,6 +957,33 @@ fn default_copy_path() -> Keybind {
 fn default_merge() -> Keybind {
     Keybind::new("m")
 }
+fn default_restack() -> Keybind {
+    Keybind::with_modifiers("b", vec!["Shift".to_string()])
+}
+fn default_queue_agent() -> Keybind {
+    Keybind::with_modifiers("m", vec!["Shift".to_string()])
+}
+fn default_land_queue() -> Keybind {
+    Keybind::with_modifiers("u", vec!["Shift".to_string()])
+}
+fn default_run_verify() -> Keybind {
+    Keybind::new("v")
+}
+fn default_send_failures() -> Keybind {
+    Keybind::with_modifiers("v", vec!["Shift".to_string()])
+}
+fn default_checkpoints() -> Keybind {
+    Keybind::new("h")
+}
+fn default_pick_session() -> Keybind {
+    Keybind::new("w")
+}
+fn default_orchestrator() -> Keybind {
+    Keybind::with_modifiers("w", vec!["Shift".to_string()])
+}
+fn default_toggle_autopilot() -> Keybind {
+    Keybind::with_modifiers("p", vec!["Shift".to_string()])
+}
 fn default_push() -> Keybind {
     Keybind::new("p")
 }
@@ -864,6 +996,9 @@ fn default_summary() -> Keybind {
 fn default_toggle_diff() -> Keybind {
     Keybind::new("/")
 }
+fn default_toggle_overlaps() -> Keybind {
+    Keybind::new("x")
+}
 fn default_toggle_logs() -> Keybind {
     Keybind::with_modifiers("l", vec!["Shift".to_string()])
 }
@@ -915,17 +1050,30 @@ impl Default for Keybinds {
             nav_first: default_nav_first(),
             nav_last: default_nav_last(),
             new_agent: default_new_agent(),
+            new_stacked_agent: default_new_stacked_agent(),
+            import_agent: default_import_agent(),
             delete_agent: default_delete_agent(),
             attach: default_attach(),
             set_note: default_set_note(),
+            set_budget: default_set_budget(),
             yank: default_yank(),
             copy_path: default_copy_path(),
             merge: default_merge(),
+            restack: default_restack(),
+            queue_agent: default_queue_agent(),
+            land_queue: default_land_queue(),
+            run_verify: default_run_verify(),
+            send_failures: default_send_failures(),
+            checkpoints: default_checkpoints(),
+            pick_session: default_pick_session(),
+            orchestrator: default_orchestrator(),
+            toggle_autopilot: default_toggle_autopilot(),
             push: default_push(),
             fetch: default_fetch(),
             summary: default_summary(),
             toggle_diff: default_toggle_diff(),
             toggle_logs: default_toggle_logs(),
+            toggle_overlaps: default_toggle_overlaps(),
             open_mr: default_open_mr(),
             asana_assign: default_asana_assign(),
             asana_open: default_asana_open(),
@@ -951,17 +1099,30 @@ impl Keybinds {
             ("nav_first", &self.nav_first),
             ("nav_last", &self.nav_last),
             ("new_agent", &self.new_agent),
+            ("new_stacked_agent", &self.new_stacked_agent),
+            ("import_agent", &self.import_agent),
             ("delete_agent", &self.delete_agent),
             ("attach", &self.attach),
             ("set_note", &self.set_note),
+            ("set_budget", &self.set_budget),
             ("yank", &self.yank),
             ("copy_path", &self.copy_path),
             ("merge", &self.merge),
+            ("restack", &self.restack),
+            ("queue_agent", &self.queue_agent),
+            ("land_queue", &self.land_queue),
+            ("run_verify", &self.run_verify),
+            ("send_failures", &self.send_failures),
+            ("checkpoints", &self.checkpoints),
+            ("pick_session", &self.pick_session),
+            ("orchestrator", &self.orchestrator),
+            ("toggle_autopilot", &self.toggle_autopilot),
             ("push", &self.push),
             ("fetch", &self.fetch),
             ("summary", &self.summary),
             ("toggle_diff", &self.toggle_diff),
             ("toggle_logs", &self.toggle_logs),
+            ("toggle_overlaps", &self.toggle_overlaps),
             ("open_mr", &self.open_mr),
             ("asana_assign", &self.asana_assign),
             ("asana_open", &self.asana_open),
@@ -1006,6 +1167,16 @@ pub struct PerformanceConfig {
     pub github_refresh_secs: u64,
     #[serde(default = "default_codeberg_refresh")]
     pub codeberg_refresh_secs: u64,
+    #[serde(default = "default_overlap_refresh")]
+    pub overlap_refresh_secs: u64,
+    /// Warn when an agent's process tree stays above this CPU (100 = one
+    /// core) or memory for `agent_warn_after_secs`. 0 disables a limit.
+    #[serde(default = "default_agent_cpu_warn")]
+    pub agent_cpu_warn_percent: f32,
+    #[serde(default = "default_agent_memory_warn")]
+    pub agent_memory_warn_mb: u64,
+    #[serde(default = "default_agent_warn_after")]
+    pub agent_warn_after_secs: u64,
 }
 
 fn default_agent_poll() -> u64 {
@@ -1028,6 +1199,22 @@ fn default_codeberg_refresh() -> u64 {
     60
 }
 
+fn default_overlap_refresh() -> u64 {
+    60
+}
+
+fn default_agent_cpu_warn() -> f32 {
+    400.0
+}
+
+fn default_agent_memory_warn() -> u64 {
+    8192
+}
+
+fn default_agent_warn_after() -> u64 {
+    60
+}
+
 impl Default for PerformanceConfig {
     fn default() -> Self {
         Self {
@@ -1036,6 +1223,10 @@ impl Default for PerformanceConfig {
             gitlab_refresh_secs: default_gitlab_refresh(),
             github_refresh_secs: default_github_refresh(),
             codeberg_refresh_secs: default_codeberg_refresh(),
+            overlap_refresh_secs: default_overlap_refresh(),
+            agent_cpu_warn_percent: default_agent_cpu_warn(),
+            agent_memory_warn_mb: default_agent_memory_warn(),
+            agent_warn_after_secs: default_agent_warn_after(),
         }
     }
 }
@@ -1132,7 +1323,7 @@ impl Config {
         }
     }
 
-    fn repo_hash(repo_path: &str) -> String {
+    pub fn repo_hash(repo_path: &str) -> String {
         let mut hasher = DefaultHasher::new();
         repo_path.hash(&mut hasher);
         format!("{:016x}", hasher.finish())
@@ -1150,9 +1341,26 @@ pub struct RepoConfig {
     #[serde(default)]
     pub dev_server: DevServerConfig,
     #[serde(default)]
+    pub provisioning: ProvisioningConfig,
+    #[serde(default)]
+    pub verify: VerifyConfig,
+    #[serde(default)]
+    pub checkpoints: CheckpointConfig,
+    #[serde(default)]
     pub appearance: AppearanceConfig,
     #[serde(default)]
     pub automation: AutomationConfig,
+    #[serde(default)]
+    pub budget: BudgetConfig,
+    #[serde(default)]
+    pub stall: StallConfig,
+    /// Launch profiles for this repo; they replace global ones of the same name
+    #[serde(default)]
+    pub profiles: Vec<LaunchProfile>,
+    #[serde(default)]
+    pub orchestrator: OrchestratorConfig,
+    #[serde(default)]
+    pub autopilot: AutopilotConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -1207,6 +1415,10 @@ pub struct RepoLinearConfig {
 pub struct PromptsConfig {
     pub summary_prompt: Option<String>,
     pub merge_prompt: Option<String>,
+    pub conflict_prompt: Option<String>,
+    pub verify_prompt: Option<String>,
+    pub devserver_error_prompt: Option<String>,
+    pub stall_prompt: Option<String>,
     pub push_prompt_opencode: Option<String>,
     pub push_prompt_codex: Option<String>,
     pub push_prompt_gemini: Option<String>,
@@ -1234,6 +1446,77 @@ impl PromptsConfig {
             })
     }
 
+    /// Prompt handing unresolved conflicts to the agent. `{files}` expands to
+    /// the conflicting paths, `{main_branch}` to the branch being merged.
+    pub fn get_conflict_prompt(&self, main_branch: &str, files: &[String]) -> String {
+        let file_list = files.join(", ");
+        self.conflict_prompt
+            .as_deref()
+            .map(|p| {
+                p.replace("{main_branch}", main_branch)
+                    .replace("{files}", &file_list)
+            })
+            .unwrap_or_else(|| {
+                format!(
+                    "Syncing {} into this branch stopped on conflicts in: {}. \
+                     Resolve the conflicts in these files, then continue the merge or rebase.",
+                    main_branch, file_list
+                )
+            })
+    }
+
+    /// Prompt handing verification failures to the agent. `{command}` expands
+    /// to the failing command, `{failures}` to the failing tests or errors.
+    pub fn get_verify_prompt(&self, command: &str, failures: &[String]) -> String {
+        let failure_list = if failures.is_empty() {
+            "see its output".to_string()
+        } else {
+            failures.join("; ")
+        };
+        self.verify_prompt
+            .as_deref()
+            .map(|p| {
+                p.replace("{command}", command)
+                    .replace("{failures}", &failure_list)
+            })
+            .unwrap_or_else(|| {
+                format!(
+                    "Verification with `{}` failed in this worktree ({}). \
+                     Run it, fix the failures and run it again until it passes.",
+                    command, failure_list
+                )
+            })
+    }
+
+    /// Prompt handing dev server output to the agent. `{service}` expands to
+    /// the service name, `{output}` to the log lines.
+    pub fn get_devserver_error_prompt(&self, service: &str, output: &str) -> String {
+        self.devserver_error_prompt
+            .as_deref()
+            .map(|p| p.replace("{service}", service).replace("{output}", output))
+            .unwrap_or_else(|| {
+                format!(
+                    "The dev server ({}) for this worktree logged the following:\n\n```\n{}\n```\n\n\
+                     Find the cause and fix it.",
+                    service, output
+                )
+            })
+    }
+
+    /// Prompt nudging a stuck agent. `{reason}` expands to what was detected.
+    pub fn get_stall_prompt(&self, reason: &str) -> String {
+        self.stall_prompt
+            .as_deref()
+            .map(|p| p.replace("{reason}", reason))
+            .unwrap_or_else(|| {
+                format!(
+                    "You appear to be stuck ({}). Stop, explain what is going wrong, \
+                     and try a different approach.",
+                    reason
+                )
+            })
+    }
+
     pub fn get_push_prompt(&self, agent: &AiAgent) -> Option<String> {
         match agent {
             AiAgent::ClaudeCode => None,
@@ -1283,6 +1566,11 @@ pub struct RepoGitConfig {
     pub branch_prefix: String,
     #[serde(default = "default_main_branch")]
     pub main_branch: String,
+    /// How "merge main" brings the base branch in
+    #[serde(default)]
+    pub sync_strategy: SyncStrategy,
+    #[serde(default)]
+    pub merge_queue: MergeQueueConfig,
     #[serde(default)]
     pub gitlab: RepoGitLabConfig,
     #[serde(default)]
@@ -1305,6 +1593,8 @@ impl Default for RepoGitConfig {
             provider: GitProvider::default(),
             branch_prefix: default_branch_prefix(),
             main_branch: default_main_branch(),
+            sync_strategy: SyncStrategy::default(),
+            merge_queue: MergeQueueConfig::default(),
             gitlab: RepoGitLabConfig::default(),
             github: RepoGitHubConfig::default(),
             codeberg: RepoCodebergConfig::default(),
@@ -1312,6 +1602,19 @@ impl Default for RepoGitConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MergeQueueConfig {
+    /// Command run in the scratch worktree after each branch is rebased.
+    /// Falls back to the repo's verify commands.
+    pub verify_command: Option<String>,
+    /// Push the main branch after landing
+    #[serde(default)]
+    pub push: bool,
+    /// Remove landed agents (the branches are kept)
+    #[serde(default)]
+    pub archive_landed: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RepoGitLabConfig {
     pub project_id: Option<u64>,
@@ -1369,18 +1672,598 @@ pub struct RepoAsanaConfig {
     pub done_section_gid: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DevServerConfig {
     pub command: Option<String>,
     #[serde(default)]
     pub run_before: Vec<String>,
     #[serde(default)]
     pub working_dir: String,
+    /// Fixed port, used when `auto_port` is off
     pub port: Option<u16>,
+    /// Give every agent its own free port from the range below so all dev
+    /// servers can run at once. The port is passed as `{port}` in the
+    /// command and through the `port_env` environment variable.
+    #[serde(default = "default_true")]
+    pub auto_port: bool,
+    #[serde(default = "default_port_range_start")]
+    pub port_range_start: u16,
+    #[serde(default = "default_port_range_end")]
+    pub port_range_end: u16,
+    #[serde(default = "default_port_env")]
+    pub port_env: String,
+    /// Serve each agent's server at `http://<agent>.localhost:<proxy_port>`
+    /// and the selected one at `http://active.localhost:<proxy_port>`
+    #[serde(default)]
+    pub proxy_enabled: bool,
+    #[serde(default = "default_proxy_port")]
+    pub proxy_port: u16,
     #[serde(default)]
     pub auto_start: bool,
     #[serde(default)]
     pub worktree_symlinks: Vec<String>,
+    /// Extra regexes that mark a log line as the start of an error
+    #[serde(default)]
+    pub error_patterns: Vec<String>,
+    /// Send new dev server errors to the agent once it is idle
+    #[serde(default)]
+    pub auto_send_errors: bool,
+    /// Named services started together (e.g. api, web, worker). When empty,
+    /// `command` runs as a single service.
+    #[serde(default)]
+    pub services: Vec<DevServiceConfig>,
+    /// Probes and restart policy for the single `command` service
+    #[serde(default)]
+    pub health: HealthConfig,
+}
+
+/// One process of an agent's dev environment. With `auto_port` each service
+/// gets the agent's port plus its position in the list; `{port}` is the
+/// service's own port and `{port.<name>}` another service's.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DevServiceConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub working_dir: String,
+    /// Fixed port, used when `auto_port` is off
+    pub port: Option<u16>,
+    /// Services that must be ready before this one starts
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub health: HealthConfig,
+}
+
+/// Readiness and liveness probes for a dev service. Probe strings may use
+/// `{port}` and `{port.<name>}` like the service command.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HealthConfig {
+    /// Must pass before the service counts as ready and dependents start
+    pub readiness: Option<ProbeConfig>,
+    #[serde(default = "default_ready_timeout")]
+    pub ready_timeout_secs: u64,
+    /// Checked periodically once ready; failing `failure_threshold` times in
+    /// a row marks the service unhealthy
+    pub liveness: Option<ProbeConfig>,
+    #[serde(default = "default_liveness_interval")]
+    pub liveness_interval_secs: u64,
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// Restarts allowed before giving up; the count resets once ready again
+    #[serde(default = "default_max_restarts")]
+    pub max_restarts: u32,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            readiness: None,
+            ready_timeout_secs: default_ready_timeout(),
+            liveness: None,
+            liveness_interval_secs: default_liveness_interval(),
+            failure_threshold: default_failure_threshold(),
+            restart: RestartPolicy::default(),
+            max_restarts: default_max_restarts(),
+        }
+    }
+}
+
+/// How a service is checked, e.g. `readiness = { http = "http://localhost:{port}/health" }`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProbeConfig {
+    /// GET succeeds with a 2xx/3xx status
+    Http(String),
+    /// TCP connect to `host:port` (or just a port on localhost) succeeds
+    Tcp(String),
+    /// Readiness: a log line matches. Liveness: fails when a new line matches.
+    Log(String),
+    /// `sh -c` command exits 0
+    Command(String),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    /// Restart after a crash or failed liveness probe
+    OnFailure,
+    /// Also restart when the process exits cleanly
+    Always,
+}
+
+fn default_ready_timeout() -> u64 {
+    60
+}
+
+fn default_liveness_interval() -> u64 {
+    10
+}
+
+fn default_failure_threshold() -> u32 {
+    3
+}
+
+fn default_max_restarts() -> u32 {
+    5
+}
+
+fn default_port_range_start() -> u16 {
+    3100
+}
+
+fn default_port_range_end() -> u16 {
+    3999
+}
+
+fn default_port_env() -> String {
+    "PORT".to_string()
+}
+
+fn default_proxy_port() -> u16 {
+    8800
+}
+
+impl Default for DevServerConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            run_before: Vec::new(),
+            working_dir: String::new(),
+            port: None,
+            auto_port: default_true(),
+            port_range_start: default_port_range_start(),
+            port_range_end: default_port_range_end(),
+            port_env: default_port_env(),
+            proxy_enabled: false,
+            proxy_port: default_proxy_port(),
+            auto_start: false,
+            worktree_symlinks: Vec::new(),
+            error_patterns: Vec::new(),
+            auto_send_errors: false,
+            services: Vec::new(),
+            health: HealthConfig::default(),
+        }
+    }
+}
+
+impl DevServerConfig {
+    /// Service names end up in tmux session names, where `.` and `:` are not
+    /// allowed. Replace anything but letters, digits, `-` and `_` with `-`,
+    /// along with `depends_on` entries and `{port.<name>}` placeholders that
+    /// refer to the service. Returns the `(old, new)` names that changed.
+    pub fn sanitize_service_names(&mut self) -> Vec<(String, String)> {
+        let renamed: Vec<(String, String)> = self
+            .services
+            .iter()
+            .map(|service| {
+                let name: String = service
+                    .name
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                            c
+                        } else {
+                            '-'
+                        }
+                    })
+                    .collect();
+                (service.name.clone(), name)
+            })
+            .filter(|(old, new)| old != new)
+            .collect();
+        if renamed.is_empty() {
+            return renamed;
+        }
+
+        let rename_placeholders = |text: &mut String| {
+            for (old, new) in &renamed {
+                *text = text.replace(&format!("{{port.{}}}", old), &format!("{{port.{}}}", new));
+            }
+        };
+        for command in &mut self.run_before {
+            rename_placeholders(command);
+        }
+        for service in &mut self.services {
+            if let Some((_, new)) = renamed.iter().find(|(old, _)| *old == service.name) {
+                service.name = new.clone();
+            }
+            for dep in &mut service.depends_on {
+                if let Some((_, new)) = renamed.iter().find(|(old, _)| old == dep) {
+                    *dep = new.clone();
+                }
+            }
+            rename_placeholders(&mut service.command);
+            for probe in [&mut service.health.readiness, &mut service.health.liveness]
+                .into_iter()
+                .flatten()
+            {
+                match probe {
+                    ProbeConfig::Http(text)
+                    | ProbeConfig::Tcp(text)
+                    | ProbeConfig::Command(text) => rename_placeholders(text),
+                    ProbeConfig::Log(_) => {}
+                }
+            }
+        }
+        renamed
+    }
+}
+
+/// Extra setup for new worktrees beyond `worktree_symlinks`. Paths are
+/// relative to the repository root; commands run with `sh -c` in the worktree.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProvisioningConfig {
+    /// Files or directories copied from the main checkout (e.g. `.env`)
+    #[serde(default)]
+    pub copy_files: Vec<String>,
+    #[serde(default)]
+    pub templates: Vec<TemplateFile>,
+    /// Run after the worktree is created (e.g. `npm install`)
+    #[serde(default)]
+    pub post_create: Vec<String>,
+    /// Run before the worktree is removed
+    #[serde(default)]
+    pub pre_delete: Vec<String>,
+}
+
+/// A file rendered into the worktree with `{{var}}` placeholders filled in
+/// with per-agent values.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TemplateFile {
+    pub source: String,
+    pub target: String,
+}
+
+/// Settings text for a list of shell commands. Commands can contain commas,
+/// so the list is shown as a TOML array.
+pub fn command_list_to_string(commands: &[String]) -> String {
+    toml::Value::Array(commands.iter().cloned().map(toml::Value::String).collect()).to_string()
+}
+
+/// Parse text from [`command_list_to_string`]. Anything that isn't an array is
+/// taken as a single command.
+pub fn parse_command_list(text: &str) -> Result<Vec<String>> {
+    #[derive(Deserialize)]
+    struct List {
+        commands: Vec<String>,
+    }
+
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !text.starts_with('[') {
+        return Ok(vec![text.to_string()]);
+    }
+    let list: List = toml::from_str(&format!("commands = {}", text))
+        .context("Expected a list like [\"npm test\", \"cargo fmt --check\"]")?;
+    Ok(list
+        .commands
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect())
+}
+
+/// Commands that check an agent's branch locally (build, lint, tests).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct VerifyConfig {
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Run automatically when an agent becomes `Completed`
+    #[serde(default)]
+    pub auto_run: bool,
+}
+
+/// Limits for agents left running unattended. Unset limits are not enforced.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct BudgetConfig {
+    /// Minutes since the agent session started
+    pub max_runtime_mins: Option<u64>,
+    /// Minutes in a row spent waiting for input
+    pub max_awaiting_input_mins: Option<u64>,
+    /// Minutes in a row spent idle
+    pub max_idle_mins: Option<u64>,
+    /// Tokens used, for AI agents that report a running total
+    pub max_tokens: Option<u64>,
+    #[serde(default)]
+    pub action: BudgetAction,
+}
+
+impl BudgetConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_runtime_mins.is_none()
+            && self.max_awaiting_input_mins.is_none()
+            && self.max_idle_mins.is_none()
+            && self.max_tokens.is_none()
+    }
+}
+
+/// What happens when an agent goes over budget.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum BudgetAction {
+    #[default]
+    Notify,
+    /// Send `C-c` to the agent
+    Interrupt,
+    /// Kill the agent's tmux session
+    Stop,
+}
+
+impl BudgetAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BudgetAction::Notify => "notify",
+            BudgetAction::Interrupt => "interrupt",
+            BudgetAction::Stop => "stop",
+        }
+    }
+}
+
+/// Detection of running agents that stopped making progress.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StallConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Seconds of spinner-only activity before a running agent is stalled
+    #[serde(default = "default_stall_after_secs")]
+    pub stall_after_secs: u64,
+    /// Identical tool calls or output blocks in a row that count as a loop
+    #[serde(default = "default_repeat_threshold")]
+    pub repeat_threshold: usize,
+    /// Send the stall prompt to the agent, not just a notification
+    #[serde(default)]
+    pub nudge: bool,
+}
+
+impl Default for StallConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            stall_after_secs: default_stall_after_secs(),
+            repeat_threshold: default_repeat_threshold(),
+            nudge: false,
+        }
+    }
+}
+
+fn default_stall_after_secs() -> u64 {
+    600
+}
+
+fn default_repeat_threshold() -> usize {
+    3
+}
+
+/// How an orchestration ends once every subtask is done.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OrchestratorFinish {
+    /// Merge the subtask branches into the parent's branch and queue the
+    /// parent agent in the merge queue
+    #[default]
+    MergeQueue,
+    /// Merge the subtask branches into the parent's branch and send its
+    /// agent the push prompt
+    PullRequest,
+}
+
+/// Dispatching one agent per subtask of a parent task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestratorConfig {
+    /// Subtask agents running at once
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Task status that marks a subtask as done once its agent is idle; an
+    /// agent that reports it completed counts as done either way
+    #[serde(default = "default_done_status")]
+    pub done_status: String,
+    /// Status the parent task moves to when every subtask is done;
+    /// defaults to `done_status`
+    #[serde(default)]
+    pub parent_status: Option<String>,
+    /// First prompt of each subtask agent; supports the launch profile
+    /// variables plus `{{parent}}` and `{{done_status}}`
+    #[serde(default = "default_orchestrator_prompt")]
+    pub prompt: String,
+    /// First prompt of the parent's agent; supports the same variables plus
+    /// `{{subtasks}}`
+    #[serde(default = "default_orchestrator_parent_prompt")]
+    pub parent_prompt: String,
+    /// Launch profile for the parent and subtask agents
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub finish: OrchestratorFinish,
+}
+
+impl Default for OrchestratorConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: default_max_concurrent(),
+            done_status: default_done_status(),
+            parent_status: None,
+            prompt: default_orchestrator_prompt(),
+            parent_prompt: default_orchestrator_parent_prompt(),
+            profile: None,
+            finish: OrchestratorFinish::default(),
+        }
+    }
+}
+
+fn default_max_concurrent() -> usize {
+    3
+}
+
+fn default_done_status() -> String {
+    "Done".to_string()
+}
+
+fn default_orchestrator_prompt() -> String {
+    "Work on \"{{task}}\", a subtask of \"{{parent}}\". Commit your changes on this \
+     branch when you are finished, then move the task to \"{{done_status}}\" with \
+     Grove's update_task_status tool."
+        .to_string()
+}
+
+fn default_orchestrator_parent_prompt() -> String {
+    "You are the lead on \"{{task}}\". Grove runs one agent per subtask ({{subtasks}}) \
+     on branches stacked on this one and merges them into this branch once every \
+     subtask is \"{{done_status}}\". Don't commit here until then; wait for \
+     instructions."
+        .to_string()
+}
+
+/// Picking up queued tasks from the project management provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutopilotConfig {
+    /// Start with autopilot on; it can be toggled while Grove runs
+    #[serde(default)]
+    pub enabled: bool,
+    /// Autopilot agents working at once
+    #[serde(default = "default_autopilot_max_active")]
+    pub max_active: usize,
+    /// Task statuses in the queue; any status if empty
+    #[serde(default = "default_autopilot_statuses")]
+    pub statuses: Vec<String>,
+    /// Labels or tags a queued task needs one of; any task if empty
+    #[serde(default = "default_autopilot_labels")]
+    pub labels: Vec<String>,
+    /// Status a picked task moves to once its agent exists
+    #[serde(default = "default_autopilot_in_progress_status")]
+    pub in_progress_status: Option<String>,
+    /// Task status that frees the agent's slot
+    #[serde(default = "default_done_status")]
+    pub done_status: String,
+    /// First prompt of each agent; supports the launch profile variables
+    /// plus `{{task_url}}` and `{{done_status}}`
+    #[serde(default = "default_autopilot_prompt")]
+    pub prompt: String,
+    /// Launch profile for autopilot agents
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// How often the task queue is polled
+    #[serde(default = "default_autopilot_poll_secs")]
+    pub poll_secs: u64,
+}
+
+impl Default for AutopilotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_active: default_autopilot_max_active(),
+            statuses: default_autopilot_statuses(),
+            labels: default_autopilot_labels(),
+            in_progress_status: default_autopilot_in_progress_status(),
+            done_status: default_done_status(),
+            prompt: default_autopilot_prompt(),
+            profile: None,
+            poll_secs: default_autopilot_poll_secs(),
+        }
+    }
+}
+
+fn default_autopilot_max_active() -> usize {
+    2
+}
+
+fn default_autopilot_statuses() -> Vec<String> {
+    vec!["Todo".to_string()]
+}
+
+fn default_autopilot_labels() -> Vec<String> {
+    vec!["agent-ready".to_string()]
+}
+
+fn default_autopilot_in_progress_status() -> Option<String> {
+    Some("In Progress".to_string())
+}
+
+fn default_autopilot_prompt() -> String {
+    "Work on \"{{task}}\" ({{task_url}}). Commit your changes on this branch when \
+     you are finished, then move the task to \"{{done_status}}\" with Grove's \
+     update_task_status tool."
+        .to_string()
+}
+
+fn default_autopilot_poll_secs() -> u64 {
+    60
+}
+
+/// A named way of starting agents, picked in the new-agent dialog.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct LaunchProfile {
+    pub name: String,
+    /// Overrides `global.ai_agent`
+    #[serde(default)]
+    pub ai_agent: Option<AiAgent>,
+    /// Extra CLI arguments, e.g. `["--model", "opus"]`
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// First prompt of a new agent; supports the provisioning `{{...}}`
+    /// variables plus `{{task}}`
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+    /// Branch for new agents, e.g. `feature/{{name}}`
+    #[serde(default)]
+    pub branch_template: Option<String>,
+}
+
+/// Automatic snapshots of agent worktrees under `refs/grove/checkpoints`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_checkpoint_interval")]
+    pub interval_secs: u64,
+    #[serde(default = "default_max_checkpoints")]
+    pub max_per_agent: usize,
+}
+
+fn default_checkpoint_interval() -> u64 {
+    300
+}
+
+fn default_max_checkpoints() -> usize {
+    50
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            interval_secs: default_checkpoint_interval(),
+            max_per_agent: default_max_checkpoints(),
+        }
+    }
 }
 
 impl RepoConfig {
@@ -1391,7 +2274,10 @@ impl RepoConfig {
             let content =
                 std::fs::read_to_string(&config_path).context("Failed to read repo config")?;
 
-            if let Ok(config) = toml::from_str::<RepoConfig>(&content) {
+            if let Ok(mut config) = toml::from_str::<RepoConfig>(&content) {
+                for (old, new) in config.dev_server.sanitize_service_names() {
+                    tracing::warn!("Dev service '{}' renamed to '{}'", old, new);
+                }
                 return Ok(config);
             }
 
@@ -1415,8 +2301,16 @@ impl RepoConfig {
                     },
                     prompts: legacy.prompts,
                     dev_server: DevServerConfig::default(),
+                    provisioning: ProvisioningConfig::default(),
+                    verify: VerifyConfig::default(),
+                    checkpoints: CheckpointConfig::default(),
                     appearance: AppearanceConfig::default(),
                     automation: AutomationConfig::default(),
+                    budget: BudgetConfig::default(),
+                    stall: StallConfig::default(),
+                    profiles: Vec::new(),
+                    orchestrator: OrchestratorConfig::default(),
+                    autopilot: AutopilotConfig::default(),
                 });
             }
 
@@ -1444,3 +2338,52 @@ impl RepoConfig {
         Ok(Self::config_dir(repo_path)?.join("project.toml"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_service_names() {
+        let service = |name: &str, command: &str, depends_on: &[&str]| DevServiceConfig {
+            name: name.to_string(),
+            command: command.to_string(),
+            working_dir: String::new(),
+            port: None,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            health: HealthConfig::default(),
+        };
+        let mut config = DevServerConfig {
+            services: vec![
+                service("api.v2", "serve --port {port}", &[]),
+                service("web", "vite --api {port.api.v2}", &["api.v2"]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.sanitize_service_names(),
+            vec![("api.v2".to_string(), "api-v2".to_string())]
+        );
+        assert_eq!(config.services[0].name, "api-v2");
+        assert_eq!(config.services[1].command, "vite --api {port.api-v2}");
+        assert_eq!(config.services[1].depends_on, vec!["api-v2"]);
+        assert!(config.sanitize_service_names().is_empty());
+    }
+
+    #[test]
+    fn test_command_list_round_trip() {
+        let commands = vec![
+            "cargo test".to_string(),
+            "jq '.a, .b' package.json".to_string(),
+        ];
+        let text = command_list_to_string(&commands);
+        assert_eq!(parse_command_list(&text).unwrap(), commands);
+        assert_eq!(
+            parse_command_list("npm run lint, --fix").unwrap(),
+            vec!["npm run lint, --fix"]
+        );
+        assert!(parse_command_list("").unwrap().is_empty());
+        assert!(parse_command_list("[\"unterminated").is_err());
+    }
+}
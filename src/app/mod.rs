@@ -1,19 +1,24 @@
 pub mod action;
+pub mod autopilot;
 pub mod config;
+pub mod orchestrator;
 pub mod state;
 pub mod task_list;
 
 pub use action::{Action, InputMode};
+pub use autopilot::Autopilot;
 pub use config::{
     AiAgent, AutomationActionType, AutomationConfig, CodebergCiProvider, Config, DevServerConfig,
     GitProvider, GlobalConfig, LogLevel as ConfigLogLevel, ProjectMgmtProvider, RepoConfig,
-    UiConfig, WorktreeLocation,
+    SyncStrategy, UiConfig, WorktreeLocation,
 };
+pub use orchestrator::Orchestration;
 pub use state::{
-    ActionButtonType, AppState, DevServerWarning, DropdownState, GitSetupState, GitSetupStep,
-    GlobalSetupState, GlobalSetupStep, LogEntry, LogLevel, PmSetupState, PmSetupStep, PreviewTab,
-    ProjectSetupState, ResetType, SettingsCategory, SettingsField, SettingsItem, SettingsState,
-    SettingsTab, SetupSource, StatusOption, TaskReassignmentWarning, TaskStatusDropdownState,
-    Toast, ToastLevel, TutorialState, TutorialStep,
+    ActionButtonType, AppState, CheckpointView, DevServerWarning, DropdownState, GitSetupState,
+    GitSetupStep, GlobalSetupState, GlobalSetupStep, LogEntry, LogLevel, LogSelection,
+    MergeConflict, PmSetupState, PmSetupStep, PreviewTab, ProjectSetupState, ResetType,
+    SessionPicker, SettingsCategory, SettingsField, SettingsItem, SettingsState, SettingsTab,
+    SetupSource, StatusOption, TaskReassignmentWarning, TaskStatusDropdownState, Toast, ToastLevel,
+    TutorialState, TutorialStep,
 };
 pub use task_list::TaskListItem;
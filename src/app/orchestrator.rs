@@ -0,0 +1,352 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::agent::{Agent, AgentStatus};
+use crate::app::task_list::{resolve_starting, TaskListItem};
+use crate::core::common::sanitize_branch_name;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlotState {
+    Pending,
+    /// Agent creation was requested
+    Starting,
+    Running {
+        agent_id: Uuid,
+    },
+    Done {
+        agent_id: Uuid,
+    },
+    Failed {
+        reason: String,
+    },
+}
+
+impl SlotState {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            SlotState::Pending => "○",
+            SlotState::Starting => "◌",
+            SlotState::Running { .. } => "●",
+            SlotState::Done { .. } => "✓",
+            SlotState::Failed { .. } => "✗",
+        }
+    }
+
+    pub fn agent_id(&self) -> Option<Uuid> {
+        match self {
+            SlotState::Running { agent_id } | SlotState::Done { agent_id } => Some(*agent_id),
+            _ => None,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        matches!(self, SlotState::Starting | SlotState::Running { .. })
+    }
+}
+
+/// A task the orchestrator runs an agent for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSlot {
+    pub task: TaskListItem,
+    pub branch: String,
+    pub state: SlotState,
+}
+
+impl TaskSlot {
+    pub fn new(task: TaskListItem) -> Self {
+        Self {
+            branch: sanitize_branch_name(&task.name),
+            task,
+            state: SlotState::Pending,
+        }
+    }
+
+    fn starting_branch(&self) -> Option<&str> {
+        (self.state == SlotState::Starting).then_some(self.branch.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+    /// Dispatching agents and waiting for subtasks to finish
+    Running,
+    /// Every subtask is done and their branches are being combined
+    Finishing,
+    Finished,
+    Failed {
+        reason: String,
+    },
+}
+
+/// One agent per subtask of `parent`, started up to a concurrency limit and
+/// stacked on the branch of the parent's own agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Orchestration {
+    pub parent: TaskSlot,
+    pub subtasks: Vec<TaskSlot>,
+    pub stage: Stage,
+}
+
+impl Orchestration {
+    pub fn new(parent: TaskListItem, subtasks: Vec<TaskListItem>) -> Self {
+        Self {
+            parent: TaskSlot::new(parent),
+            subtasks: subtasks.into_iter().map(TaskSlot::new).collect(),
+            stage: Stage::Running,
+        }
+    }
+
+    fn slots_mut(&mut self) -> impl Iterator<Item = &mut TaskSlot> {
+        std::iter::once(&mut self.parent).chain(self.subtasks.iter_mut())
+    }
+
+    /// Record the agent created for `branch`. Returns whether a slot took it.
+    pub fn agent_created(&mut self, branch: &str, agent_id: Uuid) -> bool {
+        resolve_starting(
+            self.slots_mut(),
+            branch,
+            TaskSlot::starting_branch,
+            |slot| slot.state = SlotState::Running { agent_id },
+        )
+    }
+
+    pub fn agent_failed(&mut self, branch: &str, reason: &str) -> bool {
+        resolve_starting(
+            self.slots_mut(),
+            branch,
+            TaskSlot::starting_branch,
+            |slot| {
+                slot.state = SlotState::Failed {
+                    reason: reason.to_string(),
+                }
+            },
+        )
+    }
+
+    /// Adopt agents already linked to a pending task, fail slots whose agent
+    /// was deleted, and mark subtasks done once their agent has completed, or
+    /// has stopped working and its task reached `done_status`.
+    pub fn update(&mut self, agents: &HashMap<Uuid, Agent>, done_status: &str) {
+        for slot in self.slots_mut() {
+            match slot.state {
+                SlotState::Pending => {
+                    if let Some(agent) = agents.values().find(|a| slot.task.is_linked_to(a)) {
+                        slot.branch = agent.branch.clone();
+                        slot.state = SlotState::Running { agent_id: agent.id };
+                    }
+                }
+                SlotState::Running { agent_id } | SlotState::Done { agent_id }
+                    if !agents.contains_key(&agent_id) =>
+                {
+                    slot.state = SlotState::Failed {
+                        reason: "agent was deleted".to_string(),
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        for slot in &mut self.subtasks {
+            if let SlotState::Running { agent_id } = slot.state {
+                let agent = &agents[&agent_id];
+                let task_done = agent
+                    .pm_task_status
+                    .status_name_full()
+                    .is_some_and(|status| status.eq_ignore_ascii_case(done_status));
+                let done = match agent.status {
+                    AgentStatus::Completed => true,
+                    AgentStatus::Idle | AgentStatus::Stopped => task_done,
+                    _ => false,
+                };
+                if done {
+                    slot.state = SlotState::Done { agent_id };
+                }
+            }
+        }
+    }
+
+    /// Subtasks to start now so at most `max_concurrent` run at once. Nothing
+    /// starts before the parent's agent exists, since subtasks branch off it.
+    pub fn to_dispatch(&self, max_concurrent: usize) -> Vec<usize> {
+        if !matches!(self.parent.state, SlotState::Running { .. }) {
+            return Vec::new();
+        }
+        let active = self.subtasks.iter().filter(|s| s.state.is_active()).count();
+        self.subtasks
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.state == SlotState::Pending)
+            .map(|(i, _)| i)
+            .take(max_concurrent.saturating_sub(active))
+            .collect()
+    }
+
+    pub fn all_done(&self) -> bool {
+        self.subtasks
+            .iter()
+            .all(|s| matches!(s.state, SlotState::Done { .. }))
+    }
+
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self
+            .subtasks
+            .iter()
+            .filter(|s| matches!(s.state, SlotState::Done { .. }))
+            .count();
+        (done, self.subtasks.len())
+    }
+
+    /// Agents of the finished subtasks, in task order.
+    pub fn subtask_agents(&self) -> Vec<Uuid> {
+        self.subtasks
+            .iter()
+            .filter_map(|s| s.state.agent_id())
+            .collect()
+    }
+
+    /// Put failed tasks back in line and resume a failed finish.
+    pub fn retry(&mut self) {
+        for slot in self.slots_mut() {
+            if matches!(slot.state, SlotState::Failed { .. }) {
+                slot.state = SlotState::Pending;
+            }
+        }
+        if matches!(self.stage, Stage::Failed { .. }) {
+            self.stage = Stage::Running;
+        }
+    }
+
+    /// Put back in line tasks whose agent never got created, e.g. because
+    /// Grove exited while it was being set up.
+    pub fn forget_unstarted(&mut self) {
+        for slot in self.slots_mut() {
+            if slot.state == SlotState::Starting {
+                slot.state = SlotState::Pending;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::task_list::fixtures::{linked_agent, task};
+
+    fn subtask(id: &str, name: &str) -> TaskListItem {
+        TaskListItem {
+            parent_id: Some("p".to_string()),
+            ..task(id, name)
+        }
+    }
+
+    fn orchestration() -> Orchestration {
+        Orchestration::new(
+            TaskListItem {
+                has_children: true,
+                ..task("p", "Checkout flow")
+            },
+            vec![
+                subtask("a", "Cart API"),
+                subtask("b", "Payment Form"),
+                subtask("c", "Receipt email"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_dispatch_waits_for_parent_and_respects_limit() {
+        let mut o = orchestration();
+        assert_eq!(o.subtasks[1].branch, "payment-form");
+        assert!(o.to_dispatch(2).is_empty());
+
+        o.parent.state = SlotState::Starting;
+        assert!(o.agent_created("checkout-flow", Uuid::new_v4()));
+        assert_eq!(o.to_dispatch(2), vec![0, 1]);
+
+        o.subtasks[0].state = SlotState::Starting;
+        o.subtasks[1].state = SlotState::Starting;
+        assert!(o.to_dispatch(2).is_empty());
+        assert!(o.agent_failed("cart-api", "branch exists"));
+        assert_eq!(o.to_dispatch(2), vec![2]);
+
+        o.retry();
+        assert_eq!(o.subtasks[0].state, SlotState::Pending);
+    }
+
+    #[test]
+    fn test_forget_unstarted_requeues_starting_slots() {
+        let mut o = orchestration();
+        let parent = Uuid::new_v4();
+        o.parent.state = SlotState::Running { agent_id: parent };
+        o.subtasks[0].state = SlotState::Starting;
+        o.subtasks[1].state = SlotState::Failed {
+            reason: "branch exists".to_string(),
+        };
+
+        o.forget_unstarted();
+        assert_eq!(o.parent.state, SlotState::Running { agent_id: parent });
+        assert_eq!(o.subtasks[0].state, SlotState::Pending);
+        assert!(matches!(o.subtasks[1].state, SlotState::Failed { .. }));
+        assert_eq!(o.to_dispatch(1), vec![0]);
+    }
+
+    #[test]
+    fn test_update_tracks_subtask_completion() {
+        let mut o = orchestration();
+        let parent = linked_agent(&o.parent.task, "In Progress");
+        let mut cart = linked_agent(&o.subtasks[0].task, "Done");
+        cart.status = AgentStatus::Idle;
+        let form = linked_agent(&o.subtasks[1].task, "In Progress");
+        let mut agents: HashMap<Uuid, Agent> = [&parent, &cart, &form]
+            .into_iter()
+            .map(|a| (a.id, a.clone()))
+            .collect();
+
+        o.update(&agents, "done");
+        assert_eq!(
+            o.parent.state,
+            SlotState::Running {
+                agent_id: parent.id
+            }
+        );
+        assert_eq!(o.subtasks[0].state, SlotState::Done { agent_id: cart.id });
+        assert_eq!(
+            o.subtasks[1].state,
+            SlotState::Running { agent_id: form.id }
+        );
+        assert_eq!(o.subtasks[2].state, SlotState::Pending);
+        assert_eq!(o.progress(), (1, 3));
+        assert!(!o.all_done());
+
+        agents.remove(&form.id);
+        o.update(&agents, "done");
+        assert!(matches!(o.subtasks[1].state, SlotState::Failed { .. }));
+        assert_eq!(o.subtask_agents(), vec![cart.id]);
+    }
+
+    #[test]
+    fn test_update_waits_for_the_agent_to_stop_working() {
+        let mut o = orchestration();
+        let parent = linked_agent(&o.parent.task, "In Progress");
+        let mut cart = linked_agent(&o.subtasks[0].task, "Done");
+        cart.status = AgentStatus::Running;
+        let mut form = linked_agent(&o.subtasks[1].task, "In Progress");
+        form.status = AgentStatus::Completed;
+        let mut agents: HashMap<Uuid, Agent> = [&parent, &cart, &form]
+            .into_iter()
+            .map(|a| (a.id, a.clone()))
+            .collect();
+
+        o.update(&agents, "done");
+        assert_eq!(
+            o.subtasks[0].state,
+            SlotState::Running { agent_id: cart.id }
+        );
+        assert_eq!(o.subtasks[1].state, SlotState::Done { agent_id: form.id });
+
+        agents.get_mut(&cart.id).unwrap().status = AgentStatus::Idle;
+        o.update(&agents, "done");
+        assert_eq!(o.subtasks[0].state, SlotState::Done { agent_id: cart.id });
+    }
+}
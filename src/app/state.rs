@@ -4,13 +4,19 @@ use std::path::PathBuf;
 use uuid::Uuid;
 
 use super::action::InputMode;
+use super::autopilot::Autopilot;
 use super::config::{
     AiAgent, AutomationConfig, ColumnVisibility, Config, GitProvider, Keybind, Keybinds,
-    LogLevel as ConfigLogLevel, ProjectMgmtProvider, RepoConfig, UiConfig, WorktreeLocation,
+    LaunchProfile, LogLevel as ConfigLogLevel, ProjectMgmtProvider, RepoConfig, UiConfig,
+    WorktreeLocation,
 };
+use super::orchestrator::Orchestration;
 use super::task_list::TaskListItem;
-use crate::agent::Agent;
+use crate::agent::budget::BudgetKind;
+use crate::agent::{Agent, AgentOverlap, ResourceUsage, SessionSummary};
+use crate::git::Checkpoint;
 use crate::ui::components::file_browser::DirEntry;
+use crate::verify::VerifyRun;
 use arboard::Clipboard;
 
 const SYSTEM_METRICS_HISTORY_SIZE: usize = 60;
@@ -33,6 +39,8 @@ pub enum PreviewTab {
     Preview,
     GitDiff,
     DevServer,
+    Activity,
+    Plan,
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +49,67 @@ pub struct DevServerWarning {
     pub running_servers: Vec<(String, Option<u16>)>,
 }
 
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    pub agent_id: Uuid,
+    pub agent_name: String,
+    pub base_branch: String,
+    pub files: Vec<String>,
+    pub rebase: bool,
+}
+
+/// Range of dev server log lines being picked to send to the agent. Lines
+/// are counted from the first line ever logged (see `DevServer::log_mark`)
+/// so the selection stays put while new output arrives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogSelection {
+    pub anchor: usize,
+    pub cursor: usize,
+}
+
+impl LogSelection {
+    pub fn range(&self) -> std::ops::RangeInclusive<usize> {
+        self.anchor.min(self.cursor)..=self.anchor.max(self.cursor)
+    }
+}
+
+/// Checkpoint timeline of one agent.
+#[derive(Debug, Clone)]
+pub struct CheckpointView {
+    pub agent_id: Uuid,
+    pub agent_name: String,
+    pub checkpoints: Vec<Checkpoint>,
+    pub selected: usize,
+    pub diff: Option<String>,
+    pub confirm_restore: bool,
+}
+
+impl CheckpointView {
+    pub fn selected_checkpoint(&self) -> Option<&Checkpoint> {
+        self.checkpoints.get(self.selected)
+    }
+}
+
+/// Past AI sessions of one agent to resume from.
+#[derive(Debug, Clone)]
+pub struct SessionPicker {
+    pub agent_id: Uuid,
+    pub agent_name: String,
+    pub sessions: Vec<SessionSummary>,
+    /// 0 is "Start fresh", then `sessions` in order
+    pub selected: usize,
+    pub loading: bool,
+}
+
+impl SessionPicker {
+    /// The session to resume, `None` for a fresh start.
+    pub fn selected_session(&self) -> Option<&SessionSummary> {
+        self.selected
+            .checked_sub(1)
+            .and_then(|i| self.sessions.get(i))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskReassignmentWarning {
     pub target_agent_id: Uuid,
@@ -132,6 +201,10 @@ pub enum SettingsField {
     CodebergCiProvider,
     BranchPrefix,
     MainBranch,
+    SyncStrategy,
+    QueueVerifyCommand,
+    QueuePush,
+    QueueArchive,
     WorktreeSymlinks,
     ProjectMgmtProvider,
     SetupPm,
@@ -161,28 +234,56 @@ pub enum SettingsField {
     DevServerRunBefore,
     DevServerWorkingDir,
     DevServerPort,
+    DevServerAutoPort,
+    DevServerPortRange,
+    DevServerPortEnv,
+    DevServerProxyEnabled,
+    DevServerProxyPort,
     DevServerAutoStart,
+    DevServerAutoSendErrors,
+    ProvisionCopyFiles,
+    ProvisionPostCreate,
+    ProvisionPreDelete,
+    VerifyCommands,
+    VerifyAutoRun,
     AutomationOnTaskAssign,
     AutomationOnPush,
     AutomationOnDelete,
     AutomationOnTaskAssignSubtask,
     AutomationOnDeleteSubtask,
+    StallEnabled,
+    StallAfterSecs,
+    StallRepeatThreshold,
+    StallNudge,
     KbNavDown,
     KbNavUp,
     KbNavFirst,
     KbNavLast,
     KbNewAgent,
+    KbNewStackedAgent,
+    KbImportAgent,
     KbDeleteAgent,
     KbAttach,
     KbSetNote,
+    KbSetBudget,
     KbYank,
     KbCopyPath,
     KbMerge,
+    KbRestack,
+    KbQueueAgent,
+    KbLandQueue,
+    KbRunVerify,
+    KbSendFailures,
+    KbCheckpoints,
+    KbPickSession,
+    KbOrchestrator,
+    KbToggleAutopilot,
     KbPush,
     KbFetch,
     KbSummary,
     KbToggleDiff,
     KbToggleLogs,
+    KbToggleOverlaps,
     KbOpenMr,
     KbAsanaAssign,
     KbAsanaOpen,
@@ -196,6 +297,8 @@ pub enum SettingsField {
     KbDebugStatus,
     Version,
     DebugMode,
+    ClaudeHooks,
+    McpServer,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -205,6 +308,7 @@ pub enum SettingsCategory {
     Storage,
     GitProvider,
     GitConfig,
+    MergeQueue,
     Ci,
     ProjectMgmt,
     Asana,
@@ -214,8 +318,11 @@ pub enum SettingsCategory {
     Linear,
     Prompts,
     DevServer,
+    Provisioning,
+    Verify,
     Automation,
     AsanaSubtasks,
+    Stall,
     KeybindNav,
     KeybindAgent,
     KeybindGit,
@@ -326,6 +433,7 @@ impl SettingsCategory {
             SettingsCategory::Storage => "Storage",
             SettingsCategory::GitProvider => "Provider",
             SettingsCategory::GitConfig => "Configuration",
+            SettingsCategory::MergeQueue => "Merge Queue",
             SettingsCategory::Ci => "CI/CD",
             SettingsCategory::ProjectMgmt => "Project Mgmt",
             SettingsCategory::Asana => "Asana",
@@ -335,8 +443,11 @@ impl SettingsCategory {
             SettingsCategory::Linear => "Linear",
             SettingsCategory::Prompts => "Prompts",
             SettingsCategory::DevServer => "Dev Server",
+            SettingsCategory::Provisioning => "Worktree Setup",
+            SettingsCategory::Verify => "Verify",
             SettingsCategory::Automation => "Automation",
             SettingsCategory::AsanaSubtasks => "Asana Subtasks",
+            SettingsCategory::Stall => "Stall Detection",
             SettingsCategory::KeybindNav => "Navigation",
             SettingsCategory::KeybindAgent => "Agent Management",
             SettingsCategory::KeybindGit => "Git Operations",
@@ -377,7 +488,9 @@ impl SettingsField {
             | SettingsField::SummaryPrompt
             | SettingsField::MergePrompt
             | SettingsField::PushPrompt
-            | SettingsField::DebugMode => SettingsTab::General,
+            | SettingsField::DebugMode
+            | SettingsField::ClaudeHooks
+            | SettingsField::McpServer => SettingsTab::General,
             SettingsField::GitProvider
             | SettingsField::GitLabProjectId
             | SettingsField::GitLabBaseUrl
@@ -389,6 +502,10 @@ impl SettingsField {
             | SettingsField::CodebergCiProvider
             | SettingsField::BranchPrefix
             | SettingsField::MainBranch
+            | SettingsField::SyncStrategy
+            | SettingsField::QueueVerifyCommand
+            | SettingsField::QueuePush
+            | SettingsField::QueueArchive
             | SettingsField::SetupGit => SettingsTab::Git,
             SettingsField::ProjectMgmtProvider
             | SettingsField::SetupPm
@@ -414,29 +531,57 @@ impl SettingsField {
             | SettingsField::DevServerRunBefore
             | SettingsField::DevServerWorkingDir
             | SettingsField::DevServerPort
+            | SettingsField::DevServerAutoPort
+            | SettingsField::DevServerPortRange
+            | SettingsField::DevServerPortEnv
+            | SettingsField::DevServerProxyEnabled
+            | SettingsField::DevServerProxyPort
             | SettingsField::DevServerAutoStart
-            | SettingsField::WorktreeSymlinks => SettingsTab::DevServer,
+            | SettingsField::DevServerAutoSendErrors
+            | SettingsField::WorktreeSymlinks
+            | SettingsField::ProvisionCopyFiles
+            | SettingsField::ProvisionPostCreate
+            | SettingsField::ProvisionPreDelete
+            | SettingsField::VerifyCommands
+            | SettingsField::VerifyAutoRun => SettingsTab::DevServer,
             SettingsField::AutomationOnTaskAssign
             | SettingsField::AutomationOnPush
             | SettingsField::AutomationOnDelete
             | SettingsField::AutomationOnTaskAssignSubtask
-            | SettingsField::AutomationOnDeleteSubtask => SettingsTab::Automation,
+            | SettingsField::AutomationOnDeleteSubtask
+            | SettingsField::StallEnabled
+            | SettingsField::StallAfterSecs
+            | SettingsField::StallRepeatThreshold
+            | SettingsField::StallNudge => SettingsTab::Automation,
             SettingsField::KbNavDown
             | SettingsField::KbNavUp
             | SettingsField::KbNavFirst
             | SettingsField::KbNavLast
             | SettingsField::KbNewAgent
+            | SettingsField::KbNewStackedAgent
+            | SettingsField::KbImportAgent
             | SettingsField::KbDeleteAgent
             | SettingsField::KbAttach
             | SettingsField::KbSetNote
+            | SettingsField::KbSetBudget
             | SettingsField::KbYank
             | SettingsField::KbCopyPath
             | SettingsField::KbMerge
+            | SettingsField::KbRestack
+            | SettingsField::KbQueueAgent
+            | SettingsField::KbLandQueue
+            | SettingsField::KbRunVerify
+            | SettingsField::KbSendFailures
+            | SettingsField::KbCheckpoints
+            | SettingsField::KbPickSession
+            | SettingsField::KbOrchestrator
+            | SettingsField::KbToggleAutopilot
             | SettingsField::KbPush
             | SettingsField::KbFetch
             | SettingsField::KbSummary
             | SettingsField::KbToggleDiff
             | SettingsField::KbToggleLogs
+            | SettingsField::KbToggleOverlaps
             | SettingsField::KbOpenMr
             | SettingsField::KbAsanaAssign
             | SettingsField::KbAsanaOpen
@@ -466,17 +611,30 @@ impl SettingsField {
                 | SettingsField::KbNavFirst
                 | SettingsField::KbNavLast
                 | SettingsField::KbNewAgent
+                | SettingsField::KbNewStackedAgent
+                | SettingsField::KbImportAgent
                 | SettingsField::KbDeleteAgent
                 | SettingsField::KbAttach
                 | SettingsField::KbSetNote
+                | SettingsField::KbSetBudget
                 | SettingsField::KbYank
                 | SettingsField::KbCopyPath
                 | SettingsField::KbMerge
+                | SettingsField::KbRestack
+                | SettingsField::KbQueueAgent
+                | SettingsField::KbLandQueue
+                | SettingsField::KbRunVerify
+                | SettingsField::KbSendFailures
+                | SettingsField::KbCheckpoints
+                | SettingsField::KbPickSession
+                | SettingsField::KbOrchestrator
+                | SettingsField::KbToggleAutopilot
                 | SettingsField::KbPush
                 | SettingsField::KbFetch
                 | SettingsField::KbSummary
                 | SettingsField::KbToggleDiff
                 | SettingsField::KbToggleLogs
+                | SettingsField::KbToggleOverlaps
                 | SettingsField::KbOpenMr
                 | SettingsField::KbAsanaAssign
                 | SettingsField::KbAsanaOpen
@@ -498,17 +656,30 @@ impl SettingsField {
             SettingsField::KbNavFirst => Some("Go to First"),
             SettingsField::KbNavLast => Some("Go to Last"),
             SettingsField::KbNewAgent => Some("New Agent"),
+            SettingsField::KbNewStackedAgent => Some("New Agent On Base"),
+            SettingsField::KbImportAgent => Some("Import Agent"),
             SettingsField::KbDeleteAgent => Some("Delete Agent"),
             SettingsField::KbAttach => Some("Attach to Agent"),
             SettingsField::KbSetNote => Some("Set Note"),
+            SettingsField::KbSetBudget => Some("Set Budget"),
             SettingsField::KbYank => Some("Copy Name"),
             SettingsField::KbCopyPath => Some("Copy Cd Command"),
             SettingsField::KbMerge => Some("Merge Main"),
+            SettingsField::KbRestack => Some("Restack"),
+            SettingsField::KbQueueAgent => Some("Toggle Merge Queue"),
+            SettingsField::KbLandQueue => Some("Land Merge Queue"),
+            SettingsField::KbRunVerify => Some("Run Verify"),
+            SettingsField::KbSendFailures => Some("Send Failures"),
+            SettingsField::KbCheckpoints => Some("Checkpoints"),
+            SettingsField::KbPickSession => Some("Pick AI Session"),
+            SettingsField::KbOrchestrator => Some("Orchestrations"),
+            SettingsField::KbToggleAutopilot => Some("Toggle Autopilot"),
             SettingsField::KbPush => Some("Push Changes"),
             SettingsField::KbFetch => Some("Fetch Remote"),
             SettingsField::KbSummary => Some("Request Summary"),
             SettingsField::KbToggleDiff => Some("Toggle Diff"),
             SettingsField::KbToggleLogs => Some("Toggle Logs"),
+            SettingsField::KbToggleOverlaps => Some("Agent Overlaps"),
             SettingsField::KbOpenMr => Some("Open MR/PR"),
             SettingsField::KbAsanaAssign => Some("Assign Asana"),
             SettingsField::KbAsanaOpen => Some("Open in Asana"),
@@ -554,6 +725,8 @@ impl SettingsItem {
                 SettingsItem::Field(SettingsField::Editor),
                 SettingsItem::Field(SettingsField::LogLevel),
                 SettingsItem::Field(SettingsField::DebugMode),
+                SettingsItem::Field(SettingsField::ClaudeHooks),
+                SettingsItem::Field(SettingsField::McpServer),
                 SettingsItem::Category(SettingsCategory::Storage),
                 SettingsItem::Field(SettingsField::WorktreeLocation),
                 SettingsItem::Category(SettingsCategory::Prompts),
@@ -595,6 +768,11 @@ impl SettingsItem {
                 items.push(SettingsItem::Category(SettingsCategory::GitConfig));
                 items.push(SettingsItem::Field(SettingsField::BranchPrefix));
                 items.push(SettingsItem::Field(SettingsField::MainBranch));
+                items.push(SettingsItem::Field(SettingsField::SyncStrategy));
+                items.push(SettingsItem::Category(SettingsCategory::MergeQueue));
+                items.push(SettingsItem::Field(SettingsField::QueueVerifyCommand));
+                items.push(SettingsItem::Field(SettingsField::QueuePush));
+                items.push(SettingsItem::Field(SettingsField::QueueArchive));
                 items.push(SettingsItem::ActionButton(ActionButtonType::ResetTab));
                 items
             }
@@ -648,8 +826,21 @@ impl SettingsItem {
                 SettingsItem::Field(SettingsField::DevServerRunBefore),
                 SettingsItem::Field(SettingsField::DevServerWorkingDir),
                 SettingsItem::Field(SettingsField::DevServerPort),
+                SettingsItem::Field(SettingsField::DevServerAutoPort),
+                SettingsItem::Field(SettingsField::DevServerPortRange),
+                SettingsItem::Field(SettingsField::DevServerPortEnv),
+                SettingsItem::Field(SettingsField::DevServerProxyEnabled),
+                SettingsItem::Field(SettingsField::DevServerProxyPort),
                 SettingsItem::Field(SettingsField::DevServerAutoStart),
+                SettingsItem::Field(SettingsField::DevServerAutoSendErrors),
+                SettingsItem::Category(SettingsCategory::Provisioning),
                 SettingsItem::Field(SettingsField::WorktreeSymlinks),
+                SettingsItem::Field(SettingsField::ProvisionCopyFiles),
+                SettingsItem::Field(SettingsField::ProvisionPostCreate),
+                SettingsItem::Field(SettingsField::ProvisionPreDelete),
+                SettingsItem::Category(SettingsCategory::Verify),
+                SettingsItem::Field(SettingsField::VerifyCommands),
+                SettingsItem::Field(SettingsField::VerifyAutoRun),
                 SettingsItem::ActionButton(ActionButtonType::ResetTab),
             ],
             SettingsTab::Automation => {
@@ -668,6 +859,11 @@ impl SettingsItem {
                         SettingsField::AutomationOnDeleteSubtask,
                     ));
                 }
+                items.push(SettingsItem::Category(SettingsCategory::Stall));
+                items.push(SettingsItem::Field(SettingsField::StallEnabled));
+                items.push(SettingsItem::Field(SettingsField::StallAfterSecs));
+                items.push(SettingsItem::Field(SettingsField::StallRepeatThreshold));
+                items.push(SettingsItem::Field(SettingsField::StallNudge));
                 items.push(SettingsItem::ActionButton(ActionButtonType::ResetTab));
                 items
             }
@@ -679,18 +875,31 @@ impl SettingsItem {
                 SettingsItem::Field(SettingsField::KbNavLast),
                 SettingsItem::Category(SettingsCategory::KeybindAgent),
                 SettingsItem::Field(SettingsField::KbNewAgent),
+                SettingsItem::Field(SettingsField::KbNewStackedAgent),
+                SettingsItem::Field(SettingsField::KbImportAgent),
                 SettingsItem::Field(SettingsField::KbDeleteAgent),
                 SettingsItem::Field(SettingsField::KbAttach),
                 SettingsItem::Field(SettingsField::KbSetNote),
+                SettingsItem::Field(SettingsField::KbSetBudget),
                 SettingsItem::Field(SettingsField::KbYank),
                 SettingsItem::Category(SettingsCategory::KeybindGit),
                 SettingsItem::Field(SettingsField::KbCopyPath),
                 SettingsItem::Field(SettingsField::KbMerge),
+                SettingsItem::Field(SettingsField::KbRestack),
+                SettingsItem::Field(SettingsField::KbQueueAgent),
+                SettingsItem::Field(SettingsField::KbLandQueue),
+                SettingsItem::Field(SettingsField::KbRunVerify),
+                SettingsItem::Field(SettingsField::KbSendFailures),
+                SettingsItem::Field(SettingsField::KbCheckpoints),
+                SettingsItem::Field(SettingsField::KbPickSession),
+                SettingsItem::Field(SettingsField::KbOrchestrator),
+                SettingsItem::Field(SettingsField::KbToggleAutopilot),
                 SettingsItem::Field(SettingsField::KbPush),
                 SettingsItem::Field(SettingsField::KbFetch),
                 SettingsItem::Field(SettingsField::KbSummary),
                 SettingsItem::Field(SettingsField::KbToggleDiff),
                 SettingsItem::Field(SettingsField::KbToggleLogs),
+                SettingsItem::Field(SettingsField::KbToggleOverlaps),
                 SettingsItem::Category(SettingsCategory::KeybindExternal),
                 SettingsItem::Field(SettingsField::KbOpenMr),
                 SettingsItem::Field(SettingsField::KbAsanaAssign),
@@ -830,6 +1039,18 @@ impl ColumnOption {
                 visible: true,
                 default_visible: true,
             },
+            ColumnOption {
+                key: "tests",
+                label: "Tests",
+                visible: true,
+                default_visible: true,
+            },
+            ColumnOption {
+                key: "usage",
+                label: "Usage (CPU/Mem/Procs)",
+                visible: true,
+                default_visible: true,
+            },
             ColumnOption {
                 key: "task",
                 label: "Task",
@@ -883,15 +1104,21 @@ impl ColumnOption {
         if !visibility.server {
             options[9].visible = false;
         }
-        if !visibility.task {
+        if !visibility.tests {
             options[10].visible = false;
         }
-        if !visibility.task_status {
+        if !visibility.usage {
             options[11].visible = false;
         }
-        if !visibility.note {
+        if !visibility.task {
             options[12].visible = false;
         }
+        if !visibility.task_status {
+            options[13].visible = false;
+        }
+        if !visibility.note {
+            options[14].visible = false;
+        }
         options
     }
 
@@ -938,9 +1165,11 @@ impl ColumnSelectorState {
             mr: self.columns[7].visible,
             pipeline: self.columns[8].visible,
             server: self.columns[9].visible,
-            task: self.columns[10].visible,
-            task_status: self.columns[11].visible,
-            note: self.columns[12].visible,
+            tests: self.columns[10].visible,
+            usage: self.columns[11].visible,
+            task: self.columns[12].visible,
+            task_status: self.columns[13].visible,
+            note: self.columns[14].visible,
         }
     }
 }
@@ -960,6 +1189,8 @@ pub struct SettingsState {
     pub pending_log_level: ConfigLogLevel,
     pub pending_worktree_location: WorktreeLocation,
     pub pending_debug_mode: bool,
+    pub pending_claude_hooks: bool,
+    pub pending_mcp_server: bool,
     pub pending_ui: UiConfig,
     pub repo_config: RepoConfig,
     pub pending_keybinds: Keybinds,
@@ -990,6 +1221,8 @@ impl Default for SettingsState {
             pending_log_level: ConfigLogLevel::default(),
             pending_worktree_location: WorktreeLocation::default(),
             pending_debug_mode: false,
+            pending_claude_hooks: false,
+            pending_mcp_server: true,
             pending_ui: UiConfig::default(),
             repo_config: RepoConfig::default(),
             pending_keybinds: Keybinds::default(),
@@ -1088,17 +1321,30 @@ impl SettingsState {
             SettingsField::KbNavFirst => Some(&self.pending_keybinds.nav_first),
             SettingsField::KbNavLast => Some(&self.pending_keybinds.nav_last),
             SettingsField::KbNewAgent => Some(&self.pending_keybinds.new_agent),
+            SettingsField::KbNewStackedAgent => Some(&self.pending_keybinds.new_stacked_agent),
+            SettingsField::KbImportAgent => Some(&self.pending_keybinds.import_agent),
             SettingsField::KbDeleteAgent => Some(&self.pending_keybinds.delete_agent),
             SettingsField::KbAttach => Some(&self.pending_keybinds.attach),
             SettingsField::KbSetNote => Some(&self.pending_keybinds.set_note),
+            SettingsField::KbSetBudget => Some(&self.pending_keybinds.set_budget),
             SettingsField::KbYank => Some(&self.pending_keybinds.yank),
             SettingsField::KbCopyPath => Some(&self.pending_keybinds.copy_path),
             SettingsField::KbMerge => Some(&self.pending_keybinds.merge),
+            SettingsField::KbRestack => Some(&self.pending_keybinds.restack),
+            SettingsField::KbQueueAgent => Some(&self.pending_keybinds.queue_agent),
+            SettingsField::KbLandQueue => Some(&self.pending_keybinds.land_queue),
+            SettingsField::KbRunVerify => Some(&self.pending_keybinds.run_verify),
+            SettingsField::KbSendFailures => Some(&self.pending_keybinds.send_failures),
+            SettingsField::KbCheckpoints => Some(&self.pending_keybinds.checkpoints),
+            SettingsField::KbPickSession => Some(&self.pending_keybinds.pick_session),
+            SettingsField::KbOrchestrator => Some(&self.pending_keybinds.orchestrator),
+            SettingsField::KbToggleAutopilot => Some(&self.pending_keybinds.toggle_autopilot),
             SettingsField::KbPush => Some(&self.pending_keybinds.push),
             SettingsField::KbFetch => Some(&self.pending_keybinds.fetch),
             SettingsField::KbSummary => Some(&self.pending_keybinds.summary),
             SettingsField::KbToggleDiff => Some(&self.pending_keybinds.toggle_diff),
             SettingsField::KbToggleLogs => Some(&self.pending_keybinds.toggle_logs),
+            SettingsField::KbToggleOverlaps => Some(&self.pending_keybinds.toggle_overlaps),
             SettingsField::KbOpenMr => Some(&self.pending_keybinds.open_mr),
             SettingsField::KbAsanaAssign => Some(&self.pending_keybinds.asana_assign),
             SettingsField::KbAsanaOpen => Some(&self.pending_keybinds.asana_open),
@@ -1121,17 +1367,30 @@ impl SettingsState {
             SettingsField::KbNavFirst => self.pending_keybinds.nav_first = keybind,
             SettingsField::KbNavLast => self.pending_keybinds.nav_last = keybind,
             SettingsField::KbNewAgent => self.pending_keybinds.new_agent = keybind,
+            SettingsField::KbNewStackedAgent => self.pending_keybinds.new_stacked_agent = keybind,
+            SettingsField::KbImportAgent => self.pending_keybinds.import_agent = keybind,
             SettingsField::KbDeleteAgent => self.pending_keybinds.delete_agent = keybind,
             SettingsField::KbAttach => self.pending_keybinds.attach = keybind,
             SettingsField::KbSetNote => self.pending_keybinds.set_note = keybind,
+            SettingsField::KbSetBudget => self.pending_keybinds.set_budget = keybind,
             SettingsField::KbYank => self.pending_keybinds.yank = keybind,
             SettingsField::KbCopyPath => self.pending_keybinds.copy_path = keybind,
             SettingsField::KbMerge => self.pending_keybinds.merge = keybind,
+            SettingsField::KbRestack => self.pending_keybinds.restack = keybind,
+            SettingsField::KbQueueAgent => self.pending_keybinds.queue_agent = keybind,
+            SettingsField::KbLandQueue => self.pending_keybinds.land_queue = keybind,
+            SettingsField::KbRunVerify => self.pending_keybinds.run_verify = keybind,
+            SettingsField::KbSendFailures => self.pending_keybinds.send_failures = keybind,
+            SettingsField::KbCheckpoints => self.pending_keybinds.checkpoints = keybind,
+            SettingsField::KbPickSession => self.pending_keybinds.pick_session = keybind,
+            SettingsField::KbOrchestrator => self.pending_keybinds.orchestrator = keybind,
+            SettingsField::KbToggleAutopilot => self.pending_keybinds.toggle_autopilot = keybind,
             SettingsField::KbPush => self.pending_keybinds.push = keybind,
             SettingsField::KbFetch => self.pending_keybinds.fetch = keybind,
             SettingsField::KbSummary => self.pending_keybinds.summary = keybind,
             SettingsField::KbToggleDiff => self.pending_keybinds.toggle_diff = keybind,
             SettingsField::KbToggleLogs => self.pending_keybinds.toggle_logs = keybind,
+            SettingsField::KbToggleOverlaps => self.pending_keybinds.toggle_overlaps = keybind,
             SettingsField::KbOpenMr => self.pending_keybinds.open_mr = keybind,
             SettingsField::KbAsanaAssign => self.pending_keybinds.asana_assign = keybind,
             SettingsField::KbAsanaOpen => self.pending_keybinds.asana_open = keybind,
@@ -1186,6 +1445,8 @@ impl SettingsState {
         self.pending_log_level = ConfigLogLevel::default();
         self.pending_worktree_location = WorktreeLocation::default();
         self.pending_debug_mode = false;
+        self.pending_claude_hooks = false;
+        self.pending_mcp_server = true;
         self.pending_ui = UiConfig::default();
         self.repo_config.prompts = crate::app::config::PromptsConfig::default();
     }
@@ -1200,6 +1461,8 @@ impl SettingsState {
 
     pub fn reset_dev_server_defaults(&mut self) {
         self.repo_config.dev_server = crate::app::config::DevServerConfig::default();
+        self.repo_config.provisioning = crate::app::config::ProvisioningConfig::default();
+        self.repo_config.verify = crate::app::config::VerifyConfig::default();
     }
 
     pub fn reset_keybinds_defaults(&mut self) {
@@ -1209,6 +1472,7 @@ impl SettingsState {
 
     pub fn reset_automation_defaults(&mut self) {
         self.pending_automation = AutomationConfig::default();
+        self.repo_config.stall = crate::app::config::StallConfig::default();
     }
 
     pub fn reset_appearance_defaults(&mut self) {
@@ -1250,6 +1514,10 @@ pub struct AppState {
     pub show_diff: bool,
     pub input_mode: Option<InputMode>,
     pub input_buffer: String,
+    /// Base branch chosen for the agent currently being created
+    pub pending_base_branch: Option<String>,
+    /// Launch profile chosen for the agent currently being created
+    pub pending_profile: Option<String>,
     pub output_scroll: usize,
     pub repo_path: String,
     pub logs: Vec<LogEntry>,
@@ -1259,6 +1527,13 @@ pub struct AppState {
     pub memory_history: VecDeque<f32>,
     pub memory_used: u64,
     pub memory_total: u64,
+    pub agent_resources: HashMap<Uuid, ResourceUsage>,
+    /// When each agent first went over the resource warning thresholds
+    pub resource_over_since: HashMap<Uuid, std::time::Instant>,
+    /// Agents already warned about since they went over the thresholds
+    pub resource_warned: HashSet<Uuid>,
+    /// Budget limits each agent has already been acted on for
+    pub budget_exceeded: HashMap<Uuid, HashSet<BudgetKind>>,
     pub loading_message: Option<String>,
     pub preview_content: Option<String>,
     pub settings: SettingsState,
@@ -1270,11 +1545,34 @@ pub struct AppState {
     pub git_setup: GitSetupState,
     pub worktree_base: std::path::PathBuf,
     pub preview_tab: PreviewTab,
+    /// Service shown in the DevServer tab, by position
+    pub devserver_service: usize,
     pub devserver_scroll: usize,
+    pub devserver_selection: Option<LogSelection>,
+    /// Agents with a new dev server error waiting to be auto-sent: the
+    /// service and when its output last grew
+    pub devserver_error_pending: HashMap<Uuid, (String, std::time::Instant)>,
+    /// Last error block sent to each agent, to avoid resending it
+    pub devserver_error_sent: HashMap<Uuid, String>,
+    /// Detector for the configured error patterns, built on first use
+    devserver_error_detector: Option<crate::devserver::ErrorDetector>,
     pub gitdiff_content: Option<String>,
     pub gitdiff_scroll: usize,
     pub gitdiff_line_count: usize,
     pub devserver_warning: Option<DevServerWarning>,
+    pub merge_conflict: Option<MergeConflict>,
+    /// Agents selected for landing, in order
+    pub merge_queue: Vec<Uuid>,
+    pub merge_queue_running: bool,
+    pub verify_runs: HashMap<Uuid, VerifyRun>,
+    pub checkpoint_view: Option<CheckpointView>,
+    pub session_picker: Option<SessionPicker>,
+    /// Parent tasks whose subtasks are being dispatched to agents
+    pub orchestrations: Vec<Orchestration>,
+    pub show_orchestrator: bool,
+    pub orchestrator_selected: usize,
+    /// Queued tasks picked up automatically
+    pub autopilot: Autopilot,
     pub task_reassignment_warning: Option<TaskReassignmentWarning>,
     pub task_list: Vec<TaskListItem>,
     pub task_list_loading: bool,
@@ -1287,6 +1585,8 @@ pub struct AppState {
     pub task_status_dropdown: Option<TaskStatusDropdownState>,
     pub agent_list_scroll: usize,
     pub show_status_debug: bool,
+    pub overlaps: Vec<AgentOverlap>,
+    pub show_overlap_matrix: bool,
     pub pm_status_debug: PmStatusDebugState,
     pub clipboard: ClipboardHolder,
     pub show_tutorial: bool,
@@ -1566,6 +1866,8 @@ impl AppState {
             show_diff: false,
             input_mode: None,
             input_buffer: String::new(),
+            pending_base_branch: None,
+            pending_profile: None,
             output_scroll: 0,
             repo_path,
             logs: Vec::new(),
@@ -1575,6 +1877,10 @@ impl AppState {
             memory_history: VecDeque::with_capacity(SYSTEM_METRICS_HISTORY_SIZE),
             memory_used: 0,
             memory_total: 0,
+            agent_resources: HashMap::new(),
+            resource_over_since: HashMap::new(),
+            resource_warned: HashSet::new(),
+            budget_exceeded: HashMap::new(),
             loading_message: None,
             preview_content: None,
             settings: SettingsState {
@@ -1593,11 +1899,26 @@ impl AppState {
             git_setup: GitSetupState::default(),
             worktree_base,
             preview_tab: PreviewTab::default(),
+            devserver_service: 0,
             devserver_scroll: 0,
+            devserver_selection: None,
+            devserver_error_pending: HashMap::new(),
+            devserver_error_sent: HashMap::new(),
+            devserver_error_detector: None,
             gitdiff_content: None,
             gitdiff_scroll: 0,
             gitdiff_line_count: 0,
             devserver_warning: None,
+            merge_conflict: None,
+            merge_queue: Vec::new(),
+            merge_queue_running: false,
+            verify_runs: HashMap::new(),
+            checkpoint_view: None,
+            session_picker: None,
+            orchestrations: Vec::new(),
+            show_orchestrator: false,
+            orchestrator_selected: 0,
+            autopilot: Autopilot::default(),
             task_reassignment_warning: None,
             task_list: Vec::new(),
             task_list_loading: false,
@@ -1610,6 +1931,8 @@ impl AppState {
             task_status_dropdown: None,
             agent_list_scroll: 0,
             show_status_debug: false,
+            overlaps: Vec::new(),
+            show_overlap_matrix: false,
             pm_status_debug: PmStatusDebugState::default(),
             clipboard: ClipboardHolder(None),
             show_tutorial: false,
@@ -1674,6 +1997,45 @@ impl AppState {
         self.agent_order.get(self.selected_index).cloned()
     }
 
+    /// Launch profiles from the repo and global config.
+    pub fn launch_profiles(&self) -> Vec<LaunchProfile> {
+        crate::agent::profile::available(&self.config.profiles, &self.settings.repo_config.profiles)
+    }
+
+    pub fn launch_profile(&self, name: &str) -> Option<LaunchProfile> {
+        self.launch_profiles().into_iter().find(|p| p.name == name)
+    }
+
+    /// The configured default launch profile, if it exists.
+    pub fn default_launch_profile(&self) -> Option<LaunchProfile> {
+        let name = self.config.global.default_profile.as_deref()?;
+        self.launch_profile(name)
+    }
+
+    /// AI agent running in the agent's session.
+    pub fn ai_agent_for(&self, id: Uuid) -> AiAgent {
+        match self.agents.get(&id) {
+            Some(agent) => agent.ai_agent_or(&self.config.global.ai_agent),
+            None => self.config.global.ai_agent.clone(),
+        }
+    }
+
+    /// Every agent with its AI agent, for the status poller.
+    pub fn agent_ai_agents(&self) -> HashMap<Uuid, AiAgent> {
+        self.agents
+            .keys()
+            .map(|id| (*id, self.ai_agent_for(*id)))
+            .collect()
+    }
+
+    /// Tmux session of each agent.
+    pub fn agent_sessions(&self) -> HashMap<Uuid, String> {
+        self.agents
+            .iter()
+            .map(|(id, agent)| (*id, agent.tmux_session.clone()))
+            .collect()
+    }
+
     pub fn add_agent(&mut self, agent: Agent) {
         let id = agent.id;
         self.agents.insert(id, agent);
@@ -1690,6 +2052,43 @@ impl AppState {
         });
     }
 
+    /// Port for an agent's dev server: its persisted port, or the first of a
+    /// free block (one port per service) from the configured range.
+    pub fn ensure_dev_port(&mut self, id: Uuid) -> Option<u16> {
+        let config = &self.settings.repo_config.dev_server;
+        if !config.auto_port {
+            return config.port;
+        }
+        if let Some(port) = self.agents.get(&id)?.dev_port {
+            return Some(port);
+        }
+        let count = crate::devserver::service_count(config);
+        let taken: HashSet<u16> = self
+            .agents
+            .values()
+            .filter_map(|a| a.dev_port)
+            .flat_map(|base| base..base.saturating_add(count))
+            .collect();
+        let port = crate::devserver::allocate_port(
+            config.port_range_start,
+            config.port_range_end,
+            count,
+            &taken,
+        )?;
+        self.agents.get_mut(&id)?.dev_port = Some(port);
+        Some(port)
+    }
+
+    /// Dev server error detector, rebuilt when the error patterns change.
+    pub fn devserver_error_detector(&mut self) -> anyhow::Result<&crate::devserver::ErrorDetector> {
+        let patterns = &self.settings.repo_config.dev_server.error_patterns;
+        let detector = match self.devserver_error_detector.take() {
+            Some(detector) if detector.patterns() == patterns.as_slice() => detector,
+            _ => crate::devserver::ErrorDetector::new(patterns)?,
+        };
+        Ok(self.devserver_error_detector.insert(detector))
+    }
+
     pub fn remove_agent(&mut self, id: Uuid) -> Option<Agent> {
         if let Some(pos) = self.agent_order.iter().position(|&x| x == id) {
             self.agent_order.remove(pos);
@@ -1697,6 +2096,8 @@ impl AppState {
                 self.selected_index -= 1;
             }
         }
+        self.merge_queue.retain(|&x| x != id);
+        self.verify_runs.remove(&id);
         self.agents.remove(&id)
     }
 
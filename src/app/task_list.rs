@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::agent::Agent;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskListItem {
     pub id: String,
@@ -9,6 +11,9 @@ pub struct TaskListItem {
     pub url: String,
     pub parent_id: Option<String>,
     pub has_children: bool,
+    /// Labels or tags, for providers that have them
+    #[serde(default)]
+    pub labels: Vec<String>,
 }
 
 impl TaskListItem {
@@ -19,4 +24,72 @@ impl TaskListItem {
     pub fn is_subtask(&self) -> bool {
         self.parent_id.is_some()
     }
+
+    /// Whether `agent` is linked to this task. Providers don't agree on
+    /// dashes and case in ids, so both are ignored.
+    pub fn is_linked_to(&self, agent: &Agent) -> bool {
+        agent
+            .pm_task_status
+            .id()
+            .is_some_and(|id| normalize_task_id(id) == normalize_task_id(&self.id))
+    }
+}
+
+fn normalize_task_id(id: &str) -> String {
+    id.replace('-', "").to_lowercase()
+}
+
+/// Apply `resolve` to the first of `entries` still waiting for its agent to
+/// be created on `branch`, as told by `starting_branch`. Returns whether one
+/// was waiting.
+pub(crate) fn resolve_starting<'a, T: 'a>(
+    entries: impl IntoIterator<Item = &'a mut T>,
+    branch: &str,
+    starting_branch: fn(&T) -> Option<&str>,
+    resolve: impl FnOnce(&mut T),
+) -> bool {
+    entries
+        .into_iter()
+        .find(|entry| starting_branch(entry) == Some(branch))
+        .map(resolve)
+        .is_some()
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+    use crate::agent::ProjectMgmtTaskStatus;
+    use crate::core::common::sanitize_branch_name;
+    use crate::core::projects::linear::LinearTaskStatus;
+
+    pub fn task(id: &str, name: &str) -> TaskListItem {
+        TaskListItem {
+            id: id.to_string(),
+            identifier: None,
+            name: name.to_string(),
+            status_name: "Todo".to_string(),
+            url: String::new(),
+            parent_id: None,
+            has_children: false,
+            labels: Vec::new(),
+        }
+    }
+
+    /// An agent on the task's branch, linked to it in `status`.
+    pub fn linked_agent(task: &TaskListItem, status: &str) -> Agent {
+        let mut agent = Agent::new(
+            task.name.clone(),
+            sanitize_branch_name(&task.name),
+            String::new(),
+        );
+        agent.pm_task_status = ProjectMgmtTaskStatus::Linear(LinearTaskStatus::InProgress {
+            id: task.id.clone(),
+            identifier: String::new(),
+            name: task.name.clone(),
+            status_name: status.to_string(),
+            url: String::new(),
+            is_subtask: task.is_subtask(),
+        });
+        agent
+    }
 }
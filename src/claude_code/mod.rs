@@ -1,3 +1,3 @@
 pub mod session;
 
-pub use session::{build_resume_command, find_session_by_directory};
+pub use session::{build_resume_command, find_session_by_directory, list_sessions};
@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
+use chrono::DateTime;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
 
+use crate::agent::sessions::SessionSummary;
+
 fn get_history_path() -> PathBuf {
     dirs::home_dir()
         .map(|h| h.join(".claude").join("history.jsonl"))
@@ -52,6 +55,92 @@ pub fn find_session_by_directory(worktree_path: &str) -> Result<Option<String>>
     Ok(latest.map(|(_, id)| id))
 }
 
+/// Every Claude Code session for `worktree_path`, from the prompt history.
+pub fn list_sessions(worktree_path: &str) -> Result<Vec<SessionSummary>> {
+    let history_path = get_history_path();
+    if !history_path.exists() {
+        return Ok(Vec::new());
+    }
+    let file = File::open(&history_path).context("Failed to open Claude history file")?;
+    let lines = BufReader::new(file).lines().map_while(|l| l.ok());
+    let mut sessions = sessions_from_history(lines, worktree_path);
+
+    // The session transcript has the full conversation; the history only prompts
+    for session in &mut sessions {
+        if let Some(count) = transcript_message_count(worktree_path, &session.id) {
+            session.message_count = count;
+        }
+    }
+    Ok(sessions)
+}
+
+fn sessions_from_history(
+    lines: impl Iterator<Item = String>,
+    worktree_path: &str,
+) -> Vec<SessionSummary> {
+    let mut sessions: Vec<SessionSummary> = Vec::new();
+    for line in lines {
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        if entry.get("project").and_then(|p| p.as_str()) != Some(worktree_path) {
+            continue;
+        }
+        let (Some(session_id), Some(updated_at)) = (
+            entry.get("sessionId").and_then(|s| s.as_str()),
+            entry
+                .get("timestamp")
+                .and_then(|t| t.as_i64())
+                .and_then(DateTime::from_timestamp_millis),
+        ) else {
+            continue;
+        };
+        match sessions.iter_mut().find(|s| s.id == session_id) {
+            Some(session) => {
+                session.message_count += 1;
+                session.updated_at = session.updated_at.max(updated_at);
+            }
+            None => sessions.push(SessionSummary {
+                id: session_id.to_string(),
+                updated_at,
+                first_prompt: entry
+                    .get("display")
+                    .and_then(|d| d.as_str())
+                    .map(String::from),
+                message_count: 1,
+            }),
+        }
+    }
+    sessions
+}
+
+/// User and assistant messages in `~/.claude/projects/<path>/<session>.jsonl`.
+fn transcript_message_count(worktree_path: &str, session_id: &str) -> Option<usize> {
+    let project: String = worktree_path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let path = dirs::home_dir()?
+        .join(".claude")
+        .join("projects")
+        .join(project)
+        .join(format!("{}.jsonl", session_id));
+    let file = File::open(path).ok()?;
+    let count = BufReader::new(file)
+        .lines()
+        .map_while(|l| l.ok())
+        .filter(|line| {
+            serde_json::from_str::<serde_json::Value>(line).is_ok_and(|entry| {
+                matches!(
+                    entry.get("type").and_then(|t| t.as_str()),
+                    Some("user" | "assistant")
+                )
+            })
+        })
+        .count();
+    Some(count)
+}
+
 pub fn build_resume_command(base_cmd: &str, session_id: Option<&str>) -> String {
     match session_id {
         Some(id) => format!("{} --resume {}", base_cmd, id),
@@ -71,4 +160,24 @@ mod tests {
         );
         assert_eq!(build_resume_command("claude", None), "claude");
     }
+
+    #[test]
+    fn test_sessions_from_history() {
+        let history = [
+            r#"{"display":"Add a login page","timestamp":1760000000000,"project":"/wt/a","sessionId":"s1"}"#,
+            r#"{"display":"Other repo","timestamp":1760000001000,"project":"/wt/b","sessionId":"s9"}"#,
+            r#"{"display":"Now add tests","timestamp":1760000002000,"project":"/wt/a","sessionId":"s1"}"#,
+            r#"{"display":"Fix the build","timestamp":1760000003000,"project":"/wt/a","sessionId":"s2"}"#,
+        ];
+        let sessions = sessions_from_history(history.iter().map(|l| l.to_string()), "/wt/a");
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].id, "s1");
+        assert_eq!(
+            sessions[0].first_prompt.as_deref(),
+            Some("Add a login page")
+        );
+        assert_eq!(sessions[0].message_count, 2);
+        assert_eq!(sessions[0].updated_at.timestamp_millis(), 1760000002000);
+        assert_eq!(sessions[1].id, "s2");
+    }
 }
@@ -1,3 +1,5 @@
 mod session;
+mod transcript;
 
 pub use session::{build_resume_command, find_session_by_directory};
+pub use transcript::{find_rollout, list_sessions, parse_rollout_line};
@@ -0,0 +1,270 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::agent::sessions::SessionSummary;
+use crate::agent::transcript::{newest_file, TranscriptEvent, TranscriptEventKind};
+
+const CODEX_HOME_ENV: &str = "CODEX_HOME";
+
+/// Day directories searched for a worktree's rollout, newest first.
+const MAX_DAYS_SEARCHED: usize = 31;
+
+fn get_sessions_dir() -> PathBuf {
+    let home = match std::env::var(CODEX_HOME_ENV) {
+        Ok(custom_home) => PathBuf::from(custom_home),
+        Err(_) => dirs::home_dir()
+            .map(|h| h.join(".codex"))
+            .unwrap_or_else(|| PathBuf::from("~/.codex")),
+    };
+    home.join("sessions")
+}
+
+/// Sorted subdirectories of `dir`, newest (highest name) first.
+fn subdirs_desc(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort_by(|a, b| b.cmp(a));
+    dirs
+}
+
+/// Day directories (`sessions/YYYY/MM/DD`), newest first.
+fn day_dirs() -> impl Iterator<Item = PathBuf> {
+    subdirs_desc(&get_sessions_dir())
+        .into_iter()
+        .flat_map(|year| subdirs_desc(&year))
+        .flat_map(|month| subdirs_desc(&month))
+}
+
+/// Rollout file (`sessions/YYYY/MM/DD/rollout-*.jsonl`) of the newest Codex
+/// session started in `worktree_path` and written to at or after `since`.
+pub fn find_rollout(worktree_path: &str, since: SystemTime) -> Option<PathBuf> {
+    let days: Vec<PathBuf> = day_dirs().take(MAX_DAYS_SEARCHED).collect();
+    newest_file(days, "rollout-", ".jsonl", since, |path| {
+        let file = std::fs::File::open(path).ok();
+        let mut first = String::new();
+        file.and_then(|f| BufReader::new(f).read_line(&mut first).ok())
+            .and_then(|_| session_meta(&first))
+            .is_some_and(|(_, cwd)| cwd.trim_end_matches('/') == worktree_path)
+    })
+}
+
+/// Every Codex session started in `worktree_path`, from the rollout files.
+pub fn list_sessions(worktree_path: &str) -> Result<Vec<SessionSummary>> {
+    let mut sessions = Vec::new();
+    for day in day_dirs() {
+        let Ok(entries) = std::fs::read_dir(&day) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if !(name.starts_with("rollout-") && name.ends_with(".jsonl")) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Some(mut session) = rollout_summary(&content, worktree_path) else {
+                continue;
+            };
+            if let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) {
+                session.updated_at = modified.into();
+            }
+            sessions.push(session);
+        }
+    }
+    Ok(sessions)
+}
+
+/// Summary of a rollout when it belongs to `worktree_path`. `updated_at` is
+/// the last event's time.
+fn rollout_summary(content: &str, worktree_path: &str) -> Option<SessionSummary> {
+    let mut lines = content.lines();
+    let (id, cwd) = session_meta(lines.next()?)?;
+    if cwd.trim_end_matches('/') != worktree_path {
+        return None;
+    }
+    let events: Vec<TranscriptEvent> = lines.flat_map(parse_rollout_line).collect();
+    let is_message = |e: &&TranscriptEvent| {
+        matches!(
+            e.kind,
+            TranscriptEventKind::Prompt | TranscriptEventKind::Message
+        )
+    };
+    Some(SessionSummary {
+        id,
+        updated_at: events.iter().rev().find_map(|e| e.at).unwrap_or_default(),
+        first_prompt: events
+            .iter()
+            .find(|e| e.kind == TranscriptEventKind::Prompt)
+            .map(|e| e.text.clone()),
+        message_count: events.iter().filter(is_message).count(),
+    })
+}
+
+/// Session ID and working directory from a rollout's `session_meta` line.
+fn session_meta(line: &str) -> Option<(String, String)> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value.get("type")?.as_str()? != "session_meta" {
+        return None;
+    }
+    let payload = &value["payload"];
+    Some((
+        payload["id"].as_str()?.to_string(),
+        payload["cwd"].as_str()?.to_string(),
+    ))
+}
+
+/// Events in one rollout line. Messages come from `event_msg` entries, which
+/// leave out the context Codex injects; tool calls from `response_item`s.
+pub fn parse_rollout_line(line: &str) -> Vec<TranscriptEvent> {
+    let Ok(value) = serde_json::from_str::<Value>(line) else {
+        return Vec::new();
+    };
+    let at = value
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| ts.with_timezone(&Utc));
+    let payload = &value["payload"];
+    let text = |key: &str| payload.get(key).and_then(Value::as_str).unwrap_or("");
+    let event = |kind, text: &str| vec![TranscriptEvent::new(kind, text, at)];
+
+    match (
+        value.get("type").and_then(Value::as_str),
+        payload.get("type").and_then(Value::as_str),
+    ) {
+        (Some("event_msg"), Some("user_message")) => {
+            event(TranscriptEventKind::Prompt, text("message"))
+        }
+        (Some("event_msg"), Some("agent_message")) => {
+            event(TranscriptEventKind::Message, text("message"))
+        }
+        (Some("event_msg"), Some("exec_approval_request")) => {
+            let command = command_text(&payload["command"]).unwrap_or_default();
+            event(
+                TranscriptEventKind::ApprovalRequest,
+                &format!("Approve command: {}", command),
+            )
+        }
+        (Some("event_msg"), Some("apply_patch_approval_request")) => {
+            event(TranscriptEventKind::ApprovalRequest, "Approve file changes")
+        }
+        (Some("event_msg"), Some("task_complete")) => {
+            event(TranscriptEventKind::TurnComplete, "Turn finished")
+        }
+        (Some("event_msg"), Some("turn_aborted")) => {
+            event(TranscriptEventKind::TurnComplete, "Turn interrupted")
+        }
+        (Some("event_msg"), Some("error")) => event(TranscriptEventKind::Error, text("message")),
+        (Some("response_item"), Some("function_call")) => {
+            let args = serde_json::from_str::<Value>(text("arguments")).unwrap_or(Value::Null);
+            let call = command_text(&args["command"])
+                .unwrap_or_else(|| format!("{}({})", text("name"), text("arguments")));
+            event(TranscriptEventKind::ToolCall, &call)
+        }
+        (Some("response_item"), Some("custom_tool_call" | "local_shell_call")) => {
+            let call = command_text(&payload["action"]["command"])
+                .unwrap_or_else(|| text("name").to_string());
+            event(TranscriptEventKind::ToolCall, &call)
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Shell command from a `["bash", "-lc", "cargo test"]`-style array or a
+/// plain string.
+fn command_text(command: &Value) -> Option<String> {
+    if let Some(command) = command.as_str() {
+        return Some(command.to_string());
+    }
+    let parts: Vec<&str> = command
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .collect();
+    match parts.as_slice() {
+        [] => None,
+        [shell, "-lc" | "-c", script] if shell.ends_with("sh") => Some(script.to_string()),
+        _ => Some(parts.join(" ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rollout_lines() {
+        let rollout = r#"{"timestamp":"2026-10-18T09:00:00.000Z","type":"session_meta","payload":{"id":"abc","cwd":"/tmp/wt"}}
+{"timestamp":"2026-10-18T09:00:01.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>"}]}}
+{"timestamp":"2026-10-18T09:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"Fix the failing test"}}
+{"timestamp":"2026-10-18T09:00:02.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"bash\",\"-lc\",\"cargo test\"]}","call_id":"c1"}}
+{"timestamp":"2026-10-18T09:00:03.000Z","type":"event_msg","payload":{"type":"exec_approval_request","call_id":"c2","command":["rm","-rf","target"]}}
+{"timestamp":"2026-10-18T09:00:04.000Z","type":"event_msg","payload":{"type":"agent_message","message":"Fixed the off-by-one."}}
+{"timestamp":"2026-10-18T09:00:04.500Z","type":"event_msg","payload":{"type":"token_count","info":null}}
+{"timestamp":"2026-10-18T09:00:05.000Z","type":"event_msg","payload":{"type":"task_complete","last_agent_message":"Fixed the off-by-one."}}"#;
+        let events: Vec<TranscriptEvent> = rollout.lines().flat_map(parse_rollout_line).collect();
+        let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TranscriptEventKind::Prompt,
+                TranscriptEventKind::ToolCall,
+                TranscriptEventKind::ApprovalRequest,
+                TranscriptEventKind::Message,
+                TranscriptEventKind::TurnComplete,
+            ]
+        );
+        assert_eq!(events[0].text, "Fix the failing test");
+        assert_eq!(events[1].text, "cargo test");
+        assert_eq!(events[2].text, "Approve command: rm -rf target");
+        assert_eq!(
+            events[0].at.unwrap().to_rfc3339(),
+            "2026-10-18T09:00:01+00:00"
+        );
+        assert!(parse_rollout_line("{\"type\":\"event_msg\"").is_empty());
+    }
+
+    #[test]
+    fn test_rollout_summary() {
+        let rollout = r#"{"timestamp":"2026-10-18T09:00:00.000Z","type":"session_meta","payload":{"id":"abc","cwd":"/tmp/wt/"}}
+{"timestamp":"2026-10-18T09:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"Fix the failing test"}}
+{"timestamp":"2026-10-18T09:00:04.000Z","type":"event_msg","payload":{"type":"agent_message","message":"Fixed."}}
+{"timestamp":"2026-10-18T09:00:05.000Z","type":"event_msg","payload":{"type":"task_complete"}}"#;
+        let session = rollout_summary(rollout, "/tmp/wt").unwrap();
+        assert_eq!(session.id, "abc");
+        assert_eq!(
+            session.first_prompt.as_deref(),
+            Some("Fix the failing test")
+        );
+        assert_eq!(session.message_count, 2);
+        assert_eq!(session.updated_at.to_rfc3339(), "2026-10-18T09:00:05+00:00");
+        assert!(rollout_summary(rollout, "/tmp/other").is_none());
+    }
+
+    #[test]
+    fn test_command_text() {
+        use serde_json::json;
+        assert_eq!(
+            command_text(&json!(["bash", "-lc", "ls -la"])).as_deref(),
+            Some("ls -la")
+        );
+        assert_eq!(
+            command_text(&json!(["git", "status"])).as_deref(),
+            Some("git status")
+        );
+        assert_eq!(command_text(&json!("make")).as_deref(), Some("make"));
+        assert_eq!(command_text(&Value::Null), None);
+    }
+}
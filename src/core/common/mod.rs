@@ -1,3 +1,8 @@
+mod process_tree;
 mod string_utils;
 
-pub use string_utils::{sanitize_branch_name, sanitize_linear_branch_name};
+pub use process_tree::{children_by_parent, descendants};
+
+pub use string_utils::{
+    sanitize_branch_name, sanitize_linear_branch_name, shell_quote, strip_ansi,
+};
@@ -0,0 +1,46 @@
+use std::collections::{HashMap, HashSet};
+
+/// Child PIDs keyed by parent PID, from `(pid, parent)` pairs.
+pub fn children_by_parent(pairs: impl IntoIterator<Item = (u32, u32)>) -> HashMap<u32, Vec<u32>> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (pid, parent) in pairs {
+        children.entry(parent).or_default().push(pid);
+    }
+    children
+}
+
+/// `roots` and all their descendants.
+pub fn descendants(children: &HashMap<u32, Vec<u32>>, roots: &[u32]) -> Vec<u32> {
+    let mut seen: HashSet<u32> = roots.iter().copied().collect();
+    let mut tree: Vec<u32> = roots.to_vec();
+    let mut pending: Vec<u32> = roots.to_vec();
+    while let Some(pid) = pending.pop() {
+        for &child in children.get(&pid).into_iter().flatten() {
+            if seen.insert(child) {
+                tree.push(child);
+                pending.push(child);
+            }
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descendants_cover_every_root() {
+        let children = children_by_parent([
+            (11, 10), // agent pane -> claude
+            (12, 11), // claude -> cargo
+            (13, 11), // claude -> node
+            (14, 12), // cargo -> rustc
+            (21, 20), // dev server pane -> npm
+            (31, 30), // unrelated
+        ]);
+        let mut tree = descendants(&children, &[10, 20]);
+        tree.sort_unstable();
+        assert_eq!(tree, vec![10, 11, 12, 13, 14, 20, 21]);
+    }
+}
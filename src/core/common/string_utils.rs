@@ -1,3 +1,10 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// ANSI escape sequences (colors, cursor moves, OSC titles) in terminal output
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*[a-zA-Z]|\x1b\].*?\x07").unwrap());
+
 const MAX_BRANCH_LENGTH: usize = 50;
 
 fn truncate_to_words(s: &str, max_len: usize) -> String {
@@ -32,6 +39,24 @@ pub fn sanitize_branch_name(name: &str) -> String {
     truncate_to_words(&sanitized, MAX_BRANCH_LENGTH)
 }
 
+/// Strip ANSI escape codes from text
+pub fn strip_ansi(text: &str) -> String {
+    ANSI_ESCAPE.replace_all(text, "").to_string()
+}
+
+/// Single-quote `arg` for the shell unless it's plainly safe.
+pub fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+".contains(c))
+    {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
 pub fn sanitize_linear_branch_name(username: &str, identifier: &str, title: &str) -> String {
     let id_lower = identifier.to_lowercase();
     let prefix_len = username.len() + 1 + id_lower.len() + 1;
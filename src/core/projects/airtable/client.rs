@@ -154,6 +154,8 @@ impl AirtableClient {
 
         let url = format!("https://airtable.com/{}/{}", self.base_id, self.table_name);
 
+        let tags = record.fields.labels();
+
         AirtableTaskSummary {
             id: record.id,
             name: record.fields.name.unwrap_or_else(|| "Untitled".to_string()),
@@ -161,6 +163,7 @@ impl AirtableClient {
             url,
             parent_id,
             has_children: false,
+            tags,
         }
     }
 
@@ -653,6 +656,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_record_labels_from_tags_and_labels_fields() {
+        let record: AirtableRecord = serde_json::from_value(serde_json::json!({
+            "id": "rec1",
+            "fields": {
+                "Name": "Fix login",
+                "Tags": ["agent-ready"],
+                "Labels": ["bug"]
+            }
+        }))
+        .unwrap();
+        assert_eq!(record.fields.labels(), vec!["agent-ready", "bug"]);
+    }
+
     #[test]
     fn test_parse_airtable_record_id_trailing_slash() {
         let url = "https://airtable.com/appXXX/tblYYY/recABC/";
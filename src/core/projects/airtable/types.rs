@@ -117,6 +117,7 @@ pub struct AirtableTaskSummary {
     pub url: String,
     pub parent_id: Option<String>,
     pub has_children: bool,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -140,6 +141,23 @@ pub struct AirtableFields {
     pub status: Option<String>,
     #[serde(default)]
     pub parent: Option<Vec<ParentRecord>>,
+    #[serde(rename = "Tags", default)]
+    pub tags: Option<serde_json::Value>,
+    #[serde(rename = "Labels", default)]
+    pub labels: Option<serde_json::Value>,
+}
+
+impl AirtableFields {
+    /// Options of the "Tags" and "Labels" multiple select fields.
+    pub fn labels(&self) -> Vec<String> {
+        [&self.tags, &self.labels]
+            .into_iter()
+            .flatten()
+            .filter_map(|value| value.as_array())
+            .flatten()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,7 +39,7 @@ impl AsanaClient {
             .get(&url)
             .query(&[(
                 "opt_fields",
-                "gid,name,completed,permalink_url,parent,num_subtasks,memberships.project.gid,memberships.section.gid,memberships.section.name",
+                "gid,name,completed,permalink_url,parent,num_subtasks,memberships.project.gid,memberships.section.gid,memberships.section.name,tags.name",
             )])
             .send()
             .await
@@ -160,7 +160,7 @@ impl AsanaClient {
             .get(&url)
             .query(&[(
                 "opt_fields",
-                "gid,name,completed,permalink_url,parent,num_subtasks,memberships.project.gid,memberships.section.gid,memberships.section.name",
+                "gid,name,completed,permalink_url,parent,num_subtasks,memberships.project.gid,memberships.section.gid,memberships.section.name,tags.name",
             )])
             .send()
             .await
@@ -231,7 +231,7 @@ impl AsanaClient {
             .get(&url)
             .query(&[(
                 "opt_fields",
-                "gid,name,completed,permalink_url,parent,num_subtasks,memberships.project.gid,memberships.section.gid,memberships.section.name",
+                "gid,name,completed,permalink_url,parent,num_subtasks,memberships.project.gid,memberships.section.gid,memberships.section.name,tags.name",
             )])
             .send()
             .await
@@ -130,6 +130,13 @@ pub struct AsanaTaskData {
     pub parent: Option<AsanaParent>,
     pub num_subtasks: Option<u32>,
     pub memberships: Option<Vec<AsanaTaskMembership>>,
+    #[serde(default)]
+    pub tags: Vec<AsanaTag>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AsanaTag {
+    pub name: Option<String>,
 }
 
 impl AsanaTaskData {
@@ -238,6 +245,7 @@ pub struct AsanaTaskSummary {
     pub parent_gid: Option<String>,
     pub num_subtasks: u32,
     pub section_name: Option<String>,
+    pub tags: Vec<String>,
 }
 
 impl AsanaTaskSummary {
@@ -281,6 +289,7 @@ impl AsanaTaskSummary {
             parent_gid: data.parent.map(|p| p.gid),
             num_subtasks: data.num_subtasks.unwrap_or(0),
             section_name,
+            tags: data.tags.into_iter().filter_map(|t| t.name).collect(),
         }
     }
 }
@@ -126,6 +126,13 @@ pub struct ClickUpTaskData {
     pub parent: Option<String>,
     #[serde(default)]
     pub subtasks: Vec<ClickUpTaskData>,
+    #[serde(default)]
+    pub tags: Vec<ClickUpTagData>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ClickUpTagData {
+    pub name: String,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -145,6 +152,7 @@ pub struct ClickUpTaskSummary {
     pub url: Option<String>,
     pub parent_id: Option<String>,
     pub has_children: bool,
+    pub tags: Vec<String>,
 }
 
 impl From<ClickUpTaskData> for ClickUpTaskSummary {
@@ -157,6 +165,7 @@ impl From<ClickUpTaskData> for ClickUpTaskSummary {
             url: data.url,
             parent_id: data.parent,
             has_children: !data.subtasks.is_empty(),
+            tags: data.tags.into_iter().map(|t| t.name).collect(),
         }
     }
 }
@@ -292,6 +292,7 @@ impl LinearClient {
             .unwrap_or(false);
 
         Ok(LinearIssueSummary {
+            labels: issue.label_names(),
             id: issue.id,
             identifier: issue.identifier,
             title: issue.title,
@@ -327,6 +328,7 @@ impl LinearClient {
                                 color
                             }
                             parent { id }
+                            labels { nodes { name } }
                             children(first: 50) {
                                 nodes {
                                     id
@@ -410,6 +412,7 @@ impl LinearClient {
                     .unwrap_or(false);
 
                 LinearIssueSummary {
+                    labels: issue.label_names(),
                     id: issue.id,
                     identifier: issue.identifier,
                     title: issue.title,
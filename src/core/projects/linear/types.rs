@@ -143,6 +143,7 @@ pub struct LinearIssueSummary {
     pub parent_id: Option<String>,
     pub has_children: bool,
     pub team_id: String,
+    pub labels: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -180,6 +181,27 @@ pub struct LinearIssueData {
     pub parent: Option<LinearIssueParent>,
     pub children: Option<ChildrenConnection>,
     pub team: LinearTeamData,
+    #[serde(default)]
+    pub labels: Option<LabelsConnection>,
+}
+
+impl LinearIssueData {
+    pub fn label_names(&self) -> Vec<String> {
+        self.labels
+            .as_ref()
+            .map(|l| l.nodes.iter().map(|n| n.name.clone()).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LabelsConnection {
+    pub nodes: Vec<LinearLabelData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LinearLabelData {
+    pub name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -102,6 +102,7 @@ pub struct NotionPageData {
     pub status_name: Option<String>,
     pub parent_page_id: Option<String>,
     pub related_task_ids: Vec<String>,
+    pub tags: Vec<String>,
 }
 
 impl NotionPageData {
@@ -183,6 +184,24 @@ impl NotionProperties {
 
         None
     }
+
+    /// Options of the "Tags" or "Labels" multi-select property, if any.
+    pub fn get_tags(&self) -> Vec<String> {
+        self.other
+            .iter()
+            .find(|(key, value)| {
+                matches!(key.to_lowercase().as_str(), "tags" | "labels")
+                    && value.get("type").and_then(|t| t.as_str()) == Some("multi_select")
+            })
+            .and_then(|(_, value)| value.get("multi_select")?.as_array())
+            .map(|options| {
+                options
+                    .iter()
+                    .filter_map(|o| o.get("name")?.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -334,6 +353,7 @@ impl From<NotionPageResponse> for NotionPageData {
             .as_ref()
             .map(|r| r.relation.iter().map(|i| i.id.clone()).collect())
             .unwrap_or_default();
+        let tags = page.properties.get_tags();
 
         NotionPageData {
             id: page.id,
@@ -343,6 +363,32 @@ impl From<NotionPageResponse> for NotionPageData {
             status_name,
             parent_page_id,
             related_task_ids,
+            tags,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_tags_from_multi_select() {
+        let page: NotionPageResponse = serde_json::from_value(serde_json::json!({
+            "id": "page-1",
+            "url": "https://notion.so/page-1",
+            "properties": {
+                "Name": { "title": [{ "plain_text": "Fix login" }] },
+                "Labels": {
+                    "type": "multi_select",
+                    "multi_select": [{ "name": "agent-ready" }, { "name": "bug" }]
+                },
+                "Area": { "type": "multi_select", "multi_select": [{ "name": "web" }] }
+            }
+        }))
+        .unwrap();
+
+        let data = NotionPageData::from(page);
+        assert_eq!(data.tags, vec!["agent-ready", "bug"]);
+    }
+}
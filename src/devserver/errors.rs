@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use crate::core::common::strip_ansi;
+
+/// Longest error block handed to an agent.
+const MAX_BLOCK_LINES: usize = 40;
+
+/// Lines that open an error block: compiler errors, uncaught exceptions,
+/// panics and bundler failures.
+static ERROR_START: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        // rustc, go, tsc, webpack: `error: ...`, `ERROR in ./src`, `[vite] error`
+        Regex::new(r"(?i)^\s*(\[[\w:-]+\]\s*)*(error|fatal)\b").unwrap(),
+        // JS/Python/Java exceptions: `TypeError: x`, `Uncaught ReferenceError`
+        Regex::new(r"^\s*(Uncaught\s+)?[A-Z]\w*(Error|Exception)\b").unwrap(),
+        Regex::new(r"panicked at").unwrap(),
+        Regex::new(r"^Traceback \(most recent call last\)").unwrap(),
+        Regex::new(r"(?i)failed to compile").unwrap(),
+        Regex::new(r"\berror TS\d+").unwrap(),
+        Regex::new(r"✘ \[ERROR\]").unwrap(),
+        Regex::new(r"Internal server error").unwrap(),
+        Regex::new(r"Module not found").unwrap(),
+    ]
+});
+
+/// Lines that continue a block: stack frames, code frames and notes.
+static CONTINUATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\s+\S|\s*(-->|\||>\s*\d+\s*\||\d+\s*\||[\^~]+|= |note:|help:))").unwrap()
+});
+
+/// Finds error blocks in dev server logs.
+#[derive(Debug)]
+pub struct ErrorDetector {
+    patterns: Vec<String>,
+    extra: Vec<Regex>,
+}
+
+impl ErrorDetector {
+    /// Detector with the built-in patterns plus `patterns`, which also open
+    /// a block when they match.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let extra = patterns
+            .iter()
+            .map(|p| Regex::new(p).with_context(|| format!("Invalid error pattern '{}'", p)))
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            patterns: patterns.to_vec(),
+            extra,
+        })
+    }
+
+    /// The extra patterns the detector was built with.
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    pub fn is_error_start(&self, line: &str) -> bool {
+        let line = strip_ansi(line);
+        ERROR_START
+            .iter()
+            .chain(self.extra.iter())
+            .any(|re| re.is_match(&line))
+    }
+
+    /// Ranges of error blocks in `logs`. A block runs from an error line
+    /// through the following error and continuation lines, up to a blank or
+    /// unrelated line.
+    pub fn error_blocks(&self, logs: &[String]) -> Vec<Range<usize>> {
+        let mut blocks = Vec::new();
+        let mut current: Option<Range<usize>> = None;
+        for (i, line) in logs.iter().enumerate() {
+            let clean = strip_ansi(line);
+            let in_block = current.is_some()
+                && !clean.trim().is_empty()
+                && (CONTINUATION.is_match(&clean) || self.is_error_start(&clean));
+            if in_block {
+                if let Some(block) = current.as_mut() {
+                    block.end = i + 1;
+                }
+                continue;
+            }
+            blocks.extend(current.take());
+            if self.is_error_start(&clean) {
+                current = Some(i..i + 1);
+            }
+        }
+        blocks.extend(current);
+        blocks
+    }
+
+    pub fn latest_error(&self, logs: &[String]) -> Option<Range<usize>> {
+        self.error_blocks(logs).pop()
+    }
+}
+
+/// Plain text of log lines for a prompt, capped at `MAX_BLOCK_LINES`.
+pub fn format_lines(lines: &[String]) -> String {
+    let mut text: Vec<String> = lines
+        .iter()
+        .take(MAX_BLOCK_LINES)
+        .map(|line| strip_ansi(line).trim_end().to_string())
+        .collect();
+    if lines.len() > MAX_BLOCK_LINES {
+        text.push(format!(
+            "... ({} more lines)",
+            lines.len() - MAX_BLOCK_LINES
+        ));
+    }
+    text.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(text: &str) -> Vec<String> {
+        text.lines().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_js_stack_trace_block() {
+        let logs = logs(
+            "  VITE v5.0.0  ready in 300 ms\n\
+             \x1b[31mTypeError: Cannot read properties of undefined (reading 'id')\x1b[0m\n\
+             \x20   at UserCard (src/UserCard.tsx:12:20)\n\
+             \x20   at renderWithHooks (react-dom.js:100:5)\n\
+             \n\
+             page reload src/App.tsx",
+        );
+        let detector = ErrorDetector::new(&[]).unwrap();
+        assert_eq!(detector.error_blocks(&logs), vec![1..4]);
+        assert!(format_lines(&logs[1..4]).starts_with("TypeError: Cannot read"));
+    }
+
+    #[test]
+    fn test_rust_and_python_blocks() {
+        let logs = logs(
+            "   Compiling app v0.1.0\n\
+             error[E0308]: mismatched types\n\
+             \x20 --> src/main.rs:4:18\n\
+             \x20  |\n\
+             4 |     let x: u32 = \"a\";\n\
+             \x20  |                  ^^^ expected `u32`\n\
+             Finished dev\n\
+             Traceback (most recent call last):\n\
+             \x20 File \"app.py\", line 3, in <module>\n\
+             ValueError: bad value",
+        );
+        let detector = ErrorDetector::new(&[]).unwrap();
+        assert_eq!(detector.error_blocks(&logs), vec![1..6, 7..10]);
+        assert_eq!(detector.latest_error(&logs), Some(7..10));
+    }
+
+    #[test]
+    fn test_custom_patterns() {
+        let logs = logs("ok\nBUILD BROKEN in module x\nok");
+        assert!(ErrorDetector::new(&[])
+            .unwrap()
+            .error_blocks(&logs)
+            .is_empty());
+        let detector = ErrorDetector::new(&["BUILD BROKEN".to_string()]).unwrap();
+        assert_eq!(detector.error_blocks(&logs), vec![1..2]);
+        assert!(ErrorDetector::new(&["(".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::time::Duration;
+
+use super::service::ServiceSpec;
+use crate::app::config::{ProbeConfig, RestartPolicy};
+use crate::core::common::strip_ansi;
+
+/// Longest wait between automatic restarts.
+const MAX_BACKOFF_SECS: u64 = 60;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeKind {
+    Readiness,
+    Liveness,
+}
+
+/// Run a probe once. `logs` are the lines logged since the service started
+/// (readiness) or since the previous check (liveness). The error explains
+/// why the probe failed.
+pub async fn run_probe(
+    probe: &ProbeConfig,
+    kind: ProbeKind,
+    spec: &ServiceSpec,
+    logs: &[String],
+) -> Result<()> {
+    match probe {
+        ProbeConfig::Http(url) => {
+            let client = reqwest::Client::builder().timeout(PROBE_TIMEOUT).build()?;
+            let response = client
+                .get(url)
+                .send()
+                .await
+                .with_context(|| format!("GET {} failed", url))?;
+            let status = response.status();
+            if !(status.is_success() || status.is_redirection()) {
+                anyhow::bail!("GET {} returned {}", url, status);
+            }
+            Ok(())
+        }
+        ProbeConfig::Tcp(addr) => {
+            let addr = match addr.parse::<u16>() {
+                Ok(port) => format!("127.0.0.1:{}", port),
+                Err(_) => addr.clone(),
+            };
+            tokio::time::timeout(PROBE_TIMEOUT, tokio::net::TcpStream::connect(&addr))
+                .await
+                .with_context(|| format!("Connecting to {} timed out", addr))?
+                .with_context(|| format!("Cannot connect to {}", addr))?;
+            Ok(())
+        }
+        ProbeConfig::Log(pattern) => {
+            let re = Regex::new(pattern).with_context(|| format!("Invalid regex '{}'", pattern))?;
+            let matched = logs.iter().find(|line| re.is_match(&strip_ansi(line)));
+            match (kind, matched) {
+                (ProbeKind::Readiness, Some(_)) | (ProbeKind::Liveness, None) => Ok(()),
+                (ProbeKind::Readiness, None) => anyhow::bail!("No log line matches '{}'", pattern),
+                (ProbeKind::Liveness, Some(line)) => {
+                    anyhow::bail!("Log matched '{}': {}", pattern, strip_ansi(line).trim())
+                }
+            }
+        }
+        ProbeConfig::Command(cmd) => {
+            let cmd = cmd.clone();
+            let dir = spec.working_dir.clone();
+            let env = spec.env.clone();
+            let output = tokio::task::spawn_blocking(move || {
+                std::process::Command::new("/bin/sh")
+                    .args(["-c", &cmd])
+                    .current_dir(dir)
+                    .envs(env)
+                    .output()
+            })
+            .await?
+            .context("Failed to run probe command")?;
+            if !output.status.success() {
+                anyhow::bail!("Probe command exited with {}", output.status);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Whether a service that exited with `code` should be started again.
+pub fn should_restart(policy: RestartPolicy, code: i32) -> bool {
+    match policy {
+        RestartPolicy::Never => false,
+        RestartPolicy::OnFailure => code != 0,
+        RestartPolicy::Always => true,
+    }
+}
+
+/// Delay before restart number `attempt` (starting at 0): 1s, 2s, 4s, ...
+/// capped at a minute.
+pub fn restart_backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt).min(MAX_BACKOFF_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::HealthConfig;
+    use std::path::PathBuf;
+
+    fn spec() -> ServiceSpec {
+        ServiceSpec {
+            name: "web".to_string(),
+            command: "true".to_string(),
+            working_dir: PathBuf::from("/"),
+            run_before: Vec::new(),
+            port: None,
+            env: Vec::new(),
+            depends_on: Vec::new(),
+            health: HealthConfig::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_probe_semantics() {
+        let probe = ProbeConfig::Log(r"ready in \d+ms".to_string());
+        let logs = vec!["\x1b[32mVITE\x1b[0m ready in 312ms".to_string()];
+        assert!(run_probe(&probe, ProbeKind::Readiness, &spec(), &logs)
+            .await
+            .is_ok());
+        assert!(run_probe(&probe, ProbeKind::Readiness, &spec(), &[])
+            .await
+            .is_err());
+        assert!(run_probe(&probe, ProbeKind::Liveness, &spec(), &logs)
+            .await
+            .is_err());
+        assert!(run_probe(&probe, ProbeKind::Liveness, &spec(), &[])
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tcp_probe() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let probe = ProbeConfig::Tcp(port.to_string());
+        assert!(run_probe(&probe, ProbeKind::Liveness, &spec(), &[])
+            .await
+            .is_ok());
+        drop(listener);
+        assert!(run_probe(&probe, ProbeKind::Liveness, &spec(), &[])
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_restart_policy_and_backoff() {
+        assert!(!should_restart(RestartPolicy::Never, 1));
+        assert!(should_restart(RestartPolicy::OnFailure, 1));
+        assert!(!should_restart(RestartPolicy::OnFailure, 0));
+        assert!(should_restart(RestartPolicy::Always, 0));
+        assert_eq!(restart_backoff(0), Duration::from_secs(1));
+        assert_eq!(restart_backoff(3), Duration::from_secs(8));
+        assert_eq!(restart_backoff(10), Duration::from_secs(MAX_BACKOFF_SECS));
+    }
+}
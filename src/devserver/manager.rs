@@ -1,16 +1,26 @@
 use anyhow::Result;
+use futures::future::BoxFuture;
 use std::collections::HashMap;
-use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::mpsc::UnboundedSender;
+use tokio::time::sleep;
 use uuid::Uuid;
 
-use super::process::DevServer;
-use crate::app::{Action, DevServerConfig};
+use super::health::{restart_backoff, run_probe, should_restart, ProbeKind};
+use super::process::{launch, DevServer, DevServerStatus, CRASH_PREFIX};
+use super::proxy::{host_label, proxy_url, ProxyRoutes, SharedProxyRoutes};
+use super::service::ServiceSpec;
+use crate::app::Action;
 
+/// Runs every agent's dev services. Each agent has one `DevServer` per
+/// service, kept in start order.
 pub struct DevServerManager {
-    servers: HashMap<Uuid, DevServer>,
+    servers: HashMap<Uuid, Vec<DevServer>>,
     action_tx: UnboundedSender<Action>,
+    routes: SharedProxyRoutes,
+    active: Option<Uuid>,
+    proxy_port: Option<u16>,
 }
 
 impl DevServerManager {
@@ -18,89 +28,226 @@ impl DevServerManager {
         Self {
             servers: HashMap::new(),
             action_tx,
+            routes: SharedProxyRoutes::default(),
+            active: None,
+            proxy_port: None,
+        }
+    }
+
+    /// Routing table for the reverse proxy, updated whenever servers change.
+    pub fn proxy_routes(&self) -> SharedProxyRoutes {
+        Arc::clone(&self.routes)
+    }
+
+    pub fn set_proxy_port(&mut self, port: Option<u16>) {
+        self.proxy_port = port;
+    }
+
+    /// Point the `active` proxy host at this agent's server.
+    pub fn set_active(&mut self, agent_id: Option<Uuid>) {
+        self.active = agent_id;
+        self.sync_routes();
+    }
+
+    /// URL to open for a service (the agent's first service when `None`):
+    /// its proxy hostname when the proxy is running, otherwise the port on
+    /// localhost.
+    pub fn url_for(&self, agent_id: Uuid, service: Option<&str>) -> Option<String> {
+        let services = self.servers.get(&agent_id)?;
+        let primary = services.iter().find(|s| s.status().port().is_some())?;
+        let server = match service {
+            Some(name) => services.iter().find(|s| s.service() == name)?,
+            None => primary,
+        };
+        let port = server.status().port()?;
+        let label = host_label(server.agent_name());
+        Some(match self.proxy_port {
+            Some(proxy_port) if server.service() == primary.service() => {
+                proxy_url(&label, proxy_port)
+            }
+            Some(proxy_port) => proxy_url(
+                &format!("{}.{}", host_label(server.service()), label),
+                proxy_port,
+            ),
+            None => format!("http://localhost:{}", port),
+        })
+    }
+
+    /// Record the detected listening port. Returns `true` if it changed.
+    pub fn set_port(&mut self, agent_id: Uuid, service: &str, port: u16) -> bool {
+        let changed = self
+            .service_mut(agent_id, service)
+            .map(|server| server.set_port(port))
+            .unwrap_or(false);
+        self.sync_routes();
+        changed
+    }
+
+    /// Record that a service's command exited. Returns whether its restart
+    /// policy asks for a restart.
+    pub fn service_exited(&mut self, agent_id: Uuid, service: &str, code: i32) -> bool {
+        let restart = self.service_mut(agent_id, service).is_some_and(|server| {
+            server.mark_exited(code)
+                && server
+                    .spec()
+                    .is_some_and(|spec| should_restart(spec.health.restart, code))
+        });
+        self.sync_routes();
+        restart
+    }
+
+    fn sync_routes(&self) {
+        let mut routes = ProxyRoutes::default();
+        for (id, services) in &self.servers {
+            let mut running = services
+                .iter()
+                .filter_map(|s| Some((s, s.status().port()?)));
+            let Some((primary, primary_port)) = running.next() else {
+                continue;
+            };
+            let label = host_label(primary.agent_name());
+            for (server, port) in std::iter::once((primary, primary_port)).chain(running) {
+                routes
+                    .hosts
+                    .insert(format!("{}.{}", host_label(server.service()), label), port);
+            }
+            routes.hosts.insert(label, primary_port);
+            if self.active == Some(*id) {
+                routes.active = Some(primary_port);
+            }
+        }
+        if let Ok(mut shared) = self.routes.write() {
+            *shared = routes;
         }
     }
 
     pub fn has_running_server(&self) -> bool {
-        self.servers.values().any(|s| s.status().is_running())
+        self.servers.keys().any(|id| self.is_running(*id))
     }
 
     pub fn running_servers(&self) -> Vec<(Uuid, String, Option<u16>)> {
         self.servers
             .iter()
-            .filter_map(|(id, server)| {
-                if server.status().is_running() {
-                    Some((*id, server.agent_name().to_string(), server.status().port()))
-                } else {
-                    None
-                }
+            .filter_map(|(id, services)| {
+                let server = services.iter().find(|s| s.status().is_running())?;
+                Some((*id, server.agent_name().to_string(), server.status().port()))
             })
             .collect()
     }
 
-    pub async fn start(
+    /// Start one service, replacing the configured slot or appending it.
+    /// Get the server for `spec` ready to start; see [`launch`].
+    pub fn prepare_service(
         &mut self,
         agent_id: Uuid,
         agent_name: String,
-        config: &DevServerConfig,
-        worktree: &Path,
+        spec: &ServiceSpec,
     ) -> Result<()> {
-        let server = self.servers.entry(agent_id).or_default();
-        server.set_agent_name(agent_name.clone());
-        server
-            .start(
-                config,
-                worktree,
-                agent_id,
-                agent_name,
-                self.action_tx.clone(),
-            )
-            .await
+        let services = self.servers.entry(agent_id).or_default();
+        let index = match services.iter().position(|s| s.service() == spec.name) {
+            Some(index) => index,
+            None => {
+                services.push(DevServer::for_service(&spec.name));
+                services.len() - 1
+            }
+        };
+        services[index].prepare(spec, agent_name)
+    }
+
+    /// Record a service started with [`launch`].
+    pub fn record_launch(
+        &mut self,
+        agent_id: Uuid,
+        spec: &ServiceSpec,
+        log: Vec<String>,
+        launched: Result<(String, u32)>,
+    ) -> Result<()> {
+        let tx = self.action_tx.clone();
+        let Some(server) = self.service_mut(agent_id, &spec.name) else {
+            return launched.map(|_| ());
+        };
+        let result = server.record_launch(spec, agent_id, log, launched, tx);
+        self.sync_routes();
+        result
     }
 
     pub async fn stop(&mut self, agent_id: Uuid) -> Result<()> {
-        if let Some(server) = self.servers.get_mut(&agent_id) {
+        if let Some(services) = self.servers.get_mut(&agent_id) {
+            for server in services.iter_mut().rev() {
+                server.stop().await?;
+            }
+        }
+        self.sync_routes();
+        Ok(())
+    }
+
+    pub async fn stop_service(&mut self, agent_id: Uuid, service: &str) -> Result<()> {
+        if let Some(server) = self.service_mut(agent_id, service) {
             server.stop().await?;
         }
+        self.sync_routes();
         Ok(())
     }
 
     pub async fn stop_all(&mut self) -> Result<()> {
-        for server in self.servers.values_mut() {
-            let _ = server.stop().await;
+        for services in self.servers.values_mut() {
+            for server in services.iter_mut() {
+                let _ = server.stop().await;
+            }
         }
+        self.sync_routes();
         Ok(())
     }
 
-    pub fn get(&self, agent_id: Uuid) -> Option<&DevServer> {
-        self.servers.get(&agent_id)
+    /// All services of an agent, in start order.
+    pub fn services(&self, agent_id: Uuid) -> &[DevServer] {
+        self.servers
+            .get(&agent_id)
+            .map(|s| s.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Service at `index`, clamped to the last one.
+    pub fn service_at(&self, agent_id: Uuid, index: usize) -> Option<&DevServer> {
+        let services = self.services(agent_id);
+        services.get(index.min(services.len().saturating_sub(1)))
+    }
+
+    pub fn service(&self, agent_id: Uuid, service: &str) -> Option<&DevServer> {
+        self.services(agent_id)
+            .iter()
+            .find(|s| s.service() == service)
     }
 
-    pub fn get_mut(&mut self, agent_id: Uuid) -> Option<&mut DevServer> {
-        self.servers.get_mut(&agent_id)
+    pub fn service_mut(&mut self, agent_id: Uuid, service: &str) -> Option<&mut DevServer> {
+        self.servers
+            .get_mut(&agent_id)?
+            .iter_mut()
+            .find(|s| s.service() == service)
     }
 
     pub fn remove(&mut self, agent_id: Uuid) {
         self.servers.remove(&agent_id);
+        self.sync_routes();
     }
 
     pub fn is_running(&self, agent_id: Uuid) -> bool {
-        self.servers
-            .get(&agent_id)
-            .map(|s| s.status().is_running())
-            .unwrap_or(false)
+        self.services(agent_id)
+            .iter()
+            .any(|s| s.status().is_running())
     }
 
-    pub fn get_tmux_session(&self, agent_id: Uuid) -> Option<String> {
-        self.servers
-            .get(&agent_id)
+    pub fn get_tmux_session(&self, agent_id: Uuid, service: &str) -> Option<String> {
+        self.service(agent_id, service)
             .and_then(|s| s.tmux_session().map(String::from))
     }
 
-    pub fn all_statuses(&self) -> HashMap<Uuid, super::process::DevServerStatus> {
+    /// One status per agent for the agent list: the first running service
+    /// (or the most urgent non-running state).
+    pub fn all_statuses(&self) -> HashMap<Uuid, DevServerStatus> {
         self.servers
             .iter()
-            .map(|(id, server)| (*id, server.status().clone()))
+            .map(|(id, services)| (*id, combined_status(services)))
             .collect()
     }
 
@@ -109,4 +256,264 @@ impl DevServerManager {
     }
 }
 
+fn combined_status(services: &[DevServer]) -> DevServerStatus {
+    let statuses: Vec<&DevServerStatus> = services.iter().map(|s| s.status()).collect();
+    let find = |f: fn(&DevServerStatus) -> bool| statuses.iter().find(|s| f(s)).copied();
+    find(|s| matches!(s, DevServerStatus::Failed(_)))
+        .or_else(|| find(|s| matches!(s, DevServerStatus::Crashed { .. })))
+        .or_else(|| find(|s| matches!(s, DevServerStatus::Unhealthy { .. })))
+        .or_else(|| find(|s| matches!(s, DevServerStatus::Starting { .. })))
+        .or_else(|| find(DevServerStatus::is_running))
+        .or_else(|| find(|s| matches!(s, DevServerStatus::Stopping)))
+        .cloned()
+        .unwrap_or(DevServerStatus::Stopped)
+}
+
 pub type SharedDevServerManager = Arc<tokio::sync::Mutex<DevServerManager>>;
+
+/// Start services in order, waiting for each readiness probe before moving
+/// on so dependents only start once what they need is up. Services with a
+/// liveness probe are then monitored. The manager is only locked to update
+/// service state, not while setup commands run or services launch.
+pub async fn start_services(
+    manager: SharedDevServerManager,
+    agent_id: Uuid,
+    agent_name: String,
+    specs: Vec<ServiceSpec>,
+) -> Result<()> {
+    for spec in &specs {
+        manager
+            .lock()
+            .await
+            .prepare_service(agent_id, agent_name.clone(), spec)?;
+        let (log, launched) = {
+            let spec = spec.clone();
+            tokio::task::spawn_blocking(move || {
+                let mut log = Vec::new();
+                let launched = launch(&spec, agent_id, &mut log);
+                (log, launched)
+            })
+            .await?
+        };
+        let (pid, mark) = {
+            let mut m = manager.lock().await;
+            m.record_launch(agent_id, spec, log, launched)?;
+            match m.service(agent_id, &spec.name) {
+                Some(server) => (server.status().pid(), server.log_mark()),
+                None => continue,
+            }
+        };
+        let Some(pid) = pid else {
+            continue;
+        };
+
+        if let Some(probe) = &spec.health.readiness {
+            let result = wait_until_ready(&manager, agent_id, spec, probe, mark).await;
+            let mut m = manager.lock().await;
+            if let Some(server) = m.service_mut(agent_id, &spec.name) {
+                match &result {
+                    Ok(()) => {
+                        server.mark_ready();
+                        server.append_log(format!("Service '{}' is ready", spec.name));
+                    }
+                    Err(e) => {
+                        server.mark_unhealthy(format!("not ready: {}", e));
+                        server.append_log(format!(
+                            "{}Service '{}' did not become ready: {}",
+                            CRASH_PREFIX, spec.name, e
+                        ));
+                    }
+                }
+            }
+            let _ = m.action_tx.send(Action::DevServiceReady {
+                agent_id,
+                service: spec.name.clone(),
+                ready: result.is_ok(),
+            });
+            if let Err(e) = result {
+                anyhow::bail!("Service '{}' did not become ready: {}", spec.name, e);
+            }
+        }
+
+        if spec.health.liveness.is_some() {
+            tokio::spawn(monitor_liveness(
+                Arc::clone(&manager),
+                agent_id,
+                spec.clone(),
+                pid,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Poll the readiness probe once a second until it passes or times out.
+async fn wait_until_ready(
+    manager: &SharedDevServerManager,
+    agent_id: Uuid,
+    spec: &ServiceSpec,
+    probe: &crate::app::config::ProbeConfig,
+    mark: usize,
+) -> Result<()> {
+    let deadline = Instant::now() + spec.ready_timeout();
+    loop {
+        let (logs, running) = {
+            let m = manager.lock().await;
+            match m.service(agent_id, &spec.name) {
+                Some(server) => (server.logs_since(mark).0, server.status().is_running()),
+                None => (Vec::new(), false),
+            }
+        };
+        if !running {
+            anyhow::bail!("service is no longer running");
+        }
+
+        let result = run_probe(probe, ProbeKind::Readiness, spec, &logs).await;
+        if result.is_ok() || Instant::now() >= deadline {
+            return result;
+        }
+        sleep(std::time::Duration::from_secs(1)).await;
+    }
+}
+
+/// Run the liveness probe for as long as process `pid` serves the service.
+/// After `failure_threshold` consecutive failures the service is marked
+/// unhealthy and, unless the policy is `never`, restarted.
+async fn monitor_liveness(
+    manager: SharedDevServerManager,
+    agent_id: Uuid,
+    spec: ServiceSpec,
+    pid: u32,
+) {
+    let Some(probe) = spec.health.liveness.clone() else {
+        return;
+    };
+    let mut failures = 0;
+    let mut mark = match manager.lock().await.service(agent_id, &spec.name) {
+        Some(server) => server.log_mark(),
+        None => return,
+    };
+
+    loop {
+        sleep(spec.liveness_interval()).await;
+
+        let logs = {
+            let m = manager.lock().await;
+            match m.service(agent_id, &spec.name) {
+                Some(server) if server.status().pid() == Some(pid) => {
+                    let (logs, next) = server.logs_since(mark);
+                    mark = next;
+                    logs
+                }
+                _ => return,
+            }
+        };
+
+        let result = run_probe(&probe, ProbeKind::Liveness, &spec, &logs).await;
+        let mut m = manager.lock().await;
+        let tx = m.action_tx.clone();
+        let Some(server) = m.service_mut(agent_id, &spec.name) else {
+            return;
+        };
+        if server.status().pid() != Some(pid) {
+            return;
+        }
+
+        match result {
+            Ok(()) => {
+                failures = 0;
+                if matches!(server.status(), DevServerStatus::Unhealthy { .. }) {
+                    server.mark_ready();
+                    server.append_log(format!("Service '{}' is healthy again", spec.name));
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                if failures < spec.health.failure_threshold.max(1)
+                    || matches!(server.status(), DevServerStatus::Unhealthy { .. })
+                {
+                    continue;
+                }
+                let reason = e.to_string();
+                server.mark_unhealthy(reason.clone());
+                server.append_log(format!(
+                    "{}Service '{}' is unhealthy: {}",
+                    CRASH_PREFIX, spec.name, reason
+                ));
+                let _ = tx.send(Action::DevServiceUnhealthy {
+                    agent_id,
+                    service: spec.name.clone(),
+                    reason,
+                });
+                if spec.health.restart != crate::app::config::RestartPolicy::Never {
+                    drop(m);
+                    tokio::spawn(restart_service(manager, agent_id, spec.name.clone()));
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Restart a crashed or unhealthy service after its backoff delay, unless it
+/// was stopped or started by hand in the meantime or ran out of restarts.
+///
+/// Boxed because it starts services that may in turn schedule restarts.
+pub fn restart_service(
+    manager: SharedDevServerManager,
+    agent_id: Uuid,
+    service: String,
+) -> BoxFuture<'static, Result<()>> {
+    Box::pin(async move {
+        let (spec, agent_name, status, delay) = {
+            let mut m = manager.lock().await;
+            let tx = m.action_tx.clone();
+            let Some(server) = m.service_mut(agent_id, &service) else {
+                return Ok(());
+            };
+            let Some(mut spec) = server.spec().cloned() else {
+                return Ok(());
+            };
+            if server.restart_attempts() >= spec.health.max_restarts {
+                let reason = format!("gave up after {} restarts", server.restart_attempts());
+                server.append_log(format!("{}Service '{}' {}", CRASH_PREFIX, service, reason));
+                server.mark_failed(reason.clone());
+                let _ = tx.send(Action::LogError {
+                    message: format!("Dev service '{}' {}", service, reason),
+                });
+                return Ok(());
+            }
+            let attempt = server.record_restart();
+            let delay = restart_backoff(attempt);
+            server.append_log(format!(
+                "Restarting '{}' in {}s (attempt {}/{})",
+                service,
+                delay.as_secs(),
+                attempt + 1,
+                spec.health.max_restarts
+            ));
+            // Setup commands already ran when the stack first started
+            spec.run_before.clear();
+            (
+                spec,
+                server.agent_name().to_string(),
+                server.status().clone(),
+                delay,
+            )
+        };
+
+        sleep(delay).await;
+
+        {
+            let mut m = manager.lock().await;
+            match m.service(agent_id, &service) {
+                Some(server)
+                    if server.status().label() == status.label()
+                        && server.status().pid() == status.pid() => {}
+                _ => return Ok(()),
+            }
+            m.stop_service(agent_id, &service).await?;
+        }
+        start_services(manager, agent_id, agent_name, vec![spec]).await
+    })
+}
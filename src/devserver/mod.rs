@@ -1,7 +1,16 @@
+pub mod errors;
+pub mod health;
 pub mod manager;
+pub mod port;
 pub mod process;
+pub mod proxy;
+pub mod service;
 
-pub use manager::{DevServerManager, SharedDevServerManager};
-pub use process::{tmux_session_name, DevServer, DevServerStatus};
+pub use errors::{format_lines, ErrorDetector};
+pub use manager::{restart_service, start_services, DevServerManager, SharedDevServerManager};
+pub use port::{allocate_port, listening_ports};
+pub use process::{tmux_session_name, DevServer, DevServerStatus, CRASH_PREFIX};
+pub use proxy::{host_label, proxy_url, ProxyRoutes, SharedProxyRoutes, ACTIVE_HOST};
+pub use service::{resolve_services, service_count, ServiceSpec, DEFAULT_SERVICE};
 
 pub use crate::app::DevServerConfig;
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use std::net::TcpListener;
+use std::process::Command;
+
+use crate::core::common::{children_by_parent, descendants};
+
+/// First port in `start..=end` beginning a run of `count` ports that no
+/// agent holds and nothing is bound to.
+pub fn allocate_port(start: u16, end: u16, count: u16, taken: &HashSet<u16>) -> Option<u16> {
+    let count = count.max(1);
+    (start..=end.saturating_sub(count - 1))
+        .find(|&base| (base..base + count).all(|port| !taken.contains(&port) && is_port_free(port)))
+}
+
+pub fn is_port_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// TCP ports listened on by `root_pid` or any of its descendants.
+pub fn listening_ports(root_pid: u32) -> Vec<u16> {
+    let Ok(ps) = Command::new("ps").args(["-A", "-o", "pid=,ppid="]).output() else {
+        return Vec::new();
+    };
+    let pids = process_tree(&String::from_utf8_lossy(&ps.stdout), root_pid);
+    let pid_list = pids
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let Ok(lsof) = Command::new("lsof")
+        .args(["-nP", "-iTCP", "-sTCP:LISTEN", "-a", "-p", &pid_list, "-Fn"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    parse_lsof_ports(&String::from_utf8_lossy(&lsof.stdout))
+}
+
+/// `root` and all its descendants from `ps -o pid=,ppid=` output.
+fn process_tree(ps_output: &str, root: u32) -> Vec<u32> {
+    let pairs = ps_output.lines().filter_map(|line| {
+        let mut parts = line.split_whitespace();
+        Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+    });
+    descendants(&children_by_parent(pairs), &[root])
+}
+
+/// Ports from `lsof -Fn` name lines such as `n*:3000` or `n[::1]:5173`.
+fn parse_lsof_ports(output: &str) -> Vec<u16> {
+    let mut ports: Vec<u16> = output
+        .lines()
+        .filter_map(|line| line.strip_prefix('n'))
+        .filter_map(|name| name.rsplit(':').next()?.parse().ok())
+        .collect();
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_tree() {
+        let ps = "    1     0\n  100     1\n  200   100\n  201   100\n  300   200\n  400     1\n";
+        let mut tree = process_tree(ps, 100);
+        tree.sort_unstable();
+        assert_eq!(tree, vec![100, 200, 201, 300]);
+    }
+
+    #[test]
+    fn test_parse_lsof_ports() {
+        let output = "p4242\nf23\nn*:5173\nf24\nn[::1]:5173\nn127.0.0.1:24678\n";
+        assert_eq!(parse_lsof_ports(output), vec![5173, 24678]);
+    }
+
+    #[test]
+    fn test_allocate_port_skips_taken_and_bound() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let bound = listener.local_addr().unwrap().port();
+        let taken: HashSet<u16> = [bound.wrapping_add(1)].into_iter().collect();
+
+        let port = allocate_port(bound, bound.saturating_add(20), 1, &taken).unwrap();
+        assert_ne!(port, bound);
+        assert!(!taken.contains(&port));
+
+        let base = allocate_port(bound, bound.saturating_add(20), 2, &taken).unwrap();
+        assert!(base > bound.wrapping_add(1));
+    }
+}
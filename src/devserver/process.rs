@@ -3,20 +3,123 @@ use std::collections::VecDeque;
 use std::path::Path;
 use uuid::Uuid;
 
-use crate::app::{Action, DevServerConfig};
+use super::service::{ServiceSpec, DEFAULT_SERVICE};
+use crate::app::Action;
 use crate::tmux::TmuxSession;
 
 const MAX_LOG_LINES: usize = 5000;
 
-pub fn tmux_session_name(agent_id: Uuid) -> String {
-    format!("grove-dev-{}", &agent_id.to_string()[..8])
+pub fn tmux_session_name(agent_id: Uuid, service: &str) -> String {
+    format!("grove-dev-{}-{}", &agent_id.to_string()[..8], service)
 }
 
+/// Run `run_before`, then start the command in a fresh tmux session with the
+/// spec's environment exported. Returns the session name and the pane's PID,
+/// with log lines for the server added to `log`. This blocks while the setup
+/// commands run and touches no server state, so the manager stays unlocked.
+pub fn launch(spec: &ServiceSpec, agent_id: Uuid, log: &mut Vec<String>) -> Result<(String, u32)> {
+    for cmd in &spec.run_before {
+        if let Some(error) = run_before_command(cmd, &spec.working_dir, &spec.env)? {
+            log.push(format!("Pre-command failed: {}", error));
+        }
+        log.push(format!("$ {}", cmd));
+    }
+
+    let command = if spec.env.is_empty() {
+        spec.command.clone()
+    } else {
+        let exports: Vec<String> = spec
+            .env
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        format!("export {}; {}", exports.join(" "), spec.command)
+    };
+
+    let session_name = tmux_session_name(agent_id, &spec.name);
+    let session = TmuxSession::new(&session_name);
+
+    if session.exists() {
+        session.kill()?;
+    }
+
+    session
+        .create(
+            &spec.working_dir.to_string_lossy(),
+            &format!("{}; echo \"{}$?\"", command, EXIT_MARKER),
+        )
+        .context("Failed to create tmux session for dev server")?;
+
+    let pid = tmux_session_pid(&session_name)?;
+    log.push(format!("$ {}", command));
+    Ok((session_name, pid))
+}
+
+/// Run a setup command, returning its stderr if it failed.
+fn run_before_command(
+    command: &str,
+    working_dir: &Path,
+    env: &[(String, String)],
+) -> Result<Option<String>> {
+    let mut cmd = std::process::Command::new("/bin/sh");
+    cmd.arg("-c")
+        .arg(command)
+        .current_dir(working_dir)
+        .envs(env.iter().map(|(k, v)| (k, v)));
+    let output = cmd.output().context("Failed to run pre-command")?;
+
+    Ok((!output.status.success()).then(|| String::from_utf8_lossy(&output.stderr).to_string()))
+}
+
+fn tmux_session_pid(session_name: &str) -> Result<u32> {
+    let output = std::process::Command::new("tmux")
+        .args(["list-panes", "-t", session_name, "-F", "#{pane_pid}"])
+        .output()
+        .context("Failed to get tmux pane PID")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Failed to list panes for session {}", session_name);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let pid = stdout
+        .lines()
+        .next()
+        .and_then(|s| s.trim().parse::<u32>().ok())
+        .context("Failed to parse pane PID")?;
+
+    Ok(pid)
+}
+
+/// Printed by the service's shell when its command exits, followed by the
+/// exit code. The poller watches for it to tell crashes from stops.
+pub const EXIT_MARKER: &str = "[grove] exited with code ";
+
+/// Prefix of the log line recorded when a service crashes.
+pub const CRASH_PREFIX: &str = "✗ ";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DevServerStatus {
     Stopped,
-    Starting,
-    Running { pid: u32, port: Option<u16> },
+    /// Launched, waiting for its readiness probe
+    Starting {
+        pid: u32,
+        port: Option<u16>,
+    },
+    Ready {
+        pid: u32,
+        port: Option<u16>,
+    },
+    /// Still running but failing its liveness probe
+    Unhealthy {
+        pid: u32,
+        port: Option<u16>,
+        reason: String,
+    },
+    /// Exited on its own with a non-zero code
+    Crashed {
+        code: i32,
+    },
     Stopping,
     Failed(String),
 }
@@ -25,8 +128,10 @@ impl DevServerStatus {
     pub fn symbol(&self) -> &'static str {
         match self {
             DevServerStatus::Stopped => "○",
-            DevServerStatus::Starting => "◐",
-            DevServerStatus::Running { .. } => "●",
+            DevServerStatus::Starting { .. } => "◐",
+            DevServerStatus::Ready { .. } => "●",
+            DevServerStatus::Unhealthy { .. } => "◍",
+            DevServerStatus::Crashed { .. } => "✗",
             DevServerStatus::Stopping => "◑",
             DevServerStatus::Failed(_) => "✗",
         }
@@ -35,20 +140,38 @@ impl DevServerStatus {
     pub fn label(&self) -> &'static str {
         match self {
             DevServerStatus::Stopped => "Stopped",
-            DevServerStatus::Starting => "Starting",
-            DevServerStatus::Running { .. } => "Running",
+            DevServerStatus::Starting { .. } => "Starting",
+            DevServerStatus::Ready { .. } => "Ready",
+            DevServerStatus::Unhealthy { .. } => "Unhealthy",
+            DevServerStatus::Crashed { .. } => "Crashed",
             DevServerStatus::Stopping => "Stopping",
             DevServerStatus::Failed(_) => "Failed",
         }
     }
 
+    /// The process is up, whether or not it is ready or healthy.
     pub fn is_running(&self) -> bool {
-        matches!(self, DevServerStatus::Running { .. })
+        self.pid().is_some()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        matches!(self, DevServerStatus::Ready { .. })
+    }
+
+    pub fn pid(&self) -> Option<u32> {
+        match self {
+            DevServerStatus::Starting { pid, .. }
+            | DevServerStatus::Ready { pid, .. }
+            | DevServerStatus::Unhealthy { pid, .. } => Some(*pid),
+            _ => None,
+        }
     }
 
     pub fn port(&self) -> Option<u16> {
         match self {
-            DevServerStatus::Running { port, .. } => *port,
+            DevServerStatus::Starting { port, .. }
+            | DevServerStatus::Ready { port, .. }
+            | DevServerStatus::Unhealthy { port, .. } => *port,
             _ => None,
         }
     }
@@ -59,6 +182,12 @@ pub struct DevServer {
     logs: VecDeque<String>,
     tmux_session: Option<String>,
     agent_name: String,
+    service: String,
+    /// Spec of the last start, reused for automatic restarts
+    spec: Option<ServiceSpec>,
+    restarts: u32,
+    /// Total lines ever appended, so probes can ask for lines since a mark
+    logged: usize,
 }
 
 impl DevServer {
@@ -68,69 +197,84 @@ impl DevServer {
             logs: VecDeque::with_capacity(MAX_LOG_LINES),
             tmux_session: None,
             agent_name: String::new(),
+            service: DEFAULT_SERVICE.to_string(),
+            spec: None,
+            restarts: 0,
+            logged: 0,
         }
     }
 
-    pub async fn start(
-        &mut self,
-        config: &DevServerConfig,
-        worktree_path: &Path,
-        agent_id: Uuid,
-        agent_name: String,
-        action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
-    ) -> Result<()> {
-        if self.status.is_running() {
-            anyhow::bail!("Dev server is already running");
+    pub fn for_service(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+            ..Self::new()
         }
+    }
 
-        let command = config
-            .command
-            .as_ref()
-            .context("No dev server command configured")?;
-
-        let working_dir = if config.working_dir.is_empty() {
-            worktree_path.to_path_buf()
-        } else {
-            worktree_path.join(&config.working_dir)
-        };
-
-        self.status = DevServerStatus::Starting;
-        self.agent_name = agent_name.clone();
-
-        for cmd in &config.run_before {
-            self.run_before_command(cmd, &working_dir)?;
-            self.append_log(format!("$ {}", cmd));
+    /// Get ready to start the service for `spec`. The launch itself is done
+    /// by [`launch`] and recorded with [`DevServer::record_launch`].
+    pub fn prepare(&mut self, spec: &ServiceSpec, agent_name: String) -> Result<()> {
+        if self.status.is_running() {
+            anyhow::bail!("Service '{}' is already running", spec.name);
         }
 
-        let session_name = tmux_session_name(agent_id);
-        let session = TmuxSession::new(&session_name);
+        self.agent_name = agent_name;
+        self.service = spec.name.clone();
+        self.spec = Some(spec.clone());
+        Ok(())
+    }
 
-        if session.exists() {
-            session.kill()?;
+    /// Record the outcome of [`launch`] and start following the service's
+    /// output.
+    pub fn record_launch(
+        &mut self,
+        spec: &ServiceSpec,
+        agent_id: Uuid,
+        log: Vec<String>,
+        launched: Result<(String, u32)>,
+        action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
+    ) -> Result<()> {
+        for line in log {
+            self.append_log(line);
         }
-
-        session
-            .create(&working_dir.to_string_lossy(), command)
-            .context("Failed to create tmux session for dev server")?;
-
-        let pid = self.get_tmux_session_pid(&session_name)?;
+        let (session_name, pid) = match launched {
+            Ok(launched) => launched,
+            Err(e) => {
+                self.status = DevServerStatus::Failed(e.to_string());
+                return Err(e);
+            }
+        };
 
         self.tmux_session = Some(session_name.clone());
-        self.status = DevServerStatus::Running {
-            pid,
-            port: config.port,
+        self.status = if spec.health.readiness.is_some() {
+            DevServerStatus::Starting {
+                pid,
+                port: spec.port,
+            }
+        } else {
+            DevServerStatus::Ready {
+                pid,
+                port: spec.port,
+            }
         };
 
-        self.append_log(format!("$ {}", command));
-        self.append_log(format!("Dev server started (PID: {})", pid));
+        self.append_log(format!("Service '{}' started (PID: {})", spec.name, pid));
 
-        self.spawn_log_poller(agent_id, session_name, action_tx);
+        self.spawn_log_poller(
+            agent_id,
+            spec.name.clone(),
+            session_name,
+            pid,
+            spec.port,
+            action_tx,
+        );
 
         Ok(())
     }
 
     pub async fn stop(&mut self) -> Result<()> {
-        if !self.status.is_running() {
+        // A crashed service keeps its session open for inspection
+        if !self.status.is_running() && self.tmux_session.is_none() {
             return Ok(());
         }
 
@@ -151,19 +295,6 @@ impl DevServer {
         Ok(())
     }
 
-    pub async fn restart(
-        &mut self,
-        config: &DevServerConfig,
-        worktree_path: &Path,
-        agent_id: Uuid,
-        agent_name: String,
-        action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
-    ) -> Result<()> {
-        self.stop().await?;
-        self.start(config, worktree_path, agent_id, agent_name, action_tx)
-            .await
-    }
-
     pub fn status(&self) -> &DevServerStatus {
         &self.status
     }
@@ -178,6 +309,24 @@ impl DevServer {
             self.logs.pop_front();
         }
         self.logs.push_back(line.into());
+        self.logged += 1;
+    }
+
+    /// Mark to pass to `logs_since` later.
+    pub fn log_mark(&self) -> usize {
+        self.logged
+    }
+
+    /// Lines appended after `mark` that are still kept, plus the new mark.
+    pub fn logs_since(&self, mark: usize) -> (Vec<String>, usize) {
+        let count = self.logged.saturating_sub(mark).min(self.logs.len());
+        let lines = self
+            .logs
+            .iter()
+            .skip(self.logs.len() - count)
+            .cloned()
+            .collect();
+        (lines, self.logged)
     }
 
     pub fn clear_logs(&mut self) {
@@ -192,40 +341,82 @@ impl DevServer {
         self.agent_name = name;
     }
 
-    fn run_before_command(&mut self, command: &str, working_dir: &Path) -> Result<()> {
-        let output = std::process::Command::new("/bin/sh")
-            .arg("-c")
-            .arg(command)
-            .current_dir(working_dir)
-            .output()
-            .context("Failed to run pre-command")?;
+    pub fn service(&self) -> &str {
+        &self.service
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            self.append_log(format!("Pre-command failed: {}", stderr));
-        }
+    pub fn spec(&self) -> Option<&ServiceSpec> {
+        self.spec.as_ref()
+    }
 
-        Ok(())
+    /// The readiness (or liveness) probe passed. Resets the restart count.
+    pub fn mark_ready(&mut self) {
+        if let DevServerStatus::Starting { pid, port }
+        | DevServerStatus::Unhealthy { pid, port, .. } = self.status.clone()
+        {
+            self.status = DevServerStatus::Ready { pid, port };
+        }
+        self.restarts = 0;
     }
 
-    fn get_tmux_session_pid(&self, session_name: &str) -> Result<u32> {
-        let output = std::process::Command::new("tmux")
-            .args(["list-panes", "-t", session_name, "-F", "#{pane_pid}"])
-            .output()
-            .context("Failed to get tmux pane PID")?;
+    pub fn mark_unhealthy(&mut self, reason: String) {
+        if let Some(pid) = self.status.pid() {
+            self.status = DevServerStatus::Unhealthy {
+                pid,
+                port: self.status.port(),
+                reason,
+            };
+        }
+    }
 
-        if !output.status.success() {
-            anyhow::bail!("Failed to list panes for session {}", session_name);
+    /// The command exited on its own. A non-zero code is recorded as a crash
+    /// with a highlighted marker in the log. Returns `false` if the service
+    /// was not running (e.g. it is being stopped).
+    pub fn mark_exited(&mut self, code: i32) -> bool {
+        if !self.status.is_running() {
+            return false;
         }
+        if code == 0 {
+            self.status = DevServerStatus::Stopped;
+            self.append_log(format!("Service '{}' exited", self.service));
+        } else {
+            self.status = DevServerStatus::Crashed { code };
+            self.append_log(format!(
+                "{}Service '{}' crashed with exit code {}",
+                CRASH_PREFIX, self.service, code
+            ));
+        }
+        true
+    }
+
+    pub fn mark_failed(&mut self, reason: String) {
+        self.status = DevServerStatus::Failed(reason);
+    }
+
+    pub fn restart_attempts(&self) -> u32 {
+        self.restarts
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let pid = stdout
-            .lines()
-            .next()
-            .and_then(|s| s.trim().parse::<u32>().ok())
-            .context("Failed to parse pane PID")?;
+    /// Count an automatic restart, returning the attempt number (from 0).
+    pub fn record_restart(&mut self) -> u32 {
+        self.restarts += 1;
+        self.restarts - 1
+    }
 
-        Ok(pid)
+    /// Record the port the server actually listens on. Returns `true` if it
+    /// changed.
+    pub fn set_port(&mut self, detected: u16) -> bool {
+        match &mut self.status {
+            DevServerStatus::Starting { port, .. }
+            | DevServerStatus::Ready { port, .. }
+            | DevServerStatus::Unhealthy { port, .. }
+                if *port != Some(detected) =>
+            {
+                *port = Some(detected);
+                true
+            }
+            _ => false,
+        }
     }
 
     pub fn tmux_session(&self) -> Option<&str> {
@@ -235,7 +426,10 @@ impl DevServer {
     fn spawn_log_poller(
         &self,
         agent_id: Uuid,
+        service: String,
         session_name: String,
+        pid: u32,
+        expected_port: Option<u16>,
         action_tx: tokio::sync::mpsc::UnboundedSender<Action>,
     ) {
         let tx = action_tx;
@@ -246,18 +440,45 @@ impl DevServer {
             use tokio::time::{sleep, Duration};
 
             let mut last_content = String::new();
+            let mut detected_port: Option<u16> = None;
+            let mut tick: u32 = 0;
 
             loop {
                 sleep(Duration::from_millis(500)).await;
+                tick = tick.wrapping_add(1);
+
+                // Look for the listening port every 2s until found, then every 30s
+                let interval = if detected_port.is_some() { 60 } else { 4 };
+                if tick.is_multiple_of(interval) {
+                    let ports = super::port::listening_ports(pid);
+                    let port = ports
+                        .iter()
+                        .copied()
+                        .find(|p| Some(*p) == detected_port.or(expected_port))
+                        .or_else(|| ports.first().copied());
+                    if let Some(port) = port.filter(|p| Some(*p) != detected_port) {
+                        detected_port = Some(port);
+                        let _ = tx.send(Action::DevServerPortDetected {
+                            agent_id: id,
+                            service: service.clone(),
+                            port,
+                        });
+                    }
+                }
 
                 let tmux = TmuxSession::new(&session);
                 if !tmux.exists() {
                     let _ = tx.send(Action::UpdateDevServerStatus {
                         agent_id: id,
+                        service: service.clone(),
                         status: DevServerStatus::Stopped,
                     });
                     break;
                 }
+                // The session was replaced by a restart with its own poller
+                if tmux.pane_pid().is_some_and(|p| p != pid) {
+                    break;
+                }
 
                 match tmux.capture_pane(100) {
                     Ok(content) => {
@@ -267,15 +488,28 @@ impl DevServer {
                                 .skip_while(|line| last_content.lines().any(|l| l == *line))
                                 .collect();
 
+                            let mut exit_code = None;
                             for line in new_lines {
-                                if !line.trim().is_empty() {
+                                if let Some(code) = parse_exit_marker(line) {
+                                    exit_code = Some(code);
+                                } else if !line.trim().is_empty() {
                                     let _ = tx.send(Action::AppendDevServerLog {
                                         agent_id: id,
+                                        service: service.clone(),
                                         line: line.to_string(),
                                     });
                                 }
                             }
 
+                            if let Some(code) = exit_code {
+                                let _ = tx.send(Action::DevServiceExited {
+                                    agent_id: id,
+                                    service: service.clone(),
+                                    code,
+                                });
+                                break;
+                            }
+
                             last_content = content;
                         }
                     }
@@ -288,8 +522,50 @@ impl DevServer {
     }
 }
 
+/// Exit code from the line the shell prints after the command ends.
+fn parse_exit_marker(line: &str) -> Option<i32> {
+    line.trim().strip_prefix(EXIT_MARKER)?.parse().ok()
+}
+
 impl Default for DevServer {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_exit_marker() {
+        assert_eq!(parse_exit_marker("[grove] exited with code 1"), Some(1));
+        assert_eq!(parse_exit_marker("  [grove] exited with code 0 "), Some(0));
+        // The echoed command line itself must not count as an exit
+        assert_eq!(
+            parse_exit_marker("$ npm run dev; echo \"[grove] exited with code $?\""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_logs_since_and_crash() {
+        let mut server = DevServer::for_service("web");
+        server.append_log("one");
+        let mark = server.log_mark();
+        server.append_log("two");
+        server.append_log("three");
+        let (lines, mark) = server.logs_since(mark);
+        assert_eq!(lines, vec!["two", "three"]);
+        assert!(server.logs_since(mark).0.is_empty());
+
+        assert!(!server.mark_exited(1));
+        server.status = DevServerStatus::Ready {
+            pid: 42,
+            port: Some(3100),
+        };
+        assert!(server.mark_exited(1));
+        assert_eq!(server.status(), &DevServerStatus::Crashed { code: 1 });
+        assert!(server.logs().last().unwrap().starts_with(CRASH_PREFIX));
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Hostname that always points at the selected agent's dev server.
+pub const ACTIVE_HOST: &str = "active";
+
+/// Largest request head read before a connection is routed.
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+/// Backend ports by hostname (`<agent>` or `<service>.<agent>`), kept up to
+/// date by `DevServerManager`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyRoutes {
+    pub hosts: HashMap<String, u16>,
+    pub active: Option<u16>,
+}
+
+impl ProxyRoutes {
+    pub fn resolve(&self, label: &str) -> Option<u16> {
+        if label == ACTIVE_HOST {
+            self.active
+        } else {
+            self.hosts.get(label).copied()
+        }
+    }
+}
+
+pub type SharedProxyRoutes = Arc<RwLock<ProxyRoutes>>;
+
+/// DNS-safe hostname label for an agent name, e.g. `Fix Login` -> `fix-login`.
+pub fn host_label(agent_name: &str) -> String {
+    let label: String = agent_name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    label
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+pub fn proxy_url(label: &str, proxy_port: u16) -> String {
+    format!("http://{}.localhost:{}", label, proxy_port)
+}
+
+/// Serve `http://<label>.localhost:<port>` by forwarding each connection to
+/// the matching dev server. Connections are piped as raw bytes after the
+/// request head, so websocket upgrades (HMR) work unchanged. Other requests
+/// are sent with `Connection: close`, so a keep-alive connection cannot carry
+/// a later request for another host to the first request's backend.
+pub async fn serve(port: u16, routes: SharedProxyRoutes) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Failed to bind proxy on port {}", port))?;
+
+    loop {
+        let (client, _) = listener.accept().await?;
+        let routes = Arc::clone(&routes);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(client, routes).await {
+                tracing::debug!("Proxy connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(mut client: TcpStream, routes: SharedProxyRoutes) -> Result<()> {
+    let mut head = Vec::with_capacity(4096);
+    let mut buf = [0u8; 4096];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        if head.len() > MAX_HEAD_BYTES {
+            anyhow::bail!("Request head too large");
+        }
+        let n = client.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+
+    let label = host_label_from_request(&String::from_utf8_lossy(&head));
+    let backend = label.as_deref().and_then(|label| {
+        let routes = routes.read().ok()?;
+        routes.resolve(label)
+    });
+
+    let Some(port) = backend else {
+        let body = format!(
+            "No running dev server for '{}'\n",
+            label.unwrap_or_default()
+        );
+        client.write_all(error_response(&body).as_bytes()).await?;
+        return Ok(());
+    };
+
+    let mut server = match TcpStream::connect(("127.0.0.1", port)).await {
+        Ok(server) => server,
+        Err(e) => {
+            let body = format!("Dev server on port {} is not reachable: {}\n", port, e);
+            client.write_all(error_response(&body).as_bytes()).await?;
+            return Ok(());
+        }
+    };
+    server.write_all(&close_after_response(&head)).await?;
+    tokio::io::copy_bidirectional(&mut client, &mut server).await?;
+    Ok(())
+}
+
+/// Request's `Host` without port and `.localhost` suffix:
+/// `api.app.localhost:8800` -> `api.app`.
+fn host_label_from_request(head: &str) -> Option<String> {
+    let host = head.lines().skip(1).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("host")
+            .then(|| value.trim())
+    })?;
+    let host = host.split(':').next()?.to_lowercase();
+    let label = host.strip_suffix(".localhost").unwrap_or(&host);
+    (!label.is_empty()).then(|| label.to_string())
+}
+
+/// `head` with its connection headers replaced by `Connection: close`,
+/// unless it asks for a protocol upgrade.
+fn close_after_response(head: &[u8]) -> Vec<u8> {
+    let Some(end) = head.windows(4).position(|w| w == b"\r\n\r\n") else {
+        return head.to_vec();
+    };
+    let text = String::from_utf8_lossy(&head[..end]);
+    let mut lines = text.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut headers = Vec::new();
+    for line in lines {
+        let name = line.split_once(':').map_or("", |(name, _)| name.trim());
+        if name.eq_ignore_ascii_case("upgrade") {
+            return head.to_vec();
+        }
+        if !name.eq_ignore_ascii_case("connection") && !name.eq_ignore_ascii_case("keep-alive") {
+            headers.push(line);
+        }
+    }
+    let mut rewritten = format!("{}\r\n", request_line);
+    for line in headers {
+        rewritten.push_str(line);
+        rewritten.push_str("\r\n");
+    }
+    rewritten.push_str("Connection: close\r\n\r\n");
+    let mut rewritten = rewritten.into_bytes();
+    rewritten.extend_from_slice(&head[end + 4..]);
+    rewritten
+}
+
+fn error_response(body: &str) -> String {
+    format!(
+        "HTTP/1.1 502 Bad Gateway\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_label() {
+        assert_eq!(host_label("Fix Login"), "fix-login");
+        assert_eq!(host_label("feat/api_v2--x"), "feat-api-v2-x");
+    }
+
+    #[test]
+    fn test_host_label_from_request() {
+        let head = "GET / HTTP/1.1\r\nhost: Fix-Login.localhost:8800\r\nAccept: */*\r\n\r\n";
+        assert_eq!(host_label_from_request(head), Some("fix-login".to_string()));
+        let head = "GET / HTTP/1.1\r\nHost: api.fix-login.localhost\r\n\r\n";
+        assert_eq!(
+            host_label_from_request(head),
+            Some("api.fix-login".to_string())
+        );
+        assert_eq!(host_label_from_request("GET / HTTP/1.1\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn test_close_after_response() {
+        let head = b"GET / HTTP/1.1\r\nHost: app.localhost\r\nConnection: keep-alive\r\n\r\nbody";
+        assert_eq!(
+            String::from_utf8_lossy(&close_after_response(head)),
+            "GET / HTTP/1.1\r\nHost: app.localhost\r\nConnection: close\r\n\r\nbody"
+        );
+        let upgrade = b"GET /hmr HTTP/1.1\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\r\n";
+        assert_eq!(close_after_response(upgrade), upgrade.to_vec());
+    }
+
+    #[test]
+    fn test_resolve_active_alias() {
+        let routes = ProxyRoutes {
+            hosts: [("fix-login".to_string(), 3101)].into_iter().collect(),
+            active: Some(3101),
+        };
+        assert_eq!(routes.resolve("fix-login"), Some(3101));
+        assert_eq!(routes.resolve(ACTIVE_HOST), Some(3101));
+        assert_eq!(routes.resolve("other"), None);
+    }
+
+    #[tokio::test]
+    async fn test_proxy_forwards_to_backend() {
+        let backend = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let backend_port = backend.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut conn, _) = backend.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let n = conn.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            assert!(request.starts_with("GET /hmr"));
+            assert!(request.contains("Connection: close\r\n"));
+            conn.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok")
+                .await
+                .unwrap();
+        });
+
+        let routes: SharedProxyRoutes = Arc::default();
+        routes
+            .write()
+            .unwrap()
+            .hosts
+            .insert("app".to_string(), backend_port);
+
+        let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_port = proxy.local_addr().unwrap().port();
+        let proxy_routes = Arc::clone(&routes);
+        tokio::spawn(async move {
+            let (client, _) = proxy.accept().await.unwrap();
+            let _ = handle_connection(client, proxy_routes).await;
+        });
+
+        let mut client = TcpStream::connect(("127.0.0.1", proxy_port)).await.unwrap();
+        client
+            .write_all(b"GET /hmr HTTP/1.1\r\nHost: app.localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.ends_with("ok"));
+    }
+}
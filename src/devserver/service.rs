@@ -0,0 +1,271 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::app::config::{DevServiceConfig, HealthConfig, ProbeConfig};
+use crate::app::DevServerConfig;
+
+/// Name of the service created from the plain `command` setting.
+pub const DEFAULT_SERVICE: &str = "dev";
+
+/// A service resolved for one agent: commands templated, ports assigned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceSpec {
+    pub name: String,
+    pub command: String,
+    pub working_dir: PathBuf,
+    pub run_before: Vec<String>,
+    pub port: Option<u16>,
+    pub env: Vec<(String, String)>,
+    pub depends_on: Vec<String>,
+    /// Probes with `{port}` placeholders already rendered
+    pub health: HealthConfig,
+}
+
+impl ServiceSpec {
+    pub fn ready_timeout(&self) -> Duration {
+        Duration::from_secs(self.health.ready_timeout_secs)
+    }
+
+    pub fn liveness_interval(&self) -> Duration {
+        Duration::from_secs(self.health.liveness_interval_secs.max(1))
+    }
+}
+
+/// Number of ports an agent needs, one per service.
+pub fn service_count(config: &DevServerConfig) -> u16 {
+    config.services.len().max(1) as u16
+}
+
+/// Resolve the configured services for an agent, in start order: every
+/// service comes after the services it depends on. `base_port` is the
+/// agent's allocated port. `run_before` is attached to the first service.
+pub fn resolve_services(
+    config: &DevServerConfig,
+    worktree: &Path,
+    base_port: Option<u16>,
+) -> Result<Vec<ServiceSpec>> {
+    let services = if config.services.is_empty() {
+        let command = config
+            .command
+            .clone()
+            .context("No dev server command configured")?;
+        vec![DevServiceConfig {
+            name: DEFAULT_SERVICE.to_string(),
+            command,
+            working_dir: config.working_dir.clone(),
+            port: config.port,
+            depends_on: Vec::new(),
+            health: config.health.clone(),
+        }]
+    } else {
+        config.services.clone()
+    };
+
+    let ports: Vec<(String, Option<u16>)> = services
+        .iter()
+        .enumerate()
+        .map(|(i, service)| {
+            let port = match base_port {
+                Some(base) if config.auto_port => base.checked_add(i as u16),
+                _ => service.port,
+            };
+            (service.name.clone(), port)
+        })
+        .collect();
+
+    let mut specs = Vec::new();
+    for index in start_order(&services)? {
+        let service = &services[index];
+        let port = ports[index].1;
+        let render = |text: &str| {
+            let mut rendered = text.to_string();
+            for (name, other) in &ports {
+                if let Some(other) = other {
+                    rendered = rendered.replace(&format!("{{port.{}}}", name), &other.to_string());
+                }
+            }
+            match port {
+                Some(port) => rendered.replace("{port}", &port.to_string()),
+                None => rendered,
+            }
+        };
+
+        let mut env: Vec<(String, String)> = ports
+            .iter()
+            .filter_map(|(name, port)| {
+                Some((format!("{}_PORT", env_name(name)), (*port)?.to_string()))
+            })
+            .collect();
+        if let Some(port) = port.filter(|_| !config.port_env.is_empty()) {
+            env.push((config.port_env.clone(), port.to_string()));
+        }
+
+        let working_dir = if service.working_dir.is_empty() {
+            worktree.to_path_buf()
+        } else {
+            worktree.join(&service.working_dir)
+        };
+
+        specs.push(ServiceSpec {
+            name: service.name.clone(),
+            command: render(&service.command),
+            working_dir,
+            run_before: if specs.is_empty() {
+                config.run_before.iter().map(|cmd| render(cmd)).collect()
+            } else {
+                Vec::new()
+            },
+            port,
+            env,
+            depends_on: service.depends_on.clone(),
+            health: HealthConfig {
+                readiness: service
+                    .health
+                    .readiness
+                    .as_ref()
+                    .map(|p| render_probe(p, &render)),
+                liveness: service
+                    .health
+                    .liveness
+                    .as_ref()
+                    .map(|p| render_probe(p, &render)),
+                ..service.health.clone()
+            },
+        });
+    }
+    Ok(specs)
+}
+
+/// Indices of `services` ordered so dependencies start first. Services
+/// without ordering constraints keep their configured order.
+fn start_order(services: &[DevServiceConfig]) -> Result<Vec<usize>> {
+    for service in services {
+        for dep in &service.depends_on {
+            if !services.iter().any(|s| &s.name == dep) {
+                anyhow::bail!(
+                    "Service '{}' depends on unknown service '{}'",
+                    service.name,
+                    dep
+                );
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = Vec::with_capacity(services.len());
+    while order.len() < services.len() {
+        let next = (0..services.len()).find(|&i| {
+            !order.contains(&i)
+                && services[i]
+                    .depends_on
+                    .iter()
+                    .all(|dep| order.iter().any(|&j| &services[j].name == dep))
+        });
+        match next {
+            Some(i) => order.push(i),
+            None => anyhow::bail!("Dev services have a dependency cycle"),
+        }
+    }
+    Ok(order)
+}
+
+fn render_probe(probe: &ProbeConfig, render: &impl Fn(&str) -> String) -> ProbeConfig {
+    match probe {
+        ProbeConfig::Http(url) => ProbeConfig::Http(render(url)),
+        ProbeConfig::Tcp(addr) => ProbeConfig::Tcp(render(addr)),
+        // Braces are regex syntax, so log patterns are left as written
+        ProbeConfig::Log(pattern) => ProbeConfig::Log(pattern.clone()),
+        ProbeConfig::Command(cmd) => ProbeConfig::Command(render(cmd)),
+    }
+}
+
+fn env_name(service: &str) -> String {
+    service
+        .to_uppercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(name: &str, command: &str, depends_on: &[&str]) -> DevServiceConfig {
+        DevServiceConfig {
+            name: name.to_string(),
+            command: command.to_string(),
+            working_dir: String::new(),
+            port: None,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            health: HealthConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_single_command_becomes_default_service() {
+        let config = DevServerConfig {
+            command: Some("npm run dev -- --port {port}".to_string()),
+            ..Default::default()
+        };
+        let specs = resolve_services(&config, Path::new("/wt"), Some(3100)).unwrap();
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].name, DEFAULT_SERVICE);
+        assert_eq!(specs[0].command, "npm run dev -- --port 3100");
+        assert!(specs[0]
+            .env
+            .contains(&("PORT".to_string(), "3100".to_string())));
+    }
+
+    #[test]
+    fn test_services_start_after_dependencies() {
+        let config = DevServerConfig {
+            services: vec![
+                service("web", "vite --port {port} --api {port.api}", &["api"]),
+                service("worker", "worker", &["api"]),
+                service("api", "api --port {port}", &[]),
+            ],
+            ..Default::default()
+        };
+        let specs = resolve_services(&config, Path::new("/wt"), Some(3100)).unwrap();
+        let names: Vec<&str> = specs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["api", "web", "worker"]);
+        assert_eq!(specs[0].command, "api --port 3102");
+        assert_eq!(specs[1].command, "vite --port 3100 --api 3102");
+        assert!(specs[2]
+            .env
+            .contains(&("API_PORT".to_string(), "3102".to_string())));
+    }
+
+    #[test]
+    fn test_probes_are_rendered() {
+        let mut api = service("api", "api", &[]);
+        api.health.readiness = Some(ProbeConfig::Http("http://localhost:{port}/health".into()));
+        api.health.liveness = Some(ProbeConfig::Log("error{2}".into()));
+        let config = DevServerConfig {
+            services: vec![api],
+            ..Default::default()
+        };
+        let specs = resolve_services(&config, Path::new("/wt"), Some(3100)).unwrap();
+        assert_eq!(
+            specs[0].health.readiness,
+            Some(ProbeConfig::Http("http://localhost:3100/health".into()))
+        );
+        assert_eq!(
+            specs[0].health.liveness,
+            Some(ProbeConfig::Log("error{2}".into()))
+        );
+    }
+
+    #[test]
+    fn test_dependency_errors() {
+        let mut config = DevServerConfig {
+            services: vec![service("web", "web", &["db"])],
+            ..Default::default()
+        };
+        assert!(resolve_services(&config, Path::new("/wt"), None).is_err());
+
+        config.services = vec![service("a", "a", &["b"]), service("b", "b", &["a"])];
+        assert!(resolve_services(&config, Path::new("/wt"), None).is_err());
+    }
+}
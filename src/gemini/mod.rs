@@ -1,3 +1,5 @@
 pub mod session;
+mod transcript;
 
 pub use session::{build_resume_command, find_session_by_directory};
+pub use transcript::{find_chat, list_sessions, parse_chat};
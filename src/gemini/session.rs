@@ -10,7 +10,7 @@ struct ProjectsJson {
     projects: std::collections::HashMap<String, String>,
 }
 
-fn get_gemini_dir() -> PathBuf {
+pub(crate) fn get_gemini_dir() -> PathBuf {
     dirs::home_dir()
         .map(|h| h.join(".gemini"))
         .unwrap_or_else(|| PathBuf::from("~/.gemini"))
@@ -20,6 +20,13 @@ fn get_projects_json_path() -> PathBuf {
     get_gemini_dir().join("projects.json")
 }
 
+/// Name Gemini gave the project at `worktree_path` in projects.json.
+pub(crate) fn project_name(worktree_path: &str) -> Option<String> {
+    let content = std::fs::read_to_string(get_projects_json_path()).ok()?;
+    let projects: ProjectsJson = serde_json::from_str(&content).ok()?;
+    projects.projects.get(worktree_path).cloned()
+}
+
 pub fn find_session_by_directory(worktree_path: &str) -> Result<Option<String>> {
     let projects_path = get_projects_json_path();
 
@@ -0,0 +1,227 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::session::{get_gemini_dir, project_name};
+use crate::agent::sessions::SessionSummary;
+use crate::agent::transcript::{newest_file, TranscriptEvent, TranscriptEventKind};
+
+/// Written by Gemini in each project's temp directory.
+const PROJECT_ROOT_FILE: &str = ".project_root";
+
+/// `chats` directories of the Gemini projects for `worktree_path`.
+fn chat_dirs(worktree_path: &str) -> Vec<PathBuf> {
+    let tmp = get_gemini_dir().join("tmp");
+    let mut project_dirs: Vec<PathBuf> = project_name(worktree_path)
+        .map(|name| tmp.join(name))
+        .into_iter()
+        .collect();
+    // Older Gemini versions name the directory after a hash of the path
+    if let Ok(entries) = std::fs::read_dir(&tmp) {
+        project_dirs.extend(
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|dir| {
+                    std::fs::read_to_string(dir.join(PROJECT_ROOT_FILE))
+                        .is_ok_and(|root| root.trim().trim_end_matches('/') == worktree_path)
+                }),
+        );
+    }
+    project_dirs
+        .into_iter()
+        .map(|dir| dir.join("chats"))
+        .collect()
+}
+
+/// Chat file (`tmp/<project>/chats/session-*.json`) of the newest Gemini
+/// session in `worktree_path` written to at or after `since`.
+pub fn find_chat(worktree_path: &str, since: SystemTime) -> Option<PathBuf> {
+    newest_file(chat_dirs(worktree_path), "session-", ".json", since, |_| {
+        true
+    })
+}
+
+/// Every Gemini session for `worktree_path`, from the chat files.
+pub fn list_sessions(worktree_path: &str) -> Result<Vec<SessionSummary>> {
+    let mut sessions = Vec::new();
+    for dir in chat_dirs(worktree_path) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if !(name.starts_with("session-") && name.ends_with(".json")) {
+                continue;
+            }
+            if let Some(session) = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| chat_summary(&content))
+            {
+                sessions.push(session);
+            }
+        }
+    }
+    Ok(sessions)
+}
+
+fn chat_summary(content: &str) -> Option<SessionSummary> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    let messages = value.get("messages")?.as_array()?;
+    let of_type = |kind: &str, m: &&Value| m.get("type").and_then(Value::as_str) == Some(kind);
+    let updated_at = ["lastUpdated", "startTime"]
+        .iter()
+        .filter_map(|key| value.get(key).and_then(Value::as_str))
+        .find_map(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| ts.with_timezone(&Utc))
+        .unwrap_or_default();
+    Some(SessionSummary {
+        id: value.get("sessionId")?.as_str()?.to_string(),
+        updated_at,
+        first_prompt: messages
+            .iter()
+            .find(|m| of_type("user", m))
+            .map(|m| content_text(&m["content"])),
+        message_count: messages
+            .iter()
+            .filter(|m| of_type("user", m) || of_type("gemini", m))
+            .count(),
+    })
+}
+
+/// Events from the messages after the first `skip` in a chat file, plus the
+/// total message count. `None` if the file isn't valid JSON.
+pub fn parse_chat(content: &str, skip: usize) -> Option<(Vec<TranscriptEvent>, usize)> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    let messages = value.get("messages")?.as_array()?;
+    let events = messages
+        .iter()
+        .skip(skip)
+        .flat_map(message_events)
+        .collect();
+    Some((events, messages.len()))
+}
+
+fn message_events(message: &Value) -> Vec<TranscriptEvent> {
+    let at = message
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| ts.with_timezone(&Utc));
+    let content = content_text(&message["content"]);
+    let event = |kind, text: &str| TranscriptEvent::new(kind, text, at);
+
+    match message.get("type").and_then(Value::as_str) {
+        Some("user") => vec![event(TranscriptEventKind::Prompt, &content)],
+        Some("gemini") => {
+            let calls: Vec<&Value> = message
+                .get("toolCalls")
+                .and_then(Value::as_array)
+                .map(|calls| calls.iter().collect())
+                .unwrap_or_default();
+            let mut events = Vec::new();
+            if !content.trim().is_empty() {
+                events.push(event(TranscriptEventKind::Message, &content));
+            }
+            events.extend(
+                calls
+                    .iter()
+                    .map(|call| event(TranscriptEventKind::ToolCall, &tool_call_text(call))),
+            );
+            // A reply without tool calls ends the turn
+            if calls.is_empty() {
+                events.push(event(TranscriptEventKind::TurnComplete, "Turn finished"));
+            }
+            events
+        }
+        Some("error") => vec![event(TranscriptEventKind::Error, &content)],
+        _ => Vec::new(),
+    }
+}
+
+/// Message content, stored as a string or a list of `{ "text": ... }` parts.
+fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// `run_shell_command` calls show their command; file tools their path.
+fn tool_call_text(call: &Value) -> String {
+    let name = call
+        .get("displayName")
+        .or_else(|| call.get("name"))
+        .and_then(Value::as_str)
+        .unwrap_or("tool");
+    let args = &call["args"];
+    let detail = ["command", "file_path", "absolute_path", "path", "pattern"]
+        .iter()
+        .find_map(|key| args.get(key).and_then(Value::as_str));
+    match detail {
+        Some(detail) => format!("{}: {}", name, detail),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAT: &str = r#"{
+  "sessionId": "8cfa2711-514a-4197-ac0e-df46c9fee46f",
+  "messages": [
+    { "id": "1", "timestamp": "2026-10-18T09:00:00.000Z", "type": "user", "content": "Add a --verbose flag" },
+    { "id": "2", "timestamp": "2026-10-18T09:00:05.000Z", "type": "gemini", "content": "",
+      "toolCalls": [{ "id": "t1", "name": "run_shell_command", "displayName": "Shell", "args": { "command": "cargo build" }, "status": "success" }] },
+    { "id": "3", "timestamp": "2026-10-18T09:00:09.000Z", "type": "info", "content": "Request cancelled." },
+    { "id": "4", "timestamp": "2026-10-18T09:00:12.000Z", "type": "gemini", "content": [{ "text": "Added the flag." }] }
+  ]
+}"#;
+
+    #[test]
+    fn test_parse_chat() {
+        let (events, total) = parse_chat(CHAT, 0).unwrap();
+        assert_eq!(total, 4);
+        let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TranscriptEventKind::Prompt,
+                TranscriptEventKind::ToolCall,
+                TranscriptEventKind::Message,
+                TranscriptEventKind::TurnComplete,
+            ]
+        );
+        assert_eq!(events[1].text, "Shell: cargo build");
+        assert_eq!(events[2].text, "Added the flag.");
+    }
+
+    #[test]
+    fn test_chat_summary() {
+        let session = chat_summary(CHAT).unwrap();
+        assert_eq!(session.id, "8cfa2711-514a-4197-ac0e-df46c9fee46f");
+        assert_eq!(
+            session.first_prompt.as_deref(),
+            Some("Add a --verbose flag")
+        );
+        assert_eq!(session.message_count, 3);
+    }
+
+    #[test]
+    fn test_parse_chat_skips_seen_messages() {
+        let (events, total) = parse_chat(CHAT, 3).unwrap();
+        assert_eq!(total, 4);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].kind, TranscriptEventKind::Message);
+        // Partially written files are retried later
+        assert!(parse_chat(&CHAT[..40], 0).is_none());
+    }
+}
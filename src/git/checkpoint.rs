@@ -0,0 +1,310 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+
+const CHECKPOINT_NAMESPACE: &str = "refs/grove/checkpoints";
+
+/// Serializes numbering so a timed and a manual snapshot can't pick the
+/// same index.
+static SNAPSHOT_LOCK: Mutex<()> = Mutex::new(());
+
+/// A snapshot of a worktree, including uncommitted and untracked files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub index: u32,
+    pub commit: String,
+    pub created_at: DateTime<Utc>,
+    pub label: String,
+}
+
+impl Checkpoint {
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+}
+
+/// Checkpoints of one agent's worktree, stored as commits under
+/// `refs/grove/checkpoints/<agent>/<n>`. Snapshots are built with scratch
+/// indexes so the agent's own index and working tree are never touched.
+pub struct Checkpoints {
+    worktree_path: PathBuf,
+    agent_key: String,
+}
+
+impl Checkpoints {
+    pub fn new(worktree_path: &str, agent_key: &str) -> Self {
+        Self {
+            worktree_path: PathBuf::from(worktree_path),
+            agent_key: agent_key.to_string(),
+        }
+    }
+
+    fn prefix(&self) -> String {
+        format!("{}/{}/", CHECKPOINT_NAMESPACE, self.agent_key)
+    }
+
+    /// Record the current worktree state. Returns `None` when nothing
+    /// changed since the latest checkpoint.
+    pub fn snapshot(&self, label: &str) -> Result<Option<Checkpoint>> {
+        let tree = self.current_tree()?;
+        let _guard = SNAPSHOT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let existing = self.list()?;
+
+        if let Some(latest) = existing.last() {
+            let latest_tree = self.git(&["rev-parse", &format!("{}^{{tree}}", latest.commit)])?;
+            if latest_tree == tree {
+                return Ok(None);
+            }
+        }
+
+        let head = self.git(&["rev-parse", "HEAD"])?;
+        let commit = self.git(&["commit-tree", &tree, "-p", &head, "-m", label])?;
+        let index = existing.last().map(|c| c.index + 1).unwrap_or(1);
+        // An empty old value makes git refuse to overwrite an existing ref
+        self.git(&[
+            "update-ref",
+            &format!("{}{}", self.prefix(), index),
+            &commit,
+            "",
+        ])?;
+
+        Ok(Some(Checkpoint {
+            index,
+            commit,
+            created_at: Utc::now(),
+            label: label.to_string(),
+        }))
+    }
+
+    /// All checkpoints, oldest first.
+    pub fn list(&self) -> Result<Vec<Checkpoint>> {
+        let output = self.git(&[
+            "for-each-ref",
+            "--format=%(refname)\t%(objectname)\t%(creatordate:unix)\t%(contents:subject)",
+            &self.prefix(),
+        ])?;
+
+        let mut checkpoints: Vec<Checkpoint> = output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(4, '\t');
+                let refname = parts.next()?;
+                let commit = parts.next()?.to_string();
+                let timestamp: i64 = parts.next()?.parse().ok()?;
+                let label = parts.next().unwrap_or("").to_string();
+                let index = refname.rsplit('/').next()?.parse().ok()?;
+                Some(Checkpoint {
+                    index,
+                    commit,
+                    created_at: Utc.timestamp_opt(timestamp, 0).single()?,
+                    label,
+                })
+            })
+            .collect();
+        checkpoints.sort_by_key(|c| c.index);
+        Ok(checkpoints)
+    }
+
+    /// Drop the oldest checkpoints so at most `keep` remain.
+    pub fn prune(&self, keep: usize) -> Result<()> {
+        let checkpoints = self.list()?;
+        let excess = checkpoints.len().saturating_sub(keep);
+        for checkpoint in &checkpoints[..excess] {
+            self.git(&[
+                "update-ref",
+                "-d",
+                &format!("{}{}", self.prefix(), checkpoint.index),
+            ])?;
+        }
+        Ok(())
+    }
+
+    /// Remove every checkpoint of this agent.
+    pub fn clear(&self) -> Result<()> {
+        self.prune(0)
+    }
+
+    /// Diff from a checkpoint to the current worktree state.
+    pub fn diff(&self, checkpoint: &Checkpoint) -> Result<String> {
+        let tree = self.current_tree()?;
+        let output = Command::new("git")
+            .args(["diff", "--color=always", &checkpoint.commit, &tree])
+            .current_dir(&self.worktree_path)
+            .output()
+            .context("Failed to execute git diff")?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Put the worktree back to a checkpoint: HEAD moves to the commit the
+    /// checkpoint was taken on and its uncommitted changes are reapplied.
+    /// The current state is checkpointed first so the restore can be undone.
+    pub fn restore(&self, checkpoint: &Checkpoint) -> Result<()> {
+        self.snapshot(&format!("before restoring #{}", checkpoint.index))?;
+
+        let parent = self.git(&["rev-parse", &format!("{}^", checkpoint.commit)])?;
+        self.git(&["reset", "--hard", "-q", &parent])?;
+        self.git(&["clean", "-fdq"])?;
+        self.git(&["read-tree", "-u", "--reset", &checkpoint.commit])?;
+        self.git(&["reset", "-q"])?;
+        Ok(())
+    }
+
+    /// Tree object for the worktree as it is now, built in a scratch index of
+    /// its own so concurrent calls don't share one.
+    fn current_tree(&self) -> Result<String> {
+        let git_path = |name: &str| -> Result<PathBuf> {
+            let path = self.git(&["rev-parse", "--git-path", name])?;
+            Ok(self.worktree_path.join(path))
+        };
+        let index_path = git_path(&format!("grove-checkpoint-{}.index", uuid::Uuid::new_v4()))?;
+        let real_index = git_path("index")?;
+        if real_index.exists() {
+            std::fs::copy(&real_index, &index_path).context("Failed to copy index")?;
+            // Keep the index's mtime so git still re-checks files modified in
+            // the same second (racy entries) instead of trusting their stat
+            if let Ok(modified) = std::fs::metadata(&real_index).and_then(|m| m.modified()) {
+                let _ = std::fs::File::options()
+                    .write(true)
+                    .open(&index_path)
+                    .and_then(|f| f.set_modified(modified));
+            }
+        }
+
+        let run = |args: &[&str]| -> Result<String> {
+            let output = Command::new("git")
+                .args(args)
+                .env("GIT_INDEX_FILE", &index_path)
+                .current_dir(&self.worktree_path)
+                .output()
+                .with_context(|| format!("Failed to execute git {}", args[0]))?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Git {} failed: {}", args[0], stderr.trim());
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        let result = run(&["add", "-A"]).and_then(|_| run(&["write-tree"]));
+        let _ = std::fs::remove_file(&index_path);
+        result
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&self.worktree_path)
+            .output()
+            .with_context(|| format!("Failed to execute git {}", args[0]))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git {} failed: {}", args[0], stderr.trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+    }
+
+    fn setup_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q", "-b", "main"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+        git(dir.path(), &["config", "user.name", "Test"]);
+        std::fs::write(dir.path().join("tracked.txt"), "one\n").unwrap();
+        git(dir.path(), &["add", "tracked.txt"]);
+        git(dir.path(), &["commit", "-q", "-m", "init"]);
+        dir
+    }
+
+    #[test]
+    fn test_snapshot_skips_unchanged_state() {
+        let dir = setup_repo();
+        let checkpoints = Checkpoints::new(&dir.path().to_string_lossy(), "agent");
+
+        std::fs::write(dir.path().join("tracked.txt"), "two\n").unwrap();
+        let first = checkpoints.snapshot("edit").unwrap().unwrap();
+        assert_eq!(first.index, 1);
+        assert!(checkpoints.snapshot("again").unwrap().is_none());
+
+        std::fs::write(dir.path().join("new.txt"), "untracked\n").unwrap();
+        let second = checkpoints.snapshot("new file").unwrap().unwrap();
+        assert_eq!(second.index, 2);
+
+        let listed = checkpoints.list().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[1].label, "new file");
+
+        checkpoints.prune(1).unwrap();
+        assert_eq!(checkpoints.list().unwrap()[0].index, 2);
+    }
+
+    #[test]
+    fn test_concurrent_snapshots_get_distinct_indexes() {
+        let dir = setup_repo();
+        let path = dir.path().to_string_lossy().to_string();
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    std::fs::write(Path::new(&path).join(format!("file{}.txt", i)), "x\n").unwrap();
+                    Checkpoints::new(&path, "agent").snapshot("timer").unwrap()
+                })
+            })
+            .collect();
+        let mut indexes: Vec<u32> = handles
+            .into_iter()
+            .filter_map(|h| h.join().unwrap())
+            .map(|c| c.index)
+            .collect();
+        indexes.sort();
+
+        let listed: Vec<u32> = Checkpoints::new(&path, "agent")
+            .list()
+            .unwrap()
+            .iter()
+            .map(|c| c.index)
+            .collect();
+        assert_eq!(indexes, listed);
+        assert_eq!(listed, (1..=listed.len() as u32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_restore_brings_back_uncommitted_changes() {
+        let dir = setup_repo();
+        let checkpoints = Checkpoints::new(&dir.path().to_string_lossy(), "agent");
+
+        std::fs::write(dir.path().join("tracked.txt"), "good\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "keep\n").unwrap();
+        let good = checkpoints.snapshot("good").unwrap().unwrap();
+
+        std::fs::write(dir.path().join("tracked.txt"), "broken\n").unwrap();
+        std::fs::remove_file(dir.path().join("notes.txt")).unwrap();
+        std::fs::write(dir.path().join("junk.txt"), "junk\n").unwrap();
+        git(dir.path(), &["add", "-A"]);
+        git(dir.path(), &["commit", "-q", "-m", "wreck"]);
+
+        checkpoints.restore(&good).unwrap();
+
+        let read = |name: &str| std::fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("tracked.txt"), "good\n");
+        assert_eq!(read("notes.txt"), "keep\n");
+        assert!(!dir.path().join("junk.txt").exists());
+        // The wrecked state was checkpointed before restoring
+        assert_eq!(checkpoints.list().unwrap().len(), 2);
+    }
+}
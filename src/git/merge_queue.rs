@@ -0,0 +1,323 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Lines of verification output kept when a branch fails.
+const FAILURE_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LandStage {
+    Rebase,
+    Verify,
+}
+
+impl LandStage {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LandStage::Rebase => "rebase",
+            LandStage::Verify => "verification",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandFailure {
+    pub branch: String,
+    pub stage: LandStage,
+    pub detail: String,
+}
+
+/// Result of landing a queue. Branches ahead of a failure are still landed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LandReport {
+    pub landed: Vec<String>,
+    pub failure: Option<LandFailure>,
+    /// New tip of the main branch, if it moved
+    pub head: Option<String>,
+    /// Main could not be moved to `head`, so nothing actually landed
+    pub fast_forward_error: Option<String>,
+    pub push_error: Option<String>,
+}
+
+/// Lands branches onto the main branch one after another using a scratch
+/// worktree. The agents' worktrees are never touched and main only moves once
+/// the queue has been processed.
+pub struct MergeQueue {
+    repo_path: PathBuf,
+    scratch_path: PathBuf,
+}
+
+impl MergeQueue {
+    pub fn new(repo_path: &str, worktree_base: &Path) -> Self {
+        Self {
+            repo_path: PathBuf::from(repo_path),
+            scratch_path: worktree_base.join(".merge-queue"),
+        }
+    }
+
+    /// Rebase each branch onto the result of the previous one and run
+    /// `verify` after each step, stopping at the first failure. Main is then
+    /// fast-forwarded to the last good commit and optionally pushed.
+    pub fn land(
+        &self,
+        main_branch: &str,
+        branches: &[String],
+        verify: Option<&str>,
+        push: bool,
+        mut progress: impl FnMut(&str),
+    ) -> Result<LandReport> {
+        let start = self.git_output(&self.repo_path, &["rev-parse", main_branch])?;
+
+        self.remove_scratch();
+        if let Some(parent) = self.scratch_path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create worktrees directory")?;
+        }
+        let scratch = self.scratch_path.to_string_lossy().to_string();
+        self.git_output(
+            &self.repo_path,
+            &["worktree", "add", "--detach", &scratch, &start],
+        )?;
+
+        let result = self.land_in_scratch(&start, branches, verify, &mut progress);
+        self.remove_scratch();
+        let mut report = result?;
+
+        if let Some(head) = report.head.clone() {
+            progress(&format!("fast-forwarding {}", main_branch));
+            if let Err(e) = self.fast_forward(main_branch, &start, &head) {
+                report.fast_forward_error = Some(e.to_string());
+                return Ok(report);
+            }
+
+            if push {
+                progress(&format!("pushing {}", main_branch));
+                if let Err(e) = self.git_output(&self.repo_path, &["push", "origin", main_branch]) {
+                    report.push_error = Some(e.to_string());
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn land_in_scratch(
+        &self,
+        start: &str,
+        branches: &[String],
+        verify: Option<&str>,
+        progress: &mut impl FnMut(&str),
+    ) -> Result<LandReport> {
+        let mut report = LandReport::default();
+        let mut head = start.to_string();
+
+        for (i, branch) in branches.iter().enumerate() {
+            progress(&format!(
+                "[{}/{}] rebasing {}",
+                i + 1,
+                branches.len(),
+                branch
+            ));
+            self.git_output(&self.scratch_path, &["checkout", "--detach", branch])?;
+
+            let rebase = self.git(&self.scratch_path, &["rebase", &head])?;
+            if !rebase.status.success() {
+                let conflicts = self
+                    .git_output(
+                        &self.scratch_path,
+                        &["diff", "--name-only", "--diff-filter=U"],
+                    )
+                    .unwrap_or_default();
+                let _ = self.git(&self.scratch_path, &["rebase", "--abort"]);
+                report.failure = Some(LandFailure {
+                    branch: branch.clone(),
+                    stage: LandStage::Rebase,
+                    detail: if conflicts.is_empty() {
+                        String::from_utf8_lossy(&rebase.stderr).trim().to_string()
+                    } else {
+                        format!(
+                            "Conflicts in: {}",
+                            conflicts.lines().collect::<Vec<_>>().join(", ")
+                        )
+                    },
+                });
+                break;
+            }
+
+            if let Some(cmd) = verify.filter(|c| !c.trim().is_empty()) {
+                progress(&format!(
+                    "[{}/{}] verifying {}: {}",
+                    i + 1,
+                    branches.len(),
+                    branch,
+                    cmd
+                ));
+                let output = Command::new("sh")
+                    .args(["-c", cmd])
+                    .current_dir(&self.scratch_path)
+                    .output()
+                    .context("Failed to run verify command")?;
+                if !output.status.success() {
+                    report.failure = Some(LandFailure {
+                        branch: branch.clone(),
+                        stage: LandStage::Verify,
+                        detail: output_tail(&output),
+                    });
+                    break;
+                }
+            }
+
+            head = self.git_output(&self.scratch_path, &["rev-parse", "HEAD"])?;
+            report.landed.push(branch.clone());
+        }
+
+        if head != start {
+            report.head = Some(head);
+        }
+        Ok(report)
+    }
+
+    fn fast_forward(&self, main_branch: &str, old: &str, new: &str) -> Result<()> {
+        let checked_out = self
+            .git_output(&self.repo_path, &["symbolic-ref", "--short", "HEAD"])
+            .map(|b| b == main_branch)
+            .unwrap_or(false);
+
+        if checked_out {
+            self.git_output(&self.repo_path, &["merge", "--ff-only", new])?;
+        } else {
+            let main_ref = format!("refs/heads/{}", main_branch);
+            self.git_output(&self.repo_path, &["update-ref", &main_ref, new, old])?;
+        }
+        Ok(())
+    }
+
+    fn remove_scratch(&self) {
+        if self.scratch_path.exists() {
+            let scratch = self.scratch_path.to_string_lossy().to_string();
+            let _ = self.git(
+                &self.repo_path,
+                &["worktree", "remove", "--force", &scratch],
+            );
+            let _ = std::fs::remove_dir_all(&self.scratch_path);
+        }
+        let _ = self.git(&self.repo_path, &["worktree", "prune"]);
+    }
+
+    fn git(&self, dir: &Path, args: &[&str]) -> Result<std::process::Output> {
+        Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .with_context(|| format!("Failed to execute git {}", args[0]))
+    }
+
+    fn git_output(&self, dir: &Path, args: &[&str]) -> Result<String> {
+        let output = self.git(dir, args)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git {} failed: {}", args[0], stderr.trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+fn output_tail(output: &std::process::Output) -> String {
+    let combined = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = combined.lines().collect();
+    let start = lines.len().saturating_sub(FAILURE_TAIL_LINES);
+    lines[start..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(status.status.success(), "git {:?} failed", args);
+    }
+
+    fn commit_file(dir: &Path, name: &str, content: &str) {
+        std::fs::write(dir.join(name), content).unwrap();
+        git(dir, &["add", name]);
+        git(dir, &["commit", "-q", "-m", name]);
+    }
+
+    fn setup_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "test@example.com"]);
+        git(&repo, &["config", "user.name", "Test"]);
+        commit_file(&repo, "README", "base\n");
+
+        for (branch, file) in [("one", "a.txt"), ("two", "b.txt")] {
+            git(&repo, &["checkout", "-q", "-b", branch, "main"]);
+            commit_file(&repo, file, branch);
+        }
+        git(&repo, &["checkout", "-q", "main"]);
+        dir
+    }
+
+    #[test]
+    fn test_land_branches_in_order() {
+        let dir = setup_repo();
+        let repo = dir.path().join("repo");
+        let queue = MergeQueue::new(&repo.to_string_lossy(), &dir.path().join("wt"));
+
+        let branches = vec!["one".to_string(), "two".to_string()];
+        let report = queue
+            .land("main", &branches, Some("test -f README"), false, |_| {})
+            .unwrap();
+
+        assert_eq!(report.landed, branches);
+        assert!(report.failure.is_none());
+        assert!(repo.join("a.txt").exists());
+        assert!(repo.join("b.txt").exists());
+        assert!(!dir.path().join("wt/.merge-queue").exists());
+    }
+
+    #[test]
+    fn test_land_stops_at_failing_branch() {
+        let dir = setup_repo();
+        let repo = dir.path().join("repo");
+        let queue = MergeQueue::new(&repo.to_string_lossy(), &dir.path().join("wt"));
+
+        let branches = vec!["one".to_string(), "two".to_string()];
+        let report = queue
+            .land("main", &branches, Some("test ! -f b.txt"), false, |_| {})
+            .unwrap();
+
+        assert_eq!(report.landed, vec!["one".to_string()]);
+        let failure = report.failure.unwrap();
+        assert_eq!(failure.branch, "two");
+        assert_eq!(failure.stage, LandStage::Verify);
+        assert!(repo.join("a.txt").exists());
+        assert!(!repo.join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_land_reports_failed_fast_forward() {
+        let dir = setup_repo();
+        let repo = dir.path().join("repo");
+        let queue = MergeQueue::new(&repo.to_string_lossy(), &dir.path().join("wt"));
+        // An untracked file in the way makes `merge --ff-only` refuse
+        std::fs::write(repo.join("a.txt"), "local\n").unwrap();
+
+        let branches = vec!["one".to_string(), "two".to_string()];
+        let report = queue.land("main", &branches, None, false, |_| {}).unwrap();
+
+        assert_eq!(report.landed, branches);
+        assert!(report.fast_forward_error.is_some());
+        assert!(!repo.join("b.txt").exists());
+    }
+}
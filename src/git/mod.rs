@@ -1,9 +1,13 @@
+pub mod checkpoint;
+pub mod merge_queue;
 pub mod remote;
 pub mod status;
 pub mod sync;
 pub mod worktree;
 
+pub use checkpoint::{Checkpoint, Checkpoints};
+pub use merge_queue::{LandFailure, LandReport, LandStage, MergeQueue};
 pub use remote::{parse_remote_info, RemoteInfo};
-pub use status::GitSyncStatus;
+pub use status::{GitSyncStatus, SyncOutcome};
 pub use sync::GitSync;
-pub use worktree::Worktree;
+pub use worktree::{exclude_from_git, Worktree};
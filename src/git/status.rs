@@ -7,8 +7,13 @@ pub struct GitSyncStatus {
     pub ahead: u32,
     /// Commits behind remote tracking branch
     pub behind: u32,
-    /// Commits since branching from main
+    /// Commits since branching from the base branch (main unless stacked)
     pub divergence_from_main: u32,
+    /// Commits on the base branch not yet in this branch
+    pub behind_main: u32,
+    /// Branch the divergence counts are against
+    #[serde(default)]
+    pub base_branch: String,
     /// Whether the worktree is clean (no uncommitted changes)
     pub is_clean: bool,
     /// Whether we're synced with remote
@@ -18,8 +23,17 @@ pub struct GitSyncStatus {
 impl GitSyncStatus {
     pub fn format_short(&self) -> String {
         format!(
-            "↑{} ↓{} main+{}",
-            self.ahead, self.behind, self.divergence_from_main
+            "↑{} ↓{} {}+{}-{}",
+            self.ahead, self.behind, self.base_branch, self.divergence_from_main, self.behind_main
         )
     }
 }
+
+/// Result of merging or rebasing the base branch into a worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// Merge/rebase finished without conflicts
+    Clean,
+    /// Stopped on conflicts; the operation is left in progress for resolution
+    Conflicts(Vec<String>),
+}
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use git2::{BranchType, Repository};
 use std::process::Command;
 
-use super::GitSyncStatus;
+use super::{GitSyncStatus, SyncOutcome};
 
 /// Git synchronization operations.
 pub struct GitSync {
@@ -27,8 +27,8 @@ impl GitSync {
         // Get ahead/behind from remote tracking branch
         let (ahead, behind) = self.get_ahead_behind(&repo)?;
 
-        // Get divergence from main
-        let divergence_from_main =
+        // Get divergence from main (or the agent's base branch)
+        let (divergence_from_main, behind_main) =
             self.get_divergence_from_main(&repo, main_branch, &head_commit)?;
 
         // Check if working tree is clean
@@ -38,6 +38,8 @@ impl GitSync {
             ahead,
             behind,
             divergence_from_main,
+            behind_main,
+            base_branch: main_branch.to_string(),
             is_clean,
             is_synced: ahead == 0 && behind == 0,
         })
@@ -71,12 +73,13 @@ impl GitSync {
         Ok((ahead as u32, behind as u32))
     }
 
+    /// Commits ahead of / behind the base branch (local ref first, then origin).
     fn get_divergence_from_main(
         &self,
         repo: &Repository,
         main_branch: &str,
         head_commit: &git2::Commit,
-    ) -> Result<u32> {
+    ) -> Result<(u32, u32)> {
         // Try to find main branch
         let main_ref = format!("refs/heads/{}", main_branch);
         let main_branch = match repo.find_reference(&main_ref) {
@@ -86,7 +89,7 @@ impl GitSync {
                 let remote_ref = format!("refs/remotes/origin/{}", main_branch);
                 match repo.find_reference(&remote_ref) {
                     Ok(r) => r,
-                    Err(_) => return Ok(0),
+                    Err(_) => return Ok((0, 0)),
                 }
             }
         };
@@ -94,19 +97,10 @@ impl GitSync {
         let main_oid = main_branch.target().context("Failed to get main OID")?;
         let head_oid = head_commit.id();
 
-        // Find merge base
-        let merge_base = match repo.merge_base(main_oid, head_oid) {
-            Ok(mb) => mb,
-            Err(_) => return Ok(0),
-        };
-
-        // Count commits from merge base to HEAD
-        let mut revwalk = repo.revwalk()?;
-        revwalk.push(head_oid)?;
-        revwalk.hide(merge_base)?;
-
-        let count = revwalk.count();
-        Ok(count as u32)
+        match repo.graph_ahead_behind(head_oid, main_oid) {
+            Ok((ahead, behind)) => Ok((ahead as u32, behind as u32)),
+            Err(_) => Ok((0, 0)),
+        }
     }
 
     fn is_clean(&self, repo: &Repository) -> Result<bool> {
@@ -197,16 +191,140 @@ impl GitSync {
 
     /// Get diff against main branch.
     pub fn get_diff_against_main(&self, main_branch: &str) -> Result<String> {
+        self.get_diff_against(&format!("origin/{}", main_branch))
+    }
+
+    /// Get diff of HEAD against its merge base with `base_ref`.
+    pub fn get_diff_against(&self, base_ref: &str) -> Result<String> {
         let output = Command::new("git")
-            .args([
-                "diff",
-                "--color=never",
-                &format!("origin/{}...HEAD", main_branch),
-            ])
+            .args(["diff", "--color=never", &format!("{}...HEAD", base_ref)])
             .current_dir(&self.worktree_path)
             .output()
             .context("Failed to execute git diff")?;
 
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
+
+    /// Rebase the current branch onto `new_base`. When `old_base` is given only
+    /// the commits after it are replayed, which keeps stacked branches from
+    /// re-applying commits of a rewritten parent. Aborts on conflict.
+    pub fn rebase_onto(&self, new_base: &str, old_base: Option<&str>) -> Result<()> {
+        let mut args = vec!["rebase", "--autostash"];
+        if let Some(old_base) = old_base {
+            args.extend(["--onto", new_base, old_base]);
+        } else {
+            args.push(new_base);
+        }
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(&self.worktree_path)
+            .output()
+            .context("Failed to execute git rebase")?;
+
+        if !output.status.success() {
+            let _ = Command::new("git")
+                .args(["rebase", "--abort"])
+                .current_dir(&self.worktree_path)
+                .output();
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stdout.contains("CONFLICT") || stderr.contains("CONFLICT") {
+                anyhow::bail!("Rebase conflict onto {}, rebase aborted", new_base);
+            }
+            anyhow::bail!("Git rebase failed: {}", stderr.trim());
+        }
+
+        Ok(())
+    }
+
+    /// Merge (or rebase onto) `base_ref` in the worktree. On conflicts the
+    /// merge/rebase is left in progress so it can be resolved or aborted.
+    pub fn sync_with(&self, base_ref: &str, rebase: bool) -> Result<SyncOutcome> {
+        let args: Vec<&str> = if rebase {
+            vec!["rebase", "--autostash", base_ref]
+        } else {
+            vec!["merge", base_ref, "--no-edit"]
+        };
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(&self.worktree_path)
+            .output()
+            .context("Failed to execute git sync")?;
+
+        if output.status.success() {
+            return Ok(SyncOutcome::Clean);
+        }
+
+        let conflicts = self.conflicted_files()?;
+        if conflicts.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git {} failed: {}", args[0], stderr.trim());
+        }
+
+        Ok(SyncOutcome::Conflicts(conflicts))
+    }
+
+    /// Files changed since the merge base with `base_ref`, including
+    /// uncommitted changes to tracked files.
+    pub fn changed_files(&self, base_ref: &str) -> Result<Vec<String>> {
+        let merge_base = Command::new("git")
+            .args(["merge-base", base_ref, "HEAD"])
+            .current_dir(&self.worktree_path)
+            .output()
+            .context("Failed to execute git merge-base")?;
+
+        if !merge_base.status.success() {
+            let stderr = String::from_utf8_lossy(&merge_base.stderr);
+            anyhow::bail!("Git merge-base failed: {}", stderr.trim());
+        }
+        let merge_base = String::from_utf8_lossy(&merge_base.stdout)
+            .trim()
+            .to_string();
+
+        let output = Command::new("git")
+            .args(["diff", "--name-only", &merge_base])
+            .current_dir(&self.worktree_path)
+            .output()
+            .context("Failed to execute git diff")?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect())
+    }
+
+    /// Files with unresolved conflicts.
+    pub fn conflicted_files(&self) -> Result<Vec<String>> {
+        let output = Command::new("git")
+            .args(["diff", "--name-only", "--diff-filter=U"])
+            .current_dir(&self.worktree_path)
+            .output()
+            .context("Failed to list conflicted files")?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect())
+    }
+
+    /// Abort an in-progress merge or rebase.
+    pub fn abort_sync(&self, rebase: bool) -> Result<()> {
+        let command = if rebase { "rebase" } else { "merge" };
+        let output = Command::new("git")
+            .args([command, "--abort"])
+            .current_dir(&self.worktree_path)
+            .output()
+            .context("Failed to abort")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git {} --abort failed: {}", command, stderr.trim());
+        }
+
+        Ok(())
+    }
 }
@@ -16,10 +16,17 @@ impl Worktree {
     }
 
     pub fn create(&self, branch: &str) -> Result<String> {
+        self.create_from(branch, None)
+    }
+
+    /// Create a worktree for `branch`. A missing branch is created from
+    /// `base` (local or `origin/`), or from HEAD when no base is given.
+    pub fn create_from(&self, branch: &str, base: Option<&str>) -> Result<String> {
         tracing::debug!(
-            "Worktree::create - repo_path: {:?}, branch: {:?}, worktree_base: {:?}",
+            "Worktree::create - repo_path: {:?}, branch: {:?}, base: {:?}, worktree_base: {:?}",
             self.repo_path,
             branch,
+            base,
             self.worktree_base
         );
 
@@ -58,8 +65,15 @@ impl Worktree {
                     "Worktree::create - reference not found: {:?}, creating new branch",
                     e
                 );
-                let head = repo.head().context("Failed to get HEAD")?;
-                let commit = head.peel_to_commit().context("Failed to get HEAD commit")?;
+                let commit = match base {
+                    Some(base) => Self::resolve_branch(&repo, base)?
+                        .peel_to_commit()
+                        .with_context(|| format!("Failed to get commit for '{}'", base))?,
+                    None => {
+                        let head = repo.head().context("Failed to get HEAD")?;
+                        head.peel_to_commit().context("Failed to get HEAD commit")?
+                    }
+                };
                 repo.branch(branch, &commit, false)
                     .context("Failed to create branch")?
                     .into_reference()
@@ -91,6 +105,47 @@ impl Worktree {
         Ok(worktree_path_str)
     }
 
+    fn resolve_branch<'r>(repo: &'r Repository, branch: &str) -> Result<git2::Reference<'r>> {
+        repo.find_reference(&format!("refs/heads/{}", branch))
+            .or_else(|_| repo.find_reference(&format!("refs/remotes/origin/{}", branch)))
+            .with_context(|| format!("Base branch '{}' not found", branch))
+    }
+
+    /// Commit id at the tip of `branch` (local or `origin/`).
+    pub fn branch_tip(&self, branch: &str) -> Result<String> {
+        let repo = Repository::open(&self.repo_path).context("Failed to open repository")?;
+        let commit = Self::resolve_branch(&repo, branch)?
+            .peel_to_commit()
+            .with_context(|| format!("Failed to get commit for '{}'", branch))?;
+        Ok(commit.id().to_string())
+    }
+
+    /// Merge the tips of two branches in memory and return the paths that
+    /// would conflict. Neither branch nor any worktree is touched.
+    pub fn trial_merge_conflicts(&self, branch_a: &str, branch_b: &str) -> Result<Vec<String>> {
+        let repo = Repository::open(&self.repo_path).context("Failed to open repository")?;
+        let commit_a = Self::resolve_branch(&repo, branch_a)?.peel_to_commit()?;
+        let commit_b = Self::resolve_branch(&repo, branch_b)?.peel_to_commit()?;
+
+        let index = repo
+            .merge_commits(&commit_a, &commit_b, None)
+            .context("Failed to run trial merge")?;
+
+        if !index.has_conflicts() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths: Vec<String> = index
+            .conflicts()?
+            .flatten()
+            .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+            .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
     pub fn remove(&self, worktree_path: &str) -> Result<()> {
         let repo = Repository::open(&self.repo_path).context("Failed to open repository")?;
 
@@ -133,6 +188,82 @@ impl Worktree {
         self.worktree_base.join(branch.replace('/', "-"))
     }
 
+    /// Make sure `branch` exists locally, creating it from `origin/<branch>` when
+    /// only the remote-tracking branch is present.
+    pub fn ensure_local_branch(&self, branch: &str) -> Result<()> {
+        let repo = Repository::open(&self.repo_path).context("Failed to open repository")?;
+
+        if repo.find_branch(branch, git2::BranchType::Local).is_ok() {
+            return Ok(());
+        }
+
+        let remote_name = format!("origin/{}", branch);
+        let remote = repo
+            .find_branch(&remote_name, git2::BranchType::Remote)
+            .with_context(|| format!("Branch '{}' not found locally or on origin", branch))?;
+        let commit = remote
+            .get()
+            .peel_to_commit()
+            .context("Failed to resolve remote branch")?;
+
+        let mut local = repo
+            .branch(branch, &commit, false)
+            .context("Failed to create local branch")?;
+        local
+            .set_upstream(Some(&remote_name))
+            .context("Failed to set upstream")?;
+
+        Ok(())
+    }
+
+    /// Fetch a pull/merge request head from origin into a local branch.
+    pub fn fetch_pull_request(&self, remote_ref: &str, local_branch: &str) -> Result<()> {
+        let output = std::process::Command::new("git")
+            .current_dir(&self.repo_path)
+            .args([
+                "fetch",
+                "origin",
+                &format!("+{}:refs/heads/{}", remote_ref, local_branch),
+            ])
+            .output()
+            .context("Failed to run git fetch")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to fetch {}: {}", remote_ref, stderr.trim());
+        }
+
+        Ok(())
+    }
+
+    /// Check that `path` is a worktree of this repository and return its branch.
+    pub fn branch_for_existing(&self, path: &str) -> Result<String> {
+        let repo = Repository::open(&self.repo_path).context("Failed to open repository")?;
+        let wt_repo =
+            Repository::open(path).with_context(|| format!("'{}' is not a git worktree", path))?;
+
+        let target = Path::new(path).canonicalize().ok();
+        let is_known_worktree = repo
+            .worktrees()
+            .context("Failed to list worktrees")?
+            .iter()
+            .flatten()
+            .filter_map(|name| repo.find_worktree(name).ok())
+            .any(|wt| wt.path().canonicalize().ok() == target);
+        if !is_known_worktree {
+            anyhow::bail!("'{}' is not a worktree of this repository", path);
+        }
+
+        let head = wt_repo.head().context("Failed to get HEAD")?;
+        if !head.is_branch() {
+            anyhow::bail!("'{}' has a detached HEAD", path);
+        }
+
+        head.shorthand()
+            .map(String::from)
+            .context("Invalid branch name")
+    }
+
     pub fn create_symlinks(&self, worktree_path: &str, files: &[String]) -> Result<()> {
         let worktree = Path::new(worktree_path);
 
@@ -203,3 +334,35 @@ impl Worktree {
         Ok(())
     }
 }
+
+/// Keep `file` (relative to the worktree) out of `git status` for every
+/// worktree of the repository.
+pub fn exclude_from_git(worktree_path: &str, file: &str) -> Result<()> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--git-common-dir"])
+        .current_dir(worktree_path)
+        .output()
+        .context("Failed to find git directory")?;
+    if !output.status.success() {
+        return Ok(());
+    }
+    let common_dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let exclude = Path::new(worktree_path)
+        .join(common_dir)
+        .join("info")
+        .join("exclude");
+    let current = std::fs::read_to_string(&exclude).unwrap_or_default();
+    if current.lines().any(|line| line.trim() == file) {
+        return Ok(());
+    }
+    if let Some(dir) = exclude.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut updated = current;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(file);
+    updated.push('\n');
+    std::fs::write(&exclude, updated).context("Failed to update git exclude file")
+}
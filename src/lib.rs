@@ -9,8 +9,10 @@ pub mod core;
 pub mod devserver;
 pub mod gemini;
 pub mod git;
+pub mod mcp;
 pub mod opencode;
 pub mod storage;
 pub mod tmux;
 pub mod ui;
+pub mod verify;
 pub mod version;
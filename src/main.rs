@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,8 +20,9 @@ use tokio::sync::{mpsc, watch};
 use uuid::Uuid;
 
 use grove::agent::{
-    detect_checklist_progress, detect_mr_url, detect_status_for_agent, Agent, AgentManager,
-    AgentStatus, ForegroundProcess, ProjectMgmtTaskStatus, StatusDetection,
+    detect_checklist, detect_mr_url, detect_overlaps, detect_status_for_agent, detect_token_usage,
+    Agent, AgentManager, AgentStatus, ForegroundProcess, OverlapCandidate, ProjectMgmtTaskStatus,
+    StatusDetection,
 };
 use grove::app::{
     Action, AppState, Config, InputMode, PreviewTab, ProjectMgmtProvider, StatusOption,
@@ -43,10 +44,11 @@ use grove::core::projects::linear::{
 use grove::core::projects::notion::{parse_notion_page_id, NotionTaskStatus, OptionalNotionClient};
 use grove::core::projects::{fetch_status_options, ProjectClients};
 use grove::devserver::DevServerManager;
-use grove::git::{GitSync, Worktree};
+use grove::git::{GitSync, MergeQueue, Worktree};
 use grove::storage::{save_session, SessionStorage};
 use grove::tmux::is_tmux_available;
-use grove::ui::{AppWidget, DevServerRenderInfo};
+use grove::ui::{AppWidget, DevServerRenderInfo, ServiceRenderInfo};
+use grove::verify::{VerifyRun, VerifyStatus};
 
 fn matches_keybind(key: crossterm::event::KeyEvent, keybind: &grove::app::config::Keybind) -> bool {
     let has_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
@@ -128,6 +130,28 @@ async fn main() -> Result<()> {
             .init();
     }
 
+    // `grove detect <fixture file or directory>` replays detector fixtures
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("detect") {
+        let Some(path) = args.get(2) else {
+            anyhow::bail!("Usage: grove detect <fixture file or directory>");
+        };
+        return run_detect(std::path::Path::new(path));
+    }
+
+    // `grove mcp --socket <path> --agent <id>` is the MCP server Grove gives its agents
+    if args.get(1).map(String::as_str) == Some("mcp") {
+        let flag = |name: &str| {
+            args.iter()
+                .position(|a| a == name)
+                .and_then(|i| args.get(i + 1))
+        };
+        let (Some(socket), Some(agent_id)) = (flag("--socket"), flag("--agent")) else {
+            anyhow::bail!("Usage: grove mcp --socket <path> --agent <agent id>");
+        };
+        return grove::mcp::server::run(std::path::Path::new(socket), agent_id.parse()?);
+    }
+
     tracing::info!("=== Grove starting ===");
 
     // Check prerequisites
@@ -188,7 +212,16 @@ async fn main() -> Result<()> {
         state.tutorial = Some(grove::app::TutorialState::default());
     }
 
-    let agent_manager = Arc::new(AgentManager::new(&repo_path, state.worktree_base.clone()));
+    let mcp_socket = config
+        .global
+        .mcp_server
+        .then(|| grove::mcp::socket_path(&repo_path).ok())
+        .flatten();
+    let agent_manager = Arc::new(
+        AgentManager::new(&repo_path, state.worktree_base.clone())
+            .with_claude_hooks(config.global.claude_hooks)
+            .with_mcp_socket(mcp_socket.clone()),
+    );
 
     let mut agents_to_continue: Vec<Agent> = Vec::new();
 
@@ -197,14 +230,25 @@ async fn main() -> Result<()> {
         for mut agent in session.agents {
             agent.migrate_legacy();
             agent.continue_session = true;
+            // Agents whose session is gone are resumed below with a fresh budget
+            if !grove::tmux::TmuxSession::new(&agent.tmux_session).exists() {
+                agent.session_started_at = chrono::Utc::now();
+            }
             agents_to_continue.push(agent.clone());
             state.add_agent(agent);
         }
         state.selected_index = session
             .selected_index
             .min(state.agent_order.len().saturating_sub(1));
+        state.orchestrations = session.orchestrations;
+        state.autopilot = session.autopilot;
         state.log_info(format!("Loaded {} agents from session", count));
     }
+    state.autopilot.enabled = state.settings.repo_config.autopilot.enabled;
+    state.autopilot.forget_unstarted();
+    for orchestration in &mut state.orchestrations {
+        orchestration.forget_unstarted();
+    }
 
     let gitlab_base_url = &state.settings.repo_config.git.gitlab.base_url;
     let gitlab_project_id = state.settings.repo_config.git.gitlab.project_id;
@@ -298,7 +342,8 @@ async fn main() -> Result<()> {
 
     // Auto-continue agents that have continue_session enabled
     if !agents_to_continue.is_empty() {
-        let ai_agent = config.global.ai_agent.clone();
+        let default_ai_agent = config.global.ai_agent.clone();
+        let agent_manager = Arc::clone(&agent_manager);
         let worktree_symlinks = state
             .settings
             .repo_config
@@ -311,10 +356,9 @@ async fn main() -> Result<()> {
         tokio::spawn(async move {
             for agent in agents_to_continue {
                 let worktree_path = agent.worktree_path.clone();
-                let tmux_session = agent.tmux_session.clone();
                 let branch = agent.branch.clone();
                 let name = agent.name.clone();
-                let ai_session_id = agent.ai_session_id.clone();
+                let ai_agent = agent.ai_agent_or(&default_ai_agent);
 
                 let worktree = grove::git::Worktree::new(&repo_path, worktree_base.clone());
                 if !std::path::Path::new(&worktree_path).exists() {
@@ -330,95 +374,9 @@ async fn main() -> Result<()> {
                     }
                 }
 
-                let session = grove::tmux::TmuxSession::new(&tmux_session);
-                if !session.exists() {
-                    let command = match &ai_agent {
-                        grove::app::config::AiAgent::Opencode => {
-                            let session_id = ai_session_id
-                                .as_deref()
-                                .and_then(|cached| {
-                                    if cached.is_empty() {
-                                        None
-                                    } else {
-                                        Some(cached.to_string())
-                                    }
-                                })
-                                .or_else(|| {
-                                    grove::opencode::find_session_by_directory(&worktree_path)
-                                        .ok()
-                                        .flatten()
-                                });
-                            grove::opencode::build_command_with_session(
-                                ai_agent.command(),
-                                session_id.as_deref(),
-                            )
-                        }
-                        grove::app::config::AiAgent::ClaudeCode => {
-                            let session_id = ai_session_id
-                                .as_deref()
-                                .and_then(|cached| {
-                                    if cached.is_empty() {
-                                        None
-                                    } else {
-                                        Some(cached.to_string())
-                                    }
-                                })
-                                .or_else(|| {
-                                    grove::claude_code::find_session_by_directory(&worktree_path)
-                                        .ok()
-                                        .flatten()
-                                });
-                            grove::claude_code::build_resume_command(
-                                ai_agent.command(),
-                                session_id.as_deref(),
-                            )
-                        }
-                        grove::app::config::AiAgent::Codex => {
-                            let session_id = ai_session_id
-                                .as_deref()
-                                .and_then(|cached| {
-                                    if cached.is_empty() {
-                                        None
-                                    } else {
-                                        Some(cached.to_string())
-                                    }
-                                })
-                                .or_else(|| {
-                                    grove::codex::find_session_by_directory(&worktree_path)
-                                        .ok()
-                                        .flatten()
-                                });
-                            grove::codex::build_resume_command(
-                                ai_agent.command(),
-                                session_id.as_deref(),
-                            )
-                        }
-                        grove::app::config::AiAgent::Gemini => {
-                            let session_id = ai_session_id
-                                .as_deref()
-                                .and_then(|cached| {
-                                    if cached.is_empty() {
-                                        None
-                                    } else {
-                                        Some(cached.to_string())
-                                    }
-                                })
-                                .or_else(|| {
-                                    grove::gemini::find_session_by_directory(&worktree_path)
-                                        .ok()
-                                        .flatten()
-                                });
-                            grove::gemini::build_resume_command(
-                                ai_agent.command(),
-                                session_id.as_deref(),
-                            )
-                        }
-                    };
-
-                    if let Err(e) = session.create(&worktree_path, &command) {
-                        eprintln!("Failed to create tmux session for '{}': {}", name, e);
-                        continue;
-                    }
+                if let Err(e) = agent_manager.continue_agent(&agent, &ai_agent) {
+                    eprintln!("Failed to create tmux session for '{}': {}", name, e);
+                    continue;
                 }
                 let _ = tx.send(Action::ShowToast {
                     message: format!("Auto-continued '{}'", name),
@@ -434,7 +392,7 @@ async fn main() -> Result<()> {
     )));
 
     // Create watch channel for agent list updates (polling task needs current agents)
-    let initial_agents: HashSet<Uuid> = state.agents.keys().cloned().collect();
+    let initial_agents = state.agent_ai_agents();
     let (agent_watch_tx, agent_watch_rx) = watch::channel(initial_agents);
 
     // Create watch channel for agent branches (GitLab polling needs branch names)
@@ -445,6 +403,9 @@ async fn main() -> Result<()> {
         .collect();
     let (branch_watch_tx, branch_watch_rx) = watch::channel(initial_branches);
 
+    // Create watch channel for agent tmux sessions (resource polling walks their panes)
+    let (session_watch_tx, session_watch_rx) = watch::channel(state.agent_sessions());
+
     // Create watch channel for selected agent (preview polling needs current selection)
     let initial_selected: Option<Uuid> = state.selected_agent_id();
     tracing::info!(
@@ -455,11 +416,55 @@ async fn main() -> Result<()> {
     );
     let (selected_watch_tx, selected_watch_rx) = watch::channel(initial_selected);
 
+    // Create watch channel for stall detection settings (agent polling applies edits)
+    let (stall_watch_tx, stall_watch_rx) = watch::channel(state.settings.repo_config.stall.clone());
+
+    // Grove's MCP server, reached by each agent's `grove mcp` process
+    if let Some(socket) = mcp_socket {
+        let mcp_tx = action_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = grove::mcp::serve(socket, mcp_tx.clone()).await {
+                let _ = mcp_tx.send(Action::LogError {
+                    message: format!("MCP server stopped: {}", e),
+                });
+            }
+        });
+    }
+
+    // Reverse proxy for <agent>.localhost, with `active` following the selection
+    let dev_server_config = &state.settings.repo_config.dev_server;
+    if dev_server_config.proxy_enabled {
+        let proxy_port = dev_server_config.proxy_port;
+        let routes = {
+            let mut manager = devserver_manager.lock().await;
+            manager.set_proxy_port(Some(proxy_port));
+            manager.set_active(initial_selected);
+            manager.proxy_routes()
+        };
+        let proxy_tx = action_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = grove::devserver::proxy::serve(proxy_port, routes).await {
+                let _ = proxy_tx.send(Action::LogError {
+                    message: format!("Dev server proxy stopped: {}", e),
+                });
+            }
+        });
+
+        let mut selected_rx = selected_watch_rx.clone();
+        let manager = Arc::clone(&devserver_manager);
+        tokio::spawn(async move {
+            while selected_rx.changed().await.is_ok() {
+                let selected = *selected_rx.borrow_and_update();
+                manager.lock().await.set_active(selected);
+            }
+        });
+    }
+
     // Start background polling task for agent status
     let agent_poll_tx = action_tx.clone();
     let selected_rx_clone = selected_watch_rx.clone();
-    let ai_agent = config.global.ai_agent.clone();
     let debug_mode = config.global.debug_mode;
+    let claude_hooks = config.global.claude_hooks;
     tokio::spawn(async move {
         use futures::future::FutureExt;
         use std::panic::AssertUnwindSafe;
@@ -469,8 +474,9 @@ async fn main() -> Result<()> {
                 agent_watch_rx,
                 selected_rx_clone,
                 agent_poll_tx,
-                ai_agent,
                 debug_mode,
+                stall_watch_rx,
+                claude_hooks,
             )
             .await
         })
@@ -499,7 +505,7 @@ async fn main() -> Result<()> {
     // Start background polling task for global system metrics (CPU/memory)
     let system_poll_tx = action_tx.clone();
     tokio::spawn(async move {
-        poll_system_metrics(system_poll_tx).await;
+        poll_system_metrics(system_poll_tx, session_watch_rx).await;
     });
 
     // Start GitLab polling task (if configured)
@@ -800,6 +806,18 @@ async fn main() -> Result<()> {
     let mut last_gitdiff_refresh = std::time::Instant::now()
         .checked_sub(gitdiff_refresh_interval)
         .unwrap_or_else(std::time::Instant::now);
+    let overlap_refresh_interval = Duration::from_secs(config.performance.overlap_refresh_secs);
+    let mut last_overlap_refresh = std::time::Instant::now();
+    let checkpoint_interval =
+        Duration::from_secs(state.settings.repo_config.checkpoints.interval_secs.max(30));
+    let mut last_checkpoint = std::time::Instant::now();
+    let autopilot_interval =
+        Duration::from_secs(state.settings.repo_config.autopilot.poll_secs.max(10));
+    let mut last_autopilot_poll = std::time::Instant::now()
+        .checked_sub(autopilot_interval)
+        .unwrap_or_else(std::time::Instant::now);
+    let orchestrator_interval = Duration::from_secs(5);
+    let mut last_orchestrator_tick = std::time::Instant::now();
     let mut pending_attach: Option<Uuid> = None;
     let mut pending_devserver_attach: Option<Uuid> = None;
     let mut pending_editor: Option<Uuid> = None;
@@ -807,10 +825,10 @@ async fn main() -> Result<()> {
     loop {
         // Handle pending dev server attach (outside of async context)
         if let Some(id) = pending_devserver_attach.take() {
-            let session_name = devserver_manager
-                .try_lock()
-                .ok()
-                .and_then(|m| m.get_tmux_session(id));
+            let session_name = devserver_manager.try_lock().ok().and_then(|m| {
+                m.service_at(id, state.devserver_service)
+                    .and_then(|s| s.tmux_session().map(String::from))
+            });
 
             if let Some(session_name) = session_name {
                 state.log_info(format!(
@@ -820,7 +838,14 @@ async fn main() -> Result<()> {
 
                 // Save session before attaching
                 let agents: Vec<Agent> = state.agents.values().cloned().collect();
-                let _ = save_session(&storage, &state.repo_path, &agents, state.selected_index);
+                let _ = save_session(
+                    &storage,
+                    &state.repo_path,
+                    &agents,
+                    state.selected_index,
+                    &state.orchestrations,
+                    &state.autopilot,
+                );
 
                 // Leave TUI mode
                 disable_raw_mode()?;
@@ -868,7 +893,14 @@ async fn main() -> Result<()> {
 
                 // Save session before attaching
                 let agents: Vec<Agent> = state.agents.values().cloned().collect();
-                let _ = save_session(&storage, &state.repo_path, &agents, state.selected_index);
+                let _ = save_session(
+                    &storage,
+                    &state.repo_path,
+                    &agents,
+                    state.selected_index,
+                    &state.orchestrations,
+                    &state.autopilot,
+                );
 
                 // Leave TUI mode
                 disable_raw_mode()?;
@@ -880,7 +912,8 @@ async fn main() -> Result<()> {
                 )?;
 
                 // Attach to tmux (blocks until detach)
-                let ai_agent = state.config.global.ai_agent.clone();
+                let ai_agent = state.ai_agent_for(id);
+                let restarted = !agent_manager.is_session_alive(&agent);
                 let attach_result = agent_manager.attach_to_agent(&agent, &ai_agent);
 
                 // Restore TUI mode
@@ -900,6 +933,14 @@ async fn main() -> Result<()> {
 
                 state.log_info("Returned from tmux session");
 
+                // A session started by this attach gets a fresh budget
+                if restarted {
+                    if let Some(agent) = state.agents.get_mut(&id) {
+                        agent.session_started_at = chrono::Utc::now();
+                    }
+                    state.budget_exceeded.remove(&id);
+                }
+
                 if let Err(e) = attach_result {
                     state.log_error(format!("Attach error: {}", e));
                 }
@@ -962,10 +1003,24 @@ async fn main() -> Result<()> {
         terminal.draw(|f| {
             let devserver_info = if let Some(agent) = state.selected_agent() {
                 if let Ok(manager) = devserver_manager.try_lock() {
-                    manager.get(agent.id).map(|server| DevServerRenderInfo {
+                    let services = manager.services(agent.id);
+                    let selected = state
+                        .devserver_service
+                        .min(services.len().saturating_sub(1));
+                    services.get(selected).map(|server| DevServerRenderInfo {
                         status: server.status().clone(),
                         logs: server.logs().to_vec(),
+                        first_line: server.log_mark().saturating_sub(server.logs().len()),
                         agent_name: server.agent_name().to_string(),
+                        url: manager.url_for(agent.id, Some(server.service())),
+                        services: services
+                            .iter()
+                            .map(|s| ServiceRenderInfo {
+                                name: s.service().to_string(),
+                                status: s.status().clone(),
+                            })
+                            .collect(),
+                        selected_service: selected,
                     })
                 } else {
                     None
@@ -1021,11 +1076,11 @@ async fn main() -> Result<()> {
         {
             if let Some(agent) = state.selected_agent() {
                 let worktree_path = agent.worktree_path.clone();
-                let main_branch = state.settings.repo_config.git.main_branch.clone();
+                let base_ref = agent.diff_base_ref(&state.settings.repo_config.git.main_branch);
                 let gitdiff_tx = action_tx.clone();
 
                 tokio::spawn(async move {
-                    let diff = get_combined_git_diff(&worktree_path, &main_branch);
+                    let diff = get_combined_git_diff(&worktree_path, &base_ref);
                     let _ = gitdiff_tx.send(Action::UpdateGitDiffContent(Some(diff)));
                 });
 
@@ -1033,6 +1088,32 @@ async fn main() -> Result<()> {
             }
         }
 
+        // Periodically recompute which agents touch the same files
+        if last_overlap_refresh.elapsed() >= overlap_refresh_interval {
+            action_tx.send(Action::RefreshOverlaps)?;
+            last_overlap_refresh = std::time::Instant::now();
+        }
+
+        // Periodically checkpoint agent worktrees
+        if last_checkpoint.elapsed() >= checkpoint_interval {
+            action_tx.send(Action::CheckpointAll)?;
+            last_checkpoint = std::time::Instant::now();
+        }
+
+        // Pick up queued tasks
+        if state.autopilot.enabled && last_autopilot_poll.elapsed() >= autopilot_interval {
+            action_tx.send(Action::AutopilotPoll)?;
+            last_autopilot_poll = std::time::Instant::now();
+        }
+
+        // Dispatch and track orchestrated subtasks
+        if !state.orchestrations.is_empty()
+            && last_orchestrator_tick.elapsed() >= orchestrator_interval
+        {
+            action_tx.send(Action::AdvanceOrchestrations)?;
+            last_orchestrator_tick = std::time::Instant::now();
+        }
+
         // Process any pending actions from background tasks
         while let Ok(action) = action_rx.try_recv() {
             match process_action(
@@ -1051,7 +1132,9 @@ async fn main() -> Result<()> {
                 &action_tx,
                 &agent_watch_tx,
                 &branch_watch_tx,
+                &session_watch_tx,
                 &selected_watch_tx,
+                &stall_watch_tx,
                 &asana_watch_tx,
                 &notion_watch_tx,
                 &clickup_watch_tx,
@@ -1089,7 +1172,14 @@ async fn main() -> Result<()> {
 
     // Save session on exit
     let agents: Vec<Agent> = state.agents.values().cloned().collect();
-    save_session(&storage, &state.repo_path, &agents, state.selected_index)?;
+    save_session(
+        &storage,
+        &state.repo_path,
+        &agents,
+        state.selected_index,
+        &state.orchestrations,
+        &state.autopilot,
+    )?;
 
     Ok(())
 }
@@ -1131,6 +1221,16 @@ fn handle_key_event(key: crossterm::event::KeyEvent, state: &AppState) -> Option
         };
     }
 
+    // Handle merge conflict modal
+    if state.merge_conflict.is_some() {
+        return match key.code {
+            KeyCode::Char('a') => Some(Action::SendConflictsToAgent),
+            KeyCode::Char('x') => Some(Action::AbortMainSync),
+            KeyCode::Esc => Some(Action::DismissMergeConflict),
+            _ => None,
+        };
+    }
+
     // Handle input mode
     if state.is_input_mode() {
         return handle_input_mode_key(key.code, state);
@@ -1141,12 +1241,99 @@ fn handle_key_event(key: crossterm::event::KeyEvent, state: &AppState) -> Option
         return Some(Action::ToggleHelp);
     }
 
+    // Handle orchestrator overlay
+    if state.show_orchestrator {
+        if matches_keybind(key, &state.config.keybinds.orchestrator) {
+            return Some(Action::ToggleOrchestrator);
+        }
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(Action::OrchestratorSelectNext),
+            KeyCode::Char('k') | KeyCode::Up => Some(Action::OrchestratorSelectPrev),
+            KeyCode::Char('r') => Some(Action::RetryOrchestration),
+            KeyCode::Char('x') => Some(Action::CancelOrchestration),
+            KeyCode::Esc => Some(Action::ToggleOrchestrator),
+            _ => None,
+        };
+    }
+
+    // Handle session picker
+    if let Some(picker) = &state.session_picker {
+        if matches_keybind(key, &state.config.keybinds.pick_session) {
+            return Some(Action::CloseSessionPicker);
+        }
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(Action::SessionPickerSelectNext),
+            KeyCode::Char('k') | KeyCode::Up => Some(Action::SessionPickerSelectPrev),
+            KeyCode::Enter if !picker.loading => Some(Action::ResumeSession {
+                id: picker.agent_id,
+                session_id: picker.selected_session().map(|s| s.id.clone()),
+            }),
+            KeyCode::Esc => Some(Action::CloseSessionPicker),
+            _ => None,
+        };
+    }
+
+    // Handle checkpoint timeline
+    if let Some(view) = &state.checkpoint_view {
+        if view.confirm_restore {
+            return match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    Some(Action::RestoreCheckpoint { confirmed: true })
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    Some(Action::CancelRestoreCheckpoint)
+                }
+                _ => None,
+            };
+        }
+        let kb = &state.config.keybinds;
+        if matches_keybind(key, &kb.checkpoints) {
+            return Some(Action::CloseCheckpoints);
+        }
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(Action::CheckpointSelectNext),
+            KeyCode::Char('k') | KeyCode::Up => Some(Action::CheckpointSelectPrev),
+            KeyCode::Char('d') => Some(Action::CheckpointShowDiff),
+            KeyCode::Char('r') => Some(Action::RestoreCheckpoint { confirmed: false }),
+            KeyCode::Esc => Some(Action::CloseCheckpoints),
+            _ => None,
+        };
+    }
+
+    // Handle dev server log selection
+    if state.devserver_selection.is_some() {
+        return match key.code {
+            KeyCode::Char('k') | KeyCode::Up => Some(Action::MoveLogSelection { delta: -1 }),
+            KeyCode::Char('j') | KeyCode::Down => Some(Action::MoveLogSelection { delta: 1 }),
+            KeyCode::PageUp => Some(Action::MoveLogSelection { delta: -10 }),
+            KeyCode::PageDown => Some(Action::MoveLogSelection { delta: 10 }),
+            KeyCode::Enter => Some(Action::SendLogSelection),
+            KeyCode::Esc => Some(Action::CancelLogSelection),
+            _ => None,
+        };
+    }
+
+    // Handle overlap matrix overlay
+    if state.show_overlap_matrix {
+        let kb = &state.config.keybinds;
+        if matches_keybind(key, &kb.toggle_overlaps) || key.code == KeyCode::Esc {
+            return Some(Action::ToggleOverlapMatrix);
+        }
+        if key.code == KeyCode::Char('r') {
+            return Some(Action::RefreshOverlaps);
+        }
+        return None;
+    }
+
     // Handle status debug overlay
     if state.show_status_debug {
         let kb = &state.config.keybinds;
         if matches_keybind(key, &kb.debug_status) || key.code == KeyCode::Esc {
             return Some(Action::ToggleStatusDebug);
         }
+        if key.code == KeyCode::Char('c') {
+            return Some(Action::EnterInputMode(InputMode::CaptureFixture));
+        }
     }
 
     // Handle PM status debug overlay
@@ -1390,11 +1577,26 @@ fn handle_key_event(key: crossterm::event::KeyEvent, state: &AppState) -> Option
         return Some(Action::EnterInputMode(InputMode::SetNote));
     }
 
+    // Budget
+    if matches_keybind(key, &kb.set_budget) && state.selected_agent_id().is_some() {
+        return Some(Action::EnterInputMode(InputMode::SetBudget));
+    }
+
     // New agent
     if matches_keybind(key, &kb.new_agent) {
         return Some(Action::EnterInputMode(InputMode::NewAgent));
     }
 
+    // New agent on a chosen base branch
+    if matches_keybind(key, &kb.new_stacked_agent) {
+        return Some(Action::EnterInputMode(InputMode::SelectBaseBranch));
+    }
+
+    // Import existing branch/worktree/PR as agent
+    if matches_keybind(key, &kb.import_agent) {
+        return Some(Action::EnterInputMode(InputMode::ImportAgent));
+    }
+
     // Delete agent
     if matches_keybind(key, &kb.delete_agent) {
         let has_task = state
@@ -1411,7 +1613,7 @@ fn handle_key_event(key: crossterm::event::KeyEvent, state: &AppState) -> Option
     // Attach to agent
     if matches_keybind(key, &kb.attach) {
         return match state.preview_tab {
-            PreviewTab::Preview => state
+            PreviewTab::Preview | PreviewTab::Activity | PreviewTab::Plan => state
                 .selected_agent_id()
                 .map(|id| Action::AttachToAgent { id }),
             PreviewTab::DevServer => state
@@ -1433,6 +1635,57 @@ fn handle_key_event(key: crossterm::event::KeyEvent, state: &AppState) -> Option
         return Some(Action::EnterInputMode(InputMode::ConfirmMerge));
     }
 
+    // Restack dependent agents
+    if matches_keybind(key, &kb.restack) {
+        return state
+            .selected_agent_id()
+            .map(|id| Action::RestackAgent { id });
+    }
+
+    // Merge queue
+    if matches_keybind(key, &kb.queue_agent) {
+        return state
+            .selected_agent_id()
+            .map(|id| Action::ToggleMergeQueue { id });
+    }
+    // Checkpoint timeline
+    if matches_keybind(key, &kb.checkpoints) {
+        return state
+            .selected_agent_id()
+            .map(|id| Action::OpenCheckpoints { id });
+    }
+    if matches_keybind(key, &kb.pick_session) {
+        return state
+            .selected_agent_id()
+            .map(|id| Action::OpenSessionPicker { id });
+    }
+    if matches_keybind(key, &kb.orchestrator) {
+        return Some(Action::ToggleOrchestrator);
+    }
+    if matches_keybind(key, &kb.toggle_autopilot) {
+        return Some(Action::ToggleAutopilot);
+    }
+
+    // Verify
+    if matches_keybind(key, &kb.run_verify) {
+        return state.selected_agent_id().map(|id| Action::RunVerify { id });
+    }
+    if matches_keybind(key, &kb.send_failures) {
+        return state
+            .selected_agent_id()
+            .map(|id| Action::SendVerifyFailures { id });
+    }
+
+    if matches_keybind(key, &kb.land_queue) {
+        if state.merge_queue.is_empty() {
+            return Some(Action::ShowToast {
+                message: "Merge queue is empty".to_string(),
+                level: ToastLevel::Info,
+            });
+        }
+        return Some(Action::EnterInputMode(InputMode::ConfirmLandQueue));
+    }
+
     // Push
     if matches_keybind(key, &kb.push) && state.selected_agent_id().is_some() {
         return Some(Action::EnterInputMode(InputMode::ConfirmPush));
@@ -1462,6 +1715,11 @@ fn handle_key_event(key: crossterm::event::KeyEvent, state: &AppState) -> Option
         return Some(Action::ToggleLogs);
     }
 
+    // Agent overlap matrix
+    if matches_keybind(key, &kb.toggle_overlaps) {
+        return Some(Action::ToggleOverlapMatrix);
+    }
+
     // Toggle settings
     if matches_keybind(key, &kb.toggle_settings) && !key.modifiers.contains(KeyModifiers::CONTROL) {
         return Some(Action::ToggleSettings);
@@ -1564,6 +1822,24 @@ fn handle_key_event(key: crossterm::event::KeyEvent, state: &AppState) -> Option
         KeyCode::Char('O') if state.preview_tab == PreviewTab::DevServer => {
             Some(Action::OpenDevServerInBrowser)
         }
+        KeyCode::Char('[') if state.preview_tab == PreviewTab::DevServer => {
+            Some(Action::SelectDevService { delta: -1 })
+        }
+        KeyCode::Char(']') if state.preview_tab == PreviewTab::DevServer => {
+            Some(Action::SelectDevService { delta: 1 })
+        }
+        KeyCode::Char('X') if state.preview_tab == PreviewTab::DevServer => {
+            Some(Action::ToggleDevService)
+        }
+        KeyCode::Char('E') if state.preview_tab == PreviewTab::DevServer => {
+            Some(Action::RestartDevService)
+        }
+        KeyCode::Char('F') if state.preview_tab == PreviewTab::DevServer => {
+            Some(Action::SendDevServerError)
+        }
+        KeyCode::Char('H') if state.preview_tab == PreviewTab::DevServer => {
+            Some(Action::StartLogSelection)
+        }
 
         // Preview panel scrolling (works on all tabs)
         KeyCode::PageUp => Some(Action::ScrollPreviewUp),
@@ -1620,6 +1896,7 @@ fn handle_input_mode_key(key: KeyCode, state: &AppState) -> Option<Action> {
             KeyCode::Char('j') | KeyCode::Down => Some(Action::SelectTaskNext),
             KeyCode::Char('k') | KeyCode::Up => Some(Action::SelectTaskPrev),
             KeyCode::Char('a') => Some(Action::AssignSelectedTaskToAgent),
+            KeyCode::Char('o') => Some(Action::OrchestrateSelectedTask),
             KeyCode::Char('s') => Some(Action::ToggleSubtaskStatus),
             KeyCode::Char('r') => Some(Action::RefreshTaskList),
             KeyCode::Char('f') => Some(Action::ToggleTaskListFilter),
@@ -1645,6 +1922,7 @@ fn handle_input_mode_key(key: KeyCode, state: &AppState) -> Option<Action> {
         Some(InputMode::ConfirmDelete)
             | Some(InputMode::ConfirmMerge)
             | Some(InputMode::ConfirmPush)
+            | Some(InputMode::ConfirmLandQueue)
     );
 
     if is_confirm_mode {
@@ -1657,6 +1935,14 @@ fn handle_input_mode_key(key: KeyCode, state: &AppState) -> Option<Action> {
     } else {
         // Text input modes
         match key {
+            KeyCode::Tab
+                if matches!(
+                    state.input_mode,
+                    Some(InputMode::NewAgent | InputMode::ImportAgent)
+                ) && !state.launch_profiles().is_empty() =>
+            {
+                Some(Action::CycleLaunchProfile)
+            }
             KeyCode::Enter => Some(Action::SubmitInput),
             KeyCode::Esc => Some(Action::ExitInputMode),
             KeyCode::Backspace => {
@@ -2137,9 +2423,11 @@ async fn process_action(
     linear_client: &Arc<OptionalLinearClient>,
     _storage: &SessionStorage,
     action_tx: &mpsc::UnboundedSender<Action>,
-    agent_watch_tx: &watch::Sender<HashSet<Uuid>>,
+    agent_watch_tx: &watch::Sender<HashMap<Uuid, grove::app::config::AiAgent>>,
     branch_watch_tx: &watch::Sender<Vec<(Uuid, String)>>,
+    session_watch_tx: &watch::Sender<HashMap<Uuid, String>>,
     selected_watch_tx: &watch::Sender<Option<Uuid>>,
+    stall_watch_tx: &watch::Sender<grove::app::config::StallConfig>,
     asana_watch_tx: &watch::Sender<Vec<(Uuid, String)>>,
     notion_watch_tx: &watch::Sender<Vec<(Uuid, String)>>,
     clickup_watch_tx: &watch::Sender<Vec<(Uuid, String)>>,
@@ -2188,8 +2476,25 @@ async fn process_action(
         }
 
         // Agent lifecycle
-        Action::CreateAgent { name, branch, task } => {
-            state.log_info(format!("Creating agent '{}' on branch '{}'", name, branch));
+        Action::CreateAgent {
+            name,
+            branch,
+            task,
+            base_branch,
+            profile,
+            prompt,
+        } => {
+            match &base_branch {
+                Some(base) => state.log_info(format!(
+                    "Creating agent '{}' on branch '{}' from '{}'",
+                    name, branch, base
+                )),
+                None => state.log_info(format!("Creating agent '{}' on branch '{}'", name, branch)),
+            }
+            let profile = profile.and_then(|name| state.launch_profile(&name));
+            if let Some(profile) = &profile {
+                state.log_info(format!("Using launch profile '{}'", profile.name));
+            }
             let ai_agent = state.config.global.ai_agent.clone();
             let worktree_symlinks = state
                 .settings
@@ -2197,9 +2502,34 @@ async fn process_action(
                 .dev_server
                 .worktree_symlinks
                 .clone();
-            match agent_manager.create_agent(&name, &branch, &ai_agent, &worktree_symlinks) {
+            match agent_manager.create_agent(
+                &name,
+                &branch,
+                base_branch.as_deref(),
+                profile.as_ref(),
+                &worktree_symlinks,
+            ) {
                 Ok(mut agent) => {
+                    let command = agent_manager.initial_command(
+                        &agent,
+                        &agent.ai_agent_or(&ai_agent),
+                        task.as_ref().map(|t| t.name.as_str()),
+                        prompt.as_ref(),
+                    );
                     state.log_info(format!("Agent '{}' created successfully", agent.name));
+                    for orchestration in &mut state.orchestrations {
+                        orchestration.agent_created(&branch, agent.id);
+                    }
+                    if state.autopilot.agent_created(&branch, agent.id) {
+                        if let Some(status) =
+                            &state.settings.repo_config.autopilot.in_progress_status
+                        {
+                            action_tx.send(Action::MoveTaskToStatus {
+                                agent_id: agent.id,
+                                status: status.clone(),
+                            })?;
+                        }
+                    }
 
                     if let Some(ref task_item) = task {
                         let pm_status = match state.settings.repo_config.project_mgmt.provider {
@@ -2264,8 +2594,13 @@ async fn process_action(
                     state.add_agent(agent);
                     state.select_last();
                     state.toast = None;
+                    action_tx.send(Action::ProvisionAgent {
+                        id: agent_id,
+                        command,
+                    })?;
                     // Notify polling tasks of new agent
-                    let _ = agent_watch_tx.send(state.agents.keys().cloned().collect());
+                    let _ = agent_watch_tx.send(state.agent_ai_agents());
+                    let _ = session_watch_tx.send(state.agent_sessions());
                     let _ = branch_watch_tx.send(
                         state
                             .agents
@@ -2289,8 +2624,169 @@ async fn process_action(
                         format!("Failed to create agent: {}", e),
                         ToastLevel::Error,
                     ));
+                    for orchestration in &mut state.orchestrations {
+                        orchestration.agent_failed(&branch, &e.to_string());
+                    }
+                    state.autopilot.agent_failed(&branch, &e.to_string());
+                }
+            }
+        }
+
+        Action::ImportAgent { source, profile } => {
+            state.log_info(format!("Importing {}", source.label()));
+            let profile = profile.and_then(|name| state.launch_profile(&name));
+            if let Some(profile) = &profile {
+                state.log_info(format!("Using launch profile '{}'", profile.name));
+            }
+            let ai_agent = state.config.global.ai_agent.clone();
+            let provider = state.settings.repo_config.git.provider;
+            let worktree_symlinks = state
+                .settings
+                .repo_config
+                .dev_server
+                .worktree_symlinks
+                .clone();
+
+            if let grove::agent::ImportSource::Branch(branch) = &source {
+                if state.agents.values().any(|a| &a.branch == branch) {
+                    state.show_warning(format!("Branch '{}' already has an agent", branch));
+                    return Ok(false);
+                }
+            }
+            let worktree_path = agent_manager.import_worktree_path(&source, provider);
+            if let Some(existing) = state
+                .agents
+                .values()
+                .find(|a| grove::agent::same_worktree(&a.worktree_path, &worktree_path))
+            {
+                state.show_warning(format!(
+                    "Worktree '{}' already has agent '{}'",
+                    worktree_path, existing.name
+                ));
+                return Ok(false);
+            }
+
+            match agent_manager.import_agent(
+                &source,
+                provider,
+                profile.as_ref(),
+                &worktree_symlinks,
+            ) {
+                Ok(agent) => {
+                    let command = grove::agent::resume_command(
+                        &agent.ai_agent_or(&ai_agent),
+                        &agent.worktree_path,
+                        None,
+                    );
+                    state.log_info(format!(
+                        "Imported {} as agent '{}'",
+                        source.label(),
+                        agent.name
+                    ));
+                    state.show_success(format!("Imported '{}'", agent.name));
+                    let agent_id = agent.id;
+                    state.add_agent(agent);
+                    state.select_last();
+                    action_tx.send(Action::ProvisionAgent {
+                        id: agent_id,
+                        command,
+                    })?;
+                    let _ = agent_watch_tx.send(state.agent_ai_agents());
+                    let _ = session_watch_tx.send(state.agent_sessions());
+                    let _ = branch_watch_tx.send(
+                        state
+                            .agents
+                            .values()
+                            .map(|a| (a.id, a.branch.clone()))
+                            .collect(),
+                    );
+                    let _ = selected_watch_tx.send(state.selected_agent_id());
+                }
+                Err(e) => {
+                    state.log_error(format!("Failed to import {}: {}", source.label(), e));
+                    state.show_error(format!("Import failed: {}", e));
+                }
+            }
+        }
+
+        Action::ProvisionAgent { id, command } => {
+            state.ensure_dev_port(id);
+            let Some(agent) = state.agents.get(&id) else {
+                return Ok(false);
+            };
+            let config = state.settings.repo_config.provisioning.for_agent(agent);
+            let vars = grove::agent::TemplateVars::for_agent(agent, &state.repo_path);
+            let name = agent.name.clone();
+            let worktree_path = std::path::PathBuf::from(&agent.worktree_path);
+
+            if let Err(e) = grove::agent::prepare_files(
+                &config,
+                std::path::Path::new(&state.repo_path),
+                &worktree_path,
+                &vars,
+            ) {
+                state.log_warn(format!("Failed to provision files for '{}': {}", name, e));
+                state.show_warning(format!("Provisioning files failed: {}", e));
+            }
+
+            if config.post_create.is_empty() {
+                start_agent_session(state, agent_manager, id, &command);
+                return Ok(false);
+            }
+            state.log_info(format!("Running setup hooks for '{}'", name));
+            state.loading_message = Some(format!("Setting up '{}'...", name));
+
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let result =
+                    grove::agent::run_hooks(&config.post_create, &worktree_path, &vars, |line| {
+                        let _ = tx.send(Action::ProvisionProgress {
+                            id,
+                            line: line.to_string(),
+                        });
+                    });
+                let _ = tx.send(Action::ProvisionFinished {
+                    id,
+                    command,
+                    result: result.map_err(|e| e.to_string()),
+                });
+            });
+        }
+
+        Action::ProvisionProgress { id, line } => {
+            let Some(agent) = state.agents.get(&id) else {
+                return Ok(false);
+            };
+            let shown: String = line.chars().take(60).collect();
+            state.loading_message = Some(format!("Setting up '{}': {}", agent.name, shown));
+            state.log_debug(format!("[{}] {}", agent.name, line));
+        }
+
+        Action::ProvisionFinished {
+            id,
+            command,
+            result,
+        } => {
+            state.loading_message = None;
+            let name = state
+                .agents
+                .get(&id)
+                .map(|a| a.name.clone())
+                .unwrap_or_default();
+            match result {
+                Ok(()) => {
+                    state.log_info(format!("Setup hooks for '{}' finished", name));
+                    state.show_success(format!("'{}' is ready", name));
+                }
+                Err(e) => {
+                    for line in e.lines() {
+                        state.log_error(format!("[{}] {}", name, line));
+                    }
+                    let summary = e.lines().next().unwrap_or_default().to_string();
+                    state.show_error(format!("Setup hook {}", summary));
                 }
             }
+            start_agent_session(state, agent_manager, id, &command);
         }
 
         Action::DeleteAgent { id } => {
@@ -2303,16 +2799,19 @@ async fn process_action(
                     a.name.clone(),
                     a.tmux_session.clone(),
                     a.worktree_path.clone(),
+                    a.adopted_worktree,
+                    grove::agent::TemplateVars::for_agent(a, &state.repo_path),
                 )
             });
 
-            if let Some((name, tmux_session, worktree_path)) = agent_info {
+            if let Some((name, tmux_session, worktree_path, adopted_worktree, vars)) = agent_info {
                 state.log_info(format!("Deleting agent '{}'...", name));
                 state.loading_message = Some(format!("Deleting '{}'...", name));
 
                 let tx = action_tx.clone();
                 let name_clone = name.clone();
                 let repo_path = state.repo_path.clone();
+                let pre_delete = state.settings.repo_config.provisioning.pre_delete.clone();
                 tokio::spawn(async move {
                     // Kill tmux session
                     let session = grove::tmux::TmuxSession::new(&tmux_session);
@@ -2320,8 +2819,27 @@ async fn process_action(
                         let _ = session.kill();
                     }
 
-                    // Remove worktree
-                    if std::path::Path::new(&worktree_path).exists() {
+                    let _ = grove::git::Checkpoints::new(&repo_path, &id.to_string()).clear();
+
+                    // Remove worktree (adopted worktrees belong to the user)
+                    if !adopted_worktree && std::path::Path::new(&worktree_path).exists() {
+                        let hooks = grove::agent::run_hooks(
+                            &pre_delete,
+                            std::path::Path::new(&worktree_path),
+                            &vars,
+                            |line| {
+                                let shown: String = line.chars().take(60).collect();
+                                let _ = tx.send(Action::SetLoading(Some(format!(
+                                    "Cleaning up '{}': {}",
+                                    name_clone, shown
+                                ))));
+                            },
+                        );
+                        if let Err(e) = hooks {
+                            let _ = tx.send(Action::LogWarning {
+                                message: format!("Cleanup for '{}': {}", name_clone, e),
+                            });
+                        }
                         let _ = std::process::Command::new("git")
                             .args([
                                 "-C",
@@ -2393,6 +2911,8 @@ async fn process_action(
         } => {
             const STATUS_DEBOUNCE_THRESHOLD: u32 = 4;
 
+            let mut became_completed = false;
+            let mut transition = None;
             if let Some(agent) = state.agents.get_mut(&id) {
                 let old_label = agent.status.label();
                 let name = agent.name.clone();
@@ -2430,6 +2950,8 @@ async fn process_action(
                         agent.status_reason = Some(reason);
                     }
                     if old_label != new_label {
+                        became_completed = matches!(agent.status, AgentStatus::Completed);
+                        transition = Some(format!("{} → {}", old_label, new_label));
                         state
                             .log_debug(format!("Agent '{}': {} -> {}", name, old_label, new_label));
                     }
@@ -2437,14 +2959,107 @@ async fn process_action(
                     agent.status_reason = Some(reason);
                 }
             }
-        }
 
-        Action::UpdateAgentOutput { id, output } => {
-            if let Some(agent) = state.agents.get_mut(&id) {
+            if let Some(label) = transition {
+                action_tx.send(Action::CreateCheckpoint { id, label })?;
+            }
+            let verify = &state.settings.repo_config.verify;
+            if became_completed && verify.auto_run && !verify.commands.is_empty() {
+                action_tx.send(Action::RunVerify { id })?;
+            }
+
+            enforce_budget(state, id);
+
+            // Hand a settled dev server error to the agent once it is idle
+            const ERROR_SETTLE: std::time::Duration = std::time::Duration::from_secs(2);
+            let idle = state
+                .agents
+                .get(&id)
+                .is_some_and(|a| matches!(a.status, AgentStatus::Idle | AgentStatus::Completed));
+            if let Some((service, at)) = state.devserver_error_pending.get(&id).cloned() {
+                if idle && at.elapsed() >= ERROR_SETTLE {
+                    state.devserver_error_pending.remove(&id);
+                    send_latest_devserver_error(state, devserver_manager, id, &service).await;
+                }
+            }
+        }
+
+        Action::UpdateAgentOutput { id, output } => {
+            if let Some(agent) = state.agents.get_mut(&id) {
                 agent.update_output(output, state.config.ui.output_buffer_lines);
             }
         }
 
+        Action::SetAgentBudget { id, budget } => {
+            if let Some(agent) = state.agents.get_mut(&id) {
+                let name = agent.name.clone();
+                agent.budget = budget;
+                state.budget_exceeded.remove(&id);
+                state.show_success(format!("Budget updated for '{}'", name));
+            }
+        }
+
+        Action::UpdateStall { id, stall } => {
+            let Some(agent) = state.agents.get_mut(&id) else {
+                return Ok(false);
+            };
+            agent.stall = stall.clone();
+            let Some(stall) = stall else {
+                return Ok(false);
+            };
+            let name = agent.name.clone();
+            let tmux_session = agent.tmux_session.clone();
+            let reason = stall.label();
+            state.log_warn(format!("Agent '{}' looks {}", name, reason));
+            state.show_warning(format!("'{}' looks {}", name, reason));
+            if state.settings.repo_config.stall.nudge {
+                let prompt = state.settings.repo_config.prompts.get_stall_prompt(&reason);
+                if let Err(e) = grove::tmux::TmuxSession::new(&tmux_session).send_keys(&prompt) {
+                    state.log_error(format!("Failed to nudge '{}': {}", name, e));
+                }
+            }
+        }
+
+        Action::CaptureDetectionFixture { id, expected } => {
+            if let Some(agent) = state.agents.get(&id) {
+                let name = agent.name.clone();
+                let result = grove::agent::DetectionFixture::capture(
+                    &agent.tmux_session,
+                    state.ai_agent_for(id),
+                    expected,
+                )
+                .and_then(|fixture| {
+                    let path = fixture.save(&grove::agent::fixture::fixtures_dir()?, &name)?;
+                    Ok((fixture, path))
+                });
+                match result {
+                    Ok((fixture, path)) => {
+                        state.log_info(format!(
+                            "Saved detector fixture for '{}' (detected {}, expected {}) to {}",
+                            name,
+                            fixture.detected.label(),
+                            fixture.expected.label(),
+                            path.display()
+                        ));
+                        state.show_success(format!("Fixture saved to {}", path.display()));
+                    }
+                    Err(e) => state.show_error(format!("Failed to capture fixture: {}", e)),
+                }
+            }
+        }
+
+        Action::UpdateTokenUsage { id, tokens } => {
+            if let Some(agent) = state.agents.get_mut(&id) {
+                agent.token_usage = Some(tokens);
+            }
+        }
+
+        Action::AppendTranscript { id, events } => {
+            if let Some(agent) = state.agents.get_mut(&id) {
+                agent.push_transcript(events);
+            }
+        }
+
         Action::SetAgentNote { id, note } => {
             if let Some(agent) = state.agents.get_mut(&id) {
                 agent.custom_note = note;
@@ -2501,7 +3116,56 @@ async fn process_action(
         }
 
         Action::MergeMain { id } => {
-            let main_branch = state.settings.repo_config.git.main_branch.clone();
+            let strategy = state.settings.repo_config.git.sync_strategy;
+            if strategy != grove::app::SyncStrategy::Prompt {
+                let main_branch = state.settings.repo_config.git.main_branch.clone();
+                let Some(agent) = state.agents.get_mut(&id) else {
+                    return Ok(false);
+                };
+                let name = agent.name.clone();
+                let worktree_path = agent.worktree_path.clone();
+                let tmux_session = agent.tmux_session.clone();
+                let base_ref = agent.diff_base_ref(&main_branch);
+                let rebase = strategy == grove::app::SyncStrategy::Rebase;
+
+                state.log_info(format!("Syncing {} into '{}'", base_ref, name));
+                state.loading_message = Some(format!("Syncing {} into '{}'...", base_ref, name));
+
+                let tx = action_tx.clone();
+                tokio::spawn(async move {
+                    // Keep the agent from touching the worktree mid-merge
+                    let session = grove::tmux::TmuxSession::new(&tmux_session);
+                    let paused = session.pause().is_ok();
+
+                    let git_sync = GitSync::new(&worktree_path);
+                    let result = if base_ref.starts_with("origin/") {
+                        git_sync.fetch()
+                    } else {
+                        Ok(())
+                    }
+                    .and_then(|_| git_sync.sync_with(&base_ref, rebase));
+
+                    if paused {
+                        let _ = session.resume();
+                    }
+
+                    let _ = match result {
+                        Ok(outcome) => tx.send(Action::MainSyncComplete { id, outcome }),
+                        Err(e) => tx.send(Action::MainSyncFailed {
+                            id,
+                            message: e.to_string(),
+                        }),
+                    };
+                });
+                return Ok(false);
+            }
+
+            let main_branch = state
+                .agents
+                .get(&id)
+                .map(|a| a.base_branch_or(&state.settings.repo_config.git.main_branch))
+                .unwrap_or(&state.settings.repo_config.git.main_branch)
+                .to_string();
             let prompt = state
                 .settings
                 .repo_config
@@ -2530,6 +3194,709 @@ async fn process_action(
             }
         }
 
+        Action::MainSyncComplete { id, outcome } => {
+            state.loading_message = None;
+            let main_branch = state.settings.repo_config.git.main_branch.clone();
+            let rebase =
+                state.settings.repo_config.git.sync_strategy == grove::app::SyncStrategy::Rebase;
+            if let Some(agent) = state.agents.get_mut(&id) {
+                let name = agent.name.clone();
+                let base_ref = agent.diff_base_ref(&main_branch);
+                match outcome {
+                    grove::git::SyncOutcome::Clean => {
+                        let git_sync = GitSync::new(&agent.worktree_path);
+                        if let Ok(status) = git_sync.get_status(agent.base_branch_or(&main_branch))
+                        {
+                            agent.git_status = Some(status);
+                        }
+                        state.log_info(format!("Synced {} into '{}'", base_ref, name));
+                        state.show_success(format!("Synced {} into '{}'", base_ref, name));
+                    }
+                    grove::git::SyncOutcome::Conflicts(files) => {
+                        state.log_warn(format!(
+                            "Conflicts syncing {} into '{}': {}",
+                            base_ref,
+                            name,
+                            files.join(", ")
+                        ));
+                        state.merge_conflict = Some(grove::app::MergeConflict {
+                            agent_id: id,
+                            agent_name: name,
+                            base_branch: base_ref,
+                            files,
+                            rebase,
+                        });
+                    }
+                }
+            }
+        }
+
+        Action::MainSyncFailed { message, .. } => {
+            state.loading_message = None;
+            state.log_error(format!("Sync with main failed: {}", message));
+            state.show_error(format!("Sync failed: {}", message));
+        }
+
+        Action::SendConflictsToAgent => {
+            if let Some(conflict) = state.merge_conflict.take() {
+                let prompt = state
+                    .settings
+                    .repo_config
+                    .prompts
+                    .get_conflict_prompt(&conflict.base_branch, &conflict.files);
+                if let Some(agent) = state.agents.get(&conflict.agent_id) {
+                    let session = grove::tmux::TmuxSession::new(&agent.tmux_session);
+                    match session.send_keys(&prompt) {
+                        Ok(()) => {
+                            state.log_info(format!(
+                                "Sent {} conflicting file(s) to '{}'",
+                                conflict.files.len(),
+                                conflict.agent_name
+                            ));
+                            state.show_success(format!(
+                                "Handed conflicts to '{}'",
+                                conflict.agent_name
+                            ));
+                        }
+                        Err(e) => {
+                            state.show_error(format!("Failed to send conflicts: {}", e));
+                        }
+                    }
+                }
+            }
+        }
+
+        Action::AbortMainSync => {
+            if let Some(conflict) = state.merge_conflict.take() {
+                if let Some(agent) = state.agents.get(&conflict.agent_id) {
+                    let git_sync = GitSync::new(&agent.worktree_path);
+                    match git_sync.abort_sync(conflict.rebase) {
+                        Ok(()) => {
+                            state.show_info(format!("Aborted sync in '{}'", conflict.agent_name));
+                        }
+                        Err(e) => state.show_error(format!("Abort failed: {}", e)),
+                    }
+                }
+            }
+        }
+
+        Action::DismissMergeConflict => {
+            state.merge_conflict = None;
+        }
+
+        Action::RestackAgent { id } => {
+            let agents: Vec<&Agent> = state.agents.values().collect();
+            let order = grove::agent::restack_order(&agents, id);
+            let main_branch = state.settings.repo_config.git.main_branch.clone();
+            let steps: Vec<_> = order
+                .iter()
+                .filter_map(|agent_id| state.agents.get(agent_id))
+                .map(|a| {
+                    (
+                        a.id,
+                        a.name.clone(),
+                        a.worktree_path.clone(),
+                        a.base_branch_or(&main_branch).to_string(),
+                        a.base_commit.clone(),
+                    )
+                })
+                .collect();
+
+            if steps.is_empty() {
+                state.show_info("Nothing to restack");
+                return Ok(false);
+            }
+
+            state.log_info(format!("Restacking {} agent(s)", steps.len()));
+            state.show_info(format!("Restacking {} agent(s)...", steps.len()));
+            let repo_path = state.repo_path.clone();
+            let worktree_base = state.worktree_base.clone();
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let worktree = Worktree::new(&repo_path, worktree_base);
+                let total = steps.len();
+                for (agent_id, name, worktree_path, base, base_commit) in steps {
+                    let new_tip = match worktree.branch_tip(&base) {
+                        Ok(tip) => tip,
+                        Err(e) => {
+                            let _ = tx.send(Action::ShowError(format!(
+                                "Restack stopped at '{}': {}",
+                                name, e
+                            )));
+                            return;
+                        }
+                    };
+                    let git_sync = GitSync::new(&worktree_path);
+                    if let Err(e) = git_sync.rebase_onto(&base, base_commit.as_deref()) {
+                        let _ = tx.send(Action::ShowError(format!(
+                            "Restack stopped at '{}': {}",
+                            name, e
+                        )));
+                        return;
+                    }
+                    let _ = tx.send(Action::AgentRestacked {
+                        id: agent_id,
+                        base_commit: new_tip,
+                    });
+                }
+                let _ = tx.send(Action::ShowToast {
+                    message: format!("Restacked {} agent(s)", total),
+                    level: ToastLevel::Success,
+                });
+            });
+        }
+
+        Action::AgentRestacked { id, base_commit } => {
+            if let Some(agent) = state.agents.get_mut(&id) {
+                agent.base_commit = Some(base_commit);
+                let name = agent.name.clone();
+                state.log_info(format!("Restacked '{}'", name));
+            }
+        }
+
+        Action::ToggleMergeQueue { id } => {
+            if state.merge_queue_running {
+                state.show_info("Merge queue is running");
+                return Ok(false);
+            }
+            let Some(name) = state.agents.get(&id).map(|a| a.name.clone()) else {
+                return Ok(false);
+            };
+            if let Some(pos) = state.merge_queue.iter().position(|&x| x == id) {
+                state.merge_queue.remove(pos);
+                state.show_info(format!("Removed '{}' from merge queue", name));
+            } else {
+                state.merge_queue.push(id);
+                state.show_info(format!(
+                    "Queued '{}' for landing (#{})",
+                    name,
+                    state.merge_queue.len()
+                ));
+            }
+        }
+
+        Action::LandMergeQueue => {
+            if state.merge_queue_running {
+                state.show_info("Merge queue is already running");
+                return Ok(false);
+            }
+            let (ids, branches): (Vec<Uuid>, Vec<String>) = state
+                .merge_queue
+                .iter()
+                .filter_map(|id| state.agents.get(id))
+                .map(|a| (a.id, a.branch.clone()))
+                .unzip();
+            if ids.is_empty() {
+                state.show_info("Merge queue is empty");
+                return Ok(false);
+            }
+
+            let main_branch = state.settings.repo_config.git.main_branch.clone();
+            let mut queue_config = state.settings.repo_config.git.merge_queue.clone();
+            let verify_commands = &state.settings.repo_config.verify.commands;
+            if queue_config.verify_command.is_none() && !verify_commands.is_empty() {
+                queue_config.verify_command = Some(verify_commands.join(" && "));
+            }
+            let queue = MergeQueue::new(&state.repo_path, &state.worktree_base);
+            state.merge_queue_running = true;
+            state.log_info(format!(
+                "Landing {} branch(es) into {}",
+                branches.len(),
+                main_branch
+            ));
+
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let progress_tx = tx.clone();
+                let result = queue.land(
+                    &main_branch,
+                    &branches,
+                    queue_config.verify_command.as_deref(),
+                    queue_config.push,
+                    |message| {
+                        let _ = progress_tx.send(Action::MergeQueueProgress {
+                            message: message.to_string(),
+                        });
+                    },
+                );
+                let _ = match result {
+                    Ok(report) => tx.send(Action::MergeQueueFinished { ids, report }),
+                    Err(e) => tx.send(Action::MergeQueueFailed {
+                        message: e.to_string(),
+                    }),
+                };
+            });
+        }
+
+        Action::MergeQueueProgress { message } => {
+            state.log_info(format!("Merge queue: {}", message));
+            state.show_info(message);
+        }
+
+        Action::MergeQueueFinished { ids, report } => {
+            state.merge_queue_running = false;
+            if let Some(err) = &report.fast_forward_error {
+                let main_branch = state.settings.repo_config.git.main_branch.clone();
+                state.log_error(format!(
+                    "Merge queue: {} passed but {} could not be fast-forwarded: {}",
+                    report.landed.join(", "),
+                    main_branch,
+                    err
+                ));
+                state.show_error(format!(
+                    "Could not fast-forward {}; nothing was landed",
+                    main_branch
+                ));
+                return Ok(false);
+            }
+            let landed_ids: Vec<Uuid> = ids.into_iter().take(report.landed.len()).collect();
+            state.merge_queue.retain(|id| !landed_ids.contains(id));
+            let main_branch = state.settings.repo_config.git.main_branch.clone();
+
+            if let Some(failure) = &report.failure {
+                state.log_error(format!(
+                    "Merge queue stopped: '{}' failed {}",
+                    failure.branch,
+                    failure.stage.label()
+                ));
+                for line in failure.detail.lines() {
+                    state.log_error(format!("  {}", line));
+                }
+                state.show_error(format!(
+                    "'{}' failed {} ({} landed)",
+                    failure.branch,
+                    failure.stage.label(),
+                    report.landed.len()
+                ));
+            } else {
+                state.log_info(format!(
+                    "Landed {} into {}",
+                    report.landed.join(", "),
+                    main_branch
+                ));
+                state.show_success(format!(
+                    "Landed {} branch(es) into {}",
+                    report.landed.len(),
+                    main_branch
+                ));
+            }
+
+            if let Some(err) = &report.push_error {
+                state.log_error(format!("Push of {} failed: {}", main_branch, err));
+                state.show_error(format!("Push of {} failed", main_branch));
+            }
+
+            if state.settings.repo_config.git.merge_queue.archive_landed {
+                for id in landed_ids {
+                    action_tx.send(Action::DeleteAgent { id })?;
+                }
+            }
+        }
+
+        Action::MergeQueueFailed { message } => {
+            state.merge_queue_running = false;
+            state.log_error(format!("Merge queue failed: {}", message));
+            state.show_error(format!("Merge queue failed: {}", message));
+        }
+
+        Action::RunVerify { id } => {
+            let commands = state.settings.repo_config.verify.commands.clone();
+            if commands.is_empty() {
+                state.show_info("No verify commands configured (Settings > Dev Server)");
+                return Ok(false);
+            }
+            if matches!(
+                state.verify_runs.get(&id).map(|r| &r.status),
+                Some(VerifyStatus::Running)
+            ) {
+                state.show_info("Verification is already running");
+                return Ok(false);
+            }
+            let Some((name, worktree_path)) = state
+                .agents
+                .get(&id)
+                .map(|a| (a.name.clone(), a.worktree_path.clone()))
+            else {
+                return Ok(false);
+            };
+
+            state.verify_runs.insert(id, VerifyRun::running());
+            state.log_info(format!("Verifying '{}': {}", name, commands.join(" && ")));
+
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let run =
+                    grove::verify::run_verify(&commands, std::path::Path::new(&worktree_path));
+                let _ = tx.send(Action::VerifyFinished { id, run });
+            });
+        }
+
+        Action::VerifyFinished { id, run } => {
+            let name = state
+                .agents
+                .get(&id)
+                .map(|a| a.name.clone())
+                .unwrap_or_default();
+            match &run.status {
+                VerifyStatus::Passed(_) => {
+                    state.log_info(format!("Verify passed for '{}'", name));
+                    state.show_success(format!("'{}' verified", name));
+                }
+                VerifyStatus::Failed { command, .. } => {
+                    state.log_warn(format!("Verify failed for '{}': {}", name, command));
+                    for failure in &run.failures {
+                        state.log_warn(format!("  {}", failure));
+                    }
+                    state.show_error(format!(
+                        "'{}' failed verification ({} to send failures)",
+                        name,
+                        state.config.keybinds.send_failures.display_short()
+                    ));
+                }
+                VerifyStatus::Error(message) => {
+                    state.log_error(format!("Verify error for '{}': {}", name, message));
+                    state.show_error(message.clone());
+                }
+                VerifyStatus::Running => {}
+            }
+            if state.agents.contains_key(&id) {
+                state.verify_runs.insert(id, run);
+            }
+        }
+
+        Action::CreateCheckpoint { id, label } => {
+            let config = &state.settings.repo_config.checkpoints;
+            if !config.enabled {
+                return Ok(false);
+            }
+            let max_per_agent = config.max_per_agent;
+            let Some(worktree_path) = state
+                .agents
+                .get(&id)
+                .map(|a| a.worktree_path.clone())
+                .filter(|p| std::path::Path::new(p).exists())
+            else {
+                return Ok(false);
+            };
+
+            tokio::task::spawn_blocking(move || {
+                let checkpoints = grove::git::Checkpoints::new(&worktree_path, &id.to_string());
+                match checkpoints.snapshot(&label) {
+                    Ok(Some(_)) => {
+                        let _ = checkpoints.prune(max_per_agent);
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::debug!("Checkpoint of {} failed: {}", worktree_path, e),
+                }
+            });
+        }
+
+        Action::CheckpointAll => {
+            for id in state.agents.keys() {
+                action_tx.send(Action::CreateCheckpoint {
+                    id: *id,
+                    label: "periodic".to_string(),
+                })?;
+            }
+        }
+
+        Action::OpenCheckpoints { id } => {
+            let Some((name, worktree_path)) = state
+                .agents
+                .get(&id)
+                .map(|a| (a.name.clone(), a.worktree_path.clone()))
+            else {
+                return Ok(false);
+            };
+            state.checkpoint_view = Some(grove::app::CheckpointView {
+                agent_id: id,
+                agent_name: name,
+                checkpoints: Vec::new(),
+                selected: 0,
+                diff: None,
+                confirm_restore: false,
+            });
+
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                match grove::git::Checkpoints::new(&worktree_path, &id.to_string()).list() {
+                    Ok(checkpoints) => {
+                        let _ = tx.send(Action::CheckpointsLoaded { id, checkpoints });
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Action::ShowError(format!(
+                            "Failed to load checkpoints: {}",
+                            e
+                        )));
+                    }
+                }
+            });
+        }
+
+        Action::CheckpointsLoaded { id, checkpoints } => {
+            if let Some(view) = state.checkpoint_view.as_mut() {
+                if view.agent_id == id {
+                    view.selected = checkpoints.len().saturating_sub(1);
+                    view.checkpoints = checkpoints;
+                    view.diff = None;
+                    action_tx.send(Action::CheckpointShowDiff)?;
+                }
+            }
+        }
+
+        Action::CheckpointSelectNext | Action::CheckpointSelectPrev => {
+            if let Some(view) = state.checkpoint_view.as_mut() {
+                // The timeline lists the newest checkpoint first
+                let selected = if matches!(action, Action::CheckpointSelectNext) {
+                    view.selected.saturating_sub(1)
+                } else {
+                    (view.selected + 1).min(view.checkpoints.len().saturating_sub(1))
+                };
+                if selected != view.selected {
+                    view.selected = selected;
+                    view.diff = None;
+                    action_tx.send(Action::CheckpointShowDiff)?;
+                }
+            }
+        }
+
+        Action::CheckpointShowDiff => {
+            let Some(view) = &state.checkpoint_view else {
+                return Ok(false);
+            };
+            let Some(checkpoint) = view.selected_checkpoint().cloned() else {
+                return Ok(false);
+            };
+            let Some(worktree_path) = state
+                .agents
+                .get(&view.agent_id)
+                .map(|a| a.worktree_path.clone())
+            else {
+                return Ok(false);
+            };
+            let key = view.agent_id.to_string();
+
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let diff = grove::git::Checkpoints::new(&worktree_path, &key)
+                    .diff(&checkpoint)
+                    .unwrap_or_else(|e| format!("Failed to diff: {}", e));
+                let _ = tx.send(Action::CheckpointDiffLoaded {
+                    index: checkpoint.index,
+                    diff,
+                });
+            });
+        }
+
+        Action::CheckpointDiffLoaded { index, diff } => {
+            if let Some(view) = state.checkpoint_view.as_mut() {
+                if view.selected_checkpoint().map(|c| c.index) == Some(index) {
+                    view.diff = Some(diff);
+                }
+            }
+        }
+
+        Action::RestoreCheckpoint { confirmed } => {
+            let Some(view) = state.checkpoint_view.as_mut() else {
+                return Ok(false);
+            };
+            let Some(checkpoint) = view.selected_checkpoint().cloned() else {
+                return Ok(false);
+            };
+            if !confirmed {
+                view.confirm_restore = true;
+                return Ok(false);
+            }
+            view.confirm_restore = false;
+
+            let id = view.agent_id;
+            let Some((worktree_path, tmux_session)) = state
+                .agents
+                .get(&id)
+                .map(|a| (a.worktree_path.clone(), a.tmux_session.clone()))
+            else {
+                return Ok(false);
+            };
+
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                // Keep the agent from writing while files are swapped out
+                let session = grove::tmux::TmuxSession::new(&tmux_session);
+                let paused = session.pause().is_ok();
+                let result = grove::git::Checkpoints::new(&worktree_path, &id.to_string())
+                    .restore(&checkpoint)
+                    .map_err(|e| e.to_string());
+                if paused {
+                    let _ = session.resume();
+                }
+                let _ = tx.send(Action::CheckpointRestored {
+                    id,
+                    index: checkpoint.index,
+                    result,
+                });
+            });
+        }
+
+        Action::OpenSessionPicker { id } => {
+            let Some((name, worktree_path)) = state
+                .agents
+                .get(&id)
+                .map(|a| (a.name.clone(), a.worktree_path.clone()))
+            else {
+                return Ok(false);
+            };
+            state.session_picker = Some(grove::app::SessionPicker {
+                agent_id: id,
+                agent_name: name,
+                sessions: Vec::new(),
+                selected: 0,
+                loading: true,
+            });
+
+            let ai_agent = state.ai_agent_for(id);
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let sessions = grove::agent::sessions::list_sessions(&ai_agent, &worktree_path)
+                    .unwrap_or_else(|e| {
+                        let _ = tx.send(Action::ShowError(format!(
+                            "Failed to list {} sessions: {}",
+                            ai_agent.display_name(),
+                            e
+                        )));
+                        Vec::new()
+                    });
+                let _ = tx.send(Action::SessionsLoaded { id, sessions });
+            });
+        }
+
+        Action::SessionsLoaded { id, sessions } => {
+            if let Some(picker) = state.session_picker.as_mut() {
+                if picker.agent_id == id {
+                    // Start on the session the agent is on, if it's listed
+                    let current = state
+                        .agents
+                        .get(&id)
+                        .and_then(|a| a.ai_session_id.as_deref());
+                    picker.selected = sessions
+                        .iter()
+                        .position(|s| Some(s.id.as_str()) == current)
+                        .map_or(0, |i| i + 1);
+                    picker.sessions = sessions;
+                    picker.loading = false;
+                }
+            }
+        }
+
+        Action::SessionPickerSelectNext | Action::SessionPickerSelectPrev => {
+            if let Some(picker) = state.session_picker.as_mut() {
+                picker.selected = if matches!(action, Action::SessionPickerSelectNext) {
+                    (picker.selected + 1).min(picker.sessions.len())
+                } else {
+                    picker.selected.saturating_sub(1)
+                };
+            }
+        }
+
+        Action::ResumeSession { id, session_id } => {
+            state.session_picker = None;
+            let Some(agent) = state.agents.get(&id).cloned() else {
+                return Ok(false);
+            };
+            let ai_agent = state.ai_agent_for(id);
+            let manager = Arc::clone(agent_manager);
+            let relaunch = {
+                let session_id = session_id.clone();
+                tokio::task::spawn_blocking(move || {
+                    manager.relaunch_agent(&agent, &ai_agent, session_id.as_deref())
+                })
+            };
+            match relaunch.await? {
+                Ok(()) => {
+                    let Some(agent) = state.agents.get_mut(&id) else {
+                        return Ok(false);
+                    };
+                    agent.ai_session_id = session_id.clone();
+                    agent.session_started_at = chrono::Utc::now();
+                    let name = agent.name.clone();
+                    state.budget_exceeded.remove(&id);
+                    let message = match &session_id {
+                        Some(session_id) => format!("Resumed session {} in '{}'", session_id, name),
+                        None => format!("Started a fresh session in '{}'", name),
+                    };
+                    state.log_info(message.clone());
+                    state.show_success(message);
+                }
+                Err(e) => {
+                    state.log_error(format!("Failed to relaunch agent: {}", e));
+                    state.show_error(format!("Failed to relaunch agent: {}", e));
+                }
+            }
+        }
+
+        Action::CloseSessionPicker => {
+            state.session_picker = None;
+        }
+
+        Action::CancelRestoreCheckpoint => {
+            if let Some(view) = state.checkpoint_view.as_mut() {
+                view.confirm_restore = false;
+            }
+        }
+
+        Action::CheckpointRestored { id, index, result } => {
+            let name = state
+                .agents
+                .get(&id)
+                .map(|a| a.name.clone())
+                .unwrap_or_default();
+            match result {
+                Ok(()) => {
+                    state.log_info(format!("Restored '{}' to checkpoint #{}", name, index));
+                    state.show_success(format!("Restored '{}' to #{}", name, index));
+                    if state.checkpoint_view.is_some() {
+                        action_tx.send(Action::OpenCheckpoints { id })?;
+                    }
+                }
+                Err(e) => {
+                    state.log_error(format!("Restore of '{}' failed: {}", name, e));
+                    state.show_error(format!("Restore failed: {}", e));
+                }
+            }
+        }
+
+        Action::CloseCheckpoints => {
+            state.checkpoint_view = None;
+        }
+
+        Action::SendVerifyFailures { id } => {
+            let Some(run) = state.verify_runs.get(&id) else {
+                state.show_info("No verification results for this agent");
+                return Ok(false);
+            };
+            let VerifyStatus::Failed { command, .. } = &run.status else {
+                state.show_info("Last verification did not fail");
+                return Ok(false);
+            };
+            let prompt = state
+                .settings
+                .repo_config
+                .prompts
+                .get_verify_prompt(command, &run.failures);
+            if let Some(agent) = state.agents.get(&id) {
+                let name = agent.name.clone();
+                let session = grove::tmux::TmuxSession::new(&agent.tmux_session);
+                match session.send_keys(&prompt) {
+                    Ok(()) => {
+                        state.log_info(format!("Sent verify failures to '{}'", name));
+                        state.show_success(format!("Handed failures to '{}'", name));
+                    }
+                    Err(e) => {
+                        state.show_error(format!("Failed to send failures: {}", e));
+                    }
+                }
+            }
+        }
+
         Action::PushBranch { id } => {
             let agent_info = state
                 .agents
@@ -2538,7 +3905,7 @@ async fn process_action(
 
             if let Some((name, tmux_session)) = agent_info {
                 let session = grove::tmux::TmuxSession::new(&tmux_session);
-                let agent_type = state.config.global.ai_agent.clone();
+                let agent_type = state.ai_agent_for(id);
                 let push_cmd = agent_type.push_command();
                 let push_prompt = state
                     .settings
@@ -4751,189 +6118,22 @@ async fn process_action(
             let tx = action_tx.clone();
 
             let asana_client_status = asana_client.clone();
-            let notion_client_status = notion_client.clone();
-            let clickup_client_status = clickup_client.clone();
-            let airtable_client_status = airtable_client.clone();
-            let linear_client_status = linear_client.clone();
-            let tx_status = action_tx.clone();
-
-            tokio::spawn(async move {
-                let result = match provider {
-                    ProjectMgmtProvider::Asana => {
-                        match asana_client.get_project_tasks_with_subtasks().await {
-                            Ok(tasks) => {
-                                let mut items: Vec<TaskListItem> = tasks
-                                    .into_iter()
-                                    .map(|t| {
-                                        let status_name = if t.parent_gid.is_some() {
-                                            if t.completed {
-                                                "Complete".to_string()
-                                            } else {
-                                                "Not Complete".to_string()
-                                            }
-                                        } else {
-                                            t.section_name
-                                                .clone()
-                                                .unwrap_or_else(|| "No Section".to_string())
-                                        };
-                                        TaskListItem {
-                                            id: t.gid,
-                                            identifier: None,
-                                            name: t.name,
-                                            status_name,
-                                            url: t.permalink_url.unwrap_or_default(),
-                                            parent_id: t.parent_gid,
-                                            has_children: t.num_subtasks > 0,
-                                        }
-                                    })
-                                    .collect();
-                                sort_tasks_by_parent(&mut items);
-                                Ok(items)
-                            }
-                            Err(e) => Err(e.to_string()),
-                        }
-                    }
-                    ProjectMgmtProvider::Notion => {
-                        match notion_client.query_database_with_children(true).await {
-                            Ok(pages) => {
-                                let parent_ids: std::collections::HashSet<String> = pages
-                                    .iter()
-                                    .filter_map(|p| p.parent_page_id.as_ref())
-                                    .cloned()
-                                    .collect();
-
-                                let mut items: Vec<TaskListItem> = pages
-                                    .into_iter()
-                                    .map(|p| {
-                                        let status_name = p
-                                            .status_name
-                                            .clone()
-                                            .unwrap_or_else(|| "Unknown".to_string());
-                                        let has_children = parent_ids.contains(&p.id);
-                                        TaskListItem {
-                                            id: p.id,
-                                            identifier: None,
-                                            name: p.name,
-                                            status_name,
-                                            url: p.url,
-                                            parent_id: p.parent_page_id,
-                                            has_children,
-                                        }
-                                    })
-                                    .collect();
-                                sort_tasks_by_parent(&mut items);
-                                Ok(items)
-                            }
-                            Err(e) => Err(e.to_string()),
-                        }
-                    }
-                    ProjectMgmtProvider::Clickup => {
-                        match clickup_client.get_list_tasks_with_subtasks().await {
-                            Ok(tasks) => {
-                                let parent_ids: std::collections::HashSet<String> = tasks
-                                    .iter()
-                                    .filter_map(|t| t.parent_id.as_ref())
-                                    .cloned()
-                                    .collect();
-
-                                let mut items: Vec<TaskListItem> = tasks
-                                    .into_iter()
-                                    .map(|t| {
-                                        let status_name = t.status.clone();
-                                        let has_children = parent_ids.contains(&t.id);
-                                        TaskListItem {
-                                            id: t.id,
-                                            identifier: None,
-                                            name: t.name,
-                                            status_name,
-                                            url: t.url.unwrap_or_default(),
-                                            parent_id: t.parent_id,
-                                            has_children,
-                                        }
-                                    })
-                                    .collect();
-                                sort_tasks_by_parent(&mut items);
-                                Ok(items)
-                            }
-                            Err(e) => Err(e.to_string()),
-                        }
-                    }
-                    ProjectMgmtProvider::Airtable => {
-                        match airtable_client.list_records_with_children().await {
-                            Ok(tasks) => {
-                                let parent_ids: std::collections::HashSet<String> = tasks
-                                    .iter()
-                                    .filter_map(|t| t.parent_id.as_ref())
-                                    .cloned()
-                                    .collect();
-
-                                let mut items: Vec<TaskListItem> = tasks
-                                    .into_iter()
-                                    .map(|t| {
-                                        let status_name = t
-                                            .status
-                                            .clone()
-                                            .unwrap_or_else(|| "Unknown".to_string());
-                                        let has_children = parent_ids.contains(&t.id);
-                                        TaskListItem {
-                                            id: t.id,
-                                            identifier: None,
-                                            name: t.name,
-                                            status_name,
-                                            url: t.url,
-                                            parent_id: t.parent_id,
-                                            has_children,
-                                        }
-                                    })
-                                    .collect();
-                                sort_tasks_by_parent(&mut items);
-                                Ok(items)
-                            }
-                            Err(e) => Err(e.to_string()),
-                        }
-                    }
-                    ProjectMgmtProvider::Linear => {
-                        match linear_client.get_team_issues_with_children().await {
-                            Ok(issues) => {
-                                tracing::debug!("Linear issues before building task list:");
-                                for i in &issues {
-                                    tracing::debug!(
-                                        "  {} - state_name: {}, state_type: {}, parent_id: {:?}",
-                                        i.identifier,
-                                        i.state_name,
-                                        i.state_type,
-                                        i.parent_id
-                                    );
-                                }
-
-                                let parent_ids: std::collections::HashSet<String> = issues
-                                    .iter()
-                                    .filter_map(|i| i.parent_id.as_ref())
-                                    .cloned()
-                                    .collect();
-
-                                let mut items: Vec<TaskListItem> = issues
-                                    .into_iter()
-                                    .map(|i| {
-                                        let has_children = parent_ids.contains(&i.id);
-                                        TaskListItem {
-                                            id: i.id,
-                                            identifier: Some(i.identifier),
-                                            name: i.title,
-                                            status_name: i.state_name,
-                                            url: i.url,
-                                            parent_id: i.parent_id,
-                                            has_children,
-                                        }
-                                    })
-                                    .collect();
-                                sort_tasks_by_parent(&mut items);
-                                Ok(items)
-                            }
-                            Err(e) => Err(e.to_string()),
-                        }
-                    }
-                };
+            let notion_client_status = notion_client.clone();
+            let clickup_client_status = clickup_client.clone();
+            let airtable_client_status = airtable_client.clone();
+            let linear_client_status = linear_client.clone();
+            let tx_status = action_tx.clone();
+
+            tokio::spawn(async move {
+                let result = fetch_task_list(
+                    provider,
+                    &asana_client,
+                    &notion_client,
+                    &clickup_client,
+                    &airtable_client,
+                    &linear_client,
+                )
+                .await;
                 match result {
                     Ok(tasks) => {
                         let _ = tx.send(Action::TaskListFetched { tasks });
@@ -5498,6 +6698,10 @@ async fn process_action(
                 };
 
                 state.log_info(format!("Creating agent '{}' on branch '{}'", name, branch));
+                let profile = state.default_launch_profile();
+                if let Some(profile) = &profile {
+                    state.log_info(format!("Using launch profile '{}'", profile.name));
+                }
                 let ai_agent = state.config.global.ai_agent.clone();
                 tracing::debug!(
                     "CreateAgentFromSelectedTask - name: {:?}, branch: {:?}, ai_agent: {:?}",
@@ -5511,8 +6715,20 @@ async fn process_action(
                     .dev_server
                     .worktree_symlinks
                     .clone();
-                match agent_manager.create_agent(&name, &branch, &ai_agent, &worktree_symlinks) {
+                match agent_manager.create_agent(
+                    &name,
+                    &branch,
+                    None,
+                    profile.as_ref(),
+                    &worktree_symlinks,
+                ) {
                     Ok(mut agent) => {
+                        let command = agent_manager.initial_command(
+                            &agent,
+                            &agent.ai_agent_or(&ai_agent),
+                            Some(&task.name),
+                            None,
+                        );
                         state.log_info(format!("Agent '{}' created successfully", agent.name));
 
                         let pm_status = match provider {
@@ -5571,8 +6787,14 @@ async fn process_action(
                         state.select_last();
                         state.toast = None;
                         state.exit_input_mode();
+                        action_tx.send(Action::ProvisionAgent {
+                            id: agent_id,
+                            command,
+                        })?;
+
+                        let _ = agent_watch_tx.send(state.agent_ai_agents());
 
-                        let _ = agent_watch_tx.send(state.agents.keys().cloned().collect());
+                        let _ = session_watch_tx.send(state.agent_sessions());
                         let _ = branch_watch_tx.send(
                             state
                                 .agents
@@ -5665,6 +6887,297 @@ async fn process_action(
             state.task_reassignment_warning = None;
         }
 
+        Action::ToggleAutopilot => {
+            state.autopilot.enabled = !state.autopilot.enabled;
+            if state.autopilot.enabled {
+                state.autopilot.forget_unstarted();
+                let config = &state.settings.repo_config.autopilot;
+                state.show_success(format!(
+                    "Autopilot on: up to {} agents (status {}, label {})",
+                    config.max_active,
+                    describe_any(&config.statuses),
+                    describe_any(&config.labels)
+                ));
+                action_tx.send(Action::AutopilotPoll)?;
+            } else {
+                state.show_info("Autopilot off; running agents are kept");
+            }
+        }
+
+        Action::AutopilotPoll => {
+            if !state.autopilot.enabled {
+                return Ok(false);
+            }
+            let provider = state.settings.repo_config.project_mgmt.provider;
+            let asana_client = Arc::clone(asana_client);
+            let notion_client = Arc::clone(notion_client);
+            let clickup_client = Arc::clone(clickup_client);
+            let airtable_client = Arc::clone(airtable_client);
+            let linear_client = Arc::clone(linear_client);
+            let tx = action_tx.clone();
+            tokio::spawn(async move {
+                let tasks = fetch_task_list(
+                    provider,
+                    &asana_client,
+                    &notion_client,
+                    &clickup_client,
+                    &airtable_client,
+                    &linear_client,
+                )
+                .await;
+                let _ = tx.send(Action::AutopilotTasksFetched { tasks });
+            });
+        }
+
+        Action::AutopilotTasksFetched { tasks } => {
+            let tasks = match tasks {
+                Ok(tasks) => tasks,
+                Err(e) => {
+                    state.log_warn(format!("Autopilot could not fetch tasks: {}", e));
+                    return Ok(false);
+                }
+            };
+            if !state.autopilot.enabled {
+                return Ok(false);
+            }
+            let config = state.settings.repo_config.autopilot.clone();
+            for name in state
+                .autopilot
+                .release_finished(&state.agents, &config.done_status)
+            {
+                state.log_info(format!("Autopilot: released the slot of {}", name));
+            }
+
+            for (task, branch) in state.autopilot.pick(&tasks, &state.agents, &config) {
+                state.log_info(format!("Autopilot: picking up '{}'", task.name));
+                let mut vars = grove::agent::TemplateVars::default();
+                vars.set("task_url", &task.url);
+                vars.set("done_status", &config.done_status);
+                action_tx.send(Action::CreateAgent {
+                    name: task.name.clone(),
+                    branch,
+                    prompt: Some(grove::agent::InitialPrompt::Template(
+                        vars.render(&config.prompt),
+                    )),
+                    task: Some(task),
+                    base_branch: None,
+                    profile: config.profile.clone(),
+                })?;
+            }
+        }
+
+        Action::OrchestrateSelectedTask => {
+            let Some(selected) = state.task_list.get(state.task_list_selected).cloned() else {
+                return Ok(false);
+            };
+            let parent = selected
+                .parent_id
+                .as_ref()
+                .and_then(|id| state.task_list.iter().find(|t| &t.id == id).cloned())
+                .unwrap_or(selected);
+            if state
+                .orchestrations
+                .iter()
+                .any(|o| o.parent.task.id == parent.id)
+            {
+                state.show_info(format!("'{}' is already being orchestrated", parent.name));
+                return Ok(false);
+            }
+            let subtasks: Vec<TaskListItem> = state
+                .task_list
+                .iter()
+                .filter(|t| t.parent_id.as_deref() == Some(parent.id.as_str()))
+                .cloned()
+                .collect();
+            if subtasks.is_empty() {
+                state.show_warning(format!("'{}' has no subtasks", parent.name));
+                return Ok(false);
+            }
+
+            state.log_info(format!(
+                "Orchestrating '{}' with {} subtasks",
+                parent.name,
+                subtasks.len()
+            ));
+            state
+                .orchestrations
+                .push(grove::app::Orchestration::new(parent, subtasks));
+            state.orchestrator_selected = state.orchestrations.len() - 1;
+            state.show_orchestrator = true;
+            state.exit_input_mode();
+            action_tx.send(Action::AdvanceOrchestrations)?;
+        }
+
+        Action::AdvanceOrchestrations => {
+            use grove::app::orchestrator::{SlotState, Stage};
+
+            let config = state.settings.repo_config.orchestrator.clone();
+            let mut creates = Vec::new();
+            let mut finished = Vec::new();
+            for (index, orchestration) in state.orchestrations.iter_mut().enumerate() {
+                if orchestration.stage != Stage::Running {
+                    continue;
+                }
+                orchestration.update(&state.agents, &config.done_status);
+
+                let mut vars = grove::agent::TemplateVars::default();
+                vars.set("parent", &orchestration.parent.task.name);
+                vars.set("done_status", &config.done_status);
+                let subtask_names: Vec<&str> = orchestration
+                    .subtasks
+                    .iter()
+                    .map(|s| s.task.name.as_str())
+                    .collect();
+                vars.set("subtasks", &subtask_names.join(", "));
+
+                let parent = &mut orchestration.parent;
+                match &parent.state {
+                    SlotState::Pending => {
+                        parent.state = SlotState::Starting;
+                        creates.push(Action::CreateAgent {
+                            name: parent.task.name.clone(),
+                            branch: parent.branch.clone(),
+                            task: Some(parent.task.clone()),
+                            base_branch: None,
+                            profile: config.profile.clone(),
+                            prompt: Some(grove::agent::InitialPrompt::Template(
+                                vars.render(&config.parent_prompt),
+                            )),
+                        });
+                    }
+                    SlotState::Failed { reason } => {
+                        orchestration.stage = Stage::Failed {
+                            reason: format!("parent agent: {}", reason),
+                        };
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                let prompt = grove::agent::InitialPrompt::Template(vars.render(&config.prompt));
+                for i in orchestration.to_dispatch(config.max_concurrent) {
+                    let slot = &mut orchestration.subtasks[i];
+                    slot.state = SlotState::Starting;
+                    creates.push(Action::CreateAgent {
+                        name: slot.task.name.clone(),
+                        branch: slot.branch.clone(),
+                        task: Some(slot.task.clone()),
+                        base_branch: Some(orchestration.parent.branch.clone()),
+                        profile: config.profile.clone(),
+                        prompt: Some(prompt.clone()),
+                    });
+                }
+
+                if orchestration.all_done() {
+                    orchestration.stage = Stage::Finishing;
+                    finished.push(index);
+                }
+            }
+
+            for action in creates {
+                action_tx.send(action)?;
+            }
+            for index in finished {
+                finish_orchestration(state, index, action_tx);
+            }
+        }
+
+        Action::OrchestrationMerged {
+            parent_branch,
+            result,
+        } => {
+            use grove::app::config::OrchestratorFinish;
+            use grove::app::orchestrator::Stage;
+
+            let Some(orchestration) = state
+                .orchestrations
+                .iter_mut()
+                .find(|o| o.parent.branch == parent_branch)
+            else {
+                return Ok(false);
+            };
+            let parent_name = orchestration.parent.task.name.clone();
+            let parent_agent = orchestration.parent.state.agent_id();
+            match result {
+                Ok(()) => {
+                    orchestration.stage = Stage::Finished;
+                    state.log_info(format!(
+                        "Merged the subtasks of '{}' into {}",
+                        parent_name, parent_branch
+                    ));
+                    if let Some(id) = parent_agent {
+                        match state.settings.repo_config.orchestrator.finish {
+                            OrchestratorFinish::MergeQueue => {
+                                if !state.merge_queue.contains(&id) {
+                                    state.merge_queue.push(id);
+                                }
+                                state.show_success(format!(
+                                    "'{}' is done and queued for landing",
+                                    parent_name
+                                ));
+                            }
+                            OrchestratorFinish::PullRequest => {
+                                action_tx.send(Action::PushBranch { id })?;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    orchestration.stage = Stage::Failed { reason: e.clone() };
+                    state.log_error(format!(
+                        "Failed to combine the subtasks of '{}': {}",
+                        parent_name, e
+                    ));
+                    state.show_error(format!("Failed to combine subtasks: {}", e));
+                }
+            }
+        }
+
+        Action::ToggleOrchestrator => {
+            state.show_orchestrator = !state.show_orchestrator;
+            if state.show_orchestrator && state.orchestrations.is_empty() {
+                state.show_orchestrator = false;
+                state.show_info("No orchestrations. Press 'o' on a parent task in the task list");
+            }
+        }
+
+        Action::OrchestratorSelectNext | Action::OrchestratorSelectPrev => {
+            let count = state.orchestrations.len();
+            if count > 0 {
+                let delta = if matches!(action, Action::OrchestratorSelectNext) {
+                    1
+                } else {
+                    count - 1
+                };
+                state.orchestrator_selected = (state.orchestrator_selected + delta) % count;
+            }
+        }
+
+        Action::RetryOrchestration => {
+            if let Some(orchestration) = state.orchestrations.get_mut(state.orchestrator_selected) {
+                orchestration.retry();
+                let name = orchestration.parent.task.name.clone();
+                state.log_info(format!("Retrying orchestration of '{}'", name));
+                action_tx.send(Action::AdvanceOrchestrations)?;
+            }
+        }
+
+        Action::CancelOrchestration => {
+            if state.orchestrator_selected < state.orchestrations.len() {
+                let orchestration = state.orchestrations.remove(state.orchestrator_selected);
+                state.log_info(format!(
+                    "Stopped orchestrating '{}' (its agents are kept)",
+                    orchestration.parent.task.name
+                ));
+                state.orchestrator_selected = state
+                    .orchestrator_selected
+                    .min(state.orchestrations.len().saturating_sub(1));
+                if state.orchestrations.is_empty() {
+                    state.show_orchestrator = false;
+                }
+            }
+        }
+
         // UI state
         Action::ToggleDiffView => {
             // Diff view removed for simplicity
@@ -5678,6 +7191,45 @@ async fn process_action(
             state.show_logs = !state.show_logs;
         }
 
+        Action::ToggleOverlapMatrix => {
+            state.show_overlap_matrix = !state.show_overlap_matrix;
+            if state.show_overlap_matrix {
+                action_tx.send(Action::RefreshOverlaps)?;
+            }
+        }
+
+        Action::RefreshOverlaps => {
+            let main_branch = state.settings.repo_config.git.main_branch.clone();
+            let candidates: Vec<OverlapCandidate> = state
+                .agents
+                .values()
+                .map(|agent| OverlapCandidate {
+                    id: agent.id,
+                    worktree_path: agent.worktree_path.clone(),
+                    branch: agent.branch.clone(),
+                    base_ref: agent.diff_base_ref(&main_branch),
+                })
+                .collect();
+            if candidates.len() < 2 {
+                state.overlaps.clear();
+                return Ok(false);
+            }
+
+            let worktree = Worktree::new(&state.repo_path, state.worktree_base.clone());
+            let tx = action_tx.clone();
+            tokio::task::spawn_blocking(move || {
+                let overlaps = detect_overlaps(&worktree, &candidates);
+                let _ = tx.send(Action::UpdateOverlaps { overlaps });
+            });
+        }
+
+        Action::UpdateOverlaps { overlaps } => {
+            state.overlaps = overlaps
+                .into_iter()
+                .filter(|o| state.agents.contains_key(&o.a) && state.agents.contains_key(&o.b))
+                .collect();
+        }
+
         Action::ToggleStatusDebug => {
             if state.config.global.debug_mode {
                 state.show_status_debug = !state.show_status_debug;
@@ -5907,6 +7459,31 @@ async fn process_action(
 
         Action::EnterInputMode(mode) => {
             state.enter_input_mode(mode.clone());
+            match mode {
+                InputMode::NewAgent => {
+                    state.pending_base_branch = None;
+                    state.pending_profile = state.default_launch_profile().map(|p| p.name);
+                }
+                InputMode::ImportAgent => {
+                    state.pending_profile = state.default_launch_profile().map(|p| p.name);
+                }
+                InputMode::SelectBaseBranch => {
+                    // Default to stacking on the selected agent's branch
+                    state.input_buffer = state
+                        .selected_agent()
+                        .map(|a| a.branch.clone())
+                        .unwrap_or_else(|| state.settings.repo_config.git.main_branch.clone());
+                }
+                InputMode::CaptureFixture => {
+                    // Start from the detected status; the user corrects it
+                    state.show_status_debug = false;
+                    state.input_buffer = state
+                        .selected_agent()
+                        .map(|a| a.status.label().to_lowercase())
+                        .unwrap_or_default();
+                }
+                _ => {}
+            }
             if mode == InputMode::BrowseTasks {
                 state.task_list_loading = true;
                 state.task_list.clear();
@@ -5917,12 +7494,31 @@ async fn process_action(
 
         Action::ExitInputMode => {
             state.exit_input_mode();
+            state.pending_base_branch = None;
+            state.pending_profile = None;
         }
 
         Action::UpdateInput(input) => {
             state.input_buffer = input;
         }
 
+        Action::CycleLaunchProfile => {
+            let names: Vec<String> = state
+                .launch_profiles()
+                .into_iter()
+                .map(|p| p.name)
+                .collect();
+            // No profile, then each profile in turn
+            let next = match &state.pending_profile {
+                Some(current) => names
+                    .iter()
+                    .position(|n| n == current)
+                    .and_then(|i| names.get(i + 1)),
+                None => names.first(),
+            };
+            state.pending_profile = next.cloned();
+        }
+
         Action::SubmitInput => {
             if let Some(mode) = state.input_mode.clone() {
                 let input = state.input_buffer.clone();
@@ -5931,7 +7527,12 @@ async fn process_action(
                 match mode {
                     InputMode::NewAgent => {
                         if !input.is_empty() {
-                            let branch = grove::core::common::sanitize_branch_name(&input);
+                            let profile = state
+                                .pending_profile
+                                .take()
+                                .and_then(|name| state.launch_profile(&name));
+                            let branch =
+                                grove::agent::profile::branch_name(profile.as_ref(), &input);
                             if branch.is_empty() {
                                 action_tx.send(Action::ShowError(
                                     "Invalid name: name cannot be only spaces".to_string(),
@@ -5941,10 +7542,56 @@ async fn process_action(
                                     name: input.trim().to_string(),
                                     branch,
                                     task: None,
+                                    base_branch: state.pending_base_branch.take(),
+                                    profile: profile.map(|p| p.name),
+                                    prompt: None,
                                 })?;
                             }
                         }
                     }
+                    InputMode::SelectBaseBranch => {
+                        let base = input.trim();
+                        state.pending_base_branch = if base.is_empty()
+                            || base == state.settings.repo_config.git.main_branch
+                        {
+                            None
+                        } else {
+                            Some(base.to_string())
+                        };
+                        state.enter_input_mode(InputMode::NewAgent);
+                    }
+                    InputMode::ImportAgent => match grove::agent::ImportSource::parse(&input) {
+                        Some(source) => action_tx.send(Action::ImportAgent {
+                            source,
+                            profile: state.pending_profile.take(),
+                        })?,
+                        None => action_tx.send(Action::ShowError(
+                            "Enter a branch, worktree path or PR number".to_string(),
+                        ))?,
+                    },
+                    InputMode::SetBudget => {
+                        if let Some(id) = state.selected_agent_id() {
+                            match grove::agent::budget::parse_budget(&input) {
+                                Ok(budget) => {
+                                    let budget = (!budget.is_empty()).then_some(budget);
+                                    action_tx.send(Action::SetAgentBudget { id, budget })?;
+                                }
+                                Err(e) => action_tx.send(Action::ShowError(e.to_string()))?,
+                            }
+                        }
+                    }
+                    InputMode::CaptureFixture => {
+                        if let Some(id) = state.selected_agent_id() {
+                            match grove::agent::fixture::parse_status(&input) {
+                                Some(expected) => action_tx
+                                    .send(Action::CaptureDetectionFixture { id, expected })?,
+                                None => action_tx.send(Action::ShowError(format!(
+                                    "Unknown status '{}'",
+                                    input.trim()
+                                )))?,
+                            }
+                        }
+                    }
                     InputMode::SetNote => {
                         if let Some(id) = state.selected_agent_id() {
                             let note = if input.is_empty() { None } else { Some(input) };
@@ -5969,6 +7616,9 @@ async fn process_action(
                             action_tx.send(Action::PushBranch { id })?;
                         }
                     }
+                    InputMode::ConfirmLandQueue => {
+                        action_tx.send(Action::LandMergeQueue)?;
+                    }
                     InputMode::AssignAsana => {
                         if !input.is_empty() {
                             if let Some(id) = state.selected_agent_id() {
@@ -6072,7 +7722,8 @@ async fn process_action(
 
             if let Some(agent) = state.selected_agent() {
                 let git_sync = GitSync::new(&agent.worktree_path);
-                if let Ok(status) = git_sync.get_status(&state.settings.repo_config.git.main_branch)
+                if let Ok(status) = git_sync
+                    .get_status(agent.base_branch_or(&state.settings.repo_config.git.main_branch))
                 {
                     let id = agent.id;
                     action_tx.send(Action::UpdateGitStatus { id, status })?;
@@ -6090,11 +7741,13 @@ async fn process_action(
                 let branch_for_github = branch.clone();
                 let branch_for_codeberg = branch.clone();
                 let worktree_path = agent.worktree_path.clone();
-                let main_branch = state.settings.repo_config.git.main_branch.clone();
+                let base_branch = agent
+                    .base_branch_or(&state.settings.repo_config.git.main_branch)
+                    .to_string();
 
                 // Refresh git status
                 let git_sync = GitSync::new(&worktree_path);
-                if let Ok(status) = git_sync.get_status(&main_branch) {
+                if let Ok(status) = git_sync.get_status(&base_branch) {
                     action_tx.send(Action::UpdateGitStatus { id, status })?;
                 }
 
@@ -6355,9 +8008,9 @@ async fn process_action(
             }
         }
 
-        Action::UpdateChecklistProgress { id, progress } => {
+        Action::UpdateChecklist { id, checklist } => {
             if let Some(agent) = state.agents.get_mut(&id) {
-                agent.checklist_progress = progress;
+                agent.update_checklist(checklist);
             }
         }
 
@@ -6369,6 +8022,39 @@ async fn process_action(
             state.record_system_metrics(cpu_percent, memory_used, memory_total);
         }
 
+        Action::UpdateAgentResources { usage } => {
+            let perf = state.config.performance.clone();
+            let now = std::time::Instant::now();
+            let mut runaway = Vec::new();
+            for (id, agent_usage) in &usage {
+                if !agent_usage.exceeds(perf.agent_cpu_warn_percent, perf.agent_memory_warn_mb) {
+                    state.resource_over_since.remove(id);
+                    state.resource_warned.remove(id);
+                    continue;
+                }
+                let since = *state.resource_over_since.entry(*id).or_insert(now);
+                let sustained =
+                    now.duration_since(since) >= Duration::from_secs(perf.agent_warn_after_secs);
+                if sustained && state.resource_warned.insert(*id) {
+                    if let Some(agent) = state.agents.get(id) {
+                        runaway.push((agent.name.clone(), agent_usage.summary()));
+                    }
+                }
+            }
+            state
+                .resource_over_since
+                .retain(|id, _| usage.contains_key(id));
+            state.resource_warned.retain(|id| usage.contains_key(id));
+            state.agent_resources = usage;
+            for (name, summary) in runaway {
+                state.log_warn(format!(
+                    "Agent '{}' is using {} (CPU mem procs)",
+                    name, summary
+                ));
+                state.show_warning(format!("'{}' looks runaway: {}", name, summary));
+            }
+        }
+
         Action::SetLoading(message) => {
             state.loading_message = message;
         }
@@ -6396,7 +8082,8 @@ async fn process_action(
             if success {
                 state.remove_agent(id);
                 state.log_info(&message);
-                let _ = agent_watch_tx.send(state.agents.keys().cloned().collect());
+                let _ = agent_watch_tx.send(state.agent_ai_agents());
+                let _ = session_watch_tx.send(state.agent_sessions());
                 let _ = branch_watch_tx.send(
                     state
                         .agents
@@ -6427,6 +8114,8 @@ async fn process_action(
                 state.settings.pending_log_level = state.config.global.log_level;
                 state.settings.pending_worktree_location = state.config.global.worktree_location;
                 state.settings.pending_debug_mode = state.config.global.debug_mode;
+                state.settings.pending_claude_hooks = state.config.global.claude_hooks;
+                state.settings.pending_mcp_server = state.config.global.mcp_server;
                 state.settings.pending_ui = state.config.ui.clone();
                 state.settings.pending_automation = state.settings.repo_config.automation.clone();
 
@@ -6523,6 +8212,9 @@ async fn process_action(
                         grove::app::SettingsField::CodebergCiProvider => {
                             grove::app::CodebergCiProvider::all().len()
                         }
+                        grove::app::SettingsField::SyncStrategy => {
+                            grove::app::SyncStrategy::all().len()
+                        }
                         grove::app::SettingsField::ProjectMgmtProvider => {
                             grove::app::ProjectMgmtProvider::all().len()
                         }
@@ -6591,6 +8283,16 @@ async fn process_action(
                         selected_index: idx,
                     };
                 }
+                grove::app::SettingsField::SyncStrategy => {
+                    let current = &state.settings.repo_config.git.sync_strategy;
+                    let idx = grove::app::SyncStrategy::all()
+                        .iter()
+                        .position(|s| s == current)
+                        .unwrap_or(0);
+                    state.settings.dropdown = grove::app::DropdownState::Open {
+                        selected_index: idx,
+                    };
+                }
                 grove::app::SettingsField::CodebergCiProvider => {
                     let current = &state.settings.repo_config.git.codeberg.ci_provider;
                     let idx = grove::app::CodebergCiProvider::all()
@@ -6610,6 +8312,25 @@ async fn process_action(
                     state.settings.editing_text = true;
                     state.settings.text_buffer = state.settings.repo_config.git.main_branch.clone();
                 }
+                grove::app::SettingsField::QueueVerifyCommand => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer = state
+                        .settings
+                        .repo_config
+                        .git
+                        .merge_queue
+                        .verify_command
+                        .clone()
+                        .unwrap_or_default();
+                }
+                grove::app::SettingsField::QueuePush => {
+                    let queue = &mut state.settings.repo_config.git.merge_queue;
+                    queue.push = !queue.push;
+                }
+                grove::app::SettingsField::QueueArchive => {
+                    let queue = &mut state.settings.repo_config.git.merge_queue;
+                    queue.archive_landed = !queue.archive_landed;
+                }
                 grove::app::SettingsField::WorktreeSymlinks => {
                     state.settings.init_file_browser(&state.repo_path);
                 }
@@ -6795,6 +8516,16 @@ async fn process_action(
                     state.settings.pending_debug_mode = !state.settings.pending_debug_mode;
                     state.config.global.debug_mode = state.settings.pending_debug_mode;
                 }
+                grove::app::SettingsField::ClaudeHooks => {
+                    state.settings.pending_claude_hooks = !state.settings.pending_claude_hooks;
+                    state.config.global.claude_hooks = state.settings.pending_claude_hooks;
+                    state.show_info("Claude Code hooks change applies after restarting Grove");
+                }
+                grove::app::SettingsField::McpServer => {
+                    state.settings.pending_mcp_server = !state.settings.pending_mcp_server;
+                    state.config.global.mcp_server = state.settings.pending_mcp_server;
+                    state.show_info("MCP server change applies after restarting Grove");
+                }
                 grove::app::SettingsField::ProjectMgmtProvider => {
                     let current = state.settings.repo_config.project_mgmt.provider;
                     let idx = grove::app::ProjectMgmtProvider::all()
@@ -7017,6 +8748,93 @@ async fn process_action(
                     state.settings.repo_config.dev_server.auto_start =
                         !state.settings.repo_config.dev_server.auto_start;
                 }
+                grove::app::SettingsField::DevServerAutoPort => {
+                    state.settings.repo_config.dev_server.auto_port =
+                        !state.settings.repo_config.dev_server.auto_port;
+                }
+                grove::app::SettingsField::DevServerPortRange => {
+                    let dev_server = &state.settings.repo_config.dev_server;
+                    state.settings.text_buffer = format!(
+                        "{}-{}",
+                        dev_server.port_range_start, dev_server.port_range_end
+                    );
+                    state.settings.editing_text = true;
+                }
+                grove::app::SettingsField::DevServerPortEnv => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer =
+                        state.settings.repo_config.dev_server.port_env.clone();
+                }
+                grove::app::SettingsField::DevServerProxyEnabled => {
+                    state.settings.repo_config.dev_server.proxy_enabled =
+                        !state.settings.repo_config.dev_server.proxy_enabled;
+                }
+                grove::app::SettingsField::DevServerAutoSendErrors => {
+                    state.settings.repo_config.dev_server.auto_send_errors =
+                        !state.settings.repo_config.dev_server.auto_send_errors;
+                }
+                grove::app::SettingsField::StallEnabled => {
+                    state.settings.repo_config.stall.enabled =
+                        !state.settings.repo_config.stall.enabled;
+                }
+                grove::app::SettingsField::StallAfterSecs => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer = state
+                        .settings
+                        .repo_config
+                        .stall
+                        .stall_after_secs
+                        .to_string();
+                }
+                grove::app::SettingsField::StallRepeatThreshold => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer = state
+                        .settings
+                        .repo_config
+                        .stall
+                        .repeat_threshold
+                        .to_string();
+                }
+                grove::app::SettingsField::StallNudge => {
+                    state.settings.repo_config.stall.nudge =
+                        !state.settings.repo_config.stall.nudge;
+                }
+                grove::app::SettingsField::DevServerProxyPort => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer =
+                        state.settings.repo_config.dev_server.proxy_port.to_string();
+                }
+                grove::app::SettingsField::ProvisionCopyFiles => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer = state
+                        .settings
+                        .repo_config
+                        .provisioning
+                        .copy_files
+                        .join(", ");
+                }
+                grove::app::SettingsField::ProvisionPostCreate => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer = grove::app::config::command_list_to_string(
+                        &state.settings.repo_config.provisioning.post_create,
+                    );
+                }
+                grove::app::SettingsField::ProvisionPreDelete => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer = grove::app::config::command_list_to_string(
+                        &state.settings.repo_config.provisioning.pre_delete,
+                    );
+                }
+                grove::app::SettingsField::VerifyCommands => {
+                    state.settings.editing_text = true;
+                    state.settings.text_buffer = grove::app::config::command_list_to_string(
+                        &state.settings.repo_config.verify.commands,
+                    );
+                }
+                grove::app::SettingsField::VerifyAutoRun => {
+                    state.settings.repo_config.verify.auto_run =
+                        !state.settings.repo_config.verify.auto_run;
+                }
                 grove::app::SettingsField::AutomationOnTaskAssign => {
                     let current = &state.settings.pending_automation.on_task_assign;
                     let idx = if current.is_none() {
@@ -7123,6 +8941,11 @@ async fn process_action(
                         state.settings.repo_config.git.main_branch =
                             state.settings.text_buffer.clone();
                     }
+                    grove::app::SettingsField::QueueVerifyCommand => {
+                        let val = state.settings.text_buffer.clone();
+                        state.settings.repo_config.git.merge_queue.verify_command =
+                            if val.is_empty() { None } else { Some(val) };
+                    }
                     grove::app::SettingsField::WorktreeSymlinks => {
                         state.settings.repo_config.dev_server.worktree_symlinks = state
                             .settings
@@ -7263,10 +9086,78 @@ async fn process_action(
                         state.settings.repo_config.dev_server.working_dir =
                             state.settings.text_buffer.clone();
                     }
+                    grove::app::SettingsField::ProvisionCopyFiles => {
+                        state.settings.repo_config.provisioning.copy_files = state
+                            .settings
+                            .text_buffer
+                            .split(',')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect();
+                    }
+                    grove::app::SettingsField::ProvisionPostCreate => {
+                        match grove::app::config::parse_command_list(&state.settings.text_buffer) {
+                            Ok(commands) => {
+                                state.settings.repo_config.provisioning.post_create = commands
+                            }
+                            Err(e) => state.show_error(format!("Invalid commands: {:#}", e)),
+                        }
+                    }
+                    grove::app::SettingsField::ProvisionPreDelete => {
+                        match grove::app::config::parse_command_list(&state.settings.text_buffer) {
+                            Ok(commands) => {
+                                state.settings.repo_config.provisioning.pre_delete = commands
+                            }
+                            Err(e) => state.show_error(format!("Invalid commands: {:#}", e)),
+                        }
+                    }
+                    grove::app::SettingsField::VerifyCommands => {
+                        match grove::app::config::parse_command_list(&state.settings.text_buffer) {
+                            Ok(commands) => state.settings.repo_config.verify.commands = commands,
+                            Err(e) => state.show_error(format!("Invalid commands: {:#}", e)),
+                        }
+                    }
                     grove::app::SettingsField::DevServerPort => {
                         state.settings.repo_config.dev_server.port =
                             state.settings.text_buffer.parse().ok();
                     }
+                    grove::app::SettingsField::DevServerPortRange => {
+                        let range =
+                            state
+                                .settings
+                                .text_buffer
+                                .split_once('-')
+                                .and_then(|(start, end)| {
+                                    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+                                });
+                        match range {
+                            Some((start, end)) if start <= end => {
+                                let dev_server = &mut state.settings.repo_config.dev_server;
+                                dev_server.port_range_start = start;
+                                dev_server.port_range_end = end;
+                            }
+                            _ => state.show_error("Port range must look like 3100-3999"),
+                        }
+                    }
+                    grove::app::SettingsField::DevServerPortEnv => {
+                        state.settings.repo_config.dev_server.port_env =
+                            state.settings.text_buffer.trim().to_string();
+                    }
+                    grove::app::SettingsField::StallAfterSecs => {
+                        if let Ok(secs) = state.settings.text_buffer.trim().parse() {
+                            state.settings.repo_config.stall.stall_after_secs = secs;
+                        }
+                    }
+                    grove::app::SettingsField::StallRepeatThreshold => {
+                        if let Ok(threshold) = state.settings.text_buffer.trim().parse() {
+                            state.settings.repo_config.stall.repeat_threshold = threshold;
+                        }
+                    }
+                    grove::app::SettingsField::DevServerProxyPort => {
+                        if let Ok(port) = state.settings.text_buffer.trim().parse() {
+                            state.settings.repo_config.dev_server.proxy_port = port;
+                        }
+                    }
                     grove::app::SettingsField::SummaryPrompt => {
                         let val = state.settings.text_buffer.clone();
                         state.settings.repo_config.prompts.summary_prompt =
@@ -7547,6 +9438,12 @@ async fn process_action(
                             state.worktree_base = state.config.worktree_base_path(&state.repo_path);
                         }
                     }
+                    grove::app::SettingsField::SyncStrategy => {
+                        if let Some(strategy) = grove::app::SyncStrategy::all().get(selected_index)
+                        {
+                            state.settings.repo_config.git.sync_strategy = *strategy;
+                        }
+                    }
                     grove::app::SettingsField::CodebergCiProvider => {
                         if let Some(provider) =
                             grove::app::CodebergCiProvider::all().get(selected_index)
@@ -7705,6 +9602,7 @@ async fn process_action(
             if let Err(e) = state.settings.repo_config.save(&state.repo_path) {
                 state.log_error(format!("Failed to save repo config: {}", e));
             }
+            let _ = stall_watch_tx.send(state.settings.repo_config.stall.clone());
             state.settings.active = false;
         }
 
@@ -7727,6 +9625,7 @@ async fn process_action(
             if let Err(e) = state.settings.repo_config.save(&state.repo_path) {
                 state.log_error(format!("Failed to save repo config: {}", e));
             }
+            let _ = stall_watch_tx.send(state.settings.repo_config.stall.clone());
 
             let new_provider = state.settings.repo_config.project_mgmt.provider;
             if old_provider != new_provider {
@@ -9614,18 +11513,17 @@ async fn process_action(
             action_type,
         } => {
             let automation_data = state.agents.get(&agent_id).and_then(|agent| {
-                agent.pm_task_status.id().map(|task_id| {
+                agent.pm_task_status.id().map(|_| {
                     let config = state.settings.repo_config.automation.clone();
-                    let provider = state.settings.repo_config.project_mgmt.provider;
                     let is_subtask = match &agent.pm_task_status {
                         ProjectMgmtTaskStatus::Asana(status) => status.is_subtask(),
                         _ => false,
                     };
-                    (task_id.to_string(), config, provider, is_subtask)
+                    (config, is_subtask)
                 })
             });
 
-            if let Some((task_id, config, provider, is_subtask)) = automation_data {
+            if let Some((config, is_subtask)) = automation_data {
                 let status_name = match action_type {
                     grove::app::config::AutomationActionType::TaskAssign => {
                         if is_subtask {
@@ -9653,143 +11551,90 @@ async fn process_action(
                 };
 
                 state.log_info(format!("Automation: Moving task to '{}'...", status));
+                action_tx.send(Action::MoveTaskToStatus { agent_id, status })?;
+            }
+        }
 
-                match provider {
-                    grove::app::config::ProjectMgmtProvider::Asana => {
-                        let client = asana_client.clone();
-                        let task_id = task_id.to_string();
-                        let status = status.clone();
-                        let status_lower = status.to_lowercase();
-                        let tx = action_tx.clone();
+        Action::MoveTaskToStatus { agent_id, status } => {
+            let Some((task_id, is_subtask)) = state.agents.get(&agent_id).and_then(|agent| {
+                let is_subtask = match &agent.pm_task_status {
+                    ProjectMgmtTaskStatus::Asana(status) => status.is_subtask(),
+                    _ => false,
+                };
+                agent
+                    .pm_task_status
+                    .id()
+                    .map(|task_id| (task_id.to_string(), is_subtask))
+            }) else {
+                return Ok(false);
+            };
+            let provider = state.settings.repo_config.project_mgmt.provider;
 
-                        tokio::spawn(async move {
-                            if is_subtask {
-                                if status_lower == "complete" {
-                                    match client.complete_task(&task_id).await {
-                                        Ok(_) => {
-                                            let _ = tx.send(Action::ShowToast {
-                                                message: "Automation: Marked task as complete"
-                                                    .to_string(),
-                                                level: grove::app::ToastLevel::Success,
+            match provider {
+                grove::app::config::ProjectMgmtProvider::Asana => {
+                    let client = asana_client.clone();
+                    let task_id = task_id.to_string();
+                    let status = status.clone();
+                    let status_lower = status.to_lowercase();
+                    let tx = action_tx.clone();
+
+                    tokio::spawn(async move {
+                        if is_subtask {
+                            if status_lower == "complete" {
+                                match client.complete_task(&task_id).await {
+                                    Ok(_) => {
+                                        let _ = tx.send(Action::ShowToast {
+                                            message: "Automation: Marked task as complete"
+                                                .to_string(),
+                                            level: grove::app::ToastLevel::Success,
+                                        });
+                                        if let Ok(task) = client.get_task(&task_id).await {
+                                            let new_status = ProjectMgmtTaskStatus::Asana(
+                                                AsanaTaskStatus::Completed {
+                                                    gid: task.gid,
+                                                    name: task.name,
+                                                    is_subtask: task.parent.is_some(),
+                                                    status_name: "Complete".to_string(),
+                                                },
+                                            );
+                                            let _ = tx.send(Action::UpdateProjectTaskStatus {
+                                                id: agent_id,
+                                                status: new_status,
                                             });
-                                            if let Ok(task) = client.get_task(&task_id).await {
-                                                let new_status = ProjectMgmtTaskStatus::Asana(
-                                                    AsanaTaskStatus::Completed {
-                                                        gid: task.gid,
-                                                        name: task.name,
-                                                        is_subtask: task.parent.is_some(),
-                                                        status_name: "Complete".to_string(),
-                                                    },
-                                                );
-                                                let _ = tx.send(Action::UpdateProjectTaskStatus {
-                                                    id: agent_id,
-                                                    status: new_status,
-                                                });
-                                            }
-                                        }
-                                        Err(e) => {
-                                            let _ = tx.send(Action::ShowError(format!(
-                                                "Automation failed: {}",
-                                                e
-                                            )));
                                         }
                                     }
-                                } else if status_lower == "incomplete" {
-                                    match client.incomplete_task(&task_id).await {
-                                        Ok(_) => {
-                                            let _ = tx.send(Action::ShowToast {
-                                                message: "Automation: Marked task as incomplete"
-                                                    .to_string(),
-                                                level: grove::app::ToastLevel::Success,
-                                            });
-                                            if let Ok(task) = client.get_task(&task_id).await {
-                                                let url = task.permalink_url.unwrap_or_else(|| {
-                                                    format!(
-                                                        "https://app.asana.com/0/0/{}/f",
-                                                        task.gid
-                                                    )
-                                                });
-                                                let new_status = ProjectMgmtTaskStatus::Asana(
-                                                    AsanaTaskStatus::InProgress {
-                                                        gid: task.gid,
-                                                        name: task.name,
-                                                        url,
-                                                        is_subtask: task.parent.is_some(),
-                                                        status_name: "Incomplete".to_string(),
-                                                    },
-                                                );
-                                                let _ = tx.send(Action::UpdateProjectTaskStatus {
-                                                    id: agent_id,
-                                                    status: new_status,
-                                                });
-                                            }
-                                        }
-                                        Err(e) => {
-                                            let _ = tx.send(Action::ShowError(format!(
-                                                "Automation failed: {}",
-                                                e
-                                            )));
-                                        }
+                                    Err(e) => {
+                                        let _ = tx.send(Action::ShowError(format!(
+                                            "Automation failed: {}",
+                                            e
+                                        )));
                                     }
                                 }
-                            } else {
-                                match client.get_sections().await {
-                                    Ok(sections) => {
-                                        let mut found = false;
-                                        for section in &sections {
-                                            if section.name.eq_ignore_ascii_case(&status) {
-                                                match client
-                                                    .move_task_to_section(&task_id, &section.gid)
-                                                    .await
-                                                {
-                                                    Ok(_) => {
-                                                        let _ = tx.send(Action::ShowToast {
-                                                            message: format!(
-                                                                "Automation: Moved task to '{}'",
-                                                                section.name
-                                                            ),
-                                                            level: grove::app::ToastLevel::Success,
-                                                        });
-                                                        if let Ok(task) =
-                                                            client.get_task(&task_id).await
-                                                        {
-                                                            let url = task.permalink_url
-                                                                .unwrap_or_else(|| format!("https://app.asana.com/0/0/{}/f", task.gid));
-                                                            let new_status =
-                                                                ProjectMgmtTaskStatus::Asana(
-                                                                    AsanaTaskStatus::InProgress {
-                                                                        gid: task.gid,
-                                                                        name: task.name,
-                                                                        url,
-                                                                        is_subtask: false,
-                                                                        status_name: section
-                                                                            .name
-                                                                            .clone(),
-                                                                    },
-                                                                );
-                                                            let _ = tx.send(
-                                                                Action::UpdateProjectTaskStatus {
-                                                                    id: agent_id,
-                                                                    status: new_status,
-                                                                },
-                                                            );
-                                                        }
-                                                        found = true;
-                                                    }
-                                                    Err(e) => {
-                                                        let _ = tx.send(Action::ShowError(
-                                                            format!("Automation failed: {}", e),
-                                                        ));
-                                                    }
-                                                }
-                                                break;
-                                            }
-                                        }
-                                        if !found {
-                                            let _ = tx.send(Action::ShowError(format!(
-                                                "Automation: No section found matching '{}'",
-                                                status
-                                            )));
+                            } else if status_lower == "incomplete" {
+                                match client.incomplete_task(&task_id).await {
+                                    Ok(_) => {
+                                        let _ = tx.send(Action::ShowToast {
+                                            message: "Automation: Marked task as incomplete"
+                                                .to_string(),
+                                            level: grove::app::ToastLevel::Success,
+                                        });
+                                        if let Ok(task) = client.get_task(&task_id).await {
+                                            let url = task.permalink_url.unwrap_or_else(|| {
+                                                format!("https://app.asana.com/0/0/{}/f", task.gid)
+                                            });
+                                            let new_status = ProjectMgmtTaskStatus::Asana(
+                                                AsanaTaskStatus::InProgress {
+                                                    gid: task.gid,
+                                                    name: task.name,
+                                                    url,
+                                                    is_subtask: task.parent.is_some(),
+                                                    status_name: "Incomplete".to_string(),
+                                                },
+                                            );
+                                            let _ = tx.send(Action::UpdateProjectTaskStatus {
+                                                id: agent_id,
+                                                status: new_status,
+                                            });
                                         }
                                     }
                                     Err(e) => {
@@ -9800,54 +11645,39 @@ async fn process_action(
                                     }
                                 }
                             }
-                        });
-                    }
-                    grove::app::config::ProjectMgmtProvider::Notion => {
-                        let client = notion_client.clone();
-                        let status_prop_name = state
-                            .settings
-                            .repo_config
-                            .project_mgmt
-                            .notion
-                            .status_property_name
-                            .clone();
-                        let task_id = task_id.to_string();
-                        let status = status.clone();
-                        let tx = action_tx.clone();
-
-                        tokio::spawn(async move {
-                            match client.get_status_options().await {
-                                Ok(opts) => {
+                        } else {
+                            match client.get_sections().await {
+                                Ok(sections) => {
                                     let mut found = false;
-                                    for opt in &opts.all_options {
-                                        if opt.name.eq_ignore_ascii_case(&status) {
-                                            let prop_name = status_prop_name
-                                                .unwrap_or_else(|| "Status".to_string());
+                                    for section in &sections {
+                                        if section.name.eq_ignore_ascii_case(&status) {
                                             match client
-                                                .update_page_status(&task_id, &prop_name, &opt.id)
+                                                .move_task_to_section(&task_id, &section.gid)
                                                 .await
                                             {
                                                 Ok(_) => {
                                                     let _ = tx.send(Action::ShowToast {
                                                         message: format!(
                                                             "Automation: Moved task to '{}'",
-                                                            opt.name
+                                                            section.name
                                                         ),
                                                         level: grove::app::ToastLevel::Success,
                                                     });
-                                                    if let Ok(page) =
-                                                        client.get_page(&task_id).await
+                                                    if let Ok(task) =
+                                                        client.get_task(&task_id).await
                                                     {
+                                                        let url = task.permalink_url
+                                                                .unwrap_or_else(|| format!("https://app.asana.com/0/0/{}/f", task.gid));
                                                         let new_status =
-                                                            ProjectMgmtTaskStatus::Notion(
-                                                                NotionTaskStatus::Linked {
-                                                                    page_id: page.id,
-                                                                    name: page.name,
-                                                                    url: page.url,
-                                                                    status_option_id: opt
-                                                                        .id
+                                                            ProjectMgmtTaskStatus::Asana(
+                                                                AsanaTaskStatus::InProgress {
+                                                                    gid: task.gid,
+                                                                    name: task.name,
+                                                                    url,
+                                                                    is_subtask: false,
+                                                                    status_name: section
+                                                                        .name
                                                                         .clone(),
-                                                                    status_name: opt.name.clone(),
                                                                 },
                                                             );
                                                         let _ = tx.send(
@@ -9871,7 +11701,7 @@ async fn process_action(
                                     }
                                     if !found {
                                         let _ = tx.send(Action::ShowError(format!(
-                                            "Automation: No status found matching '{}'",
+                                            "Automation: No section found matching '{}'",
                                             status
                                         )));
                                     }
@@ -9883,197 +11713,260 @@ async fn process_action(
                                     )));
                                 }
                             }
-                        });
-                    }
-                    grove::app::config::ProjectMgmtProvider::Clickup => {
-                        let client = clickup_client.clone();
-                        let task_id = task_id.to_string();
-                        let status = status.clone();
-                        let tx = action_tx.clone();
+                        }
+                    });
+                }
+                grove::app::config::ProjectMgmtProvider::Notion => {
+                    let client = notion_client.clone();
+                    let status_prop_name = state
+                        .settings
+                        .repo_config
+                        .project_mgmt
+                        .notion
+                        .status_property_name
+                        .clone();
+                    let task_id = task_id.to_string();
+                    let status = status.clone();
+                    let tx = action_tx.clone();
 
-                        tokio::spawn(async move {
-                            match client.update_task_status(&task_id, &status).await {
-                                Ok(_) => {
-                                    let _ = tx.send(Action::ShowToast {
-                                        message: format!("Automation: Moved task to '{}'", status),
-                                        level: grove::app::ToastLevel::Success,
-                                    });
-                                    if let Ok(task) = client.get_task(&task_id).await {
-                                        let url = task.url.clone().unwrap_or_default();
-                                        let is_subtask = task.parent.is_some();
-                                        let new_status = if task.status.status_type == "closed" {
-                                            ProjectMgmtTaskStatus::ClickUp(
-                                                ClickUpTaskStatus::Completed {
-                                                    id: task.id,
-                                                    name: task.name,
-                                                    is_subtask,
-                                                },
-                                            )
-                                        } else {
-                                            ProjectMgmtTaskStatus::ClickUp(
-                                                ClickUpTaskStatus::InProgress {
-                                                    id: task.id,
-                                                    name: task.name,
-                                                    url,
-                                                    status: task.status.status,
-                                                    is_subtask,
-                                                },
-                                            )
-                                        };
-                                        let _ = tx.send(Action::UpdateProjectTaskStatus {
-                                            id: agent_id,
-                                            status: new_status,
-                                        });
+                    tokio::spawn(async move {
+                        match client.get_status_options().await {
+                            Ok(opts) => {
+                                let mut found = false;
+                                for opt in &opts.all_options {
+                                    if opt.name.eq_ignore_ascii_case(&status) {
+                                        let prop_name = status_prop_name
+                                            .unwrap_or_else(|| "Status".to_string());
+                                        match client
+                                            .update_page_status(&task_id, &prop_name, &opt.id)
+                                            .await
+                                        {
+                                            Ok(_) => {
+                                                let _ = tx.send(Action::ShowToast {
+                                                    message: format!(
+                                                        "Automation: Moved task to '{}'",
+                                                        opt.name
+                                                    ),
+                                                    level: grove::app::ToastLevel::Success,
+                                                });
+                                                if let Ok(page) = client.get_page(&task_id).await {
+                                                    let new_status = ProjectMgmtTaskStatus::Notion(
+                                                        NotionTaskStatus::Linked {
+                                                            page_id: page.id,
+                                                            name: page.name,
+                                                            url: page.url,
+                                                            status_option_id: opt.id.clone(),
+                                                            status_name: opt.name.clone(),
+                                                        },
+                                                    );
+                                                    let _ =
+                                                        tx.send(Action::UpdateProjectTaskStatus {
+                                                            id: agent_id,
+                                                            status: new_status,
+                                                        });
+                                                }
+                                                found = true;
+                                            }
+                                            Err(e) => {
+                                                let _ = tx.send(Action::ShowError(format!(
+                                                    "Automation failed: {}",
+                                                    e
+                                                )));
+                                            }
+                                        }
+                                        break;
                                     }
                                 }
-                                Err(e) => {
+                                if !found {
                                     let _ = tx.send(Action::ShowError(format!(
-                                        "Automation failed: {}",
-                                        e
+                                        "Automation: No status found matching '{}'",
+                                        status
                                     )));
                                 }
                             }
-                        });
-                    }
-                    grove::app::config::ProjectMgmtProvider::Airtable => {
-                        let client = airtable_client.clone();
-                        let task_id = task_id.to_string();
-                        let status = status.clone();
-                        let tx = action_tx.clone();
+                            Err(e) => {
+                                let _ =
+                                    tx.send(Action::ShowError(format!("Automation failed: {}", e)));
+                            }
+                        }
+                    });
+                }
+                grove::app::config::ProjectMgmtProvider::Clickup => {
+                    let client = clickup_client.clone();
+                    let task_id = task_id.to_string();
+                    let status = status.clone();
+                    let tx = action_tx.clone();
 
-                        tokio::spawn(async move {
-                            match client.update_record_status(&task_id, &status).await {
-                                Ok(_) => {
-                                    let _ = tx.send(Action::ShowToast {
-                                        message: format!("Automation: Moved task to '{}'", status),
-                                        level: grove::app::ToastLevel::Success,
+                    tokio::spawn(async move {
+                        match client.update_task_status(&task_id, &status).await {
+                            Ok(_) => {
+                                let _ = tx.send(Action::ShowToast {
+                                    message: format!("Automation: Moved task to '{}'", status),
+                                    level: grove::app::ToastLevel::Success,
+                                });
+                                if let Ok(task) = client.get_task(&task_id).await {
+                                    let url = task.url.clone().unwrap_or_default();
+                                    let is_subtask = task.parent.is_some();
+                                    let new_status = if task.status.status_type == "closed" {
+                                        ProjectMgmtTaskStatus::ClickUp(
+                                            ClickUpTaskStatus::Completed {
+                                                id: task.id,
+                                                name: task.name,
+                                                is_subtask,
+                                            },
+                                        )
+                                    } else {
+                                        ProjectMgmtTaskStatus::ClickUp(
+                                            ClickUpTaskStatus::InProgress {
+                                                id: task.id,
+                                                name: task.name,
+                                                url,
+                                                status: task.status.status,
+                                                is_subtask,
+                                            },
+                                        )
+                                    };
+                                    let _ = tx.send(Action::UpdateProjectTaskStatus {
+                                        id: agent_id,
+                                        status: new_status,
                                     });
-                                    if let Ok(record) = client.get_record(&task_id).await {
-                                        let status_name = record.status.clone().unwrap_or_default();
-                                        let is_subtask = record.parent_id.is_some();
-                                        let is_completed =
-                                            status_name.to_lowercase().contains("done")
-                                                || status_name.to_lowercase().contains("complete");
-                                        let new_status = if is_completed {
-                                            ProjectMgmtTaskStatus::Airtable(
-                                                AirtableTaskStatus::Completed {
-                                                    id: record.id,
-                                                    name: record.name,
-                                                    is_subtask,
-                                                },
-                                            )
-                                        } else if status_name.to_lowercase().contains("progress") {
-                                            ProjectMgmtTaskStatus::Airtable(
-                                                AirtableTaskStatus::InProgress {
-                                                    id: record.id,
-                                                    name: record.name,
-                                                    url: record.url,
-                                                    is_subtask,
-                                                },
-                                            )
-                                        } else {
-                                            ProjectMgmtTaskStatus::Airtable(
-                                                AirtableTaskStatus::NotStarted {
-                                                    id: record.id,
-                                                    name: record.name,
-                                                    url: record.url,
-                                                    is_subtask,
-                                                },
-                                            )
-                                        };
-                                        let _ = tx.send(Action::UpdateProjectTaskStatus {
-                                            id: agent_id,
-                                            status: new_status,
-                                        });
-                                    }
-                                }
-                                Err(e) => {
-                                    let _ = tx.send(Action::ShowError(format!(
-                                        "Automation failed: {}",
-                                        e
-                                    )));
                                 }
                             }
-                        });
-                    }
-                    grove::app::config::ProjectMgmtProvider::Linear => {
-                        let client = linear_client.clone();
-                        let task_id = task_id.to_string();
-                        let status = status.clone();
-                        let tx = action_tx.clone();
+                            Err(e) => {
+                                let _ =
+                                    tx.send(Action::ShowError(format!("Automation failed: {}", e)));
+                            }
+                        }
+                    });
+                }
+                grove::app::config::ProjectMgmtProvider::Airtable => {
+                    let client = airtable_client.clone();
+                    let task_id = task_id.to_string();
+                    let status = status.clone();
+                    let tx = action_tx.clone();
 
-                        tokio::spawn(async move {
-                            match client.get_workflow_states().await {
-                                Ok(states) => {
-                                    let mut found = false;
-                                    for workflow_state in states {
-                                        if workflow_state.name.eq_ignore_ascii_case(&status) {
-                                            match client
-                                                .update_issue_status(&task_id, &workflow_state.id)
-                                                .await
-                                            {
-                                                Ok(_) => {
-                                                    let _ = tx.send(Action::ShowToast {
-                                                        message: format!(
-                                                            "Automation: Moved task to '{}'",
-                                                            workflow_state.name
-                                                        ),
-                                                        level: grove::app::ToastLevel::Success,
-                                                    });
-                                                    if let Ok(issue) =
-                                                        client.get_issue(&task_id).await
-                                                    {
-                                                        let identifier = issue.identifier.clone();
-                                                        let new_status =
-                                                            ProjectMgmtTaskStatus::Linear(
-                                                                LinearTaskStatus::InProgress {
-                                                                    id: issue.id,
-                                                                    identifier,
-                                                                    name: issue.title,
-                                                                    status_name: workflow_state
-                                                                        .name
-                                                                        .clone(),
-                                                                    url: issue.url,
-                                                                    is_subtask: issue
-                                                                        .parent_id
-                                                                        .is_some(),
-                                                                },
-                                                            );
-                                                        let _ = tx.send(
-                                                            Action::UpdateProjectTaskStatus {
-                                                                id: agent_id,
-                                                                status: new_status,
-                                                            },
-                                                        );
-                                                    }
-                                                    found = true;
-                                                }
-                                                Err(e) => {
-                                                    let _ = tx.send(Action::ShowError(format!(
-                                                        "Automation failed: {}",
-                                                        e
-                                                    )));
+                    tokio::spawn(async move {
+                        match client.update_record_status(&task_id, &status).await {
+                            Ok(_) => {
+                                let _ = tx.send(Action::ShowToast {
+                                    message: format!("Automation: Moved task to '{}'", status),
+                                    level: grove::app::ToastLevel::Success,
+                                });
+                                if let Ok(record) = client.get_record(&task_id).await {
+                                    let status_name = record.status.clone().unwrap_or_default();
+                                    let is_subtask = record.parent_id.is_some();
+                                    let is_completed = status_name.to_lowercase().contains("done")
+                                        || status_name.to_lowercase().contains("complete");
+                                    let new_status = if is_completed {
+                                        ProjectMgmtTaskStatus::Airtable(
+                                            AirtableTaskStatus::Completed {
+                                                id: record.id,
+                                                name: record.name,
+                                                is_subtask,
+                                            },
+                                        )
+                                    } else if status_name.to_lowercase().contains("progress") {
+                                        ProjectMgmtTaskStatus::Airtable(
+                                            AirtableTaskStatus::InProgress {
+                                                id: record.id,
+                                                name: record.name,
+                                                url: record.url,
+                                                is_subtask,
+                                            },
+                                        )
+                                    } else {
+                                        ProjectMgmtTaskStatus::Airtable(
+                                            AirtableTaskStatus::NotStarted {
+                                                id: record.id,
+                                                name: record.name,
+                                                url: record.url,
+                                                is_subtask,
+                                            },
+                                        )
+                                    };
+                                    let _ = tx.send(Action::UpdateProjectTaskStatus {
+                                        id: agent_id,
+                                        status: new_status,
+                                    });
+                                }
+                            }
+                            Err(e) => {
+                                let _ =
+                                    tx.send(Action::ShowError(format!("Automation failed: {}", e)));
+                            }
+                        }
+                    });
+                }
+                grove::app::config::ProjectMgmtProvider::Linear => {
+                    let client = linear_client.clone();
+                    let task_id = task_id.to_string();
+                    let status = status.clone();
+                    let tx = action_tx.clone();
+
+                    tokio::spawn(async move {
+                        match client.get_workflow_states().await {
+                            Ok(states) => {
+                                let mut found = false;
+                                for workflow_state in states {
+                                    if workflow_state.name.eq_ignore_ascii_case(&status) {
+                                        match client
+                                            .update_issue_status(&task_id, &workflow_state.id)
+                                            .await
+                                        {
+                                            Ok(_) => {
+                                                let _ = tx.send(Action::ShowToast {
+                                                    message: format!(
+                                                        "Automation: Moved task to '{}'",
+                                                        workflow_state.name
+                                                    ),
+                                                    level: grove::app::ToastLevel::Success,
+                                                });
+                                                if let Ok(issue) = client.get_issue(&task_id).await
+                                                {
+                                                    let identifier = issue.identifier.clone();
+                                                    let new_status = ProjectMgmtTaskStatus::Linear(
+                                                        LinearTaskStatus::InProgress {
+                                                            id: issue.id,
+                                                            identifier,
+                                                            name: issue.title,
+                                                            status_name: workflow_state
+                                                                .name
+                                                                .clone(),
+                                                            url: issue.url,
+                                                            is_subtask: issue.parent_id.is_some(),
+                                                        },
+                                                    );
+                                                    let _ =
+                                                        tx.send(Action::UpdateProjectTaskStatus {
+                                                            id: agent_id,
+                                                            status: new_status,
+                                                        });
                                                 }
+                                                found = true;
+                                            }
+                                            Err(e) => {
+                                                let _ = tx.send(Action::ShowError(format!(
+                                                    "Automation failed: {}",
+                                                    e
+                                                )));
                                             }
-                                            break;
                                         }
-                                    }
-                                    if !found {
-                                        let _ = tx.send(Action::ShowError(format!(
-                                            "Automation: No status found matching '{}'",
-                                            status
-                                        )));
+                                        break;
                                     }
                                 }
-                                Err(e) => {
+                                if !found {
                                     let _ = tx.send(Action::ShowError(format!(
-                                        "Automation failed: {}",
-                                        e
+                                        "Automation: No status found matching '{}'",
+                                        status
                                     )));
                                 }
                             }
-                        });
-                    }
+                            Err(e) => {
+                                let _ =
+                                    tx.send(Action::ShowError(format!("Automation failed: {}", e)));
+                            }
+                        }
+                    });
                 }
             }
         }
@@ -10356,15 +12249,14 @@ async fn process_action(
             if let Some(agent) = state.selected_agent() {
                 let agent_id = agent.id;
                 if let Ok(manager) = devserver_manager.try_lock() {
-                    let current_running = manager
-                        .get(agent_id)
-                        .map(|s| s.status().is_running())
-                        .unwrap_or(false);
+                    let current_running = manager.is_running(agent_id);
 
                     if current_running {
                         drop(manager);
                         action_tx.send(Action::StopDevServer)?;
-                    } else if manager.has_running_server() {
+                    } else if manager.has_running_server()
+                        && !state.settings.repo_config.dev_server.auto_port
+                    {
                         let running = manager.running_servers();
                         state.devserver_warning = Some(grove::app::DevServerWarning {
                             agent_id,
@@ -10386,155 +12278,907 @@ async fn process_action(
             action_tx.send(Action::StartDevServer)?;
         }
 
-        Action::DismissDevServerWarning => {
-            state.devserver_warning = None;
-        }
+        Action::McpToolCall { call, reply } => {
+            handle_mcp_tool(call, reply, state, action_tx, devserver_manager).await;
+        }
+
+        Action::DismissDevServerWarning => {
+            state.devserver_warning = None;
+        }
+
+        Action::StartDevServer => {
+            if let Some(agent_id) = state.selected_agent_id() {
+                let port = state.ensure_dev_port(agent_id);
+                let Some(agent) = state.agents.get(&agent_id) else {
+                    return Ok(false);
+                };
+                let config = &state.settings.repo_config.dev_server;
+                let worktree = std::path::PathBuf::from(&agent.worktree_path);
+                let agent_name = agent.name.clone();
+                let specs = match grove::devserver::resolve_services(config, &worktree, port) {
+                    Ok(specs) => specs,
+                    Err(e) => {
+                        state.log_error(format!("Cannot start dev server: {}", e));
+                        state.show_error(format!("Cannot start dev server: {}", e));
+                        return Ok(false);
+                    }
+                };
+
+                match port {
+                    Some(port) => state.log_info(format!(
+                        "Starting dev server for '{}' on port {}",
+                        agent_name, port
+                    )),
+                    None => state.log_info(format!("Starting dev server for '{}'", agent_name)),
+                }
+
+                let manager = Arc::clone(devserver_manager);
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        grove::devserver::start_services(manager, agent_id, agent_name, specs).await
+                    {
+                        tracing::error!("Failed to start dev server: {}", e);
+                    }
+                });
+            }
+        }
+
+        Action::StopDevServer => {
+            if let Some(agent) = state.selected_agent() {
+                let agent_id = agent.id;
+                let manager = Arc::clone(devserver_manager);
+                let name = agent.name.clone();
+
+                state.log_info(format!("Stopping dev server for '{}'", name));
+
+                tokio::spawn(async move {
+                    let mut m = manager.lock().await;
+                    let _ = m.stop(agent_id).await;
+                });
+            }
+        }
+
+        Action::RestartDevServer => {
+            if let Some(agent_id) = state.selected_agent_id() {
+                if let Err(e) = restart_dev_server(state, devserver_manager, agent_id) {
+                    state.show_error(e);
+                }
+            }
+        }
+
+        Action::SelectDevService { delta } => {
+            state.devserver_selection = None;
+            if let Some(agent_id) = state.selected_agent_id() {
+                let count = devserver_manager.lock().await.services(agent_id).len();
+                if count > 0 {
+                    let current = state.devserver_service.min(count - 1) as i32;
+                    state.devserver_service = (current + delta).rem_euclid(count as i32) as usize;
+                }
+            }
+        }
+
+        Action::ToggleDevService | Action::RestartDevService => {
+            let restart = matches!(action, Action::RestartDevService);
+            let Some(agent_id) = state.selected_agent_id() else {
+                return Ok(false);
+            };
+            let port = state.ensure_dev_port(agent_id);
+            let Some(agent) = state.agents.get(&agent_id) else {
+                return Ok(false);
+            };
+            let (service, running) = {
+                let manager = devserver_manager.lock().await;
+                match manager.service_at(agent_id, state.devserver_service) {
+                    Some(server) => (server.service().to_string(), server.status().is_running()),
+                    None => return Ok(false),
+                }
+            };
+
+            let worktree = std::path::PathBuf::from(&agent.worktree_path);
+            let agent_name = agent.name.clone();
+            let spec = grove::devserver::resolve_services(
+                &state.settings.repo_config.dev_server,
+                &worktree,
+                port,
+            )
+            .ok()
+            .and_then(|specs| specs.into_iter().find(|s| s.name == service));
+
+            let manager = Arc::clone(devserver_manager);
+            if running && !restart {
+                state.log_info(format!(
+                    "Stopping service '{}' for '{}'",
+                    service, agent_name
+                ));
+                tokio::spawn(async move {
+                    let _ = manager.lock().await.stop_service(agent_id, &service).await;
+                });
+            } else if let Some(mut spec) = spec {
+                // Only the stack as a whole runs the shared `run_before` commands
+                spec.run_before.clear();
+                state.log_info(format!(
+                    "Starting service '{}' for '{}'",
+                    service, agent_name
+                ));
+                tokio::spawn(async move {
+                    let _ = manager.lock().await.stop_service(agent_id, &service).await;
+                    if let Err(e) =
+                        grove::devserver::start_services(manager, agent_id, agent_name, vec![spec])
+                            .await
+                    {
+                        tracing::error!("Failed to start service: {}", e);
+                    }
+                });
+            } else {
+                state.show_error(format!("Service '{}' is no longer configured", service));
+            }
+        }
+
+        Action::NextPreviewTab => {
+            state.preview_tab = match state.preview_tab {
+                PreviewTab::Preview => PreviewTab::GitDiff,
+                PreviewTab::GitDiff => PreviewTab::DevServer,
+                PreviewTab::DevServer => PreviewTab::Activity,
+                PreviewTab::Activity => PreviewTab::Plan,
+                PreviewTab::Plan => PreviewTab::Preview,
+            };
+        }
+
+        Action::PrevPreviewTab => {
+            state.preview_tab = match state.preview_tab {
+                PreviewTab::Preview => PreviewTab::Plan,
+                PreviewTab::GitDiff => PreviewTab::Preview,
+                PreviewTab::DevServer => PreviewTab::GitDiff,
+                PreviewTab::Activity => PreviewTab::DevServer,
+                PreviewTab::Plan => PreviewTab::Activity,
+            };
+        }
+
+        Action::ScrollPreviewUp => match state.preview_tab {
+            PreviewTab::Preview => {
+                state.output_scroll = state.output_scroll.saturating_add(10);
+            }
+            PreviewTab::GitDiff => {
+                state.gitdiff_scroll = state.gitdiff_scroll.saturating_sub(10);
+            }
+            PreviewTab::DevServer | PreviewTab::Activity | PreviewTab::Plan => {}
+        },
+
+        Action::ScrollPreviewDown => match state.preview_tab {
+            PreviewTab::Preview => {
+                state.output_scroll = state.output_scroll.saturating_sub(10);
+            }
+            PreviewTab::GitDiff => {
+                let max_scroll = state.gitdiff_line_count.saturating_sub(1);
+                state.gitdiff_scroll = state.gitdiff_scroll.saturating_add(10).min(max_scroll);
+            }
+            PreviewTab::DevServer | PreviewTab::Activity | PreviewTab::Plan => {}
+        },
+
+        Action::ClearDevServerLogs => {
+            if let Some(agent_id) = state.selected_agent_id() {
+                let mut manager = devserver_manager.lock().await;
+                let service = manager
+                    .service_at(agent_id, state.devserver_service)
+                    .map(|s| s.service().to_string());
+                if let Some(server) = service.and_then(|name| manager.service_mut(agent_id, &name))
+                {
+                    server.clear_logs();
+                }
+            }
+        }
+
+        Action::OpenDevServerInBrowser => {
+            if let Some(agent_id) = state.selected_agent_id() {
+                let manager = devserver_manager.lock().await;
+                let service = manager
+                    .service_at(agent_id, state.devserver_service)
+                    .map(|s| s.service().to_string());
+                if let Some(url) = manager.url_for(agent_id, service.as_deref()) {
+                    match open::that(&url) {
+                        Ok(_) => state.log_info(format!("Opening {} in browser", url)),
+                        Err(e) => state.log_error(format!("Failed to open browser: {}", e)),
+                    }
+                }
+            }
+        }
+
+        Action::AppendDevServerLog {
+            agent_id,
+            service,
+            line,
+        } => {
+            if state.settings.repo_config.dev_server.auto_send_errors {
+                // Restart the settle timer for every line of a pending error
+                let pending = state
+                    .devserver_error_pending
+                    .get(&agent_id)
+                    .is_some_and(|(s, _)| *s == service);
+                let is_error = state
+                    .devserver_error_detector()
+                    .is_ok_and(|detector| detector.is_error_start(&line));
+                if pending || is_error {
+                    state
+                        .devserver_error_pending
+                        .insert(agent_id, (service.clone(), std::time::Instant::now()));
+                }
+            }
+
+            let mut manager = devserver_manager.lock().await;
+            if let Some(server) = manager.service_mut(agent_id, &service) {
+                server.append_log(line);
+            }
+        }
+
+        Action::SendDevServerError => {
+            let Some(agent_id) = state.selected_agent_id() else {
+                return Ok(false);
+            };
+            let service = devserver_manager
+                .lock()
+                .await
+                .service_at(agent_id, state.devserver_service)
+                .map(|s| s.service().to_string());
+            match service {
+                Some(service) => {
+                    if !send_latest_devserver_error(state, devserver_manager, agent_id, &service)
+                        .await
+                    {
+                        state.show_info("No errors in the dev server log");
+                    }
+                }
+                None => state.show_info("No dev server running"),
+            }
+        }
+
+        Action::StartLogSelection => {
+            if let Some(agent_id) = state.selected_agent_id() {
+                let manager = devserver_manager.lock().await;
+                if let Some(server) = manager.service_at(agent_id, state.devserver_service) {
+                    if let Some(last) = server.log_mark().checked_sub(1) {
+                        state.devserver_selection = Some(grove::app::LogSelection {
+                            anchor: last,
+                            cursor: last,
+                        });
+                    }
+                }
+            }
+        }
+
+        Action::MoveLogSelection { delta } => {
+            let Some(agent_id) = state.selected_agent_id() else {
+                return Ok(false);
+            };
+            let manager = devserver_manager.lock().await;
+            let Some(server) = manager.service_at(agent_id, state.devserver_service) else {
+                return Ok(false);
+            };
+            let last = server.log_mark().saturating_sub(1);
+            let first = server.log_mark().saturating_sub(server.logs().len());
+            if let Some(selection) = state.devserver_selection.as_mut() {
+                selection.cursor = (selection.cursor as i64 + delta as i64)
+                    .clamp(first as i64, last as i64) as usize;
+            }
+        }
+
+        Action::SendLogSelection => {
+            let Some(selection) = state.devserver_selection.take() else {
+                return Ok(false);
+            };
+            let Some(agent_id) = state.selected_agent_id() else {
+                return Ok(false);
+            };
+            let (service, lines) = {
+                let manager = devserver_manager.lock().await;
+                let Some(server) = manager.service_at(agent_id, state.devserver_service) else {
+                    return Ok(false);
+                };
+                let first = server.log_mark().saturating_sub(server.logs().len());
+                let lines: Vec<String> = server
+                    .logs()
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| selection.range().contains(&(first + i)))
+                    .map(|(_, line)| line.clone())
+                    .collect();
+                (server.service().to_string(), lines)
+            };
+            if !lines.is_empty() {
+                send_devserver_output(state, agent_id, &service, &lines);
+            }
+        }
+
+        Action::CancelLogSelection => {
+            state.devserver_selection = None;
+        }
+
+        Action::DevServerPortDetected {
+            agent_id,
+            service,
+            port,
+        } => {
+            let mut manager = devserver_manager.lock().await;
+            if manager.set_port(agent_id, &service, port) {
+                if let Some(agent) = state.agents.get(&agent_id) {
+                    state.log_info(format!(
+                        "Service '{}' for '{}' is listening on port {}",
+                        service, agent.name, port
+                    ));
+                }
+            }
+        }
+
+        Action::DevServiceReady {
+            agent_id,
+            service,
+            ready,
+        } => {
+            let name = state
+                .agents
+                .get(&agent_id)
+                .map(|a| a.name.clone())
+                .unwrap_or_default();
+            if ready {
+                state.log_info(format!("Service '{}' for '{}' is ready", service, name));
+            } else {
+                state.log_error(format!(
+                    "Service '{}' for '{}' did not pass its readiness check",
+                    service, name
+                ));
+                state.show_error(format!("Service '{}' did not become ready", service));
+            }
+        }
+
+        Action::DevServiceUnhealthy {
+            agent_id,
+            service,
+            reason,
+        } => {
+            let name = state
+                .agents
+                .get(&agent_id)
+                .map(|a| a.name.clone())
+                .unwrap_or_default();
+            state.log_warn(format!(
+                "Service '{}' for '{}' is unhealthy: {}",
+                service, name, reason
+            ));
+        }
+
+        Action::DevServiceExited {
+            agent_id,
+            service,
+            code,
+        } => {
+            let restart = devserver_manager
+                .lock()
+                .await
+                .service_exited(agent_id, &service, code);
+            let name = state
+                .agents
+                .get(&agent_id)
+                .map(|a| a.name.clone())
+                .unwrap_or_default();
+            if code == 0 {
+                state.log_info(format!("Service '{}' for '{}' exited", service, name));
+            } else {
+                state.log_error(format!(
+                    "Service '{}' for '{}' crashed with exit code {}",
+                    service, name, code
+                ));
+                if !restart {
+                    state.show_error(format!("Dev service '{}' crashed", service));
+                }
+            }
+            if restart {
+                let manager = Arc::clone(devserver_manager);
+                tokio::spawn(async move {
+                    if let Err(e) =
+                        grove::devserver::restart_service(manager, agent_id, service).await
+                    {
+                        tracing::error!("Failed to restart dev service: {}", e);
+                    }
+                });
+            }
+        }
+
+        Action::UpdateDevServerStatus {
+            agent_id,
+            service,
+            status,
+        } => {
+            state.log_debug(format!(
+                "Dev server {} service '{}' status: {}",
+                agent_id,
+                service,
+                status.label()
+            ));
+        }
+    }
+
+    Ok(false)
+}
+
+/// Start a provisioned agent's session, reporting a failure to the user.
+fn start_agent_session(
+    state: &mut AppState,
+    agent_manager: &AgentManager,
+    id: Uuid,
+    command: &str,
+) {
+    let Some(agent) = state.agents.get(&id) else {
+        return;
+    };
+    let name = agent.name.clone();
+    if let Err(e) = agent_manager.start_session(agent, &state.ai_agent_for(id), command) {
+        state.log_error(format!("Failed to start session for '{}': {}", name, e));
+        state.show_error(format!("Failed to start '{}': {}", name, e));
+    }
+}
 
-        Action::StartDevServer => {
-            if let Some(agent) = state.selected_agent() {
-                let config = state.settings.repo_config.dev_server.clone();
-                let worktree = std::path::PathBuf::from(agent.worktree_path.clone());
-                let agent_id = agent.id;
-                let agent_name = agent.name.clone();
-                let manager = Arc::clone(devserver_manager);
+/// `items` joined with "or", or "any" when empty.
+fn describe_any(items: &[String]) -> String {
+    if items.is_empty() {
+        "any".to_string()
+    } else {
+        items.join(" or ")
+    }
+}
 
-                state.log_info(format!("Starting dev server for '{}'", agent.name));
+/// Move the parent task of a finished orchestration and merge its subtask
+/// branches into the parent's branch. The parent is then queued for landing
+/// or pushed once `OrchestrationMerged` arrives.
+fn finish_orchestration(
+    state: &mut AppState,
+    index: usize,
+    action_tx: &mpsc::UnboundedSender<Action>,
+) {
+    let config = state.settings.repo_config.orchestrator.clone();
+    let orchestration = &state.orchestrations[index];
+    let parent_name = orchestration.parent.task.name.clone();
+    let parent_branch = orchestration.parent.branch.clone();
+    let Some(parent_agent) = orchestration
+        .parent
+        .state
+        .agent_id()
+        .and_then(|id| state.agents.get(&id))
+    else {
+        return;
+    };
+    let parent_id = parent_agent.id;
+    let parent_worktree = parent_agent.worktree_path.clone();
+    let subtask_agents = orchestration.subtask_agents();
+
+    state.log_info(format!("All subtasks of '{}' are done", parent_name));
+    let _ = action_tx.send(Action::MoveTaskToStatus {
+        agent_id: parent_id,
+        status: config.parent_status.unwrap_or(config.done_status),
+    });
 
-                tokio::spawn(async move {
-                    let mut m = manager.lock().await;
-                    if let Err(e) = m.start(agent_id, agent_name, &config, &worktree).await {
-                        tracing::error!("Failed to start dev server: {}", e);
-                    }
-                });
-            }
-        }
+    let branches: Vec<String> = subtask_agents
+        .iter()
+        .filter_map(|id| state.agents.get(id))
+        .map(|a| a.branch.clone())
+        .collect();
+    state.show_info(format!(
+        "Subtasks of '{}' are done, merging them into {}",
+        parent_name, parent_branch
+    ));
+    let tx = action_tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let sync = GitSync::new(&parent_worktree);
+        let result = branches
+            .iter()
+            .try_for_each(|branch| match sync.sync_with(branch, false) {
+                Ok(grove::git::SyncOutcome::Clean) => Ok(()),
+                Ok(grove::git::SyncOutcome::Conflicts(files)) => {
+                    let _ = sync.abort_sync(false);
+                    Err(format!(
+                        "conflicts merging {}: {}",
+                        branch,
+                        files.join(", ")
+                    ))
+                }
+                Err(e) => Err(e.to_string()),
+            });
+        let _ = tx.send(Action::OrchestrationMerged {
+            parent_branch,
+            result,
+        });
+    });
+}
 
-        Action::StopDevServer => {
-            if let Some(agent) = state.selected_agent() {
-                let agent_id = agent.id;
-                let manager = Arc::clone(devserver_manager);
-                let name = agent.name.clone();
+/// Stop the agent's dev server if it is running and start it again.
+fn restart_dev_server(
+    state: &mut AppState,
+    devserver_manager: &Arc<tokio::sync::Mutex<DevServerManager>>,
+    agent_id: Uuid,
+) -> Result<String, String> {
+    let port = state.ensure_dev_port(agent_id);
+    let Some(agent) = state.agents.get(&agent_id) else {
+        return Err("Agent not found".to_string());
+    };
+    let config = &state.settings.repo_config.dev_server;
+    let worktree = std::path::PathBuf::from(&agent.worktree_path);
+    let agent_name = agent.name.clone();
+    let specs = grove::devserver::resolve_services(config, &worktree, port)
+        .map_err(|e| format!("Cannot restart dev server: {}", e))?;
 
-                state.log_info(format!("Stopping dev server for '{}'", name));
+    state.log_info(format!("Restarting dev server for '{}'", agent_name));
 
-                tokio::spawn(async move {
-                    let mut m = manager.lock().await;
-                    let _ = m.stop(agent_id).await;
-                });
-            }
+    let manager = Arc::clone(devserver_manager);
+    tokio::spawn(async move {
+        let _ = manager.lock().await.stop(agent_id).await;
+        if let Err(e) = grove::devserver::start_services(manager, agent_id, agent_name, specs).await
+        {
+            tracing::error!("Failed to restart dev server: {}", e);
         }
+    });
 
-        Action::RestartDevServer => {
-            if let Some(agent) = state.selected_agent() {
-                let config = state.settings.repo_config.dev_server.clone();
-                let worktree = std::path::PathBuf::from(agent.worktree_path.clone());
-                let agent_id = agent.id;
-                let agent_name = agent.name.clone();
-                let manager = Arc::clone(devserver_manager);
+    Ok(match port {
+        Some(port) => format!("Restarting the dev server on port {}", port),
+        None => "Restarting the dev server".to_string(),
+    })
+}
 
-                state.log_info(format!("Restarting dev server for '{}'", agent.name));
+/// Agent named `query`, or working on the branch `query`.
+fn find_agent<'a>(state: &'a AppState, query: Option<&str>) -> Result<&'a Agent, String> {
+    let query = query.ok_or("Missing agent")?;
+    state
+        .agents
+        .values()
+        .find(|a| a.name.eq_ignore_ascii_case(query) || a.branch == query)
+        .ok_or_else(|| format!("No agent named '{}'", query))
+}
 
-                tokio::spawn(async move {
-                    let mut m = manager.lock().await;
-                    let _ = m.stop(agent_id).await;
-                    if let Err(e) = m.start(agent_id, agent_name, &config, &worktree).await {
-                        tracing::error!("Failed to restart dev server: {}", e);
-                    }
-                });
+fn describe_agent(agent: &Agent) -> String {
+    let mut lines = vec![
+        format!("Agent: {}", agent.name),
+        format!("Branch: {}", agent.branch),
+        format!("Status: {}", agent.status.label()),
+    ];
+    if let Some(base) = &agent.base_branch {
+        lines.push(format!("Based on: {}", base));
+    }
+    if let Some(task) = agent.pm_task_status.name() {
+        lines.push(format!(
+            "Task: {} ({})",
+            task,
+            agent.pm_task_status.format_status_name()
+        ));
+    }
+    if let Some((done, total)) = agent.checklist_progress {
+        lines.push(format!("Checklist: {}/{}", done, total));
+    }
+    if let Some(git) = &agent.git_status {
+        lines.push(format!(
+            "Commits: {} ({} behind {}), {}",
+            git.divergence_from_main,
+            git.behind_main,
+            git.base_branch,
+            if git.is_clean {
+                "clean"
+            } else {
+                "uncommitted changes"
             }
-        }
+        ));
+    }
+    if let Some(note) = &agent.custom_note {
+        lines.push(format!("Note: {}", note));
+    }
+    if let Some(summary) = &agent.summary {
+        lines.push(format!("Summary: {}", summary));
+    }
+    lines.join("\n")
+}
 
-        Action::NextPreviewTab => {
-            state.preview_tab = match state.preview_tab {
-                PreviewTab::Preview => PreviewTab::GitDiff,
-                PreviewTab::GitDiff => PreviewTab::DevServer,
-                PreviewTab::DevServer => PreviewTab::Preview,
-            };
-        }
+/// Largest diff returned to an agent, in bytes.
+const MCP_MAX_DIFF_BYTES: usize = 100 * 1024;
 
-        Action::PrevPreviewTab => {
-            state.preview_tab = match state.preview_tab {
-                PreviewTab::Preview => PreviewTab::DevServer,
-                PreviewTab::GitDiff => PreviewTab::Preview,
-                PreviewTab::DevServer => PreviewTab::GitDiff,
-            };
-        }
+/// Answer a tool call from an agent's MCP server.
+async fn handle_mcp_tool(
+    call: grove::mcp::ToolCall,
+    reply: grove::mcp::Reply,
+    state: &mut AppState,
+    action_tx: &mpsc::UnboundedSender<Action>,
+    devserver_manager: &Arc<tokio::sync::Mutex<DevServerManager>>,
+) {
+    let agent_id = call.agent_id;
+    let Some(agent) = state.agents.get(&agent_id).cloned() else {
+        reply.send(Err(
+            "This session doesn't belong to a Grove agent".to_string()
+        ));
+        return;
+    };
+    tracing::debug!("MCP tool call from '{}': {}", agent.name, call.tool);
 
-        Action::ScrollPreviewUp => match state.preview_tab {
-            PreviewTab::Preview => {
-                state.output_scroll = state.output_scroll.saturating_add(10);
+    let result = match call.tool.as_str() {
+        "get_task" => match agent.pm_task_status.name() {
+            Some(name) => {
+                let mut text = format!(
+                    "Task: {}\nStatus: {}",
+                    name,
+                    agent.pm_task_status.format_status_name()
+                );
+                if let Some(url) = agent.pm_task_status.url() {
+                    text.push_str(&format!("\nURL: {}", url));
+                }
+                Ok(text)
             }
-            PreviewTab::GitDiff => {
-                state.gitdiff_scroll = state.gitdiff_scroll.saturating_sub(10);
+            None => Err("No task is linked to this agent".to_string()),
+        },
+        "update_task_status" => match call.str_arg("status") {
+            None => Err("Missing status".to_string()),
+            Some(_) if !agent.pm_task_status.is_linked() => {
+                Err("No task is linked to this agent".to_string())
+            }
+            Some(status) => {
+                state.log_info(format!(
+                    "Agent '{}' is moving its task to '{}'",
+                    agent.name, status
+                ));
+                let _ = action_tx.send(Action::MoveTaskToStatus {
+                    agent_id,
+                    status: status.to_string(),
+                });
+                Ok(format!("Moving the task to '{}'", status))
             }
-            PreviewTab::DevServer => {}
         },
-
-        Action::ScrollPreviewDown => match state.preview_tab {
-            PreviewTab::Preview => {
-                state.output_scroll = state.output_scroll.saturating_sub(10);
+        "post_summary" => match call.str_arg("summary") {
+            None => Err("Missing summary".to_string()),
+            Some(summary) => {
+                let summary = summary.to_string();
+                if let Some(agent) = state.agents.get_mut(&agent_id) {
+                    agent.summary = Some(summary.clone());
+                    agent.summary_requested = false;
+                    if agent.custom_note.as_deref() == Some("summary...") {
+                        agent.custom_note = None;
+                    }
+                    agent.push_transcript(vec![grove::agent::TranscriptEvent::new(
+                        grove::agent::TranscriptEventKind::Message,
+                        &format!("Summary: {}", summary),
+                        Some(chrono::Utc::now()),
+                    )]);
+                }
+                state.log_info(format!("Summary from '{}': {}", agent.name, summary));
+                state.show_info(format!("'{}' posted a summary", agent.name));
+                Ok("Summary posted".to_string())
             }
-            PreviewTab::GitDiff => {
-                let max_scroll = state.gitdiff_line_count.saturating_sub(1);
-                state.gitdiff_scroll = state.gitdiff_scroll.saturating_add(10).min(max_scroll);
+        },
+        "list_agents" => {
+            let others: Vec<String> = state
+                .agent_order
+                .iter()
+                .filter(|id| **id != agent_id)
+                .filter_map(|id| state.agents.get(id))
+                .map(|a| format!("{} ({}): {}", a.name, a.branch, a.status.label()))
+                .collect();
+            if others.is_empty() {
+                Ok("No other agents".to_string())
+            } else {
+                Ok(others.join("\n"))
+            }
+        }
+        "get_agent_status" => find_agent(state, call.str_arg("agent")).map(describe_agent),
+        "get_agent_diff" => match find_agent(state, call.str_arg("agent")) {
+            Ok(sibling) => {
+                let worktree_path = sibling.worktree_path.clone();
+                let base_ref = sibling.diff_base_ref(&state.settings.repo_config.git.main_branch);
+                tokio::task::spawn_blocking(move || {
+                    let result = GitSync::new(&worktree_path)
+                        .get_diff_against(&base_ref)
+                        .map_err(|e| e.to_string())
+                        .map(|diff| {
+                            if diff.is_empty() {
+                                format!("No changes against {}", base_ref)
+                            } else if diff.len() > MCP_MAX_DIFF_BYTES {
+                                let mut end = MCP_MAX_DIFF_BYTES;
+                                while !diff.is_char_boundary(end) {
+                                    end -= 1;
+                                }
+                                format!("{}\n[diff truncated]", &diff[..end])
+                            } else {
+                                diff
+                            }
+                        });
+                    reply.send(result);
+                });
+                return;
             }
-            PreviewTab::DevServer => {}
+            Err(e) => Err(e),
         },
-
-        Action::ClearDevServerLogs => {
-            if let Some(agent) = state.selected_agent() {
-                let agent_id = agent.id;
-                let mut manager = devserver_manager.lock().await;
-                if let Some(server) = manager.get_mut(agent_id) {
-                    server.clear_logs();
+        "restart_dev_server" => restart_dev_server(state, devserver_manager, agent_id),
+        "get_dev_server_logs" => {
+            let lines = call.u64_arg("lines").unwrap_or(100) as usize;
+            let manager = devserver_manager.lock().await;
+            let server = match call.str_arg("service") {
+                Some(service) => manager.service(agent_id, service),
+                None => manager.services(agent_id).first(),
+            };
+            match server {
+                Some(server) => {
+                    let logs = server.logs();
+                    let start = logs.len().saturating_sub(lines);
+                    Ok(format!(
+                        "{} ({})\n{}",
+                        server.service(),
+                        server.status().label(),
+                        logs[start..].join("\n")
+                    ))
+                }
+                None => Err("The dev server hasn't been started".to_string()),
+            }
+        }
+        "spawn_agent" => match (call.str_arg("name"), call.str_arg("prompt")) {
+            (Some(name), Some(prompt)) => {
+                let profile = agent.profile.as_ref();
+                let branch = grove::agent::profile::branch_name(profile, name);
+                if branch.is_empty() {
+                    Err("Invalid agent name".to_string())
+                } else if state.agents.values().any(|a| a.branch == branch) {
+                    Err(format!("Branch '{}' already has an agent", branch))
+                } else {
+                    state.log_info(format!(
+                        "Agent '{}' is spawning sub-agent '{}'",
+                        agent.name, name
+                    ));
+                    let _ = action_tx.send(Action::CreateAgent {
+                        name: name.to_string(),
+                        branch: branch.clone(),
+                        task: None,
+                        base_branch: Some(agent.branch.clone()),
+                        profile: profile.map(|p| p.name.clone()),
+                        prompt: Some(grove::agent::InitialPrompt::Text(prompt.to_string())),
+                    });
+                    Ok(format!(
+                        "Starting agent '{}' on branch '{}' from '{}'",
+                        name, branch, agent.branch
+                    ))
                 }
             }
-        }
+            _ => Err("Both name and prompt are required".to_string()),
+        },
+        other => Err(format!("Unknown tool: {}", other)),
+    };
+    reply.send(result);
+}
 
-        Action::OpenDevServerInBrowser => {
-            if let Some(agent) = state.selected_agent() {
-                let agent_id = agent.id;
-                let manager = devserver_manager.lock().await;
-                if let Some(server) = manager.get(agent_id) {
-                    if let Some(port) = server.status().port() {
-                        let url = format!("http://localhost:{}", port);
-                        match open::that(&url) {
-                            Ok(_) => state.log_info("Opening dev server in browser"),
-                            Err(e) => state.log_error(format!("Failed to open browser: {}", e)),
-                        }
-                    }
-                }
-            }
+/// Send the latest error block of a dev service to its agent, unless the
+/// same block was already sent. Returns `false` if the log has no errors.
+async fn send_latest_devserver_error(
+    state: &mut AppState,
+    devserver_manager: &Arc<tokio::sync::Mutex<DevServerManager>>,
+    agent_id: Uuid,
+    service: &str,
+) -> bool {
+    let detector = match state.devserver_error_detector() {
+        Ok(detector) => detector,
+        Err(e) => {
+            state.log_warn(format!("Dev server error patterns: {}", e));
+            return false;
         }
-
-        Action::AppendDevServerLog { agent_id, line } => {
-            let mut manager = devserver_manager.lock().await;
-            if let Some(server) = manager.get_mut(agent_id) {
-                server.append_log(line);
-            }
+    };
+    let block = {
+        let manager = devserver_manager.lock().await;
+        let Some(server) = manager.service(agent_id, service) else {
+            return false;
+        };
+        let logs = server.logs();
+        match detector.latest_error(logs) {
+            Some(range) => logs[range].to_vec(),
+            None => return false,
         }
+    };
 
-        Action::UpdateDevServerStatus { agent_id, status } => {
-            state.log_debug(format!(
-                "Dev server {} status: {}",
-                agent_id,
-                status.label()
+    let output = grove::devserver::format_lines(&block);
+    if state.devserver_error_sent.get(&agent_id) == Some(&output) {
+        state.log_debug(format!(
+            "Dev server error for '{}' was already sent",
+            service
+        ));
+        return true;
+    }
+    if send_devserver_output(state, agent_id, service, &block) {
+        state.devserver_error_sent.insert(agent_id, output);
+    }
+    true
+}
+
+/// Paste dev server log lines into the agent's session as a prompt.
+fn send_devserver_output(
+    state: &mut AppState,
+    agent_id: Uuid,
+    service: &str,
+    lines: &[String],
+) -> bool {
+    let Some(agent) = state.agents.get(&agent_id) else {
+        return false;
+    };
+    let name = agent.name.clone();
+    let prompt = state
+        .settings
+        .repo_config
+        .prompts
+        .get_devserver_error_prompt(service, &grove::devserver::format_lines(lines));
+    let session = grove::tmux::TmuxSession::new(&agent.tmux_session);
+    match session.paste_and_submit(&prompt) {
+        Ok(()) => {
+            state.log_info(format!(
+                "Sent {} dev server log line(s) to '{}'",
+                lines.len(),
+                name
             ));
+            state.show_success(format!("Sent dev server output to '{}'", name));
+            true
+        }
+        Err(e) => {
+            state.log_error(format!("Failed to send dev server output: {}", e));
+            state.show_error(format!("Failed to send dev server output: {}", e));
+            false
         }
     }
+}
 
-    Ok(false)
+/// Apply the budget action once for each limit an agent newly went over.
+fn enforce_budget(state: &mut AppState, id: Uuid) {
+    use grove::agent::budget::{self, BudgetKind};
+    use grove::app::config::BudgetAction;
+
+    let Some(agent) = state.agents.get_mut(&id) else {
+        return;
+    };
+    let budget = agent
+        .budget
+        .clone()
+        .unwrap_or_else(|| state.settings.repo_config.budget.clone());
+    let exceeded: HashSet<BudgetKind> = budget::exceeded(&budget, agent, chrono::Utc::now())
+        .into_iter()
+        .collect();
+    let fired = state.budget_exceeded.entry(id).or_default();
+    let newly: Vec<&'static str> = exceeded
+        .difference(fired)
+        .map(|kind| kind.label())
+        .collect();
+    *fired = exceeded;
+    if newly.is_empty() {
+        return;
+    }
+
+    let name = agent.name.clone();
+    let session = grove::tmux::TmuxSession::new(&agent.tmux_session);
+    let result = match budget.action {
+        BudgetAction::Notify => Ok(()),
+        BudgetAction::Interrupt => session.interrupt(),
+        BudgetAction::Stop => session
+            .kill()
+            .map(|_| agent.set_status(AgentStatus::Stopped)),
+    };
+    let message = format!(
+        "Agent '{}' is over its {} budget ({})",
+        name,
+        newly.join(" and "),
+        budget.action.label()
+    );
+    match result {
+        Ok(()) => {
+            state.log_warn(message.clone());
+            state.show_warning(message);
+        }
+        Err(e) => state.show_error(format!("{}: {}", message, e)),
+    }
 }
 
 /// Background task to poll agent status from tmux sessions.
 async fn poll_agents(
-    mut agent_rx: watch::Receiver<HashSet<Uuid>>,
+    mut agent_rx: watch::Receiver<HashMap<Uuid, grove::app::config::AiAgent>>,
     mut selected_rx: watch::Receiver<Option<Uuid>>,
     tx: mpsc::UnboundedSender<Action>,
-    ai_agent: grove::app::config::AiAgent,
     debug_mode: bool,
+    mut stall_rx: watch::Receiver<grove::app::config::StallConfig>,
+    claude_hooks: bool,
 ) {
-    use std::collections::HashMap;
+    use grove::agent::claude_hooks;
+    use grove::agent::{transcript, Stall, StallDetector, TranscriptEvent, TranscriptTail};
 
     // Track previous content hash for activity detection
     let mut previous_content: HashMap<Uuid, u64> = HashMap::new();
@@ -10544,6 +13188,12 @@ async fn poll_agents(
     let mut deep_scan_counter: u32 = 0;
     // Track previous selected_id to log changes
     let mut prev_selected_id: Option<Uuid> = None;
+    // Per-agent stall/loop tracking and the last result sent
+    let mut stall_detectors: HashMap<Uuid, StallDetector> = HashMap::new();
+    let mut stalls: HashMap<Uuid, Option<Stall>> = HashMap::new();
+    // Codex/Gemini session logs being followed and the latest event read from each
+    let mut transcripts: HashMap<Uuid, Option<TranscriptTail>> = HashMap::new();
+    let mut last_events: HashMap<Uuid, (TranscriptEvent, std::time::Instant)> = HashMap::new();
 
     loop {
         deep_scan_counter += 1;
@@ -10552,16 +13202,28 @@ async fn poll_agents(
         tokio::time::sleep(Duration::from_millis(250)).await;
 
         // Get current agent list and selected agent
-        let agent_ids = agent_rx.borrow_and_update().clone();
+        let agents = agent_rx.borrow_and_update().clone();
         let selected_id = *selected_rx.borrow_and_update();
 
+        // Start stall tracking over with edited settings
+        if stall_rx.has_changed().unwrap_or(false) {
+            stall_detectors.clear();
+            for (id, stall) in stalls.drain() {
+                if stall.is_some() {
+                    let _ = tx.send(Action::UpdateStall { id, stall: None });
+                }
+            }
+        }
+        let stall_config = stall_rx.borrow_and_update().clone();
+
         // Log when selected_id changes
         if selected_id != prev_selected_id {
             tracing::debug!("poll_agents: selected_id changed to {:?}", selected_id);
             prev_selected_id = selected_id;
         }
 
-        for id in agent_ids {
+        for (id, ai_agent) in agents {
+            let use_hooks = claude_hooks && ai_agent == grove::app::config::AiAgent::ClaudeCode;
             let is_selected = selected_id == Some(id);
             let session_name = format!("grove-{}", id.as_simple());
 
@@ -10651,12 +13313,43 @@ async fn poll_agents(
                         }
                     };
                     let status = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                        detect_status_for_agent(&content, foreground, ai_agent.clone())
+                        detect_status_for_agent(&content, foreground.clone(), ai_agent.clone())
                     }))
                     .unwrap_or_else(|e| {
                         tracing::warn!("detect_status_for_agent panicked: {:?}", e);
                         StatusDetection::new(AgentStatus::Idle)
                     });
+                    // Prefer Claude Code's own lifecycle events over the scraped status
+                    let status = if use_hooks {
+                        let event = claude_hooks::read_event(id);
+                        claude_hooks::resolve(event.as_ref(), &content, &foreground, status)
+                    } else {
+                        status
+                    };
+                    // Likewise for Codex's and Gemini's session logs
+                    if let std::collections::hash_map::Entry::Vacant(entry) = transcripts.entry(id)
+                    {
+                        if let Some(worktree) = tmux_session_path(&session_name) {
+                            entry.insert(TranscriptTail::new(
+                                ai_agent.clone(),
+                                &worktree,
+                                std::time::SystemTime::now(),
+                            ));
+                        }
+                    }
+                    if let Some(Some(tail)) = transcripts.get_mut(&id) {
+                        let events = tail.poll();
+                        if let Some(last) = events.last() {
+                            last_events.insert(id, (last.clone(), std::time::Instant::now()));
+                            let _ = tx.send(Action::AppendTranscript { id, events });
+                        }
+                    }
+                    let status = match last_events.get(&id) {
+                        Some((event, read_at)) => {
+                            transcript::resolve(Some(event), read_at.elapsed(), &foreground, status)
+                        }
+                        None => status,
+                    };
 
                     let status_reason = if debug_mode {
                         status.to_status_reason()
@@ -10664,6 +13357,23 @@ async fn poll_agents(
                         None
                     };
 
+                    if stall_config.enabled {
+                        let now = std::time::Instant::now();
+                        let detector = stall_detectors.entry(id).or_insert_with(|| {
+                            StallDetector::new(
+                                Duration::from_secs(stall_config.stall_after_secs),
+                                stall_config.repeat_threshold,
+                                now,
+                            )
+                        });
+                        let running = status.status == AgentStatus::Running;
+                        let stall = detector.observe(&content, running, now);
+                        if stalls.get(&id) != Some(&stall) {
+                            stalls.insert(id, stall.clone());
+                            let _ = tx.send(Action::UpdateStall { id, stall });
+                        }
+                    }
+
                     let _ = tx.send(Action::UpdateAgentStatus {
                         id,
                         status: status.status,
@@ -10681,15 +13391,19 @@ async fn poll_agents(
                         }
                     }
 
-                    // Check for checklist progress (wrap in catch_unwind to prevent crashing the loop)
-                    let progress = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                        detect_checklist_progress(&content, ai_agent.clone())
+                    // Check for checklist items (wrap in catch_unwind to prevent crashing the loop)
+                    let checklist = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                        detect_checklist(&content, ai_agent.clone())
                     }))
                     .unwrap_or_else(|e| {
-                        tracing::warn!("detect_checklist_progress panicked, skipping: {:?}", e);
+                        tracing::warn!("detect_checklist panicked, skipping: {:?}", e);
                         None
                     });
-                    let _ = tx.send(Action::UpdateChecklistProgress { id, progress });
+                    let _ = tx.send(Action::UpdateChecklist { id, checklist });
+
+                    if let Some(tokens) = detect_token_usage(&content, ai_agent.clone()) {
+                        let _ = tx.send(Action::UpdateTokenUsage { id, tokens });
+                    }
                 }
             } else {
                 tracing::warn!(
@@ -10733,8 +13447,65 @@ async fn poll_agents(
     }
 }
 
-/// Background task to poll global system metrics (CPU/memory).
-async fn poll_system_metrics(tx: mpsc::UnboundedSender<Action>) {
+/// Replay detector fixtures and report which ones no longer detect as expected.
+fn run_detect(path: &std::path::Path) -> Result<()> {
+    let fixtures = grove::agent::DetectionFixture::load(path)?;
+    let mut failed = 0;
+    for (file, fixture) in &fixtures {
+        let detection = fixture.replay();
+        let passed = fixture.matches(&detection);
+        if !passed {
+            failed += 1;
+        }
+        println!(
+            "{} {} [{}, {}]: expected {}, detected {}",
+            if passed { "PASS" } else { "FAIL" },
+            file.display(),
+            fixture.ai_agent.display_name(),
+            fixture.foreground,
+            fixture.expected.label(),
+            detection.status.label()
+        );
+        if let Some(reason) = &detection.reason {
+            println!("     reason: {}", reason);
+        }
+        if let Some(pattern) = &detection.pattern {
+            println!("     pattern: {}", pattern);
+        }
+    }
+    println!("{} passed, {} failed", fixtures.len() - failed, failed);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Directory a tmux session was started in (the agent's worktree).
+fn tmux_session_path(session_name: &str) -> Option<String> {
+    let output = std::process::Command::new("tmux")
+        .args([
+            "display-message",
+            "-t",
+            session_name,
+            "-p",
+            "#{session_path}",
+        ])
+        .output()
+        .ok()?;
+    let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !path.is_empty()).then_some(path)
+}
+
+/// Background task to poll global system metrics (CPU/memory) and the
+/// resource usage of each agent's process tree.
+async fn poll_system_metrics(
+    tx: mpsc::UnboundedSender<Action>,
+    session_rx: watch::Receiver<HashMap<Uuid, String>>,
+) {
+    use grove::agent::resources::{agent_sessions, children_map, tree_usage};
+    use grove::tmux::{list_grove_sessions, TmuxSession};
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate};
+
     let mut sys = System::new_all();
 
     loop {
@@ -10758,13 +13529,45 @@ async fn poll_system_metrics(tx: mpsc::UnboundedSender<Action>) {
             memory_used,
             memory_total,
         });
+
+        let agents: Vec<(Uuid, String)> = session_rx
+            .borrow()
+            .iter()
+            .map(|(id, session)| (*id, session.clone()))
+            .collect();
+        if agents.is_empty() {
+            continue;
+        }
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            ProcessRefreshKind::new().with_cpu().with_memory(),
+        );
+        let roots = tokio::task::spawn_blocking(move || {
+            let sessions = list_grove_sessions().unwrap_or_default();
+            agents
+                .into_iter()
+                .map(|(id, session)| {
+                    let pids: Vec<u32> = agent_sessions(id, &session, &sessions)
+                        .into_iter()
+                        .filter_map(|name| TmuxSession::new(name).pane_pid())
+                        .collect();
+                    (id, pids)
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+        let children = children_map(&sys);
+        let usage = roots
+            .into_iter()
+            .map(|(id, pids)| (id, tree_usage(&sys, &children, &pids)))
+            .collect();
+        let _ = tx.send(Action::UpdateAgentResources { usage });
     }
 }
 
 /// Sort tasks so children appear directly after their parents.
 fn sort_tasks_by_parent(tasks: &mut [TaskListItem]) {
-    use std::collections::HashMap;
-
     let mut parent_to_children: HashMap<String, Vec<usize>> = HashMap::new();
     for (idx, task) in tasks.iter().enumerate() {
         if let Some(parent_id) = &task.parent_id {
@@ -10923,6 +13726,187 @@ fn compute_visible_task_indices(
         .collect()
 }
 
+/// Fetch the task list of the configured provider, parents before subtasks.
+async fn fetch_task_list(
+    provider: ProjectMgmtProvider,
+    asana_client: &OptionalAsanaClient,
+    notion_client: &OptionalNotionClient,
+    clickup_client: &OptionalClickUpClient,
+    airtable_client: &OptionalAirtableClient,
+    linear_client: &OptionalLinearClient,
+) -> Result<Vec<TaskListItem>, String> {
+    match provider {
+        ProjectMgmtProvider::Asana => match asana_client.get_project_tasks_with_subtasks().await {
+            Ok(tasks) => {
+                let mut items: Vec<TaskListItem> = tasks
+                    .into_iter()
+                    .map(|t| {
+                        let status_name = if t.parent_gid.is_some() {
+                            if t.completed {
+                                "Complete".to_string()
+                            } else {
+                                "Not Complete".to_string()
+                            }
+                        } else {
+                            t.section_name
+                                .clone()
+                                .unwrap_or_else(|| "No Section".to_string())
+                        };
+                        TaskListItem {
+                            id: t.gid,
+                            identifier: None,
+                            name: t.name,
+                            status_name,
+                            url: t.permalink_url.unwrap_or_default(),
+                            parent_id: t.parent_gid,
+                            has_children: t.num_subtasks > 0,
+                            labels: t.tags,
+                        }
+                    })
+                    .collect();
+                sort_tasks_by_parent(&mut items);
+                Ok(items)
+            }
+            Err(e) => Err(e.to_string()),
+        },
+        ProjectMgmtProvider::Notion => {
+            match notion_client.query_database_with_children(true).await {
+                Ok(pages) => {
+                    let parent_ids: std::collections::HashSet<String> = pages
+                        .iter()
+                        .filter_map(|p| p.parent_page_id.as_ref())
+                        .cloned()
+                        .collect();
+
+                    let mut items: Vec<TaskListItem> = pages
+                        .into_iter()
+                        .map(|p| {
+                            let status_name = p
+                                .status_name
+                                .clone()
+                                .unwrap_or_else(|| "Unknown".to_string());
+                            let has_children = parent_ids.contains(&p.id);
+                            TaskListItem {
+                                id: p.id,
+                                identifier: None,
+                                name: p.name,
+                                status_name,
+                                url: p.url,
+                                parent_id: p.parent_page_id,
+                                has_children,
+                                labels: p.tags,
+                            }
+                        })
+                        .collect();
+                    sort_tasks_by_parent(&mut items);
+                    Ok(items)
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        ProjectMgmtProvider::Clickup => match clickup_client.get_list_tasks_with_subtasks().await {
+            Ok(tasks) => {
+                let parent_ids: std::collections::HashSet<String> = tasks
+                    .iter()
+                    .filter_map(|t| t.parent_id.as_ref())
+                    .cloned()
+                    .collect();
+
+                let mut items: Vec<TaskListItem> = tasks
+                    .into_iter()
+                    .map(|t| {
+                        let status_name = t.status.clone();
+                        let has_children = parent_ids.contains(&t.id);
+                        TaskListItem {
+                            id: t.id,
+                            identifier: None,
+                            name: t.name,
+                            status_name,
+                            url: t.url.unwrap_or_default(),
+                            parent_id: t.parent_id,
+                            has_children,
+                            labels: t.tags,
+                        }
+                    })
+                    .collect();
+                sort_tasks_by_parent(&mut items);
+                Ok(items)
+            }
+            Err(e) => Err(e.to_string()),
+        },
+        ProjectMgmtProvider::Airtable => match airtable_client.list_records_with_children().await {
+            Ok(tasks) => {
+                let parent_ids: std::collections::HashSet<String> = tasks
+                    .iter()
+                    .filter_map(|t| t.parent_id.as_ref())
+                    .cloned()
+                    .collect();
+
+                let mut items: Vec<TaskListItem> = tasks
+                    .into_iter()
+                    .map(|t| {
+                        let status_name = t.status.clone().unwrap_or_else(|| "Unknown".to_string());
+                        let has_children = parent_ids.contains(&t.id);
+                        TaskListItem {
+                            id: t.id,
+                            identifier: None,
+                            name: t.name,
+                            status_name,
+                            url: t.url,
+                            parent_id: t.parent_id,
+                            has_children,
+                            labels: t.tags,
+                        }
+                    })
+                    .collect();
+                sort_tasks_by_parent(&mut items);
+                Ok(items)
+            }
+            Err(e) => Err(e.to_string()),
+        },
+        ProjectMgmtProvider::Linear => match linear_client.get_team_issues_with_children().await {
+            Ok(issues) => {
+                tracing::debug!("Linear issues before building task list:");
+                for i in &issues {
+                    tracing::debug!(
+                        "  {} - state_name: {}, state_type: {}, parent_id: {:?}",
+                        i.identifier,
+                        i.state_name,
+                        i.state_type,
+                        i.parent_id
+                    );
+                }
+
+                let parent_ids: std::collections::HashSet<String> = issues
+                    .iter()
+                    .filter_map(|i| i.parent_id.as_ref())
+                    .cloned()
+                    .collect();
+
+                let mut items: Vec<TaskListItem> = issues
+                    .into_iter()
+                    .map(|i| {
+                        let has_children = parent_ids.contains(&i.id);
+                        TaskListItem {
+                            id: i.id,
+                            identifier: Some(i.identifier),
+                            name: i.title,
+                            status_name: i.state_name,
+                            url: i.url,
+                            parent_id: i.parent_id,
+                            has_children,
+                            labels: i.labels,
+                        }
+                    })
+                    .collect();
+                sort_tasks_by_parent(&mut items);
+                Ok(items)
+            }
+            Err(e) => Err(e.to_string()),
+        },
+    }
+}
+
 /// Background task to poll Asana for task status updates.
 async fn poll_asana_tasks(
     asana_rx: watch::Receiver<Vec<(Uuid, String)>>,
@@ -11239,7 +14223,7 @@ async fn poll_codeberg_prs(
     }
 }
 
-fn get_combined_git_diff(worktree_path: &str, main_branch: &str) -> String {
+fn get_combined_git_diff(worktree_path: &str, base_ref: &str) -> String {
     use grove::git::GitSync;
 
     let git_sync = GitSync::new(worktree_path);
@@ -11254,13 +14238,13 @@ fn get_combined_git_diff(worktree_path: &str, main_branch: &str) -> String {
         _ => {}
     }
 
-    match git_sync.get_diff_against_main(main_branch) {
+    match git_sync.get_diff_against(base_ref) {
         Ok(diff) if !diff.trim().is_empty() => {
             if !result.is_empty() {
                 result.push_str("╰───────────────────────╯\n\n");
             }
             result.push_str("╭─ Commits vs ");
-            result.push_str(main_branch);
+            result.push_str(base_ref);
             result.push_str(" ─╮\n");
             result.push_str(&diff);
             result.push_str("\n╰───────────────────────╯\n");
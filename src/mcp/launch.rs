@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::app::config::AiAgent;
+use crate::core::common::shell_quote;
+
+/// Workspace Gemini CLI settings, kept out of git.
+const GEMINI_SETTINGS_FILE: &str = ".gemini/settings.json";
+
+/// Name the server is registered under in each AI agent's config.
+const SERVER_NAME: &str = "grove";
+
+/// How to start Grove's MCP server for one agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl ServerCommand {
+    /// `grove mcp` for `agent_id`, run by the current Grove binary.
+    pub fn new(socket: &Path, agent_id: Uuid) -> Result<Self> {
+        let program = std::env::current_exe().context("Failed to find the grove binary")?;
+        Ok(Self::for_program(
+            &program.to_string_lossy(),
+            socket,
+            agent_id,
+        ))
+    }
+
+    pub fn for_program(program: &str, socket: &Path, agent_id: Uuid) -> Self {
+        Self {
+            program: program.to_string(),
+            args: vec![
+                "mcp".to_string(),
+                "--socket".to_string(),
+                socket.to_string_lossy().to_string(),
+                "--agent".to_string(),
+                agent_id.to_string(),
+            ],
+        }
+    }
+
+    fn config(&self) -> Value {
+        json!({ "command": self.program, "args": self.args })
+    }
+}
+
+/// Register the server with `ai_agent` for a session started by `command`,
+/// either through its command line or its workspace settings.
+pub fn apply(
+    ai_agent: &AiAgent,
+    command: &str,
+    worktree_path: &str,
+    server: &ServerCommand,
+) -> Result<String> {
+    Ok(match ai_agent {
+        AiAgent::ClaudeCode => {
+            let config = json!({ "mcpServers": { SERVER_NAME: server.config() } });
+            format!(
+                "{} --mcp-config {}",
+                command,
+                shell_quote(&config.to_string())
+            )
+        }
+        AiAgent::Codex => {
+            let key = format!("mcp_servers.{}", SERVER_NAME);
+            format!(
+                "{} -c {} -c {}",
+                command,
+                shell_quote(&format!("{}.command={}", key, json!(server.program))),
+                shell_quote(&format!("{}.args={}", key, json!(server.args)))
+            )
+        }
+        AiAgent::Opencode => {
+            let mut local = vec![server.program.clone()];
+            local.extend(server.args.iter().cloned());
+            let config = json!({
+                "mcp": { SERVER_NAME: { "type": "local", "command": local, "enabled": true } }
+            });
+            format!(
+                "OPENCODE_CONFIG_CONTENT={} {}",
+                shell_quote(&config.to_string()),
+                command
+            )
+        }
+        AiAgent::Gemini => {
+            install_gemini_settings(worktree_path, server)?;
+            command.to_string()
+        }
+    })
+}
+
+/// Add the server to the worktree's Gemini CLI settings, keeping the rest.
+fn install_gemini_settings(worktree_path: &str, server: &ServerCommand) -> Result<()> {
+    let path = Path::new(worktree_path).join(GEMINI_SETTINGS_FILE);
+    let mut settings = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?,
+        Err(_) => json!({}),
+    };
+    if !settings.is_object() {
+        settings = json!({});
+    }
+    if !settings["mcpServers"].is_object() {
+        settings["mcpServers"] = json!({});
+    }
+    settings["mcpServers"][SERVER_NAME] = server.config();
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create .gemini directory")?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&settings)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    crate::git::exclude_from_git(worktree_path, GEMINI_SETTINGS_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> ServerCommand {
+        ServerCommand::for_program("/usr/bin/grove", Path::new("/tmp/grove.sock"), Uuid::nil())
+    }
+
+    #[test]
+    fn test_apply_on_command_line() {
+        let agent = "00000000-0000-0000-0000-000000000000";
+        assert_eq!(
+            apply(&AiAgent::ClaudeCode, "claude", "/tmp", &server()).unwrap(),
+            format!(
+                "claude --mcp-config '{{\"mcpServers\":{{\"grove\":{{\"args\":[\"mcp\",\
+                 \"--socket\",\"/tmp/grove.sock\",\"--agent\",\"{}\"],\
+                 \"command\":\"/usr/bin/grove\"}}}}}}'",
+                agent
+            )
+        );
+        assert_eq!(
+            apply(&AiAgent::Codex, "codex resume --last", "/tmp", &server()).unwrap(),
+            format!(
+                "codex resume --last -c 'mcp_servers.grove.command=\"/usr/bin/grove\"' \
+                 -c 'mcp_servers.grove.args=[\"mcp\",\"--socket\",\"/tmp/grove.sock\",\
+                 \"--agent\",\"{}\"]'",
+                agent
+            )
+        );
+        let opencode = apply(&AiAgent::Opencode, "opencode", "/tmp", &server()).unwrap();
+        assert!(opencode.starts_with(
+            "OPENCODE_CONFIG_CONTENT='{\"mcp\":{\"grove\":{\"command\":[\"/usr/bin/grove\",\"mcp\""
+        ));
+        assert!(opencode.ends_with("\"type\":\"local\"}}}' opencode"));
+    }
+
+    #[test]
+    fn test_apply_gemini_merges_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let worktree = dir.path().to_str().unwrap();
+        std::fs::create_dir_all(dir.path().join(".gemini")).unwrap();
+        std::fs::write(
+            dir.path().join(GEMINI_SETTINGS_FILE),
+            r#"{"theme": "Dracula", "mcpServers": {"github": {"command": "gh-mcp"}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            apply(&AiAgent::Gemini, "gemini", worktree, &server()).unwrap(),
+            "gemini"
+        );
+        let settings: Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join(GEMINI_SETTINGS_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(settings["theme"], "Dracula");
+        assert_eq!(settings["mcpServers"]["github"]["command"], "gh-mcp");
+        assert_eq!(settings["mcpServers"]["grove"]["command"], "/usr/bin/grove");
+    }
+}
@@ -0,0 +1,187 @@
+pub mod launch;
+pub mod server;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixListener;
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::app::{Action, Config};
+
+/// How long a tool call may take before the agent gets an error.
+const CALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A tool call from an agent's MCP server, forwarded to the running Grove.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub agent_id: Uuid,
+    pub tool: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+impl ToolCall {
+    pub fn str_arg(&self, key: &str) -> Option<&str> {
+        self.arguments
+            .get(key)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    }
+
+    pub fn u64_arg(&self, key: &str) -> Option<u64> {
+        self.arguments.get(key).and_then(Value::as_u64)
+    }
+}
+
+/// Text returned to the agent, or the error it should see.
+pub type ToolResult = Result<String, String>;
+
+/// Answers one tool call. Cloned into `Action::McpToolCall`; the first
+/// `send` wins.
+#[derive(Clone)]
+pub struct Reply(Arc<Mutex<Option<oneshot::Sender<ToolResult>>>>);
+
+impl Reply {
+    pub fn new() -> (Self, oneshot::Receiver<ToolResult>) {
+        let (tx, rx) = oneshot::channel();
+        (Self(Arc::new(Mutex::new(Some(tx)))), rx)
+    }
+
+    pub fn send(&self, result: ToolResult) {
+        if let Some(tx) = self.0.lock().ok().and_then(|mut tx| tx.take()) {
+            let _ = tx.send(result);
+        }
+    }
+}
+
+impl std::fmt::Debug for Reply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Reply")
+    }
+}
+
+/// Socket the running Grove listens on for `repo_path`.
+pub fn socket_path(repo_path: &str) -> Result<PathBuf> {
+    Ok(Config::config_dir()?
+        .join("mcp")
+        .join(format!("{}.sock", Config::repo_hash(repo_path))))
+}
+
+/// Accept tool calls on `path`, one JSON line per connection, and hand them
+/// to the app as `Action::McpToolCall`. Only the current user may connect.
+pub async fn serve(path: PathBuf, action_tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create MCP socket directory")?;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+            .context("Failed to restrict MCP socket directory")?;
+    }
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)
+        .with_context(|| format!("Failed to bind MCP socket {}", path.display()))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+        .context("Failed to restrict MCP socket")?;
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let action_tx = action_tx.clone();
+        tokio::spawn(async move {
+            let (read, mut write) = stream.into_split();
+            let mut line = String::new();
+            if BufReader::new(read).read_line(&mut line).await.is_err() {
+                return;
+            }
+            let result = match serde_json::from_str::<ToolCall>(&line) {
+                Ok(call) => {
+                    let (reply, rx) = Reply::new();
+                    if action_tx.send(Action::McpToolCall { call, reply }).is_err() {
+                        Err("Grove is shutting down".to_string())
+                    } else {
+                        match tokio::time::timeout(CALL_TIMEOUT, rx).await {
+                            Ok(Ok(result)) => result,
+                            Ok(Err(_)) => Err("Grove dropped the request".to_string()),
+                            Err(_) => Err("Timed out waiting for Grove".to_string()),
+                        }
+                    }
+                }
+                Err(e) => Err(format!("Invalid tool call: {}", e)),
+            };
+            if let Ok(mut response) = serde_json::to_string(&result) {
+                response.push('\n');
+                let _ = write.write_all(response.as_bytes()).await;
+            }
+        });
+    }
+}
+
+/// Send `call` to the Grove listening on `socket` and wait for its answer.
+pub fn call(socket: &Path, call: &ToolCall) -> ToolResult {
+    let send = || -> Result<ToolResult> {
+        let mut stream = std::os::unix::net::UnixStream::connect(socket)
+            .context("Grove is not running for this repository")?;
+        stream.set_read_timeout(Some(CALL_TIMEOUT + Duration::from_secs(5)))?;
+        let mut request = serde_json::to_string(call)?;
+        request.push('\n');
+        stream.write_all(request.as_bytes())?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        serde_json::from_str(&response).context("Invalid response from Grove")
+    };
+    send().unwrap_or_else(|e| Err(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_call_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("grove.sock");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tokio::spawn(serve(path.clone(), tx));
+        tokio::spawn(async move {
+            while let Some(action) = rx.recv().await {
+                if let Action::McpToolCall { call, reply } = action {
+                    reply.send(Ok(format!("{} from {}", call.tool, call.agent_id)));
+                }
+            }
+        });
+        while !path.exists() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let agent_id = Uuid::new_v4();
+        let request = ToolCall {
+            agent_id,
+            tool: "get_task".to_string(),
+            arguments: Value::Null,
+        };
+        let socket = path.clone();
+        let result = tokio::task::spawn_blocking(move || call(&socket, &request))
+            .await
+            .unwrap();
+        assert_eq!(result, Ok(format!("get_task from {}", agent_id)));
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_call_without_grove() {
+        let dir = tempfile::tempdir().unwrap();
+        let request = ToolCall {
+            agent_id: Uuid::new_v4(),
+            tool: "get_task".to_string(),
+            arguments: Value::Null,
+        };
+        assert!(call(&dir.path().join("missing.sock"), &request).is_err());
+    }
+}
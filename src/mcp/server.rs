@@ -0,0 +1,246 @@
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+use super::{ToolCall, ToolResult};
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Run the MCP server for one agent over stdio, forwarding tool calls to the
+/// Grove listening on `socket`.
+pub fn run(socket: &Path, agent_id: Uuid) -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&message, |tool, arguments| {
+                super::call(
+                    socket,
+                    &ToolCall {
+                        agent_id,
+                        tool: tool.to_string(),
+                        arguments,
+                    },
+                )
+            }),
+            Err(e) => Some(error(Value::Null, -32700, &format!("Parse error: {}", e))),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Answer one JSON-RPC message. Notifications get no response.
+pub fn handle_message(
+    message: &Value,
+    forward: impl FnOnce(&str, Value) -> ToolResult,
+) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => json!({
+            "protocolVersion": params
+                .get("protocolVersion")
+                .and_then(Value::as_str)
+                .unwrap_or(PROTOCOL_VERSION),
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "grove", "version": crate::version::version() },
+        }),
+        "ping" => json!({}),
+        "tools/list" => json!({ "tools": tools() }),
+        "tools/call" => {
+            let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+            if !tools().iter().any(|tool| tool["name"] == name) {
+                return Some(error(id, -32602, &format!("Unknown tool: {}", name)));
+            }
+            let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+            let (text, is_error) = match forward(name, arguments) {
+                Ok(text) => (text, false),
+                Err(text) => (text, true),
+            };
+            json!({
+                "content": [{ "type": "text", "text": text }],
+                "isError": is_error,
+            })
+        }
+        _ => return Some(error(id, -32601, &format!("Method not found: {}", method))),
+    };
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn tool(name: &str, description: &str, properties: Value, required: &[&str]) -> Value {
+    json!({
+        "name": name,
+        "description": description,
+        "inputSchema": {
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        },
+    })
+}
+
+/// Tools Grove offers to the agents it runs.
+pub fn tools() -> Vec<Value> {
+    let agent = json!({
+        "agent": { "type": "string", "description": "Name or branch of the agent" },
+    });
+    vec![
+        tool(
+            "get_task",
+            "Get the project management task linked to this agent.",
+            json!({}),
+            &[],
+        ),
+        tool(
+            "update_task_status",
+            "Move this agent's linked task to another status, e.g. \"In Review\".",
+            json!({ "status": { "type": "string", "description": "Status name" } }),
+            &["status"],
+        ),
+        tool(
+            "post_summary",
+            "Post a summary of the work done so far. Grove shows it to the user.",
+            json!({ "summary": { "type": "string" } }),
+            &["summary"],
+        ),
+        tool(
+            "list_agents",
+            "List the other agents working in this repository.",
+            json!({}),
+            &[],
+        ),
+        tool(
+            "get_agent_status",
+            "Get another agent's status, branch and task.",
+            agent.clone(),
+            &["agent"],
+        ),
+        tool(
+            "get_agent_diff",
+            "Get the diff of another agent's branch against its base.",
+            agent,
+            &["agent"],
+        ),
+        tool(
+            "restart_dev_server",
+            "Start or restart this agent's dev server.",
+            json!({}),
+            &[],
+        ),
+        tool(
+            "get_dev_server_logs",
+            "Read the latest lines of this agent's dev server logs.",
+            json!({
+                "lines": { "type": "integer", "description": "Number of lines (default 100)" },
+                "service": { "type": "string", "description": "Service name for multi-service setups" },
+            }),
+            &[],
+        ),
+        tool(
+            "spawn_agent",
+            "Start a sub-agent on a new worktree branched from this agent's branch.",
+            json!({
+                "name": { "type": "string", "description": "Name of the new agent" },
+                "prompt": { "type": "string", "description": "Instructions for the new agent" },
+            }),
+            &["name", "prompt"],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unused(_: &str, _: Value) -> ToolResult {
+        panic!("no tool call expected")
+    }
+
+    #[test]
+    fn test_initialize_and_list() {
+        let init = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": { "protocolVersion": "2025-03-26" },
+        });
+        let response = handle_message(&init, unused).unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(response["result"]["serverInfo"]["name"], "grove");
+
+        let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(handle_message(&initialized, unused).is_none());
+
+        let list = json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" });
+        let response = handle_message(&list, unused).unwrap();
+        let names: Vec<_> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"get_task"));
+        assert!(names.contains(&"spawn_agent"));
+    }
+
+    #[test]
+    fn test_tool_call() {
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": "a",
+            "method": "tools/call",
+            "params": { "name": "update_task_status", "arguments": { "status": "Done" } },
+        });
+        let response = handle_message(&call, |tool, arguments| {
+            assert_eq!(tool, "update_task_status");
+            Err(format!("no task to move to {}", arguments["status"]))
+        })
+        .unwrap();
+        assert_eq!(response["result"]["isError"], true);
+        assert_eq!(
+            response["result"]["content"][0]["text"],
+            "no task to move to \"Done\""
+        );
+    }
+
+    #[test]
+    fn test_unknown_method_and_tool() {
+        let unknown = json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" });
+        assert_eq!(
+            handle_message(&unknown, unused).unwrap()["error"]["code"],
+            -32601
+        );
+
+        let call = json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "tools/call",
+            "params": { "name": "rm_rf" },
+        });
+        assert_eq!(
+            handle_message(&call, unused).unwrap()["error"]["code"],
+            -32602
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod session;
 
-pub use session::{build_command_with_session, find_session_by_directory, get_db_path};
+pub use session::{
+    build_command_with_session, find_session_by_directory, get_db_path, list_sessions,
+};
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
+use chrono::DateTime;
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::agent::sessions::SessionSummary;
+
 pub fn get_db_path() -> Result<PathBuf> {
     let output = Command::new("opencode")
         .args(["db", "path"])
@@ -59,6 +62,52 @@ pub fn find_session_by_directory(worktree_path: &str) -> Result<Option<String>>
     Ok(None)
 }
 
+/// Every OpenCode session for `worktree_path`, from its database.
+pub fn list_sessions(worktree_path: &str) -> Result<Vec<SessionSummary>> {
+    let query = format!(
+        "SELECT s.id, s.title, s.time_updated, \
+         (SELECT COUNT(*) FROM message m WHERE m.session_id = s.id) AS messages \
+         FROM session s WHERE s.directory = '{}' ORDER BY s.time_updated DESC;",
+        worktree_path.replace("'", "''")
+    );
+
+    let output = Command::new("opencode")
+        .args(["db", &query, "--format", "json"])
+        .output()
+        .context("Failed to query opencode database")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("OpenCode DB query failed: {}", stderr.trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_session_rows(stdout.trim())
+}
+
+fn parse_session_rows(json: &str) -> Result<Vec<SessionSummary>> {
+    if json.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rows: Vec<serde_json::Value> =
+        serde_json::from_str(json).context("Failed to parse opencode session query result")?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(SessionSummary {
+                id: row.get("id")?.as_str()?.to_string(),
+                updated_at: row
+                    .get("time_updated")
+                    .and_then(|t| t.as_i64())
+                    .and_then(DateTime::from_timestamp_millis)
+                    .unwrap_or_default(),
+                first_prompt: row.get("title").and_then(|t| t.as_str()).map(String::from),
+                message_count: row.get("messages").and_then(|m| m.as_u64()).unwrap_or(0) as usize,
+            })
+        })
+        .collect())
+}
+
 pub fn build_command_with_session(base_cmd: &str, session_id: Option<&str>) -> String {
     match session_id {
         Some(id) => format!("{} -s {}", base_cmd, id),
@@ -78,4 +127,15 @@ mod tests {
         );
         assert_eq!(build_command_with_session("opencode", None), "opencode");
     }
+
+    #[test]
+    fn test_parse_session_rows() {
+        let json = r#"[{"id":"ses_2","title":"Add dark mode","time_updated":1760000000000,"messages":12}]"#;
+        let sessions = parse_session_rows(json).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, "ses_2");
+        assert_eq!(sessions[0].first_prompt.as_deref(), Some("Add dark mode"));
+        assert_eq!(sessions[0].message_count, 12);
+        assert!(parse_session_rows("[]").unwrap().is_empty());
+    }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::agent::Agent;
-use crate::app::Config;
+use crate::app::{Autopilot, Config, Orchestration};
 
 /// Persisted session data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +11,10 @@ pub struct SessionData {
     pub repo_path: String,
     pub agents: Vec<Agent>,
     pub selected_index: usize,
+    #[serde(default)]
+    pub orchestrations: Vec<Orchestration>,
+    #[serde(default)]
+    pub autopilot: Autopilot,
 }
 
 impl SessionData {
@@ -19,6 +23,8 @@ impl SessionData {
             repo_path,
             agents: Vec::new(),
             selected_index: 0,
+            orchestrations: Vec::new(),
+            autopilot: Autopilot::default(),
         }
     }
 }
@@ -93,11 +99,15 @@ pub fn save_session(
     repo_path: &str,
     agents: &[Agent],
     selected_index: usize,
+    orchestrations: &[Orchestration],
+    autopilot: &Autopilot,
 ) -> Result<()> {
     let session = SessionData {
         repo_path: repo_path.to_string(),
         agents: agents.to_vec(),
         selected_index,
+        orchestrations: orchestrations.to_vec(),
+        autopilot: autopilot.clone(),
     };
 
     storage.save(&session)
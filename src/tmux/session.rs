@@ -79,6 +79,21 @@ impl TmuxSession {
         Ok(())
     }
 
+    /// Send `C-c` to the pane.
+    pub fn interrupt(&self) -> Result<()> {
+        let output = Command::new("tmux")
+            .args(["send-keys", "-t", &self.name, "C-c"])
+            .output()
+            .context("Failed to send interrupt to tmux")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to send interrupt: {}", stderr);
+        }
+
+        Ok(())
+    }
+
     /// Send keys to the session and press Enter.
     pub fn send_keys(&self, keys: &str) -> Result<()> {
         // Send the text literally
@@ -98,6 +113,40 @@ impl TmuxSession {
         Ok(())
     }
 
+    /// Paste multi-line text as one block (bracketed paste, so newlines do
+    /// not submit early) and press Enter.
+    pub fn paste_and_submit(&self, text: &str) -> Result<()> {
+        let buffer = format!("{}-paste", self.name);
+        let output = Command::new("tmux")
+            .args(["set-buffer", "-b", &buffer, "--", text])
+            .output()
+            .context("Failed to set tmux buffer")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to set buffer: {}", stderr);
+        }
+
+        let output = Command::new("tmux")
+            .args(["paste-buffer", "-p", "-d", "-b", &buffer, "-t", &self.name])
+            .output()
+            .context("Failed to paste tmux buffer")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to paste: {}", stderr);
+        }
+
+        let output = Command::new("tmux")
+            .args(["send-keys", "-t", &self.name, "C-m"])
+            .output()
+            .context("Failed to send Enter to tmux")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to send Enter: {}", stderr);
+        }
+
+        Ok(())
+    }
+
     /// Attach to the session (spawns as child process, returns when detached).
     pub fn attach(&self) -> Result<()> {
         let status = Command::new("tmux")
@@ -156,6 +205,57 @@ impl TmuxSession {
         }
     }
 
+    /// Get the PID of the pane's shell.
+    pub fn pane_pid(&self) -> Option<u32> {
+        let output = Command::new("tmux")
+            .args(["display-message", "-t", &self.name, "-p", "#{pane_pid}"])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+
+    /// Suspend the processes running in the pane (SIGSTOP to the pane's
+    /// foreground process group, so grandchildren stop too).
+    pub fn pause(&self) -> Result<()> {
+        self.signal_foreground("STOP")
+    }
+
+    /// Resume processes suspended by [`TmuxSession::pause`].
+    pub fn resume(&self) -> Result<()> {
+        self.signal_foreground("CONT")
+    }
+
+    fn signal_foreground(&self, signal: &str) -> Result<()> {
+        let pid = self.pane_pid().context("Failed to get pane PID")?;
+
+        let output = Command::new("ps")
+            .args(["-o", "tpgid=", "-p", &pid.to_string()])
+            .output()
+            .context("Failed to find the pane's foreground process group")?;
+        let pgid: i64 = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .context("Failed to parse the pane's foreground process group")?;
+        if pgid <= 0 {
+            return Ok(());
+        }
+
+        let status = Command::new("kill")
+            .args([&format!("-{}", signal), "--", &format!("-{}", pgid)])
+            .status()
+            .context("Failed to signal pane processes")?;
+        if !status.success() {
+            anyhow::bail!("Failed to send SIG{} to process group {}", signal, pgid);
+        }
+
+        Ok(())
+    }
+
     /// Get pane dimensions.
     pub fn pane_size(&self) -> Result<(u16, u16)> {
         let output = Command::new("tmux")
@@ -10,23 +10,37 @@ use ratatui::{
 
 use uuid::Uuid;
 
-use crate::app::{AppState, InputMode, LogLevel, PreviewTab};
+use crate::agent::{budget, overlap_levels};
+use crate::app::{AppState, InputMode, LogLevel, PreviewTab, SyncStrategy};
 use crate::devserver::DevServerStatus;
 
 use super::components::{
-    render_confirm_modal, render_input_modal, AgentListWidget, ColumnSelectorWidget,
-    DevServerViewWidget, DevServerWarningModal, DiffViewWidget, EmptyDevServerWidget,
-    EmptyDiffWidget, EmptyOutputWidget, GitSetupModal, GlobalSetupWizard, HelpOverlay,
-    LoadingOverlay, OutputViewWidget, PmSetupModal, PmStatusDebugOverlay, ProjectSetupWizard,
-    SettingsModal, StatusBarWidget, StatusDebugOverlay, StatusDropdown, SystemMetricsWidget,
-    TaskListModal, TaskReassignmentWarningModal, ToastWidget, TutorialWizard,
+    render_confirm_modal, render_input_modal, ActivityViewWidget, AgentListWidget,
+    CheckpointTimeline, ColumnSelectorWidget, DevServerViewWidget, DevServerWarningModal,
+    DiffViewWidget, EmptyDevServerWidget, EmptyDiffWidget, EmptyOutputWidget, GitSetupModal,
+    GlobalSetupWizard, HelpOverlay, LoadingOverlay, MergeConflictModal, OrchestratorWidget,
+    OutputViewWidget, OverlapMatrixOverlay, PlanViewWidget, PmSetupModal, PmStatusDebugOverlay,
+    ProjectSetupWizard, SessionPickerWidget, SettingsModal, StatusBarWidget, StatusDebugOverlay,
+    StatusDropdown, SystemMetricsWidget, TaskListModal, TaskReassignmentWarningModal, ToastWidget,
+    TutorialWizard,
 };
 
 #[derive(Clone)]
 pub struct DevServerRenderInfo {
     pub status: DevServerStatus,
     pub logs: Vec<String>,
+    /// Position of `logs[0]` among all lines ever logged
+    pub first_line: usize,
     pub agent_name: String,
+    pub url: Option<String>,
+    pub services: Vec<ServiceRenderInfo>,
+    pub selected_service: usize,
+}
+
+#[derive(Clone)]
+pub struct ServiceRenderInfo {
+    pub name: String,
+    pub status: DevServerStatus,
 }
 
 const BANNER: &[&str] = &[
@@ -192,6 +206,8 @@ impl<'a> AppWidget<'a> {
             TaskReassignmentWarningModal::new(warning, &self.state.agents).render(frame);
         } else if let Some(warning) = &self.state.devserver_warning {
             DevServerWarningModal::new(warning).render(frame);
+        } else if let Some(conflict) = &self.state.merge_conflict {
+            MergeConflictModal::new(conflict).render(frame);
         } else if let Some(mode) = &self.state.input_mode {
             self.render_modal(frame, mode, size);
         }
@@ -202,6 +218,34 @@ impl<'a> AppWidget<'a> {
             }
         }
 
+        if self.state.show_overlap_matrix {
+            let agents: Vec<&_> = self
+                .state
+                .agent_order
+                .iter()
+                .filter_map(|id| self.state.agents.get(id))
+                .collect();
+            OverlapMatrixOverlay::new(&agents, &self.state.overlaps).render(frame, size);
+        }
+
+        if let Some(view) = &self.state.checkpoint_view {
+            CheckpointTimeline::new(view).render(frame, size);
+        }
+
+        if self.state.show_orchestrator {
+            OrchestratorWidget::new(&self.state.orchestrations, self.state.orchestrator_selected)
+                .render(frame, size);
+        }
+
+        if let Some(picker) = &self.state.session_picker {
+            let current_session = self
+                .state
+                .agents
+                .get(&picker.agent_id)
+                .and_then(|a| a.ai_session_id.as_deref());
+            SessionPickerWidget::new(picker, current_session).render(frame, size);
+        }
+
         if self.state.pm_status_debug.active {
             let configured_providers = self.get_configured_pm_providers();
             PmStatusDebugOverlay::new(&self.state.pm_status_debug, &configured_providers)
@@ -280,7 +324,50 @@ impl<'a> AppWidget<'a> {
     fn render_modal(&self, frame: &mut Frame, mode: &InputMode, _area: Rect) {
         match mode {
             InputMode::NewAgent => {
-                render_input_modal(frame, "New Agent", "Enter name:", &self.state.input_buffer);
+                let prompt = if self.state.launch_profiles().is_empty() {
+                    "Enter name:".to_string()
+                } else {
+                    format!(
+                        "Enter name (profile: {}, Tab to change):",
+                        self.state.pending_profile.as_deref().unwrap_or("none")
+                    )
+                };
+                render_input_modal(frame, "New Agent", &prompt, &self.state.input_buffer);
+            }
+            InputMode::SelectBaseBranch => {
+                render_input_modal(
+                    frame,
+                    "New Agent",
+                    "Base branch (main or another agent's branch):",
+                    &self.state.input_buffer,
+                );
+            }
+            InputMode::ImportAgent => {
+                let prompt = if self.state.launch_profiles().is_empty() {
+                    "Branch, worktree path or PR/MR number (#123):".to_string()
+                } else {
+                    format!(
+                        "Branch, worktree path or PR/MR number (profile: {}, Tab to change):",
+                        self.state.pending_profile.as_deref().unwrap_or("none")
+                    )
+                };
+                render_input_modal(frame, "Import Agent", &prompt, &self.state.input_buffer);
+            }
+            InputMode::SetBudget => {
+                render_input_modal(
+                    frame,
+                    "Set Budget",
+                    "runtime=2h idle=30m awaiting=10m tokens=500k action=notify|interrupt|stop (empty = repo default):",
+                    &self.state.input_buffer,
+                );
+            }
+            InputMode::CaptureFixture => {
+                render_input_modal(
+                    frame,
+                    "Capture Fixture",
+                    "Expected status (running, awaiting, idle, completed, error, stopped):",
+                    &self.state.input_buffer,
+                );
             }
             InputMode::SetNote => {
                 render_input_modal(
@@ -310,10 +397,29 @@ impl<'a> AppWidget<'a> {
                     .selected_agent()
                     .map(|a| a.name.as_str())
                     .unwrap_or("agent");
+                let message = match self.state.settings.repo_config.git.sync_strategy {
+                    SyncStrategy::Merge => format!("Merge main into '{}'?", agent_name),
+                    SyncStrategy::Rebase => format!("Rebase '{}' onto main?", agent_name),
+                    SyncStrategy::Prompt => format!("Send merge main request to '{}'?", agent_name),
+                };
+                render_confirm_modal(frame, "Merge Main", &message, "y", "Esc");
+            }
+            InputMode::ConfirmLandQueue => {
+                let names: Vec<&str> = self
+                    .state
+                    .merge_queue
+                    .iter()
+                    .filter_map(|id| self.state.agents.get(id))
+                    .map(|a| a.name.as_str())
+                    .collect();
                 render_confirm_modal(
                     frame,
-                    "Merge Main",
-                    &format!("Send merge main request to '{}'?", agent_name),
+                    "Land Merge Queue",
+                    &format!(
+                        "Land into {}: {}?",
+                        self.state.settings.repo_config.git.main_branch,
+                        names.join(" → ")
+                    ),
                     "y",
                     "Esc",
                 );
@@ -488,6 +594,14 @@ impl<'a> AppWidget<'a> {
                 &self.state.config.ui.column_visibility,
             )
             .with_count(self.state.agents.len())
+            .with_overlaps(overlap_levels(&self.state.overlaps))
+            .with_merge_queue(self.state.merge_queue.clone())
+            .with_verify_runs(&self.state.verify_runs)
+            .with_resources(
+                &self.state.agent_resources,
+                self.state.config.performance.agent_cpu_warn_percent,
+                self.state.config.performance.agent_memory_warn_mb,
+            )
             .render(frame, area);
         }
     }
@@ -504,6 +618,8 @@ impl<'a> AppWidget<'a> {
             PreviewTab::Preview => self.render_preview_content(frame, chunks[1]),
             PreviewTab::GitDiff => self.render_gitdiff_content(frame, chunks[1]),
             PreviewTab::DevServer => self.render_devserver_content(frame, chunks[1]),
+            PreviewTab::Activity => self.render_activity_content(frame, chunks[1]),
+            PreviewTab::Plan => self.render_plan_content(frame, chunks[1]),
         }
     }
 
@@ -542,6 +658,24 @@ impl<'a> AppWidget<'a> {
             Style::default().fg(Color::DarkGray)
         };
 
+        let activity_style = if self.state.preview_tab == PreviewTab::Activity {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        let plan_style = if self.state.preview_tab == PreviewTab::Plan {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
         let tabs = Line::from(vec![
             Span::styled(" Preview ", preview_style),
             Span::raw(" "),
@@ -551,6 +685,10 @@ impl<'a> AppWidget<'a> {
                 format!(" Dev Server{} ", devserver_indicator),
                 devserver_style,
             ),
+            Span::raw(" "),
+            Span::styled(" Activity ", activity_style),
+            Span::raw(" "),
+            Span::styled(" Plan ", plan_style),
         ]);
 
         let paragraph = Paragraph::new(tabs);
@@ -559,12 +697,19 @@ impl<'a> AppWidget<'a> {
 
     fn render_preview_content(&self, frame: &mut Frame, area: Rect) {
         if let Some(content) = &self.state.preview_content {
-            let agent_name = self
-                .state
-                .selected_agent()
-                .map(|a| a.name.as_str())
-                .unwrap_or("Preview");
-            let title = format!("PREVIEW: {}", agent_name);
+            let title = match self.state.selected_agent() {
+                Some(agent) => {
+                    let budget = agent
+                        .budget
+                        .as_ref()
+                        .unwrap_or(&self.state.settings.repo_config.budget);
+                    match budget::summary(budget, agent, chrono::Utc::now()) {
+                        Some(left) => format!("PREVIEW: {} · {}", agent.name, left),
+                        None => format!("PREVIEW: {}", agent.name),
+                    }
+                }
+                None => "PREVIEW: Preview".to_string(),
+            };
             OutputViewWidget::new(&title, content)
                 .with_scroll(self.state.output_scroll)
                 .render(frame, area);
@@ -580,12 +725,35 @@ impl<'a> AppWidget<'a> {
                 info.logs.clone(),
                 info.agent_name.clone(),
             )
+            .with_url(info.url.clone())
+            .with_services(info.services.clone(), info.selected_service)
+            .with_selection(info.first_line, self.state.devserver_selection)
             .render(frame, area);
         } else {
             EmptyDevServerWidget::render(frame, area);
         }
     }
 
+    fn render_activity_content(&self, frame: &mut Frame, area: Rect) {
+        let empty = std::collections::VecDeque::new();
+        let (name, events) = match self.state.selected_agent() {
+            Some(agent) => (agent.name.as_str(), &agent.transcript),
+            None => ("Agent", &empty),
+        };
+        ActivityViewWidget::new(name, events).render(frame, area);
+    }
+
+    fn render_plan_content(&self, frame: &mut Frame, area: Rect) {
+        let empty = std::collections::VecDeque::new();
+        let widget = match self.state.selected_agent() {
+            Some(agent) => {
+                PlanViewWidget::new(&agent.name, agent.plan.as_ref(), &agent.plan_history)
+            }
+            None => PlanViewWidget::new("Agent", None, &empty),
+        };
+        widget.render(frame, area);
+    }
+
     fn render_gitdiff_content(&self, frame: &mut Frame, area: Rect) {
         let agent_name = self
             .state
@@ -606,6 +774,12 @@ impl<'a> AppWidget<'a> {
             self.state.memory_used,
             self.state.memory_total,
         )
+        .with_agent(self.state.selected_agent().and_then(|agent| {
+            self.state
+                .agent_resources
+                .get(&agent.id)
+                .map(|usage| (agent.name.as_str(), usage))
+        }))
         .render(frame, area);
     }
 
@@ -652,6 +826,14 @@ impl<'a> AppWidget<'a> {
     }
 
     fn render_footer(&self, frame: &mut Frame, area: Rect) {
-        StatusBarWidget::new(None, false, &self.state.config.keybinds).render(frame, area);
+        let autopilot = self.state.autopilot.enabled.then(|| {
+            (
+                self.state.autopilot.active(),
+                self.state.settings.repo_config.autopilot.max_active,
+            )
+        });
+        StatusBarWidget::new(None, false, &self.state.config.keybinds)
+            .with_autopilot(autopilot)
+            .render(frame, area);
     }
 }
@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::agent::{TranscriptEvent, TranscriptEventKind};
+
+/// Session log events for the selected agent, newest at the bottom.
+pub struct ActivityViewWidget<'a> {
+    agent_name: &'a str,
+    events: &'a VecDeque<TranscriptEvent>,
+}
+
+impl<'a> ActivityViewWidget<'a> {
+    pub fn new(agent_name: &'a str, events: &'a VecDeque<TranscriptEvent>) -> Self {
+        Self { agent_name, events }
+    }
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let visible_height = area.height.saturating_sub(2) as usize;
+
+        let lines: Vec<Line> = if self.events.is_empty() {
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "No session log activity yet (Codex and Gemini agents)",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ]
+        } else {
+            let skip = self.events.len().saturating_sub(visible_height);
+            self.events.iter().skip(skip).map(event_line).collect()
+        };
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(" ACTIVITY: {} ", self.agent_name))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(paragraph, area);
+    }
+}
+
+fn event_line(event: &TranscriptEvent) -> Line<'static> {
+    let time = event
+        .at
+        .map(|at| {
+            at.with_timezone(&chrono::Local)
+                .format("%H:%M:%S ")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let color = match event.kind {
+        TranscriptEventKind::Prompt => Color::Cyan,
+        TranscriptEventKind::Message => Color::White,
+        TranscriptEventKind::ToolCall => Color::Blue,
+        TranscriptEventKind::ApprovalRequest => Color::Yellow,
+        TranscriptEventKind::TurnComplete => Color::Green,
+        TranscriptEventKind::Error => Color::Red,
+    };
+    Line::from(vec![
+        Span::styled(time, Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!("{:<8} ", event.kind.label()),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(event.text.clone()),
+    ])
+}
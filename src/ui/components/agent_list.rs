@@ -4,11 +4,12 @@ use uuid::Uuid;
 use ratatui::{
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Cell, Row, Table},
     Frame,
 };
 
-use crate::agent::{Agent, AgentStatus};
+use crate::agent::{Agent, AgentStatus, OverlapLevel, ResourceUsage, Stall};
 use crate::app::config::{AppearanceConfig, ColumnVisibility, GitProvider, ProjectMgmtProvider};
 use crate::core::git_providers::codeberg::PullRequestStatus as CodebergPullRequestStatus;
 use crate::core::git_providers::github::{
@@ -16,6 +17,7 @@ use crate::core::git_providers::github::{
 };
 use crate::core::git_providers::gitlab::{MergeRequestStatus, PipelineStatus};
 use crate::devserver::DevServerStatus;
+use crate::verify::{VerifyRun, VerifyStatus};
 
 /// Braille spinner frames for running status
 const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
@@ -34,6 +36,12 @@ pub struct AgentListWidget<'a> {
     appearance_config: &'a AppearanceConfig,
     pm_provider: ProjectMgmtProvider,
     column_visibility: &'a ColumnVisibility,
+    overlaps: HashMap<Uuid, OverlapLevel>,
+    merge_queue: Vec<Uuid>,
+    verify_runs: Option<&'a HashMap<Uuid, VerifyRun>>,
+    resources: Option<&'a HashMap<Uuid, ResourceUsage>>,
+    cpu_warn_percent: f32,
+    memory_warn_mb: u64,
 }
 
 impl<'a> AgentListWidget<'a> {
@@ -60,6 +68,12 @@ impl<'a> AgentListWidget<'a> {
             appearance_config,
             pm_provider,
             column_visibility,
+            overlaps: HashMap::new(),
+            merge_queue: Vec::new(),
+            verify_runs: None,
+            resources: None,
+            cpu_warn_percent: 0.0,
+            memory_warn_mb: 0,
         }
     }
 
@@ -68,6 +82,34 @@ impl<'a> AgentListWidget<'a> {
         self
     }
 
+    pub fn with_overlaps(mut self, overlaps: HashMap<Uuid, OverlapLevel>) -> Self {
+        self.overlaps = overlaps;
+        self
+    }
+
+    pub fn with_merge_queue(mut self, merge_queue: Vec<Uuid>) -> Self {
+        self.merge_queue = merge_queue;
+        self
+    }
+
+    pub fn with_verify_runs(mut self, verify_runs: &'a HashMap<Uuid, VerifyRun>) -> Self {
+        self.verify_runs = Some(verify_runs);
+        self
+    }
+
+    /// Per-agent usage, highlighted once over either warning threshold.
+    pub fn with_resources(
+        mut self,
+        resources: &'a HashMap<Uuid, ResourceUsage>,
+        cpu_warn_percent: f32,
+        memory_warn_mb: u64,
+    ) -> Self {
+        self.resources = Some(resources);
+        self.cpu_warn_percent = cpu_warn_percent;
+        self.memory_warn_mb = memory_warn_mb;
+        self
+    }
+
     pub fn render(self, frame: &mut Frame, area: Rect) {
         let v = self.column_visibility;
 
@@ -114,6 +156,14 @@ impl<'a> AgentListWidget<'a> {
             header_labels.push("Server");
             constraints.push(Constraint::Length(10));
         }
+        if v.tests {
+            header_labels.push("Tests");
+            constraints.push(Constraint::Length(9));
+        }
+        if v.usage {
+            header_labels.push("Usage");
+            constraints.push(Constraint::Length(14));
+        }
         if v.task {
             header_labels.push("Task");
             constraints.push(Constraint::Length(16));
@@ -287,13 +337,43 @@ impl<'a> AgentListWidget<'a> {
             } else {
                 Style::default().fg(Color::White)
             };
-            let name = truncate_string(&agent.name, 26);
-            cells.push(Cell::from(name).style(name_style));
+            let name = if agent.base_branch.is_some() {
+                format!("↳ {}", agent.name)
+            } else {
+                agent.name.clone()
+            };
+            let mut spans = Vec::new();
+            if let Some(pos) = self.merge_queue.iter().position(|&id| id == agent.id) {
+                spans.push(Span::styled(
+                    format!("[{}] ", pos + 1),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            if let Some(level) = self.overlaps.get(&agent.id) {
+                let marker_color = match level {
+                    OverlapLevel::Overlap => Color::Yellow,
+                    OverlapLevel::Conflict => Color::Red,
+                };
+                spans.push(Span::styled(
+                    format!("{} ", level.symbol()),
+                    Style::default().fg(marker_color),
+                ));
+            }
+            let marker_width: usize = spans.iter().map(|s| s.content.chars().count()).sum();
+            spans.push(Span::styled(
+                truncate_string(&name, 26usize.saturating_sub(marker_width).max(8)),
+                name_style,
+            ));
+            let cell = Cell::from(Line::from(spans));
+            cells.push(cell);
         }
 
         // Status column
         if v.status {
-            let (status_text, status_style) = self.format_status(&agent.status);
+            let (status_text, status_style) = match (&agent.status, &agent.stall) {
+                (AgentStatus::Running, Some(stall)) => self.format_stall(stall),
+                (status, _) => self.format_status(status),
+            };
             cells.push(Cell::from(status_text).style(status_style));
         }
 
@@ -344,6 +424,18 @@ impl<'a> AgentListWidget<'a> {
             cells.push(Cell::from(server_text).style(server_style));
         }
 
+        // Tests column
+        if v.tests {
+            let (tests_text, tests_style) = self.format_verify_status(agent);
+            cells.push(Cell::from(tests_text).style(tests_style));
+        }
+
+        // Usage column
+        if v.usage {
+            let (usage_text, usage_style) = self.format_resource_usage(agent);
+            cells.push(Cell::from(usage_text).style(usage_style));
+        }
+
         // PM Task column
         if v.task {
             let (pm_text, pm_style) = self.format_pm_task_name(agent);
@@ -402,6 +494,13 @@ impl<'a> AgentListWidget<'a> {
         }
     }
 
+    fn format_stall(&self, stall: &Stall) -> (String, Style) {
+        match stall {
+            Stall::Stalled => ("⧗ Stalled".to_string(), Style::default().fg(Color::Yellow)),
+            Stall::Looping(_) => ("↻ Looping".to_string(), Style::default().fg(Color::Magenta)),
+        }
+    }
+
     fn format_mr_status(&self, agent: &Agent) -> (String, Style) {
         match self.provider {
             GitProvider::GitLab => {
@@ -555,12 +654,22 @@ impl<'a> AgentListWidget<'a> {
         let status = self.devserver_statuses.get(&agent.id);
 
         match status {
-            Some(DevServerStatus::Running { .. }) => {
-                ("● Running".to_string(), Style::default().fg(Color::Green))
+            Some(DevServerStatus::Ready {
+                port: Some(port), ..
+            }) => (format!("● :{}", port), Style::default().fg(Color::Green)),
+            Some(DevServerStatus::Ready { .. }) => {
+                ("● Ready".to_string(), Style::default().fg(Color::Green))
             }
-            Some(DevServerStatus::Starting) => {
+            Some(DevServerStatus::Starting { .. }) => {
                 ("◐ Starting".to_string(), Style::default().fg(Color::Yellow))
             }
+            Some(DevServerStatus::Unhealthy { .. }) => (
+                "◍ Unhealthy".to_string(),
+                Style::default().fg(Color::LightRed),
+            ),
+            Some(DevServerStatus::Crashed { .. }) => {
+                ("✗ Crashed".to_string(), Style::default().fg(Color::Red))
+            }
             Some(DevServerStatus::Stopping) => {
                 ("◑ Stopping".to_string(), Style::default().fg(Color::Yellow))
             }
@@ -574,6 +683,39 @@ impl<'a> AgentListWidget<'a> {
         }
     }
 
+    fn format_verify_status(&self, agent: &Agent) -> (String, Style) {
+        let status = self
+            .verify_runs
+            .and_then(|runs| runs.get(&agent.id))
+            .map(|run| &run.status);
+
+        match status {
+            Some(status) => {
+                let color = match status {
+                    VerifyStatus::Running => Color::Yellow,
+                    VerifyStatus::Passed(_) => Color::Green,
+                    VerifyStatus::Failed { .. } | VerifyStatus::Error(_) => Color::Red,
+                };
+                (status.format_short(), Style::default().fg(color))
+            }
+            None => ("─".to_string(), Style::default().fg(Color::DarkGray)),
+        }
+    }
+
+    fn format_resource_usage(&self, agent: &Agent) -> (String, Style) {
+        match self.resources.and_then(|r| r.get(&agent.id)) {
+            Some(usage) => {
+                let color = if usage.exceeds(self.cpu_warn_percent, self.memory_warn_mb) {
+                    Color::Red
+                } else {
+                    Color::Gray
+                };
+                (usage.summary(), Style::default().fg(color))
+            }
+            None => ("─".to_string(), Style::default().fg(Color::DarkGray)),
+        }
+    }
+
     fn render_sparkline(&self, agent: &Agent) -> String {
         let data = agent.sparkline_data();
         if data.is_empty() {
@@ -609,7 +751,7 @@ impl<'a> AgentListWidget<'a> {
 }
 
 /// Truncate a string to fit within max_len, adding "…" if truncated
-fn truncate_string(s: &str, max_len: usize) -> String {
+pub(crate) fn truncate_string(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
         s.to_string()
     } else {
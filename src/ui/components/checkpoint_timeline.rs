@@ -0,0 +1,157 @@
+use ansi_to_tui::IntoText;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::CheckpointView;
+use crate::ui::helpers::centered_rect;
+
+pub struct CheckpointTimeline<'a> {
+    view: &'a CheckpointView,
+}
+
+impl<'a> CheckpointTimeline<'a> {
+    pub fn new(view: &'a CheckpointView) -> Self {
+        Self { view }
+    }
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(85, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Checkpoints: {} ", self.view.agent_name))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(inner);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .split(rows[0]);
+
+        self.render_list(frame, columns[0]);
+        self.render_diff(frame, columns[1]);
+        self.render_footer(frame, rows[1]);
+    }
+
+    fn render_list(&self, frame: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = if self.view.checkpoints.is_empty() {
+            vec![Line::from(Span::styled(
+                " No checkpoints yet",
+                Style::default().fg(Color::DarkGray),
+            ))]
+        } else {
+            self.view
+                .checkpoints
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, checkpoint)| {
+                    let selected = i == self.view.selected;
+                    let style = if selected {
+                        Style::default()
+                            .fg(Color::Black)
+                            .bg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::White)
+                    };
+                    Line::from(vec![
+                        Span::styled(format!(" #{:<3} ", checkpoint.index), style),
+                        Span::styled(
+                            format!(
+                                "{} ",
+                                checkpoint
+                                    .created_at
+                                    .with_timezone(&chrono::Local)
+                                    .format("%H:%M:%S")
+                            ),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::raw(checkpoint.label.clone()),
+                    ])
+                })
+                .collect()
+        };
+
+        let visible = area.height.saturating_sub(2) as usize;
+        let selected_row = self
+            .view
+            .checkpoints
+            .len()
+            .saturating_sub(self.view.selected + 1);
+        let skip = selected_row.saturating_sub(visible.saturating_sub(1));
+
+        let paragraph = Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()).block(
+            Block::default()
+                .title(" Timeline ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_diff(&self, frame: &mut Frame, area: Rect) {
+        let title = match self.view.selected_checkpoint() {
+            Some(checkpoint) => format!(
+                " #{} ({}) → now ",
+                checkpoint.index,
+                checkpoint.short_commit()
+            ),
+            None => " Diff ".to_string(),
+        };
+
+        let text = match &self.view.diff {
+            Some(diff) if diff.trim().is_empty() => Text::styled(
+                "No changes since this checkpoint",
+                Style::default().fg(Color::Green),
+            ),
+            Some(diff) => diff
+                .as_bytes()
+                .into_text()
+                .unwrap_or_else(|_| Text::raw(diff.clone())),
+            None => Text::styled("[d] to load the diff", Style::default().fg(Color::DarkGray)),
+        };
+
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let line = if self.view.confirm_restore {
+            let index = self
+                .view
+                .selected_checkpoint()
+                .map(|c| c.index)
+                .unwrap_or_default();
+            Line::from(Span::styled(
+                format!(
+                    " Restore the worktree to #{}? The current state is checkpointed first. [y] Yes  [n] No",
+                    index
+                ),
+                Style::default().fg(Color::Yellow),
+            ))
+        } else {
+            Line::from(Span::styled(
+                " [j/k] Select  [d] Diff  [r] Restore  [Esc] Close",
+                Style::default().fg(Color::DarkGray),
+            ))
+        };
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
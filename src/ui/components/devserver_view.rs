@@ -7,12 +7,23 @@ use ratatui::{
     Frame,
 };
 
-use crate::devserver::DevServerStatus;
+use crate::app::LogSelection;
+use crate::core::common::strip_ansi;
+use crate::devserver::{DevServerStatus, CRASH_PREFIX};
+use crate::ui::ServiceRenderInfo;
+
+/// Log lines before a crash marker shown in red as the crash output.
+const CRASH_CONTEXT_LINES: usize = 15;
 
 pub struct DevServerViewWidget {
     status: DevServerStatus,
     logs: Vec<String>,
     agent_name: String,
+    url: Option<String>,
+    services: Vec<ServiceRenderInfo>,
+    selected_service: usize,
+    first_line: usize,
+    selection: Option<LogSelection>,
 }
 
 impl DevServerViewWidget {
@@ -21,33 +32,83 @@ impl DevServerViewWidget {
             status,
             logs,
             agent_name,
+            url: None,
+            services: Vec::new(),
+            selected_service: 0,
+            first_line: 0,
+            selection: None,
         }
     }
 
+    pub fn with_services(mut self, services: Vec<ServiceRenderInfo>, selected: usize) -> Self {
+        self.services = services;
+        self.selected_service = selected;
+        self
+    }
+
+    pub fn with_selection(mut self, first_line: usize, selection: Option<LogSelection>) -> Self {
+        self.first_line = first_line;
+        self.selection = selection;
+        self
+    }
+
+    pub fn with_url(mut self, url: Option<String>) -> Self {
+        self.url = url;
+        self
+    }
+
     pub fn render(self, frame: &mut Frame, area: Rect) {
         let visible_height = area.height.saturating_sub(4) as usize;
 
-        let mut lines = vec![self.render_status_line(), Line::from("")];
+        let mut lines = Vec::new();
+        if self.services.len() > 1 {
+            lines.push(self.render_services_line());
+        }
+        lines.push(self.render_status_line());
+        lines.push(Line::from(""));
+
+        // Follow the tail, unless the selection cursor is further up
+        let mut end = self.logs.len();
+        if let Some(selection) = &self.selection {
+            let cursor = selection.cursor.saturating_sub(self.first_line);
+            if cursor + visible_height < end {
+                end = cursor + visible_height.max(1);
+            }
+        }
+        let start = end.saturating_sub(visible_height);
+        let visible = &self.logs[start..end];
+        let crash_output = crash_output_lines(visible);
 
         // Parse ANSI content from logs and convert to styled lines
-        let mut styled_lines: Vec<Line> = Vec::new();
-        for log_line in self.logs.iter().rev().take(visible_height) {
-            let text = match log_line.as_bytes().into_text() {
-                Ok(text) => text,
-                Err(_) => Text::raw(log_line.clone()),
-            };
-            styled_lines.extend(text.lines);
+        for (i, (log_line, in_crash)) in visible.iter().zip(crash_output).enumerate() {
+            let selected = self
+                .selection
+                .is_some_and(|s| s.range().contains(&(self.first_line + start + i)));
+            if selected {
+                lines.push(Line::styled(
+                    strip_ansi(log_line),
+                    Style::default().add_modifier(Modifier::REVERSED),
+                ));
+            } else if log_line.starts_with(CRASH_PREFIX) {
+                lines.push(Line::styled(
+                    log_line.clone(),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                ));
+            } else if in_crash {
+                lines.push(Line::styled(
+                    strip_ansi(log_line),
+                    Style::default().fg(Color::Red),
+                ));
+            } else {
+                let text = match log_line.as_bytes().into_text() {
+                    Ok(text) => text,
+                    Err(_) => Text::raw(log_line.clone()),
+                };
+                lines.extend(text.lines);
+            }
         }
-        styled_lines.reverse();
 
-        lines.extend(styled_lines);
-
-        let border_color = match &self.status {
-            DevServerStatus::Running { .. } => Color::Green,
-            DevServerStatus::Starting | DevServerStatus::Stopping => Color::Yellow,
-            DevServerStatus::Failed(_) => Color::Red,
-            DevServerStatus::Stopped => Color::DarkGray,
-        };
+        let border_color = status_color(&self.status);
 
         let paragraph = Paragraph::new(lines).block(
             Block::default()
@@ -59,20 +120,43 @@ impl DevServerViewWidget {
         frame.render_widget(paragraph, area);
     }
 
-    fn render_status_line(&self) -> Line<'static> {
-        let (status_text, status_color) = match &self.status {
-            DevServerStatus::Stopped => ("Stopped".to_string(), Color::DarkGray),
-            DevServerStatus::Starting => ("Starting...".to_string(), Color::Yellow),
-            DevServerStatus::Running { port, .. } => {
-                if let Some(p) = port {
-                    (format!("Running on port {}", p), Color::Green)
-                } else {
-                    ("Running".to_string(), Color::Green)
-                }
+    fn render_services_line(&self) -> Line<'static> {
+        let mut spans = vec![Span::styled(
+            "Services: ",
+            Style::default().fg(Color::DarkGray),
+        )];
+        for (i, service) in self.services.iter().enumerate() {
+            let mut style = Style::default().fg(status_color(&service.status));
+            if i == self.selected_service {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
             }
-            DevServerStatus::Stopping => ("Stopping...".to_string(), Color::Yellow),
-            DevServerStatus::Failed(msg) => (format!("Failed: {}", msg), Color::Red),
+            spans.push(Span::styled(
+                format!(" {} {} ", service.status.symbol(), service.name),
+                style,
+            ));
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(
+            "[ ] select  X start/stop  E restart",
+            Style::default().fg(Color::DarkGray),
+        ));
+        Line::from(spans)
+    }
+
+    fn render_status_line(&self) -> Line<'static> {
+        let status_text = match &self.status {
+            DevServerStatus::Stopped => "Stopped".to_string(),
+            DevServerStatus::Starting { .. } => "Starting, waiting until ready".to_string(),
+            DevServerStatus::Ready {
+                port: Some(port), ..
+            } => format!("Ready on port {}", port),
+            DevServerStatus::Ready { .. } => "Ready".to_string(),
+            DevServerStatus::Unhealthy { reason, .. } => format!("Unhealthy: {}", reason),
+            DevServerStatus::Crashed { code } => format!("Crashed (exit code {})", code),
+            DevServerStatus::Stopping => "Stopping...".to_string(),
+            DevServerStatus::Failed(msg) => format!("Failed: {}", msg),
         };
+        let status_color = status_color(&self.status);
 
         let mut spans = vec![
             Span::styled("Status: ", Style::default().fg(Color::DarkGray)),
@@ -84,18 +168,54 @@ impl DevServerViewWidget {
             ),
         ];
 
-        if matches!(&self.status, DevServerStatus::Running { .. }) {
+        if let Some(url) = &self.url {
             spans.push(Span::raw("  "));
-            spans.push(Span::styled(
-                "Enter: attach",
-                Style::default().fg(Color::DarkGray),
-            ));
+            spans.push(Span::styled(url.clone(), Style::default().fg(Color::Cyan)));
+        }
+
+        let hint = if self.selection.is_some() {
+            Some("j/k: extend  Enter: send to agent  Esc: cancel")
+        } else if self.status.is_running()
+            || matches!(&self.status, DevServerStatus::Crashed { .. })
+        {
+            Some("Enter: attach  F: send error  H: select lines")
+        } else if !self.logs.is_empty() {
+            Some("F: send error  H: select lines")
+        } else {
+            None
+        };
+        if let Some(hint) = hint {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(hint, Style::default().fg(Color::DarkGray)));
         }
 
         Line::from(spans)
     }
 }
 
+fn status_color(status: &DevServerStatus) -> Color {
+    match status {
+        DevServerStatus::Ready { .. } => Color::Green,
+        DevServerStatus::Starting { .. } | DevServerStatus::Stopping => Color::Yellow,
+        DevServerStatus::Unhealthy { .. } => Color::LightRed,
+        DevServerStatus::Crashed { .. } | DevServerStatus::Failed(_) => Color::Red,
+        DevServerStatus::Stopped => Color::DarkGray,
+    }
+}
+
+/// For each line, whether it is output leading up to a crash marker.
+fn crash_output_lines(logs: &[String]) -> Vec<bool> {
+    let mut flags = vec![false; logs.len()];
+    for (i, line) in logs.iter().enumerate() {
+        if line.starts_with(CRASH_PREFIX) {
+            for flag in &mut flags[i.saturating_sub(CRASH_CONTEXT_LINES)..i] {
+                *flag = true;
+            }
+        }
+    }
+    flags
+}
+
 pub struct EmptyDevServerWidget;
 
 impl EmptyDevServerWidget {
@@ -60,6 +60,14 @@ impl<'a> HelpOverlay<'a> {
                 "  {:8} Create new agent",
                 kb.new_agent.display_short()
             )),
+            Line::from(format!(
+                "  {:8} Create agent on a base branch",
+                kb.new_stacked_agent.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Import branch, worktree or PR",
+                kb.import_agent.display_short()
+            )),
             Line::from(format!(
                 "  {:8} Delete selected agent",
                 kb.delete_agent.display_short()
@@ -72,6 +80,10 @@ impl<'a> HelpOverlay<'a> {
                 "  {:8} Set/edit custom note",
                 kb.set_note.display_short()
             )),
+            Line::from(format!(
+                "  {:8} Set/clear agent budget",
+                kb.set_budget.display_short()
+            )),
             Line::from(format!(
                 "  {:8} Copy agent name to clipboard",
                 kb.yank.display_short()
@@ -95,6 +107,42 @@ impl<'a> HelpOverlay<'a> {
                 "  {:8} Merge main into branch",
                 kb.merge.display_short()
             )),
+            Line::from(format!(
+                "  {:8} Restack agents built on this one",
+                kb.restack.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Add/remove agent from merge queue",
+                kb.queue_agent.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Land merge queue into main",
+                kb.land_queue.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Run verify commands",
+                kb.run_verify.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Send verify failures to agent",
+                kb.send_failures.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Checkpoint timeline",
+                kb.checkpoints.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Resume a past AI session",
+                kb.pick_session.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Orchestrations",
+                kb.orchestrator.display_short()
+            )),
+            Line::from(format!(
+                "  {:8} Toggle autopilot",
+                kb.toggle_autopilot.display_short()
+            )),
             Line::from(format!("  {:8} Push changes", kb.push.display_short())),
             Line::from(format!("  {:8} Fetch remote", kb.fetch.display_short())),
             Line::from(""),
@@ -112,6 +160,10 @@ impl<'a> HelpOverlay<'a> {
                 "  {:8} Toggle logs panel",
                 kb.toggle_logs.display_short()
             )),
+            Line::from(format!(
+                "  {:8} Show agent overlaps",
+                kb.toggle_overlaps.display_short()
+            )),
             Line::from(format!(
                 "  {:8} Open settings",
                 kb.toggle_settings.display_short()
@@ -166,6 +218,11 @@ impl<'a> HelpOverlay<'a> {
             Line::from("  C-S      Restart dev server"),
             Line::from("  C        Clear logs"),
             Line::from("  O        Open in browser"),
+            Line::from("  [ ]      Select service"),
+            Line::from("  X        Start/stop service"),
+            Line::from("  E        Restart service"),
+            Line::from("  F        Send latest error to agent"),
+            Line::from("  H        Select log lines to send"),
             Line::from(""),
             Line::from(Span::styled(
                 "Other",
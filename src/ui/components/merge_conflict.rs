@@ -0,0 +1,91 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::MergeConflict;
+use crate::ui::helpers::centered_rect;
+
+const MAX_LISTED_FILES: usize = 12;
+
+pub struct MergeConflictModal<'a> {
+    conflict: &'a MergeConflict,
+}
+
+impl<'a> MergeConflictModal<'a> {
+    pub fn new(conflict: &'a MergeConflict) -> Self {
+        Self { conflict }
+    }
+
+    pub fn render(self, frame: &mut Frame) {
+        let area = centered_rect(60, 50, frame.area());
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(" CONFLICTS ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Red));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let operation = if self.conflict.rebase {
+            "Rebasing onto"
+        } else {
+            "Merging"
+        };
+
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "{} {} in '{}' stopped on conflicts:",
+                    operation, self.conflict.base_branch, self.conflict.agent_name
+                ),
+                Style::default().fg(Color::White),
+            )),
+            Line::from(""),
+        ];
+
+        for file in self.conflict.files.iter().take(MAX_LISTED_FILES) {
+            lines.push(Line::from(Span::styled(
+                format!("  - {}", file),
+                Style::default().fg(Color::Red),
+            )));
+        }
+        if self.conflict.files.len() > MAX_LISTED_FILES {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  ... and {} more",
+                    self.conflict.files.len() - MAX_LISTED_FILES
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        lines.extend(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "The worktree is left mid-merge until resolved or aborted.",
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "What next?",
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "[a] Hand to agent    [x] Abort    [Esc] Leave as is",
+                Style::default().fg(Color::Cyan),
+            )),
+        ]);
+
+        let paragraph = Paragraph::new(lines);
+        frame.render_widget(paragraph, inner);
+    }
+}
@@ -1,4 +1,6 @@
+pub mod activity_view;
 pub mod agent_list;
+pub mod checkpoint_timeline;
 pub mod column_selector;
 pub mod devserver_view;
 pub mod devserver_warning;
@@ -8,11 +10,16 @@ pub mod git_setup_modal;
 pub mod global_setup;
 pub mod help_overlay;
 pub mod loading_overlay;
+pub mod merge_conflict;
 pub mod modal;
+pub mod orchestrator_view;
 pub mod output_view;
+pub mod overlap_matrix;
+pub mod plan_view;
 pub mod pm_setup_modal;
 pub mod pm_status_debug_overlay;
 pub mod project_setup;
+pub mod session_picker;
 pub mod settings_modal;
 pub mod status_bar;
 pub mod status_debug_overlay;
@@ -23,7 +30,9 @@ pub mod task_reassignment_warning;
 pub mod toast;
 pub mod tutorial_wizard;
 
+pub use activity_view::ActivityViewWidget;
 pub use agent_list::AgentListWidget;
+pub use checkpoint_timeline::CheckpointTimeline;
 pub use column_selector::ColumnSelectorWidget;
 pub use devserver_view::{DevServerViewWidget, EmptyDevServerWidget};
 pub use devserver_warning::DevServerWarningModal;
@@ -32,11 +41,16 @@ pub use git_setup_modal::GitSetupModal;
 pub use global_setup::GlobalSetupWizard;
 pub use help_overlay::HelpOverlay;
 pub use loading_overlay::LoadingOverlay;
+pub use merge_conflict::MergeConflictModal;
 pub use modal::{render_confirm_modal, render_input_modal};
+pub use orchestrator_view::OrchestratorWidget;
 pub use output_view::{EmptyOutputWidget, OutputViewWidget};
+pub use overlap_matrix::OverlapMatrixOverlay;
+pub use plan_view::PlanViewWidget;
 pub use pm_setup_modal::PmSetupModal;
 pub use pm_status_debug_overlay::PmStatusDebugOverlay;
 pub use project_setup::ProjectSetupWizard;
+pub use session_picker::SessionPickerWidget;
 pub use settings_modal::SettingsModal;
 pub use status_bar::{InputBarWidget, StatusBarWidget};
 pub use status_debug_overlay::StatusDebugOverlay;
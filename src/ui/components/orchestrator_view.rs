@@ -0,0 +1,127 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::orchestrator::{SlotState, Stage, TaskSlot};
+use crate::app::Orchestration;
+use crate::ui::helpers::centered_rect;
+
+/// Running orchestrations, each with its parent task and subtask agents.
+pub struct OrchestratorWidget<'a> {
+    orchestrations: &'a [Orchestration],
+    selected: usize,
+}
+
+impl<'a> OrchestratorWidget<'a> {
+    pub fn new(orchestrations: &'a [Orchestration], selected: usize) -> Self {
+        Self {
+            orchestrations,
+            selected,
+        }
+    }
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(75, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Orchestrations ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(inner);
+
+        let mut lines = Vec::new();
+        let mut selected_line = 0;
+        for (i, orchestration) in self.orchestrations.iter().enumerate() {
+            if i == self.selected {
+                selected_line = lines.len();
+            }
+            lines.push(self.header(orchestration, i == self.selected));
+            lines.push(slot_line("  parent ", &orchestration.parent));
+            for slot in &orchestration.subtasks {
+                lines.push(slot_line("    ", slot));
+            }
+            lines.push(Line::default());
+        }
+
+        let visible = rows[0].height as usize;
+        let skip = selected_line.saturating_sub(visible / 2);
+        frame.render_widget(
+            Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()),
+            rows[0],
+        );
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                " [j/k] Select  [r] Retry  [x] Cancel  [Esc] Close",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            rows[1],
+        );
+    }
+
+    fn header(&self, orchestration: &Orchestration, selected: bool) -> Line<'static> {
+        let name_style = if selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        };
+        let (done, total) = orchestration.progress();
+        let (stage, color) = match &orchestration.stage {
+            Stage::Running => ("running".to_string(), Color::Yellow),
+            Stage::Finishing => ("combining branches".to_string(), Color::Yellow),
+            Stage::Finished => ("finished".to_string(), Color::Green),
+            Stage::Failed { reason } => (format!("failed: {}", reason), Color::Red),
+        };
+        Line::from(vec![
+            Span::styled(format!(" {} ", orchestration.parent.task.name), name_style),
+            Span::styled(
+                format!(" {}/{} done ", done, total),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(stage, Style::default().fg(color)),
+        ])
+    }
+}
+
+fn slot_line(indent: &'static str, slot: &TaskSlot) -> Line<'static> {
+    let color = match slot.state {
+        SlotState::Pending | SlotState::Starting => Color::DarkGray,
+        SlotState::Running { .. } => Color::Yellow,
+        SlotState::Done { .. } => Color::Green,
+        SlotState::Failed { .. } => Color::Red,
+    };
+    let mut spans = vec![
+        Span::raw(indent),
+        Span::styled(
+            format!("{} ", slot.state.symbol()),
+            Style::default().fg(color),
+        ),
+        Span::styled(slot.task.name.clone(), Style::default().fg(Color::White)),
+        Span::styled(
+            format!("  {}", slot.branch),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if let SlotState::Failed { reason } = &slot.state {
+        spans.push(Span::styled(
+            format!("  {}", reason),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    Line::from(spans)
+}
@@ -0,0 +1,147 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::agent::{Agent, AgentOverlap};
+use crate::ui::helpers::centered_rect;
+
+use super::agent_list::truncate_string;
+
+const NAME_WIDTH: usize = 18;
+const CELL_WIDTH: usize = 5;
+
+pub struct OverlapMatrixOverlay<'a> {
+    agents: &'a [&'a Agent],
+    overlaps: &'a [AgentOverlap],
+}
+
+impl<'a> OverlapMatrixOverlay<'a> {
+    pub fn new(agents: &'a [&'a Agent], overlaps: &'a [AgentOverlap]) -> Self {
+        Self { agents, overlaps }
+    }
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(80, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let mut lines = vec![Line::from("")];
+
+        if self.overlaps.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  No agents touch the same files.",
+                Style::default().fg(Color::Green),
+            )));
+        } else {
+            lines.extend(self.matrix_lines());
+            lines.push(Line::from(""));
+            lines.extend(self.detail_lines());
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  ◇ shared files   ⚡ trial merge conflicts   [r] refresh   [Esc] close",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .title(" Agent Overlaps ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn matrix_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+
+        let mut header = vec![Span::raw(format!("  {:width$}", "", width = NAME_WIDTH))];
+        for i in 0..self.agents.len() {
+            header.push(Span::styled(
+                format!("{:>width$}", i + 1, width = CELL_WIDTH),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(header));
+
+        for (row, agent) in self.agents.iter().enumerate() {
+            let mut spans = vec![Span::raw(format!(
+                "  {:>2} {:width$}",
+                row + 1,
+                truncate_string(&agent.name, NAME_WIDTH - 3),
+                width = NAME_WIDTH - 3
+            ))];
+
+            for (col, other) in self.agents.iter().enumerate() {
+                let (text, style) = if row == col {
+                    ("·".to_string(), Style::default().fg(Color::DarkGray))
+                } else {
+                    match self.find(agent, other) {
+                        Some(o) if !o.conflicts.is_empty() => (
+                            format!("⚡{}", o.conflicts.len()),
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        ),
+                        Some(o) => (
+                            format!("◇{}", o.files.len()),
+                            Style::default().fg(Color::Yellow),
+                        ),
+                        None => ("-".to_string(), Style::default().fg(Color::DarkGray)),
+                    }
+                };
+                spans.push(Span::styled(
+                    format!("{:>width$}", text, width = CELL_WIDTH),
+                    style,
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines
+    }
+
+    fn detail_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+
+        for overlap in self.overlaps {
+            let name = |id| {
+                self.agents
+                    .iter()
+                    .find(|a| a.id == id)
+                    .map(|a| a.name.clone())
+                    .unwrap_or_else(|| "?".to_string())
+            };
+            let (marker, color) = if overlap.conflicts.is_empty() {
+                ("◇", Color::Yellow)
+            } else {
+                ("⚡", Color::Red)
+            };
+            lines.push(Line::from(Span::styled(
+                format!("  {} {} ↔ {}", marker, name(overlap.a), name(overlap.b)),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )));
+            for file in &overlap.files {
+                let conflicting = overlap.conflicts.contains(file);
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "      {}{}",
+                        file,
+                        if conflicting { "  (conflict)" } else { "" }
+                    ),
+                    Style::default().fg(if conflicting { Color::Red } else { Color::Gray }),
+                )));
+            }
+        }
+
+        lines
+    }
+
+    fn find(&self, a: &Agent, b: &Agent) -> Option<&AgentOverlap> {
+        self.overlaps
+            .iter()
+            .find(|o| o.involves(a.id) && o.other(a.id) == b.id)
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::VecDeque;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::agent::{Checklist, ChecklistState, PlanChange};
+
+/// Most plan changes shown under the checklist.
+const MAX_HISTORY_LINES: usize = 8;
+
+/// The agent's checklist, the item it's on and how the plan changed.
+pub struct PlanViewWidget<'a> {
+    agent_name: &'a str,
+    plan: Option<&'a Checklist>,
+    history: &'a VecDeque<PlanChange>,
+}
+
+impl<'a> PlanViewWidget<'a> {
+    pub fn new(
+        agent_name: &'a str,
+        plan: Option<&'a Checklist>,
+        history: &'a VecDeque<PlanChange>,
+    ) -> Self {
+        Self {
+            agent_name,
+            plan,
+            history,
+        }
+    }
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let history_height = (self.history.len().min(MAX_HISTORY_LINES) + 2) as u16;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(4), Constraint::Length(history_height)])
+            .split(area);
+
+        self.render_items(frame, chunks[0]);
+        self.render_history(frame, chunks[1]);
+    }
+
+    fn render_items(&self, frame: &mut Frame, area: Rect) {
+        let (title, lines) = match self.plan {
+            Some(plan) => {
+                let (done, total) = plan.progress();
+                let title = match plan.current() {
+                    Some(item) => format!(
+                        " PLAN: {} · {}/{} · on: {} ",
+                        self.agent_name, done, total, item.text
+                    ),
+                    None => format!(" PLAN: {} · {}/{} ", self.agent_name, done, total),
+                };
+                let lines = plan
+                    .items
+                    .iter()
+                    .map(|item| {
+                        let style = match item.state {
+                            ChecklistState::Done => Style::default().fg(Color::DarkGray),
+                            ChecklistState::InProgress => Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                            ChecklistState::Pending => Style::default().fg(Color::White),
+                        };
+                        Line::from(vec![
+                            Span::styled(format!(" {} ", item.state.symbol()), style),
+                            Span::styled(item.text.clone(), style),
+                        ])
+                    })
+                    .collect();
+                (title, lines)
+            }
+            None => (
+                format!(" PLAN: {} ", self.agent_name),
+                vec![
+                    Line::from(""),
+                    Line::from(Span::styled(
+                        "No checklist seen in the agent's output yet",
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                ],
+            ),
+        };
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_history(&self, frame: &mut Frame, area: Rect) {
+        let skip = self.history.len().saturating_sub(MAX_HISTORY_LINES);
+        let lines: Vec<Line> = self
+            .history
+            .iter()
+            .skip(skip)
+            .map(|change| {
+                Line::from(vec![
+                    Span::styled(
+                        change
+                            .at
+                            .with_timezone(&chrono::Local)
+                            .format("%H:%M:%S ")
+                            .to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(change.description.clone()),
+                ])
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(" Plan changes ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        frame.render_widget(paragraph, area);
+    }
+}
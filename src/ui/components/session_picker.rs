@@ -0,0 +1,120 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::SessionPicker;
+use crate::ui::helpers::centered_rect;
+
+/// Past conversations of an agent's AI, with "Start fresh" on top.
+pub struct SessionPickerWidget<'a> {
+    picker: &'a SessionPicker,
+    current_session: Option<&'a str>,
+}
+
+impl<'a> SessionPickerWidget<'a> {
+    pub fn new(picker: &'a SessionPicker, current_session: Option<&'a str>) -> Self {
+        Self {
+            picker,
+            current_session,
+        }
+    }
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(75, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Sessions: {} ", self.picker.agent_name))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(inner);
+
+        self.render_list(frame, rows[0]);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                " [j/k] Select  [Enter] Resume  [Esc] Close",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            rows[1],
+        );
+    }
+
+    fn render_list(&self, frame: &mut Frame, area: Rect) {
+        let row_style = |selected: bool| {
+            if selected {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            }
+        };
+
+        let mut lines = vec![Line::from(Span::styled(
+            " + Start fresh",
+            row_style(self.picker.selected == 0),
+        ))];
+        if self.picker.loading {
+            lines.push(Line::from(Span::styled(
+                " Loading sessions...",
+                Style::default().fg(Color::DarkGray),
+            )));
+        } else if self.picker.sessions.is_empty() {
+            lines.push(Line::from(Span::styled(
+                " No past sessions for this worktree",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.extend(self.picker.sessions.iter().enumerate().map(|(i, session)| {
+            let marker = if Some(session.id.as_str()) == self.current_session {
+                "●"
+            } else {
+                " "
+            };
+            let prompt = session
+                .first_prompt
+                .as_deref()
+                .map(|p| p.lines().next().unwrap_or_default().to_string())
+                .unwrap_or_else(|| session.id.clone());
+            Line::from(vec![
+                Span::styled(format!(" {} ", marker), Style::default().fg(Color::Green)),
+                Span::styled(
+                    format!(
+                        "{} ",
+                        session
+                            .updated_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{:>4} msgs ", session.message_count),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(prompt, row_style(self.picker.selected == i + 1)),
+            ])
+        }));
+
+        let visible = area.height as usize;
+        let skip = self
+            .picker
+            .selected
+            .saturating_sub(visible.saturating_sub(1));
+        frame.render_widget(
+            Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()),
+            area,
+        );
+    }
+}
@@ -6,10 +6,11 @@ use ratatui::{
     Frame,
 };
 
+use crate::app::config::command_list_to_string;
 use crate::app::{
     ActionButtonType, AiAgent, AutomationConfig, CodebergCiProvider, Config, ConfigLogLevel,
     GitProvider, ProjectMgmtProvider, ResetType, SettingsCategory, SettingsField, SettingsItem,
-    SettingsState, SettingsTab, UiConfig, WorktreeLocation,
+    SettingsState, SettingsTab, SyncStrategy, UiConfig, WorktreeLocation,
 };
 use crate::ui::components::file_browser;
 use crate::ui::helpers::{
@@ -540,6 +541,26 @@ impl<'a> SettingsModal<'a> {
                 .to_string(),
                 true,
             ),
+            SettingsField::ClaudeHooks => (
+                "Claude Code Hooks".to_string(),
+                if self.state.pending_claude_hooks {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
+            SettingsField::McpServer => (
+                "Grove MCP Server".to_string(),
+                if self.state.pending_mcp_server {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
             SettingsField::DebugMode => (
                 "Debug Mode".to_string(),
                 if self.state.pending_debug_mode {
@@ -646,6 +667,47 @@ impl<'a> SettingsModal<'a> {
                 self.state.repo_config.git.main_branch.clone(),
                 false,
             ),
+            SettingsField::SyncStrategy => (
+                "Merge Main".to_string(),
+                self.state
+                    .repo_config
+                    .git
+                    .sync_strategy
+                    .display_name()
+                    .to_string(),
+                false,
+            ),
+            SettingsField::QueueVerifyCommand => (
+                "Verify Command".to_string(),
+                self.state
+                    .repo_config
+                    .git
+                    .merge_queue
+                    .verify_command
+                    .clone()
+                    .unwrap_or_default(),
+                false,
+            ),
+            SettingsField::QueuePush => (
+                "Push After Landing".to_string(),
+                if self.state.repo_config.git.merge_queue.push {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
+            SettingsField::QueueArchive => (
+                "Archive Landed".to_string(),
+                if self.state.repo_config.git.merge_queue.archive_landed {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
             SettingsField::WorktreeSymlinks => (
                 "Symlinks".to_string(),
                 self.state
@@ -893,6 +955,115 @@ impl<'a> SettingsModal<'a> {
                     .unwrap_or_default(),
                 false,
             ),
+            SettingsField::DevServerAutoPort => (
+                "Port Per Agent".to_string(),
+                if self.state.repo_config.dev_server.auto_port {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
+            SettingsField::DevServerPortRange => (
+                "Port Range".to_string(),
+                format!(
+                    "{}-{}",
+                    self.state.repo_config.dev_server.port_range_start,
+                    self.state.repo_config.dev_server.port_range_end
+                ),
+                false,
+            ),
+            SettingsField::DevServerPortEnv => (
+                "Port Env Var".to_string(),
+                self.state.repo_config.dev_server.port_env.clone(),
+                false,
+            ),
+            SettingsField::DevServerProxyEnabled => (
+                "Proxy (restart)".to_string(),
+                if self.state.repo_config.dev_server.proxy_enabled {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
+            SettingsField::DevServerAutoSendErrors => (
+                "Send Errors to Agent".to_string(),
+                if self.state.repo_config.dev_server.auto_send_errors {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
+            SettingsField::DevServerProxyPort => (
+                "Proxy Port (restart)".to_string(),
+                self.state.repo_config.dev_server.proxy_port.to_string(),
+                false,
+            ),
+            SettingsField::ProvisionCopyFiles => (
+                "Copy Files".to_string(),
+                self.state.repo_config.provisioning.copy_files.join(", "),
+                false,
+            ),
+            SettingsField::ProvisionPostCreate => (
+                "After Create".to_string(),
+                command_list_to_string(&self.state.repo_config.provisioning.post_create),
+                false,
+            ),
+            SettingsField::ProvisionPreDelete => (
+                "Before Delete".to_string(),
+                command_list_to_string(&self.state.repo_config.provisioning.pre_delete),
+                false,
+            ),
+            SettingsField::VerifyCommands => (
+                "Commands".to_string(),
+                command_list_to_string(&self.state.repo_config.verify.commands),
+                false,
+            ),
+            SettingsField::VerifyAutoRun => (
+                "Run On Complete".to_string(),
+                if self.state.repo_config.verify.auto_run {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
+            SettingsField::StallEnabled => (
+                "Detect Stalls".to_string(),
+                if self.state.repo_config.stall.enabled {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
+            SettingsField::StallAfterSecs => (
+                "Stalled After (secs)".to_string(),
+                self.state.repo_config.stall.stall_after_secs.to_string(),
+                false,
+            ),
+            SettingsField::StallRepeatThreshold => (
+                "Loop Threshold".to_string(),
+                self.state.repo_config.stall.repeat_threshold.to_string(),
+                false,
+            ),
+            SettingsField::StallNudge => (
+                "Nudge Agent".to_string(),
+                if self.state.repo_config.stall.nudge {
+                    "[x]"
+                } else {
+                    "[ ]"
+                }
+                .to_string(),
+                true,
+            ),
             SettingsField::DevServerAutoStart => (
                 "Auto Start".to_string(),
                 if self.state.repo_config.dev_server.auto_start {
@@ -1012,7 +1183,17 @@ impl<'a> SettingsModal<'a> {
                     | SettingsField::ShowLogs
                     | SettingsField::ShowBanner
                     | SettingsField::DebugMode
+                    | SettingsField::ClaudeHooks
+                    | SettingsField::McpServer
                     | SettingsField::DevServerAutoStart
+                    | SettingsField::DevServerAutoPort
+                    | SettingsField::DevServerProxyEnabled
+                    | SettingsField::DevServerAutoSendErrors
+                    | SettingsField::QueuePush
+                    | SettingsField::QueueArchive
+                    | SettingsField::VerifyAutoRun
+                    | SettingsField::StallEnabled
+                    | SettingsField::StallNudge
             );
             let is_keybind = field.is_keybind_field();
             if is_keybind {
@@ -1026,6 +1207,16 @@ impl<'a> SettingsModal<'a> {
 
         let mut spans = vec![Span::styled(hint, Style::default().fg(Color::DarkGray))];
 
+        if matches!(
+            self.state.current_field(),
+            SettingsField::DevServerProxyEnabled | SettingsField::DevServerProxyPort
+        ) {
+            spans.push(Span::styled(
+                "   Applies after restarting Grove",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
         if self.state.has_keybind_conflicts() && self.state.capturing_keybind.is_none() {
             spans.push(Span::styled(
                 format!(
@@ -1125,6 +1316,10 @@ impl<'a> SettingsModal<'a> {
                 .iter()
                 .map(|c| c.display_name().to_string())
                 .collect(),
+            SettingsField::SyncStrategy => SyncStrategy::all()
+                .iter()
+                .map(|s| s.display_name().to_string())
+                .collect(),
             SettingsField::ProjectMgmtProvider => ProjectMgmtProvider::all()
                 .iter()
                 .map(|p| p.display_name().to_string())
@@ -12,6 +12,8 @@ pub struct StatusBarWidget<'a> {
     message: Option<&'a str>,
     is_error: bool,
     keybinds: &'a Keybinds,
+    /// Active and maximum autopilot agents, while autopilot is on
+    autopilot: Option<(usize, usize)>,
 }
 
 impl<'a> StatusBarWidget<'a> {
@@ -20,9 +22,15 @@ impl<'a> StatusBarWidget<'a> {
             message,
             is_error,
             keybinds,
+            autopilot: None,
         }
     }
 
+    pub fn with_autopilot(mut self, autopilot: Option<(usize, usize)>) -> Self {
+        self.autopilot = autopilot;
+        self
+    }
+
     pub fn render(self, frame: &mut Frame, area: Rect) {
         let shortcuts = [
             (&self.keybinds.new_agent, "new"),
@@ -36,9 +44,17 @@ impl<'a> StatusBarWidget<'a> {
         ];
 
         let mut spans: Vec<Span> = Vec::new();
+        if let Some((active, max)) = self.autopilot {
+            spans.push(Span::styled(
+                format!("autopilot {}/{} ", active, max),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
 
         for (i, (keybind, action)) in shortcuts.iter().enumerate() {
-            if i > 0 {
+            if i > 0 || self.autopilot.is_some() {
                 spans.push(Span::raw(" "));
             }
             spans.push(Span::styled(
@@ -79,7 +79,7 @@ impl<'a> StatusDebugOverlay<'a> {
 
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "Press D or Esc to close",
+            "c capture fixture · D or Esc to close",
             Style::default().fg(Color::DarkGray),
         )));
 
@@ -8,6 +8,9 @@ use ratatui::{
     Frame,
 };
 
+use crate::agent::resources::format_bytes;
+use crate::agent::ResourceUsage;
+
 /// Unicode bar characters for sparkline rendering (bottom-aligned)
 const BARS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
@@ -16,6 +19,7 @@ pub struct SystemMetricsWidget<'a> {
     memory_history: &'a VecDeque<f32>,
     memory_used: u64,
     memory_total: u64,
+    agent: Option<(&'a str, &'a ResourceUsage)>,
 }
 
 impl<'a> SystemMetricsWidget<'a> {
@@ -30,16 +34,73 @@ impl<'a> SystemMetricsWidget<'a> {
             memory_history,
             memory_used,
             memory_total,
+            agent: None,
         }
     }
 
+    /// Also show the process tree usage of the selected agent.
+    pub fn with_agent(mut self, agent: Option<(&'a str, &'a ResourceUsage)>) -> Self {
+        self.agent = agent;
+        self
+    }
+
     pub fn render(self, frame: &mut Frame, area: Rect) {
-        // Split area into two equal halves for CPU and Memory
-        let chunks = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(area);
+        let Some((name, usage)) = self.agent else {
+            // Split area into two equal halves for CPU and Memory
+            let chunks =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(area);
+
+            self.render_cpu_graph(frame, chunks[0]);
+            self.render_memory_graph(frame, chunks[1]);
+            return;
+        };
+
+        let chunks = Layout::horizontal([
+            Constraint::Percentage(35),
+            Constraint::Percentage(35),
+            Constraint::Percentage(30),
+        ])
+        .split(area);
 
         self.render_cpu_graph(frame, chunks[0]);
         self.render_memory_graph(frame, chunks[1]);
+        self.render_agent_usage(frame, chunks[2], name, usage);
+    }
+
+    fn render_agent_usage(&self, frame: &mut Frame, area: Rect, name: &str, usage: &ResourceUsage) {
+        let block = Block::default()
+            .title(format!(" {} ", name))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled(
+                format!("{:.0}% ", usage.cpu_percent),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(
+                format_bytes(usage.memory_bytes),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                format!(" {} procs", usage.child_count),
+                Style::default().fg(Color::Gray),
+            ),
+        ])];
+        for process in &usage.top {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:>4.0}% {:>5} {}",
+                    process.cpu_percent,
+                    format_bytes(process.memory_bytes),
+                    process.name
+                ),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
     }
 
     fn render_cpu_graph(&self, frame: &mut Frame, area: Rect) {
@@ -384,6 +384,7 @@ impl<'a> TaskListModal<'a> {
                 Span::styled("[j/k] Navigate  ", Style::default().fg(Color::DarkGray)),
                 Span::styled("[Enter] Create  ", Style::default().fg(Color::DarkGray)),
                 Span::styled("[a] Assign  ", Style::default().fg(Color::DarkGray)),
+                Span::styled("[o] Orchestrate  ", Style::default().fg(Color::DarkGray)),
                 Span::styled("[s] Status  ", Style::default().fg(Color::DarkGray)),
                 Span::styled("[f] Filter  ", Style::default().fg(Color::DarkGray)),
                 Span::styled("[r] Refresh  ", Style::default().fg(Color::DarkGray)),
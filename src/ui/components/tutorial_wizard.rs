@@ -238,6 +238,8 @@ impl<'a> TutorialWizard<'a> {
             key_value_line("Preview", "See the agent's live output"),
             key_value_line("Git Diff", "View uncommitted changes"),
             key_value_line("Dev Server", "Dev server logs & status"),
+            key_value_line("Activity", "Codex/Gemini session log events"),
+            key_value_line("Plan", "The agent's checklist and how it changed"),
         ]
     }
 
@@ -3,7 +3,7 @@ pub mod appearance;
 pub mod components;
 pub mod helpers;
 
-pub use app::{AppWidget, DevServerRenderInfo};
+pub use app::{AppWidget, DevServerRenderInfo, ServiceRenderInfo};
 pub use appearance::{
     color_display_name, color_to_string, find_color_index, find_icon_index, get_color_by_index,
     get_color_name_by_index, get_icon_by_index, parse_color, COLOR_PALETTE, ICON_PRESETS,
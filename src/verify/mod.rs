@@ -0,0 +1,5 @@
+pub mod parse;
+pub mod runner;
+
+pub use parse::{extract_failures, parse_test_counts, TestCounts};
+pub use runner::{run_verify, VerifyRun, VerifyStatus};
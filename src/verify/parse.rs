@@ -0,0 +1,153 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Maximum number of failure lines handed to an agent.
+const MAX_FAILURES: usize = 10;
+
+static CARGO_RESULT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"test result: \w+\. (\d+) passed; (\d+) failed").unwrap());
+
+static PASSED_COUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+) passed").unwrap());
+
+static FAILED_COUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+) failed").unwrap());
+
+static FAILURE_PATTERNS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        // cargo test
+        Regex::new(r"^test (\S+) \.\.\. FAILED").unwrap(),
+        // pytest
+        Regex::new(r"^FAILED (.+)$").unwrap(),
+        // go test
+        Regex::new(r"^\s*--- FAIL: (\S+)").unwrap(),
+        // jest / vitest
+        Regex::new(r"^\s*[✕×] (.+?)(?: \(\d+ ?m?s\))?$").unwrap(),
+    ]
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TestCounts {
+    pub passed: u32,
+    pub failed: u32,
+}
+
+impl TestCounts {
+    pub fn total(&self) -> u32 {
+        self.passed + self.failed
+    }
+}
+
+/// Pass/fail counts from test runner output. Cargo prints one summary per
+/// test binary, so those are summed; other runners (jest, vitest, pytest)
+/// print a final summary line, so the last line with counts wins.
+pub fn parse_test_counts(output: &str) -> Option<TestCounts> {
+    let mut cargo: Option<TestCounts> = None;
+    for caps in CARGO_RESULT.captures_iter(output) {
+        let counts = cargo.get_or_insert_with(TestCounts::default);
+        counts.passed += caps[1].parse::<u32>().unwrap_or(0);
+        counts.failed += caps[2].parse::<u32>().unwrap_or(0);
+    }
+    if cargo.is_some() {
+        return cargo;
+    }
+
+    output.lines().rev().find_map(|line| {
+        let passed = PASSED_COUNT
+            .captures(line)
+            .and_then(|c| c[1].parse::<u32>().ok());
+        let failed = FAILED_COUNT
+            .captures(line)
+            .and_then(|c| c[1].parse::<u32>().ok());
+        if passed.is_none() && failed.is_none() {
+            return None;
+        }
+        Some(TestCounts {
+            passed: passed.unwrap_or(0),
+            failed: failed.unwrap_or(0),
+        })
+    })
+}
+
+/// Names of failing tests, or compiler errors when no test failed by name.
+pub fn extract_failures(output: &str) -> Vec<String> {
+    let mut failures: Vec<String> = Vec::new();
+    for line in output.lines() {
+        for pattern in FAILURE_PATTERNS.iter() {
+            if let Some(caps) = pattern.captures(line) {
+                let name = caps[1].trim().to_string();
+                if !failures.contains(&name) {
+                    failures.push(name);
+                }
+                break;
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        failures = output
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with("error") || l.starts_with("Error"))
+            .map(String::from)
+            .collect();
+        failures.dedup();
+    }
+
+    failures.truncate(MAX_FAILURES);
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_counts_are_summed() {
+        let output = "test result: ok. 10 passed; 0 failed; 0 ignored\n\
+                      test result: FAILED. 3 passed; 2 failed; 0 ignored";
+        assert_eq!(
+            parse_test_counts(output),
+            Some(TestCounts {
+                passed: 13,
+                failed: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_summary_line_counts() {
+        let jest = "Test Suites: 1 failed, 2 passed, 3 total\n\
+                    Tests:       1 failed, 7 passed, 8 total";
+        assert_eq!(
+            parse_test_counts(jest),
+            Some(TestCounts {
+                passed: 7,
+                failed: 1
+            })
+        );
+
+        let pytest = "===== 4 passed in 0.12s =====";
+        assert_eq!(
+            parse_test_counts(pytest),
+            Some(TestCounts {
+                passed: 4,
+                failed: 0
+            })
+        );
+        assert_eq!(parse_test_counts("Compiling foo v0.1.0"), None);
+    }
+
+    #[test]
+    fn test_extract_failures() {
+        let cargo = "test a::works ... ok\ntest a::breaks ... FAILED\n";
+        assert_eq!(extract_failures(cargo), vec!["a::breaks".to_string()]);
+
+        let go = "--- FAIL: TestParse (0.00s)\nFAIL";
+        assert_eq!(extract_failures(go), vec!["TestParse".to_string()]);
+
+        let build = "error[E0425]: cannot find value `x`\n  --> src/lib.rs:1:1";
+        assert_eq!(
+            extract_failures(build),
+            vec!["error[E0425]: cannot find value `x`".to_string()]
+        );
+    }
+}
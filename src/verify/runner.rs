@@ -0,0 +1,142 @@
+use std::path::Path;
+use std::process::Command;
+
+use super::parse::{extract_failures, parse_test_counts, TestCounts};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyStatus {
+    Running,
+    Passed(Option<TestCounts>),
+    Failed {
+        command: String,
+        counts: Option<TestCounts>,
+    },
+    Error(String),
+}
+
+impl VerifyStatus {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            VerifyStatus::Running => "◐",
+            VerifyStatus::Passed(_) => "✓",
+            VerifyStatus::Failed { .. } => "✗",
+            VerifyStatus::Error(_) => "!",
+        }
+    }
+
+    pub fn format_short(&self) -> String {
+        match self {
+            VerifyStatus::Running => format!("{} run", self.symbol()),
+            VerifyStatus::Passed(Some(counts)) => format!("{} {}", self.symbol(), counts.passed),
+            VerifyStatus::Passed(None) => format!("{} ok", self.symbol()),
+            VerifyStatus::Failed {
+                counts: Some(counts),
+                ..
+            } if counts.failed > 0 => {
+                format!("{} {}/{}", self.symbol(), counts.failed, counts.total())
+            }
+            VerifyStatus::Failed { .. } => format!("{} fail", self.symbol()),
+            VerifyStatus::Error(_) => format!("{} err", self.symbol()),
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, VerifyStatus::Failed { .. })
+    }
+}
+
+/// Outcome of running the verify commands in one worktree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyRun {
+    pub status: VerifyStatus,
+    /// Failing tests (or errors) worth handing to the agent
+    pub failures: Vec<String>,
+}
+
+impl VerifyRun {
+    pub fn running() -> Self {
+        Self {
+            status: VerifyStatus::Running,
+            failures: Vec::new(),
+        }
+    }
+}
+
+/// Run each command in `working_dir` in order, stopping at the first one
+/// that fails. Test counts are summed across commands.
+pub fn run_verify(commands: &[String], working_dir: &Path) -> VerifyRun {
+    let mut counts: Option<TestCounts> = None;
+
+    for command in commands {
+        let output = match Command::new("/bin/sh")
+            .arg("-c")
+            .arg(command)
+            .current_dir(working_dir)
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) => {
+                return VerifyRun {
+                    status: VerifyStatus::Error(format!("Failed to run '{}': {}", command, e)),
+                    failures: Vec::new(),
+                }
+            }
+        };
+
+        let combined = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        if let Some(parsed) = parse_test_counts(&combined) {
+            let total = counts.get_or_insert_with(TestCounts::default);
+            total.passed += parsed.passed;
+            total.failed += parsed.failed;
+        }
+
+        if !output.status.success() {
+            return VerifyRun {
+                status: VerifyStatus::Failed {
+                    command: command.clone(),
+                    counts,
+                },
+                failures: extract_failures(&combined),
+            };
+        }
+    }
+
+    VerifyRun {
+        status: VerifyStatus::Passed(counts),
+        failures: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_verify_stops_at_first_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let commands = vec![
+            "echo 'test result: ok. 2 passed; 0 failed'".to_string(),
+            "echo 'test x ... FAILED'; exit 1".to_string(),
+            "touch should-not-run".to_string(),
+        ];
+
+        let run = run_verify(&commands, dir.path());
+
+        assert_eq!(
+            run.status,
+            VerifyStatus::Failed {
+                command: commands[1].clone(),
+                counts: Some(TestCounts {
+                    passed: 2,
+                    failed: 0
+                }),
+            }
+        );
+        assert_eq!(run.failures, vec!["x".to_string()]);
+        assert!(!dir.path().join("should-not-run").exists());
+    }
+}